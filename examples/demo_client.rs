@@ -4,7 +4,9 @@
 //!
 //! ## 運行方式
 //! 1. 在一個終端啟動服務: `cargo run --release`
-//! 2. 在另一個終端運行此客戶端: `cargo run --example demo_client`
+//! 2. 在另一個終端運行此客戶端: `FROST_API_TOKEN=<token> cargo run --example demo_client`
+//!
+//! `FROST_API_TOKEN` 需具備 coordinator 或 admin 角色（服務啟動時會在日誌中印出臨時 admin Token）。
 
 use serde_json::json;
 
@@ -15,7 +17,18 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
     println!("╚════════════════════════════════════════════════════════════════╝\n");
 
     let base_url = "http://127.0.0.1:3000";
-    let client = reqwest::Client::new();
+
+    // 若有設定 API Token，所有請求都帶上 Authorization header
+    let mut headers = reqwest::header::HeaderMap::new();
+    if let Ok(token) = std::env::var("FROST_API_TOKEN") {
+        headers.insert(
+            reqwest::header::AUTHORIZATION,
+            format!("Bearer {}", token).parse()?,
+        );
+    }
+    let client = reqwest::Client::builder()
+        .default_headers(headers)
+        .build()?;
 
     // ========================================================================
     // 1. 健康檢查
@@ -80,6 +93,9 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
     println!("   ✓ 簽章已生成:");
     println!("      {}", signature);
     println!("   ✓ 驗證狀態: {}", if verified { "通過 ✓" } else { "失敗 ✗" });
+    println!("   ✓ Session ID: {}", sign_response["session_id"]);
+    println!("   ✓ 參與簽署者: {}", sign_response["signer_ids"]);
+    println!("   ✓ 總耗時: {} ms", sign_response["timings"]["total_ms"]);
     println!();
 
     // ========================================================================
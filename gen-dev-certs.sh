@@ -0,0 +1,46 @@
+#!/bin/bash
+# ============================================================================
+# FROST-T 開發用自簽憑證產生腳本
+# 用途: 在本機測試原生 TLS / mTLS（請勿用於生產環境）
+#
+# 產生檔案（預設目錄 ./dev-certs）：
+#   ca.pem / ca.key         - 自簽 CA
+#   server.pem / server.key - 伺服器憑證（SAN: localhost, 127.0.0.1）
+#   client.pem / client.key - 客戶端憑證（用於 mTLS）
+#
+# 使用方式：
+#   ./gen-dev-certs.sh
+#   TLS_CERT_PATH=dev-certs/server.pem TLS_KEY_PATH=dev-certs/server.key \
+#   TLS_CLIENT_CA_PATH=dev-certs/ca.pem cargo run
+#   curl --cacert dev-certs/ca.pem --cert dev-certs/client.pem \
+#        --key dev-certs/client.key https://localhost:3000/health
+# ============================================================================
+
+set -e
+
+OUT_DIR="${1:-dev-certs}"
+mkdir -p "$OUT_DIR"
+cd "$OUT_DIR"
+
+# 自簽 CA
+openssl req -x509 -newkey rsa:2048 -nodes -days 365 \
+    -keyout ca.key -out ca.pem -subj "/CN=FROST-T Dev CA"
+
+# 伺服器憑證
+openssl req -newkey rsa:2048 -nodes \
+    -keyout server.key -out server.csr -subj "/CN=localhost"
+openssl x509 -req -in server.csr -CA ca.pem -CAkey ca.key -CAcreateserial \
+    -days 365 -out server.pem \
+    -extfile <(printf "subjectAltName=DNS:localhost,IP:127.0.0.1\nextendedKeyUsage=serverAuth")
+
+# 客戶端憑證（mTLS）
+openssl req -newkey rsa:2048 -nodes \
+    -keyout client.key -out client.csr -subj "/CN=frost-coordinator"
+openssl x509 -req -in client.csr -CA ca.pem -CAkey ca.key -CAcreateserial \
+    -days 365 -out client.pem \
+    -extfile <(printf "extendedKeyUsage=clientAuth")
+
+rm -f server.csr client.csr ca.srl
+chmod 600 *.key
+
+echo "✓ Development certificates written to $OUT_DIR/"
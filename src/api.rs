@@ -10,6 +10,9 @@
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 
+// 結構化簽章請求屬於 API 合約的一部分
+pub use crate::message::{MessageKind, SignPayload};
+
 // ============================================================================
 // 核心類型別名
 // ============================================================================
@@ -42,11 +45,47 @@ impl std::fmt::Display for SessionId {
     }
 }
 
+/// Group ID - 用於識別一個獨立的簽章群組（一組金鑰分片 + 門檻設定）
+///
+/// 同一個服務可以同時管理多個群組（例如不同的金庫），
+/// 每個群組擁有自己的 Coordinator、Signers 與 Session 狀態，彼此完全隔離。
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct GroupId(pub Uuid);
+
+impl GroupId {
+    /// 生成新的隨機 Group ID
+    pub fn new() -> Self {
+        Self(Uuid::new_v4())
+    }
+}
+
+impl Default for GroupId {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl std::fmt::Display for GroupId {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+/// 將 FROST Identifier 轉換為 API 使用的 u16 簽署者 ID
+///
+/// secp256k1 的 Identifier 序列化為 32 bytes 的 big-endian 純量，
+/// 因此簽署者 ID 位於最後兩個位元組。
+pub fn signer_id_from_identifier(identifier: &frost_secp256k1::Identifier) -> u16 {
+    let bytes = identifier.serialize();
+    let len = bytes.len();
+    u16::from_be_bytes([bytes[len - 2], bytes[len - 1]])
+}
+
 // ============================================================================
 // Setup API - 初始化簽章群組
 // ============================================================================
 
-/// POST /setup - 初始化一個新的 FROST 簽章群組
+/// POST /groups - 初始化一個新的 FROST 簽章群組
 ///
 /// 在生產環境中，這應該使用分散式金鑰生成 (DKG)。
 /// 目前使用 Trusted Dealer 方法進行演示。
@@ -62,12 +101,22 @@ pub struct SetupRequest {
 /// Setup 成功回應
 #[derive(Debug, Serialize, Deserialize)]
 pub struct SetupResponse {
+    /// 群組 ID（後續呼叫 /groups/{group_id}/... 時使用）
+    pub group_id: GroupId,
+
+    /// 門檻值
+    pub min_signers: u16,
+
     /// 群組公鑰（用於驗證簽章）
     pub group_public_key: String, // hex-encoded
 
     /// 簽署者 ID 列表
     pub signer_ids: Vec<u16>,
 
+    /// 簽署者身分公鑰名冊（signer_id → hex 編碼的 x-only 公鑰）
+    /// 用於驗證 Round 1 / Round 2 訊息的信封簽章
+    pub identity_keys: std::collections::BTreeMap<u16, String>,
+
     /// 成功訊息
     pub message: String,
 }
@@ -88,9 +137,13 @@ pub struct Round1Request {
     /// Session ID - 關聯此次簽章流程
     pub session_id: SessionId,
 
-    /// 要簽署的訊息（通常是交易雜湊）
-    #[serde(with = "hex_serde")]
+    /// 要簽署的訊息（舊版 raw 格式，可省略）
+    #[serde(default, with = "hex_serde")]
     pub message: Vec<u8>,
+
+    /// 結構化的簽章請求（提供時簽署者會先驗證其格式與 domain separation 規則）
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub payload: Option<SignPayload>,
 }
 
 /// Round 1 成功回應
@@ -106,6 +159,9 @@ pub struct Round1Response {
     /// 注意：秘密 nonce 永遠不會離開簽署者
     pub commitment: String, // hex-encoded SigningCommitments
 
+    /// 簽署者以身分金鑰對承諾的信封簽章（hex 編碼）
+    pub envelope_signature: String,
+
     /// 時間戳
     pub timestamp: chrono::DateTime<chrono::Utc>,
 }
@@ -144,6 +200,23 @@ pub struct SigningPackageData {
     /// 要簽署的訊息
     #[serde(with = "hex_serde")]
     pub message: Vec<u8>,
+
+    /// 結構化的簽章請求
+    ///
+    /// 簽署者會自己從 payload 計算摘要，並拒絕與 `message` 不符的套件；
+    /// 未提供 payload 的套件一律被拒絕。
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub payload: Option<SignPayload>,
+}
+
+impl SigningPackageData {
+    /// 訊息類型（未提供 payload 時為 raw）
+    pub fn message_kind(&self) -> MessageKind {
+        self.payload
+            .as_ref()
+            .map(SignPayload::kind)
+            .unwrap_or_default()
+    }
 }
 
 /// 單個簽署者的承諾資料
@@ -154,6 +227,10 @@ pub struct CommitmentData {
 
     /// 承諾（hex 編碼）
     pub commitment: String, // hex-encoded
+
+    /// 信封簽章（hex 編碼）- 證明承諾確實來自此簽署者
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub envelope_signature: Option<String>,
 }
 
 /// Round 2 成功回應
@@ -168,6 +245,9 @@ pub struct Round2Response {
     /// 簽章分片（hex 編碼）
     pub signature_share: String, // hex-encoded
 
+    /// 簽署者以身分金鑰對簽章分片的信封簽章（hex 編碼）
+    pub envelope_signature: String,
+
     /// 時間戳
     pub timestamp: chrono::DateTime<chrono::Utc>,
 }
@@ -196,6 +276,10 @@ pub struct SignatureShareData {
 
     /// 簽章分片（hex 編碼）
     pub signature_share: String, // hex-encoded
+
+    /// 信封簽章（hex 編碼）- 證明分片確實來自此簽署者
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub envelope_signature: Option<String>,
 }
 
 /// 聚合簽章成功回應
@@ -214,6 +298,277 @@ pub struct AggregateResponse {
     pub message: String,
 }
 
+// ============================================================================
+// Sign API - 完整簽章流程
+// ============================================================================
+
+/// POST /sign 與 POST /groups/{group_id}/sign - 由協調者執行完整的簽章流程
+///
+/// 要簽署的內容以下列欄位之一提供（必須剛好一個）：
+/// - `message`：hex 編碼的 bytes（等同 `{"type": "raw"}` payload，簽署其 tagged hash）
+/// - `payload`：結構化的簽章請求（建議使用，套用 domain separation）
+///
+/// ```json
+/// { "signer_ids": [1, 2, 3], "payload": { "type": "document", "content": "Hello FROST" } }
+/// ```
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SignRequest {
+    /// 參與簽署的簽署者 ID 列表
+    pub signer_ids: Vec<u16>,
+
+    /// 要簽署的訊息（hex 編碼）
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub message: Option<String>,
+
+    /// 結構化的簽章請求
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub payload: Option<SignPayload>,
+}
+
+impl SignRequest {
+    /// 取得結構化請求（`message` 視為 raw payload）
+    pub fn into_payload(self) -> Result<SignPayload, crate::message::MessageError> {
+        match (self.message, self.payload) {
+            (Some(message), None) => Ok(SignPayload::Raw { message }),
+            (None, Some(payload)) => Ok(payload),
+            _ => Err(crate::message::MessageError::AmbiguousInput),
+        }
+    }
+}
+
+/// 完整簽章流程的成功回應
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SignResponse {
+    /// 實際使用的 Session ID（可用於查詢 /sessions/:id 與稽核日誌）
+    pub session_id: SessionId,
+
+    /// 群組 ID
+    pub group_id: GroupId,
+
+    /// 最終簽章（hex 編碼的 65 bytes FROST(secp256k1, SHA-256) 簽章：壓縮的 R 加上 z）
+    pub signature: String,
+
+    /// 訊息類型
+    pub message_kind: MessageKind,
+
+    /// 實際被簽署的 bytes（hex 編碼；typed payload 時為摘要）
+    pub message_digest: String,
+
+    /// 以群組公鑰驗證簽章的結果
+    pub verified: bool,
+
+    /// 參與簽署的簽署者 ID
+    pub signer_ids: Vec<u16>,
+
+    /// 實際使用的簽章通道
+    pub signing_path: SigningPath,
+
+    /// 各階段耗時
+    pub timings: RoundTimings,
+
+    /// 簽章所屬的群組公鑰（hex 編碼），任何人都可以用它驗證簽章
+    pub group_public_key: String,
+}
+
+/// `/sign` 實際使用的簽章通道
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum SigningPath {
+    /// 推播給以 WebSocket 連線的簽署者
+    #[serde(rename = "websocket")]
+    WebSocket,
+
+    /// 經由模擬 LoRa 鏈路（`lora-sim` feature）
+    #[serde(rename = "lora")]
+    LoRa,
+
+    /// 直接呼叫行程內的簽署者
+    #[serde(rename = "in_process")]
+    InProcess,
+}
+
+impl std::fmt::Display for SigningPath {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let name = match self {
+            SigningPath::WebSocket => "websocket",
+            SigningPath::LoRa => "lora",
+            SigningPath::InProcess => "in_process",
+        };
+        write!(f, "{}", name)
+    }
+}
+
+// ============================================================================
+// Session 查詢 API
+// ============================================================================
+
+/// 簽章會話的階段（狀態機）
+///
+/// ```text
+/// Created → CommitmentsCollecting → PackageIssued → SharesCollecting → Aggregated
+///    └──────────────┴──────────────────┴────────────────┴──→ Failed / Expired
+/// ```
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SessionStatus {
+    /// 已建立，尚未收到任何承諾
+    #[default]
+    Created,
+
+    /// 正在收集 Round 1 承諾
+    CommitmentsCollecting,
+
+    /// 已發出簽章套件，等待 Round 2 分片
+    PackageIssued,
+
+    /// 正在收集 Round 2 簽章分片
+    SharesCollecting,
+
+    /// 已聚合並驗證簽章（終止狀態）
+    Aggregated,
+
+    /// 失敗或被中止（終止狀態）
+    Failed,
+
+    /// 超時未完成（終止狀態）
+    Expired,
+}
+
+impl SessionStatus {
+    /// 是否為終止狀態
+    pub fn is_terminal(&self) -> bool {
+        matches!(
+            self,
+            SessionStatus::Aggregated | SessionStatus::Failed | SessionStatus::Expired
+        )
+    }
+
+    /// 狀態機允許的轉換
+    pub fn can_transition_to(&self, next: SessionStatus) -> bool {
+        use SessionStatus::*;
+        match (self, next) {
+            (from, Failed | Expired) => !from.is_terminal(),
+            (Created | CommitmentsCollecting, CommitmentsCollecting) => true,
+            (CommitmentsCollecting, PackageIssued) => true,
+            (PackageIssued | SharesCollecting, SharesCollecting) => true,
+            (SharesCollecting, Aggregated) => true,
+            _ => false,
+        }
+    }
+}
+
+impl std::fmt::Display for SessionStatus {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let name = match self {
+            SessionStatus::Created => "created",
+            SessionStatus::CommitmentsCollecting => "commitments_collecting",
+            SessionStatus::PackageIssued => "package_issued",
+            SessionStatus::SharesCollecting => "shares_collecting",
+            SessionStatus::Aggregated => "aggregated",
+            SessionStatus::Failed => "failed",
+            SessionStatus::Expired => "expired",
+        };
+        write!(f, "{}", name)
+    }
+}
+
+/// GET /sessions/:id - 會話狀態
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SessionInfo {
+    /// Session ID
+    pub session_id: SessionId,
+
+    /// 所屬群組
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub group_id: Option<GroupId>,
+
+    /// 目前階段
+    pub status: SessionStatus,
+
+    /// 訊息類型
+    pub message_kind: MessageKind,
+
+    /// 要簽署訊息的 SHA-256（不返回原文）
+    pub message_sha256: String,
+
+    /// 已提交承諾的簽署者
+    pub commitments_received: Vec<u16>,
+
+    /// 已提交簽章分片的簽署者
+    pub shares_received: Vec<u16>,
+
+    /// 最終簽章（僅 aggregated）
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub signature: Option<String>,
+
+    /// 失敗原因（僅 failed / expired）
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub failure_reason: Option<String>,
+
+    /// 建立時間
+    pub created_at: chrono::DateTime<chrono::Utc>,
+
+    /// 最後一次狀態變更時間
+    pub updated_at: chrono::DateTime<chrono::Utc>,
+}
+
+/// 簽章流程各階段耗時（毫秒）
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize)]
+pub struct RoundTimings {
+    /// Round 1：收集承諾
+    pub round1_ms: u64,
+
+    /// Round 2：收集簽章分片
+    pub round2_ms: u64,
+
+    /// 聚合與驗證
+    pub aggregation_ms: u64,
+
+    /// 整體耗時
+    pub total_ms: u64,
+}
+
+/// GET /sessions 查詢參數
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct SessionListQuery {
+    /// 只列出指定階段的會話
+    pub status: Option<SessionStatus>,
+}
+
+/// 各階段的進行中會話數量（用於 /health）
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ActiveSessionCounts {
+    /// 進行中會話總數
+    pub total: usize,
+    pub created: usize,
+    pub commitments_collecting: usize,
+    pub package_issued: usize,
+    pub shares_collecting: usize,
+}
+
+impl ActiveSessionCounts {
+    /// 計入一個會話（終止狀態不計）
+    pub fn record(&mut self, status: SessionStatus) {
+        let counter = match status {
+            SessionStatus::Created => &mut self.created,
+            SessionStatus::CommitmentsCollecting => &mut self.commitments_collecting,
+            SessionStatus::PackageIssued => &mut self.package_issued,
+            SessionStatus::SharesCollecting => &mut self.shares_collecting,
+            _ => return,
+        };
+        *counter += 1;
+        self.total += 1;
+    }
+
+    /// 加總另一組計數（例如多個群組）
+    pub fn merge(&mut self, other: &ActiveSessionCounts) {
+        self.total += other.total;
+        self.created += other.created;
+        self.commitments_collecting += other.commitments_collecting;
+        self.package_issued += other.package_issued;
+        self.shares_collecting += other.shares_collecting;
+    }
+}
+
 // ============================================================================
 // 通用錯誤回應
 // ============================================================================
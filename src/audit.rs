@@ -0,0 +1,804 @@
+//! # 稽核日誌 - 防竄改的簽章事件紀錄
+//!
+//! 記錄每一個簽章相關事件：建立了什麼 Session、誰提交了承諾、誰釋出了簽章分片、
+//! 聚合成功或失敗，以及所有被拒絕的請求。
+//!
+//! ## 格式
+//! 每行一個 JSON 物件（JSONL），只允許附加 (append-only)：
+//!
+//! ```text
+//! {"seq":0,"timestamp":"…","actor":"coordinator","event":{…},"prev_hash":"000…","hash":"…"}
+//! ```
+//!
+//! ## 雜湊鏈
+//! - `hash = SHA256(canonical_json(entry 去除 hash 欄位))`
+//! - `prev_hash` 是前一筆的 `hash`（第一筆為 64 個 `0`）
+//!
+//! 任何被修改、刪除或插入的紀錄都會破壞雜湊鏈，可以用 `frost-cli audit verify` 檢查。
+//! canonical JSON 使用 `serde_json::Value`（物件鍵依字母排序），確保寫入與驗證時的
+//! 序列化結果一致。
+//!
+//! ## 簽章檢查點
+//! 雜湊鏈只能偵測局部修改：能寫入檔案的人仍可以截斷尾端或重算整條鏈。設定檢查點
+//! 金鑰（`with_checkpoint_key`）後，每 `checkpoint_interval` 筆紀錄以及 `sync()` 時
+//! 會附加一筆 `checkpoint` 事件，以協調者的金鑰簽署前一筆紀錄的序號與雜湊。
+//!
+//! `AuditLog::head()`（`GET /audit/head`）匯出目前的鏈頭，應記錄在日誌以外的地方；
+//! `frost-cli audit verify --checkpoint-key <公鑰> --expect-head <雜湊>` 確認檢查點由
+//! 預期的金鑰簽署，且先前記錄的鏈頭仍在日誌中。
+
+use crate::api::{MessageKind, SessionId};
+use crate::envelope::{EnvelopeError, IdentityKey, IdentityPublicKey};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::collections::VecDeque;
+use std::fs::{File, OpenOptions};
+use std::io::{BufRead, BufReader, Write};
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use thiserror::Error;
+
+/// 第一筆紀錄的 prev_hash
+const GENESIS_HASH: &str = "0000000000000000000000000000000000000000000000000000000000000000";
+
+/// 記憶體中保留的最近紀錄數量（供 GET /audit 查詢）
+const MAX_RECENT_ENTRIES: usize = 1000;
+
+/// 預設每隔多少筆紀錄附加一次簽章檢查點
+pub const DEFAULT_CHECKPOINT_INTERVAL: u64 = 100;
+
+/// 檢查點紀錄的 actor
+const CHECKPOINT_ACTOR: &str = "coordinator";
+
+// ============================================================================
+// 錯誤定義
+// ============================================================================
+
+#[derive(Debug, Error)]
+pub enum AuditError {
+    #[error("Audit log I/O error: {0}")]
+    Io(#[from] std::io::Error),
+
+    #[error("Audit log serialization error: {0}")]
+    Serialization(#[from] serde_json::Error),
+
+    #[error("Existing audit log is corrupted at line {line}: {reason}")]
+    Corrupted { line: usize, reason: String },
+
+    #[error("Invalid checkpoint key: {0}")]
+    InvalidKey(#[from] EnvelopeError),
+}
+
+// ============================================================================
+// 事件定義
+// ============================================================================
+
+/// 稽核事件
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum AuditEvent {
+    /// 協調者建立了新的簽章會話
+    SessionCreated {
+        session_id: SessionId,
+        /// 訊息類型（raw、document、bitcoin_sighash 等）
+        #[serde(default)]
+        message_kind: MessageKind,
+        /// 要簽署訊息的 SHA-256（不記錄原文）
+        message_sha256: String,
+    },
+
+    /// 協調者接受了簽署者的承諾
+    CommitmentReceived { session_id: SessionId, signer_id: u16 },
+
+    /// 簽署者生成並送出了承諾
+    CommitmentIssued { session_id: SessionId, signer_id: u16 },
+
+    /// 簽署者釋出了簽章分片（消費了 Nonce）
+    ShareReleased {
+        session_id: SessionId,
+        signer_id: u16,
+        #[serde(default)]
+        message_kind: MessageKind,
+    },
+
+    /// 聚合成功
+    AggregationSucceeded {
+        session_id: SessionId,
+        signer_ids: Vec<u16>,
+        signature: String,
+    },
+
+    /// 聚合或驗證失敗
+    AggregationFailed { session_id: SessionId, reason: String },
+
+    /// 會話被中止，簽署者已被通知丟棄 Nonce
+    SessionAborted { session_id: SessionId, reason: String },
+
+    /// 被拒絕的請求（認證失敗、Nonce 不存在、信封簽章無效等）
+    RequestRejected {
+        #[serde(skip_serializing_if = "Option::is_none")]
+        session_id: Option<SessionId>,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        signer_id: Option<u16>,
+        reason: String,
+    },
+
+    /// 協調者簽署的檢查點：確認到 `head_seq` 為止的雜湊鏈
+    Checkpoint {
+        /// 被簽署的前一筆紀錄序號
+        head_seq: u64,
+        /// 被簽署的前一筆紀錄雜湊
+        head_hash: String,
+        /// 檢查點金鑰的公鑰（hex）
+        public_key: String,
+        /// 對 `(head_seq, head_hash)` 的 Schnorr 簽章（hex）
+        signature: String,
+    },
+}
+
+/// 一筆稽核紀錄
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AuditEntry {
+    /// 序號（從 0 開始連續遞增）
+    pub seq: u64,
+
+    /// 時間戳
+    pub timestamp: chrono::DateTime<chrono::Utc>,
+
+    /// 產生事件的角色（例如 "coordinator"、"signer_1"）
+    pub actor: String,
+
+    /// 事件內容
+    pub event: AuditEvent,
+
+    /// 前一筆紀錄的雜湊
+    pub prev_hash: String,
+
+    /// 本筆紀錄的雜湊
+    pub hash: String,
+}
+
+/// 計算紀錄的雜湊（不含 hash 欄位的 canonical JSON）
+fn compute_hash(value: &serde_json::Value) -> String {
+    let mut value = value.clone();
+    if let Some(object) = value.as_object_mut() {
+        object.remove("hash");
+    }
+    hex::encode(Sha256::digest(value.to_string().as_bytes()))
+}
+
+/// 檢查點的簽署內容：`SHA256(tag || tag || head_seq || head_hash)`
+fn checkpoint_digest(head_seq: u64, head_hash: &str) -> [u8; 32] {
+    let tag = Sha256::digest(b"FROST-TS/audit-checkpoint/v1");
+
+    let mut hasher = Sha256::new();
+    hasher.update(tag);
+    hasher.update(tag);
+    hasher.update(head_seq.to_be_bytes());
+    hasher.update(head_hash.as_bytes());
+    hasher.finalize().into()
+}
+
+/// 稽核日誌目前的鏈頭
+///
+/// 記錄在日誌以外（例如監控系統或紙本），之後用來偵測日誌被截斷或整條重寫。
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AuditHead {
+    /// 最後一筆的序號（空日誌為 None）
+    pub seq: Option<u64>,
+
+    /// 最後一筆的雜湊
+    pub hash: String,
+
+    /// 檢查點金鑰的公鑰（未設定時為 None）
+    pub checkpoint_key: Option<String>,
+
+    /// 最後一個檢查點之後尚未簽署的紀錄數
+    pub unsigned_entries: u64,
+}
+
+// ============================================================================
+// AuditLog
+// ============================================================================
+
+struct AuditLogInner {
+    /// JSONL 檔案（None 代表僅存在記憶體中）
+    file: Option<File>,
+
+    /// 下一筆的序號
+    next_seq: u64,
+
+    /// 最後一筆的雜湊
+    last_hash: String,
+
+    /// 最後一個檢查點之後的紀錄數
+    unsigned: u64,
+
+    /// 最近的紀錄
+    recent: VecDeque<AuditEntry>,
+}
+
+/// 雜湊鏈稽核日誌
+///
+/// 可被 Coordinator 與多個 Signer 共用（內部使用 Mutex 保證序號與雜湊鏈的順序）。
+pub struct AuditLog {
+    path: Option<PathBuf>,
+    inner: Mutex<AuditLogInner>,
+
+    /// 簽署檢查點的協調者金鑰
+    checkpoint_key: Option<IdentityKey>,
+
+    /// 每隔多少筆紀錄附加一次檢查點
+    checkpoint_interval: u64,
+}
+
+impl AuditLog {
+    /// 開啟（或建立）檔案型稽核日誌
+    ///
+    /// 若檔案已存在，會先驗證既有的雜湊鏈並從最後一筆接續。
+    pub fn open(path: impl AsRef<Path>) -> Result<Self, AuditError> {
+        let path = path.as_ref().to_path_buf();
+
+        let mut next_seq = 0;
+        let mut last_hash = GENESIS_HASH.to_string();
+        let mut unsigned = 0;
+        let mut recent = VecDeque::new();
+
+        if path.exists() {
+            let report = verify_file(&path)?;
+            if let Some(error) = report.errors.first() {
+                return Err(AuditError::Corrupted {
+                    line: error.line,
+                    reason: error.reason.clone(),
+                });
+            }
+
+            for entry in read_entries(&path)? {
+                next_seq = entry.seq + 1;
+                last_hash = entry.hash.clone();
+                unsigned = match entry.event {
+                    AuditEvent::Checkpoint { .. } => 0,
+                    _ => unsigned + 1,
+                };
+                recent.push_back(entry);
+                if recent.len() > MAX_RECENT_ENTRIES {
+                    recent.pop_front();
+                }
+            }
+        }
+
+        let file = OpenOptions::new().create(true).append(true).open(&path)?;
+
+        Ok(Self {
+            path: Some(path),
+            inner: Mutex::new(AuditLogInner {
+                file: Some(file),
+                next_seq,
+                last_hash,
+                unsigned,
+                recent,
+            }),
+            checkpoint_key: None,
+            checkpoint_interval: DEFAULT_CHECKPOINT_INTERVAL,
+        })
+    }
+
+    /// 建立僅存在記憶體中的稽核日誌（測試或未設定檔案時使用）
+    pub fn in_memory() -> Self {
+        Self {
+            path: None,
+            inner: Mutex::new(AuditLogInner {
+                file: None,
+                next_seq: 0,
+                last_hash: GENESIS_HASH.to_string(),
+                unsigned: 0,
+                recent: VecDeque::new(),
+            }),
+            checkpoint_key: None,
+            checkpoint_interval: DEFAULT_CHECKPOINT_INTERVAL,
+        }
+    }
+
+    /// 設定檢查點金鑰：每 `checkpoint_interval` 筆紀錄與 `sync()` 時附加簽章檢查點
+    pub fn with_checkpoint_key(mut self, key: IdentityKey) -> Self {
+        self.checkpoint_key = Some(key);
+        self
+    }
+
+    /// 設定檢查點間隔（預設 `DEFAULT_CHECKPOINT_INTERVAL` 筆，最少 1）
+    pub fn with_checkpoint_interval(mut self, interval: u64) -> Self {
+        self.checkpoint_interval = interval.max(1);
+        self
+    }
+
+    /// 日誌檔案路徑
+    pub fn path(&self) -> Option<&Path> {
+        self.path.as_deref()
+    }
+
+    /// 附加一筆事件
+    pub fn append(
+        &self,
+        actor: impl Into<String>,
+        event: AuditEvent,
+    ) -> Result<AuditEntry, AuditError> {
+        let mut inner = self.inner.lock().unwrap();
+        let entry = Self::write_entry(&mut inner, actor.into(), event)?;
+        if inner.unsigned >= self.checkpoint_interval {
+            self.write_checkpoint(&mut inner)?;
+        }
+        Ok(entry)
+    }
+
+    /// 寫入一筆紀錄並推進鏈頭（呼叫端持有鎖）
+    fn write_entry(
+        inner: &mut AuditLogInner,
+        actor: String,
+        event: AuditEvent,
+    ) -> Result<AuditEntry, AuditError> {
+        let is_checkpoint = matches!(event, AuditEvent::Checkpoint { .. });
+        let mut entry = AuditEntry {
+            seq: inner.next_seq,
+            timestamp: chrono::Utc::now(),
+            actor,
+            event,
+            prev_hash: inner.last_hash.clone(),
+            hash: String::new(),
+        };
+        entry.hash = compute_hash(&serde_json::to_value(&entry)?);
+
+        if let Some(file) = inner.file.as_mut() {
+            let line = serde_json::to_string(&entry)?;
+            writeln!(file, "{}", line)?;
+            file.flush()?;
+        }
+
+        inner.next_seq += 1;
+        inner.last_hash = entry.hash.clone();
+        inner.unsigned = if is_checkpoint { 0 } else { inner.unsigned + 1 };
+        inner.recent.push_back(entry.clone());
+        if inner.recent.len() > MAX_RECENT_ENTRIES {
+            inner.recent.pop_front();
+        }
+
+        Ok(entry)
+    }
+
+    /// 以檢查點金鑰簽署目前的鏈頭（未設定金鑰或沒有新紀錄時不寫入）
+    fn write_checkpoint(
+        &self,
+        inner: &mut AuditLogInner,
+    ) -> Result<Option<AuditEntry>, AuditError> {
+        let Some(key) = &self.checkpoint_key else {
+            return Ok(None);
+        };
+        if inner.unsigned == 0 {
+            return Ok(None);
+        }
+
+        let head_seq = inner.next_seq - 1;
+        let head_hash = inner.last_hash.clone();
+        let event = AuditEvent::Checkpoint {
+            head_seq,
+            signature: key.sign_digest(&checkpoint_digest(head_seq, &head_hash)),
+            head_hash,
+            public_key: key.public_key().to_hex(),
+        };
+        Self::write_entry(inner, CHECKPOINT_ACTOR.to_string(), event).map(Some)
+    }
+
+    /// 立即附加一個簽章檢查點，返回寫入的紀錄
+    pub fn checkpoint(&self) -> Result<Option<AuditEntry>, AuditError> {
+        let mut inner = self.inner.lock().unwrap();
+        self.write_checkpoint(&mut inner)
+    }
+
+    /// 目前的鏈頭
+    pub fn head(&self) -> AuditHead {
+        let inner = self.inner.lock().unwrap();
+        AuditHead {
+            seq: inner.next_seq.checked_sub(1),
+            hash: inner.last_hash.clone(),
+            checkpoint_key: self
+                .checkpoint_key
+                .as_ref()
+                .map(|key| key.public_key().to_hex()),
+            unsigned_entries: inner.unsigned,
+        }
+    }
+
+    /// 附加一筆事件；失敗時只記錄錯誤（不中斷簽章流程）
+    pub fn record(&self, actor: &str, event: AuditEvent) {
+        if let Err(e) = self.append(actor, event) {
+            tracing::error!(actor, error = %e, "Failed to write audit log entry");
+        }
+    }
+
+    /// 查詢最近的紀錄
+    ///
+    /// # 參數
+    /// - `since`: 只返回序號 >= since 的紀錄
+    /// - `limit`: 最多返回的筆數
+    pub fn recent_entries(&self, since: Option<u64>, limit: usize) -> Vec<AuditEntry> {
+        let inner = self.inner.lock().unwrap();
+        inner
+            .recent
+            .iter()
+            .filter(|entry| match since {
+                Some(since) => entry.seq >= since,
+                None => true,
+            })
+            .take(limit)
+            .cloned()
+            .collect()
+    }
+
+    /// 附加檢查點並將緩衝資料寫入磁碟（關機前呼叫）
+    pub fn sync(&self) -> Result<(), AuditError> {
+        let mut inner = self.inner.lock().unwrap();
+        self.write_checkpoint(&mut inner)?;
+        if let Some(file) = inner.file.as_mut() {
+            file.flush()?;
+            file.sync_all()?;
+        }
+        Ok(())
+    }
+}
+
+/// 載入檢查點金鑰（hex 私鑰檔），不存在時生成並寫入（只有擁有者可讀）
+pub fn load_or_generate_checkpoint_key(path: &Path) -> Result<IdentityKey, AuditError> {
+    if path.exists() {
+        let secret = std::fs::read_to_string(path)?;
+        return Ok(IdentityKey::from_secret_hex(secret.trim())?);
+    }
+
+    let key = IdentityKey::generate();
+    let mut options = OpenOptions::new();
+    options.write(true).create_new(true);
+    #[cfg(unix)]
+    std::os::unix::fs::OpenOptionsExt::mode(&mut options, 0o600);
+    let mut file = options.open(path)?;
+    writeln!(file, "{}", key.to_secret_hex())?;
+    file.sync_all()?;
+    Ok(key)
+}
+
+// ============================================================================
+// 驗證
+// ============================================================================
+
+/// 驗證選項
+#[derive(Debug, Clone, Default)]
+pub struct AuditVerifyOptions {
+    /// 檢查點必須由此公鑰簽署（未設定時只以檢查點附帶的公鑰驗證簽章）
+    pub checkpoint_key: Option<IdentityPublicKey>,
+
+    /// 先前匯出的鏈頭雜湊，必須仍出現在日誌中（偵測截斷與重寫）
+    pub expected_head: Option<String>,
+}
+
+/// 單一驗證錯誤
+#[derive(Debug, Clone, Serialize)]
+pub struct AuditVerifyError {
+    /// 行號（從 1 開始）
+    pub line: usize,
+
+    /// 錯誤原因
+    pub reason: String,
+}
+
+/// 雜湊鏈驗證報告
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct AuditVerifyReport {
+    /// 檢查的紀錄數
+    pub total_entries: usize,
+
+    /// 最後一筆的序號
+    pub last_seq: Option<u64>,
+
+    /// 最後一筆的雜湊
+    pub head_hash: Option<String>,
+
+    /// 簽章有效的檢查點數量
+    pub checkpoints: usize,
+
+    /// 最後一個檢查點之後未簽署的紀錄數
+    pub unsigned_tail: u64,
+
+    /// 預期的鏈頭是否出現在日誌中（未指定時為 None）
+    pub expected_head_found: Option<bool>,
+
+    /// 序號缺口（缺少的序號區間，含頭尾）
+    pub gaps: Vec<(u64, u64)>,
+
+    /// 雜湊或格式錯誤
+    pub errors: Vec<AuditVerifyError>,
+}
+
+impl AuditVerifyReport {
+    /// 雜湊鏈是否完整
+    pub fn is_valid(&self) -> bool {
+        self.gaps.is_empty() && self.errors.is_empty() && self.expected_head_found != Some(false)
+    }
+}
+
+/// 讀取所有紀錄（不驗證）
+fn read_entries(path: &Path) -> Result<Vec<AuditEntry>, AuditError> {
+    let reader = BufReader::new(File::open(path)?);
+    let mut entries = Vec::new();
+    for line in reader.lines() {
+        let line = line?;
+        if line.trim().is_empty() {
+            continue;
+        }
+        entries.push(serde_json::from_str(&line)?);
+    }
+    Ok(entries)
+}
+
+/// 驗證稽核日誌檔案的雜湊鏈與檢查點（不指定金鑰與鏈頭）
+pub fn verify_file(path: &Path) -> Result<AuditVerifyReport, AuditError> {
+    verify_file_with(path, &AuditVerifyOptions::default())
+}
+
+/// 驗證稽核日誌檔案
+///
+/// 檢查項目：
+/// 1. 每筆紀錄的 hash 與內容相符（偵測竄改）
+/// 2. 每筆紀錄的 prev_hash 等於前一筆的 hash（偵測刪除 / 插入 / 重排）
+/// 3. 序號連續（報告缺口）
+/// 4. 檢查點簽署的是前一筆紀錄，簽章有效且（指定時）由預期的金鑰簽署
+/// 5. （指定時）先前匯出的鏈頭仍在日誌中
+pub fn verify_file_with(
+    path: &Path,
+    options: &AuditVerifyOptions,
+) -> Result<AuditVerifyReport, AuditError> {
+    let reader = BufReader::new(File::open(path)?);
+    let mut report = AuditVerifyReport {
+        expected_head_found: options.expected_head.as_ref().map(|_| false),
+        ..Default::default()
+    };
+
+    let mut expected_prev_hash = GENESIS_HASH.to_string();
+    let mut expected_seq = 0u64;
+
+    for (index, line) in reader.lines().enumerate() {
+        let line_no = index + 1;
+        let line = line?;
+        if line.trim().is_empty() {
+            continue;
+        }
+
+        let value: serde_json::Value = match serde_json::from_str(&line) {
+            Ok(value) => value,
+            Err(e) => {
+                report.errors.push(AuditVerifyError {
+                    line: line_no,
+                    reason: format!("invalid JSON: {}", e),
+                });
+                continue;
+            }
+        };
+
+        let entry: AuditEntry = match serde_json::from_value(value.clone()) {
+            Ok(entry) => entry,
+            Err(e) => {
+                report.errors.push(AuditVerifyError {
+                    line: line_no,
+                    reason: format!("invalid entry: {}", e),
+                });
+                continue;
+            }
+        };
+
+        report.total_entries += 1;
+
+        if entry.seq != expected_seq {
+            if entry.seq > expected_seq {
+                report.gaps.push((expected_seq, entry.seq - 1));
+            } else {
+                report.errors.push(AuditVerifyError {
+                    line: line_no,
+                    reason: format!("sequence went backwards: expected {}, got {}", expected_seq, entry.seq),
+                });
+            }
+        }
+
+        if compute_hash(&value) != entry.hash {
+            report.errors.push(AuditVerifyError {
+                line: line_no,
+                reason: format!("hash mismatch for seq {} (entry modified)", entry.seq),
+            });
+        }
+
+        if entry.prev_hash != expected_prev_hash {
+            report.errors.push(AuditVerifyError {
+                line: line_no,
+                reason: format!("broken chain at seq {} (prev_hash does not match)", entry.seq),
+            });
+        }
+
+        if let AuditEvent::Checkpoint { .. } = entry.event {
+            match verify_checkpoint(&entry, options.checkpoint_key.as_ref()) {
+                Ok(()) => {
+                    report.checkpoints += 1;
+                    report.unsigned_tail = 0;
+                }
+                Err(reason) => report.errors.push(AuditVerifyError {
+                    line: line_no,
+                    reason,
+                }),
+            }
+        } else {
+            report.unsigned_tail += 1;
+        }
+
+        if options.expected_head.as_deref() == Some(entry.hash.as_str()) {
+            report.expected_head_found = Some(true);
+        }
+
+        expected_prev_hash = entry.hash.clone();
+        expected_seq = entry.seq + 1;
+        report.last_seq = Some(entry.seq);
+        report.head_hash = Some(entry.hash);
+    }
+
+    Ok(report)
+}
+
+/// 驗證一筆檢查點紀錄
+fn verify_checkpoint(
+    entry: &AuditEntry,
+    expected_key: Option<&IdentityPublicKey>,
+) -> Result<(), String> {
+    let AuditEvent::Checkpoint {
+        head_seq,
+        head_hash,
+        public_key,
+        signature,
+    } = &entry.event
+    else {
+        return Ok(());
+    };
+
+    if head_seq.checked_add(1) != Some(entry.seq) || *head_hash != entry.prev_hash {
+        return Err(format!(
+            "checkpoint at seq {} does not cover the preceding entry",
+            entry.seq
+        ));
+    }
+
+    let key = IdentityPublicKey::from_hex(public_key)
+        .map_err(|e| format!("checkpoint at seq {}: {}", entry.seq, e))?;
+    if expected_key.is_some_and(|expected| *expected != key) {
+        return Err(format!(
+            "checkpoint at seq {} signed by unexpected key {}",
+            entry.seq, public_key
+        ));
+    }
+    if !key.verify_digest(&checkpoint_digest(*head_seq, head_hash), signature) {
+        return Err(format!("invalid checkpoint signature at seq {}", entry.seq));
+    }
+
+    Ok(())
+}
+
+// ============================================================================
+// 測試
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_hash_chain_detects_tampering() {
+        let path = std::env::temp_dir().join(format!("frost-audit-{}.jsonl", uuid::Uuid::new_v4()));
+
+        {
+            let log = AuditLog::open(&path).unwrap();
+            let session_id = SessionId::new();
+            log.append("coordinator", AuditEvent::SessionCreated {
+                session_id,
+                message_kind: MessageKind::Raw,
+                message_sha256: "00".repeat(32),
+            })
+            .unwrap();
+            log.append("signer_1", AuditEvent::CommitmentIssued { session_id, signer_id: 1 })
+                .unwrap();
+            log.append(
+                "signer_1",
+                AuditEvent::ShareReleased {
+                    session_id,
+                    signer_id: 1,
+                    message_kind: MessageKind::Raw,
+                },
+            )
+            .unwrap();
+        }
+
+        // 重新開啟後應從最後一筆接續
+        let log = AuditLog::open(&path).unwrap();
+        let entry = log
+            .append("coordinator", AuditEvent::AggregationFailed {
+                session_id: SessionId::new(),
+                reason: "test".to_string(),
+            })
+            .unwrap();
+        assert_eq!(entry.seq, 3);
+        assert!(verify_file(&path).unwrap().is_valid());
+
+        // 竄改一筆紀錄
+        let content = std::fs::read_to_string(&path).unwrap();
+        let tampered = content.replacen("signer_1", "signer_2", 1);
+        std::fs::write(&path, tampered).unwrap();
+        assert!(!verify_file(&path).unwrap().is_valid());
+
+        // 刪除一筆紀錄 → 缺口
+        let lines: Vec<&str> = content.lines().collect();
+        std::fs::write(&path, format!("{}\n{}\n", lines[0], lines[2])).unwrap();
+        let report = verify_file(&path).unwrap();
+        assert_eq!(report.gaps, vec![(1, 1)]);
+
+        std::fs::remove_file(&path).ok();
+    }
+
+    #[test]
+    fn test_signed_checkpoints_pin_the_head() {
+        let path = std::env::temp_dir().join(format!("frost-audit-{}.jsonl", uuid::Uuid::new_v4()));
+        let key = IdentityKey::generate();
+        let event = || AuditEvent::CommitmentIssued {
+            session_id: SessionId::new(),
+            signer_id: 1,
+        };
+
+        let log = AuditLog::open(&path)
+            .unwrap()
+            .with_checkpoint_key(key.clone())
+            .with_checkpoint_interval(2);
+        log.append("signer_1", event()).unwrap();
+        log.append("signer_1", event()).unwrap();
+        log.append("signer_1", event()).unwrap();
+
+        // 第 2 筆之後自動簽署檢查點，第 3 筆尚未被簽署
+        let head = log.head();
+        assert_eq!(head.seq, Some(3));
+        assert_eq!(head.unsigned_entries, 1);
+        assert_eq!(head.checkpoint_key, Some(key.public_key().to_hex()));
+
+        // sync 時簽署剩餘的紀錄
+        log.sync().unwrap();
+        let head = log.head();
+        assert_eq!(head.seq, Some(4));
+        assert_eq!(head.unsigned_entries, 0);
+        drop(log);
+
+        let options = AuditVerifyOptions {
+            checkpoint_key: Some(key.public_key()),
+            expected_head: Some(head.hash.clone()),
+        };
+        let report = verify_file_with(&path, &options).unwrap();
+        assert!(report.is_valid());
+        assert_eq!(report.checkpoints, 2);
+        assert_eq!(report.unsigned_tail, 0);
+        assert_eq!(report.head_hash, Some(head.hash.clone()));
+
+        // 其他金鑰簽署的檢查點不被接受
+        let wrong_key = AuditVerifyOptions {
+            checkpoint_key: Some(IdentityKey::generate().public_key()),
+            expected_head: None,
+        };
+        assert!(!verify_file_with(&path, &wrong_key).unwrap().is_valid());
+
+        // 截斷尾端後雜湊鏈本身仍然完整，但匯出的鏈頭已不在日誌中
+        let content = std::fs::read_to_string(&path).unwrap();
+        let lines: Vec<&str> = content.lines().collect();
+        std::fs::write(&path, format!("{}\n", lines[..3].join("\n"))).unwrap();
+        assert!(verify_file(&path).unwrap().is_valid());
+        let report = verify_file_with(&path, &options).unwrap();
+        assert_eq!(report.expected_head_found, Some(false));
+        assert!(!report.is_valid());
+
+        std::fs::remove_file(&path).ok();
+    }
+}
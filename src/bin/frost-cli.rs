@@ -4,17 +4,22 @@
 //! 支援在多個終端視窗模擬不同角色進行離線簽章。
 
 use anyhow::{Context, Result};
-use axum::{extract::State, response::Json};
 use frost_secp256k1 as frost;
 use frost_threshold_signature::cli::{commands::*, file_store::*, nonce_store::*};
+use frost_threshold_signature::envelope::IdentityPublicKey;
+use frost_threshold_signature::message::raw_digest;
+use frost_threshold_signature::server::{lora, AppState, LoRaSimulation, ServerBuilder};
 use frost_threshold_signature::transport::{
-    LoRaTransportState, MessageMetadata, MessageType, SimulatedLoRaTransport,
-    Transport,
+    qr, AsyncTransport, FileTransport, GilbertElliott, LoRaConfig, Participant, QrFormat,
+    RadioConfig, SimulatedLoRaTransport, SimulationClock,
+};
+use frost_threshold_signature::{
+    api::*, audit, telemetry, Coordinator, GroupServices, MemorySessionStore, Metrics,
 };
-use frost_threshold_signature::{api::*, Coordinator, Signer};
 use rand::thread_rng;
 use std::collections::BTreeMap;
-use std::sync::{Arc, Mutex};
+use std::sync::Arc;
+use tower_http::cors::AllowOrigin;
 use uuid::Uuid;
 
 // ============================================================================
@@ -27,10 +32,7 @@ macro_rules! log_println {
         let line = format!($($arg)*);
         println!("{}", line);
         if let Ok(mut s) = $state.lock() {
-            s.cli_output.push(line);
-            if s.cli_output.len() > 500 {
-                s.cli_output.remove(0);
-            }
+            s.push_log(line);
         }
     }};
 }
@@ -88,12 +90,61 @@ fn main() -> Result<()> {
             message,
             signers,
             full_payload,
+            radio_sf,
+            distance_m,
+            keys_dir,
         } => {
+            // 指定 SF 時以物理層模型 + 虛擬時鐘估算真實的 LoRa 延遲
+            let radio = radio_sf
+                .map(|sf| RadioConfig::eu868(sf, *distance_m))
+                .transpose()?;
+
             // DemoBasic 需要異步 runtime（用於 HTTP Server）
             tokio::runtime::Runtime::new()
                 .context("無法創建 Tokio runtime")?
-                .block_on(cmd_demo_basic(message, signers, *full_payload))
+                .block_on(cmd_demo_basic(message, signers, *full_payload, radio, keys_dir))
         }
+
+        Commands::Participate {
+            share_file,
+            inbox,
+            outbox,
+            poll_ms,
+            idle_timeout_secs,
+        } => tokio::runtime::Runtime::new()
+            .context("無法創建 Tokio runtime")?
+            .block_on(cmd_participate(
+                share_file,
+                inbox,
+                outbox,
+                *poll_ms,
+                *idle_timeout_secs,
+                cli.verbose,
+            )),
+
+        Commands::QrExport {
+            input,
+            output_dir,
+            format,
+            max_fragment_len,
+            extra_parts,
+        } => cmd_qr_export(input, output_dir, *format, *max_fragment_len, *extra_parts),
+
+        Commands::QrImport { frames, output } => cmd_qr_import(frames, output, cli.verbose),
+
+        Commands::Audit {
+            action:
+                AuditAction::Verify {
+                    file,
+                    checkpoint_key,
+                    expect_head,
+                },
+        } => cmd_audit_verify(
+            file,
+            checkpoint_key.as_deref(),
+            expect_head.as_deref(),
+            cli.verbose,
+        ),
     }
 }
 
@@ -115,9 +166,6 @@ fn cmd_keygen(
         anyhow::bail!("門檻值 ({}) 不能大於總簽署者數 ({})", min_signers, max_signers);
     }
 
-    // 確保輸出目錄存在
-    FileStore::ensure_dir(output_dir)?;
-
     // 生成金鑰
     let mut rng = thread_rng();
     let (shares, pubkey_package) = frost::keys::generate_with_dealer(
@@ -130,23 +178,23 @@ fn cmd_keygen(
 
     println!("✓ 已生成 {} 個金鑰分片（門檻值：{}）\n", max_signers, min_signers);
 
-    // 儲存每個金鑰分片
-    for (identifier, secret_share) in shares {
-        let id_bytes = identifier.serialize();
-        let signer_id = u16::from_le_bytes([id_bytes[0], id_bytes[1]]);
-        let share_path = output_dir.join(format!("share_{}.json", signer_id));
-
-        // Convert SecretShare to KeyPackage for storage
-        let key_package = frost::keys::KeyPackage::try_from(secret_share)
-            .map_err(|e| anyhow::anyhow!("Failed to convert SecretShare to KeyPackage: {:?}", e))?;
-        FileStore::save_key_share(&share_path, signer_id, &key_package, min_signers, max_signers)?;
+    // Convert SecretShare to KeyPackage for storage
+    let key_packages = FileStore::key_packages_from_shares(shares)?;
 
+    // 儲存每個金鑰分片與群組公鑰（目錄不存在時自動建立）
+    let share_paths = FileStore::save_group_keys(
+        output_dir,
+        GroupId::new(),
+        &pubkey_package,
+        &key_packages,
+        min_signers,
+        max_signers,
+    )?;
+    for (signer_id, share_path) in share_paths {
         println!("  📄 簽署者 {} → {}", signer_id, share_path.display());
     }
 
-    // 儲存群組公鑰
     let pubkey_path = output_dir.join("pubkey.json");
-    FileStore::save_public_key(&pubkey_path, &pubkey_package, min_signers, max_signers)?;
 
     let group_pubkey = pubkey_package.verifying_key();
     println!("\n  🔓 群組公鑰 → {}", pubkey_path.display());
@@ -267,6 +315,7 @@ fn cmd_create_package(
         commitments.push(CommitmentData {
             signer_id: commitment_file.signer_id,
             commitment: commitment_file.commitment_hex.clone(),
+            envelope_signature: None,
         });
 
         signer_ids.push(commitment_file.signer_id);
@@ -282,6 +331,7 @@ fn cmd_create_package(
     let package_data = SigningPackageData {
         commitments,
         message: message.clone(),
+        payload: None,
     };
 
     // 儲存簽章套件
@@ -355,7 +405,8 @@ fn cmd_round2(
         commitments_map.insert(identifier, commitment);
     }
 
-    let signing_package = frost::SigningPackage::new(commitments_map, &message);
+    // 與 `SignPayload::Raw` 相同：簽署 raw 訊息的 tagged hash
+    let signing_package = frost::SigningPackage::new(commitments_map, &raw_digest(&message));
 
     // 生成簽章分片
     let signature_share = frost::round2::sign(&signing_package, &nonces, &key_package)
@@ -442,7 +493,8 @@ fn cmd_aggregate(
         commitments_map.insert(identifier, commitment);
     }
 
-    let signing_package = frost::SigningPackage::new(commitments_map, &message);
+    let digest = raw_digest(&message);
+    let signing_package = frost::SigningPackage::new(commitments_map, &digest);
 
     // 建立 Coordinator 並聚合簽章
     let coordinator = Coordinator::new(pubkey_package, 3);
@@ -454,7 +506,7 @@ fn cmd_aggregate(
     println!("  簽章 (hex): {}", hex::encode(group_signature.serialize().unwrap()));
 
     // 驗證簽章
-    coordinator.verify_signature(&message, &group_signature)
+    coordinator.verify_signature(&digest, &group_signature)
         .map_err(|e| anyhow::anyhow!("簽章驗證失敗: {}", e))?;
 
     println!("✓ 簽章驗證通過");
@@ -519,7 +571,7 @@ fn cmd_verify(
     // 驗證簽章
     println!("\n開始驗證...");
 
-    match group_pubkey.verify(&message, &signature) {
+    match group_pubkey.verify(&raw_digest(&message), &signature) {
         Ok(_) => {
             println!("\n🎊 簽章驗證成功！");
             println!("\n✓ 此訊息確實由至少 3 個簽署者共同簽署");
@@ -534,16 +586,226 @@ fn cmd_verify(
     }
 }
 
+/// 【Auditor】驗證稽核日誌的雜湊鏈與簽章檢查點
+fn cmd_audit_verify(
+    file: &std::path::Path,
+    checkpoint_key: Option<&str>,
+    expect_head: Option<&str>,
+    verbose: bool,
+) -> Result<()> {
+    println!("📜 驗證稽核日誌: {}\n", file.display());
+
+    let options = audit::AuditVerifyOptions {
+        checkpoint_key: checkpoint_key
+            .map(IdentityPublicKey::from_hex)
+            .transpose()
+            .context("無效的檢查點公鑰")?,
+        expected_head: expect_head.map(str::to_string),
+    };
+    let report = audit::verify_file_with(file, &options).context("無法讀取稽核日誌")?;
+
+    println!("✓ 紀錄數: {}", report.total_entries);
+    if let Some(last_seq) = report.last_seq {
+        println!("✓ 最後序號: {}", last_seq);
+    }
+    if let Some(head_hash) = &report.head_hash {
+        println!("✓ 鏈頭: {}", head_hash);
+    }
+    println!("✓ 檢查點: {}", report.checkpoints);
+    if report.unsigned_tail > 0 {
+        println!("⚠️  最後 {} 筆紀錄之後沒有檢查點", report.unsigned_tail);
+    }
+    if report.expected_head_found == Some(false) {
+        println!("❌ 預期的鏈頭不在日誌中（日誌被截斷或重寫）");
+    }
+
+    for (from, to) in &report.gaps {
+        println!("❌ 序號缺口: {} ..= {}", from, to);
+    }
+    for error in &report.errors {
+        println!("❌ 第 {} 行: {}", error.line, error.reason);
+    }
+
+    if verbose {
+        println!("\n{}", serde_json::to_string_pretty(&report)?);
+    }
+
+    if report.is_valid() {
+        println!("\n🎊 雜湊鏈完整，未發現竄改或缺口");
+        Ok(())
+    } else {
+        anyhow::bail!(
+            "稽核日誌驗證失敗：{} 個錯誤、{} 個缺口",
+            report.errors.len(),
+            report.gaps.len()
+        )
+    }
+}
+
+/// 【Signer】自動處理 inbox 中的協議訊息
+///
+/// 以 `FileTransport` 執行 `Signer::serve`：承諾請求與簽章套件到達時自動回覆，
+/// 直到閒置逾時（未設定時持續執行，按 Ctrl+C 結束）。
+async fn cmd_participate(
+    share_file: &std::path::Path,
+    inbox: &std::path::Path,
+    outbox: &std::path::Path,
+    poll_ms: u64,
+    idle_timeout_secs: Option<u64>,
+    verbose: bool,
+) -> Result<()> {
+    // 協議處理的進度以 tracing 輸出
+    let level = if verbose { "debug" } else { "info" };
+    let _telemetry = telemetry::init_tracing("frost-signer", level)?;
+
+    let signer = FileStore::load_signer(share_file).context("無法載入金鑰分片")?;
+    let signer_id = signer.numeric_id();
+
+    let mut transport = FileTransport::new(Participant::Signer(signer_id), inbox, outbox)
+        .with_poll_interval(std::time::Duration::from_millis(poll_ms));
+    if let Some(secs) = idle_timeout_secs {
+        transport = transport.with_idle_timeout(std::time::Duration::from_secs(secs));
+    }
+
+    println!("✍️  簽署者 {} 開始處理訊息", signer_id);
+    println!("   📥 Inbox:  {}", inbox.display());
+    println!(
+        "   📤 Outbox: {}",
+        transport.outbox_dir(Participant::Coordinator).display()
+    );
+    println!("   ⚠️  Nonce 只保存在記憶體中，Round 1 與 Round 2 之間請勿結束程式");
+    println!();
+
+    signer
+        .serve(&mut transport)
+        .await
+        .context("處理訊息時發生傳輸錯誤")?;
+
+    let stats = transport.get_stats().unwrap_or_default();
+    println!("\n✅ 閒置逾時，已結束（共回覆 {} 則訊息）", stats.total_messages);
+
+    Ok(())
+}
+
+/// 【Air-gap】把 JSON 檔匯出成多段 QR Code
+fn cmd_qr_export(
+    input: &std::path::Path,
+    output_dir: &std::path::Path,
+    format: QrFormat,
+    max_fragment_len: usize,
+    extra_parts: Option<usize>,
+) -> Result<()> {
+    let data = std::fs::read(input)
+        .with_context(|| format!("無法讀取檔案: {}", input.display()))?;
+    serde_json::from_slice::<serde_json::Value>(&data).context("輸入檔案不是合法的 JSON")?;
+
+    let seq_len = data.len().div_ceil(max_fragment_len.max(1)).max(1);
+    let parts = qr::encode_parts(
+        &data,
+        qr::UR_TYPE_JSON,
+        max_fragment_len,
+        extra_parts.unwrap_or(seq_len),
+    );
+
+    println!("📷 匯出 {} ({} bytes)", input.display(), data.len());
+    println!("   原始片段: {}，總 frame 數: {}", seq_len, parts.len());
+
+    match format {
+        QrFormat::Terminal => {
+            tokio::runtime::Runtime::new()
+                .context("無法創建 Tokio runtime")?
+                .block_on(qr::play_terminal(
+                    &parts,
+                    std::time::Duration::from_millis(400),
+                    3,
+                ))?;
+        }
+        format => {
+            let frames = qr::write_frames(&parts, output_dir, format)?;
+            println!("\n📄 已寫出 {} 個 frame → {}", frames.len(), output_dir.display());
+        }
+    }
+
+    println!("\n💡 掃描端執行 'frost-cli qr-import --frames <影像...> --output <檔案>'");
+    println!("   frame 可以亂序、漏掉部分，只要掃到的數量足夠即可還原");
+
+    Ok(())
+}
+
+/// 【Air-gap】從掃描的 QR Code 影像還原 JSON 檔
+fn cmd_qr_import(
+    frames: &[std::path::PathBuf],
+    output: &std::path::Path,
+    verbose: bool,
+) -> Result<()> {
+    // 目錄展開為其中的檔案（依檔名排序）
+    let mut images = Vec::new();
+    for path in frames {
+        if path.is_dir() {
+            let mut entries: Vec<_> = std::fs::read_dir(path)?
+                .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+                .filter(|entry| entry.is_file())
+                .collect();
+            entries.sort();
+            images.extend(entries);
+        } else {
+            images.push(path.clone());
+        }
+    }
+
+    let mut decoder = qr::QrDecoder::new(qr::UR_TYPE_JSON);
+    for image in &images {
+        let complete = decoder
+            .receive_image(image)
+            .with_context(|| format!("無法處理影像: {}", image.display()))?;
+        if verbose {
+            println!("  📷 {} → {:.0}%", image.display(), decoder.progress() * 100.0);
+        }
+        if complete {
+            break;
+        }
+    }
+
+    let data = decoder.finish().context("掃描的 frame 不足以還原檔案")?;
+    serde_json::from_slice::<serde_json::Value>(&data).context("還原的內容不是合法的 JSON")?;
+    std::fs::write(output, &data)
+        .with_context(|| format!("無法寫入檔案: {}", output.display()))?;
+
+    println!("✓ 已還原 {} bytes（校驗碼驗證通過）", data.len());
+    println!("📄 → {}", output.display());
+
+    Ok(())
+}
+
+/// 建立 Demo 使用的模擬 LoRa 鏈路
+///
+/// 指定無線電模型時使用虛擬時鐘（瞬間完成並估算真實延遲），否則使用預設的
+/// 延遲、掉包與分片設定。
+fn demo_link(radio: Option<&RadioConfig>) -> SimulatedLoRaTransport {
+    match radio {
+        Some(radio) => SimulatedLoRaTransport::new_with_config(
+            LoRaConfig {
+                packet_loss_rate: 0.0,
+                clock: SimulationClock::Virtual,
+                ..LoRaConfig::default()
+            }
+            .with_radio(radio.clone())
+            .with_burst_loss(GilbertElliott::default()),
+        ),
+        None => SimulatedLoRaTransport::new(),
+    }
+}
+
 /// 【Demo】完整流程展示
 ///
-/// 在單一 process 內模擬完整的 3-of-5 FROST 簽章流程。
+/// 在單一 process 內以真正的 `Coordinator` / `Signer` 執行 FROST 門檻簽章。
 /// 使用 SimulatedLoRaTransport 模擬真實的無線傳輸環境。
 /// 同時啟動 HTTP Server 提供 Dashboard 查詢介面。
 ///
 /// ## 流程說明
 ///
-/// 1. **啟動 HTTP Server**：在背景啟動 API 服務（port 3000）
-/// 2. **Setup 階段**：使用 Trusted Dealer 生成 5 個金鑰分片
+/// 1. **Setup 階段**：從金鑰目錄載入群組（第一次執行時生成並儲存）
+/// 2. **啟動 HTTP Server**：在背景啟動 API 服務（port 3000），`/sign` 使用同一個群組
 /// 3. **Round 1**：參與的簽署者生成 Nonce 承諾（透過 LoRa 傳輸）
 /// 4. **建立簽章套件**：協調者收集所有承諾
 /// 5. **Round 2**：簽署者生成簽章分片
@@ -553,316 +815,223 @@ fn cmd_verify(
 /// ## 新功能
 ///
 /// - ✅ SimulatedLoRaTransport：模擬延遲、掉包、分片
-/// - ✅ HTTP API：提供 /status 端點給 Dashboard 查詢
+/// - ✅ HTTP API：與 API 服務（main.rs）相同的路由，加上 /status 與 /events 給 Dashboard
 /// - ✅ 即時狀態追蹤：記錄所有傳輸事件
-async fn cmd_demo_basic(message: &str, signer_ids: &[u16], full_payload: bool) -> Result<()> {
+/// - ✅ 持久化群組：重新啟動後群組公鑰不變，Demo 與 Dashboard 的 /sign 共用同一個群組
+async fn cmd_demo_basic(
+    message: &str,
+    signer_ids: &[u16],
+    full_payload: bool,
+    radio: Option<RadioConfig>,
+    keys_dir: &std::path::Path,
+) -> Result<()> {
     // ========================================================================
     // 初始化 SimulatedLoRaTransport（必須先創建才能使用狀態）
     // ========================================================================
-    let mut transport = SimulatedLoRaTransport::new();
+    let transport = demo_link(radio.as_ref());
     let lora_state = transport.get_state();
 
     log_println!(lora_state, "");
     log_println!(lora_state, "╔════════════════════════════════════════════════════════════════╗");
     log_println!(lora_state, "║                                                                ║");
-    log_println!(lora_state, "║   FROST 3-of-5 門檻簽章 - 完整流程展示                        ║");
+    log_println!(lora_state, "║   FROST 門檻簽章 - 完整流程展示                               ║");
     log_println!(lora_state, "║   Demo for bitcoin++ Taipei 2025                              ║");
     log_println!(lora_state, "║                                                                ║");
     log_println!(lora_state, "╚════════════════════════════════════════════════════════════════╝");
     log_println!(lora_state, "");
 
-    // 驗證參數
-    if signer_ids.len() < 3 {
-        anyhow::bail!("至少需要 3 個簽署者參與，目前只有 {}", signer_ids.len());
+    // ========================================================================
+    // Setup: 載入或生成群組
+    // ========================================================================
+    println!("╔════════════════════════════════════════════════════════════════╗");
+    println!("║  階段 1: Setup - 載入群組金鑰                                  ║");
+    println!("╚════════════════════════════════════════════════════════════════╝\n");
+
+    let audit_log = Arc::new(audit::AuditLog::in_memory());
+    let session_store = Arc::new(MemorySessionStore::new());
+    let metrics = Arc::new(Metrics::new());
+    let services = GroupServices {
+        audit_log: Some(Arc::clone(&audit_log)),
+        session_store: Some(session_store.clone()),
+        metrics: Some(Arc::clone(&metrics)),
+    };
+
+    // 目錄格式與 `frost-cli keygen` 相同，也可以先用 keygen 產生金鑰再交給 Demo 使用
+    let (group, generated) = FileStore::load_or_generate_group(keys_dir, 5, 3, &services)?;
+    if generated {
+        println!("🔑 {} 中沒有群組，以 Trusted Dealer 生成...", keys_dir.display());
+        println!(
+            "✓ 已生成 {} 個金鑰分片（門檻值：{}）並儲存到 {}",
+            group.max_signers(),
+            group.min_signers(),
+            keys_dir.display()
+        );
+    } else {
+        println!(
+            "✓ 已從 {} 載入 {}-of-{} 群組",
+            keys_dir.display(),
+            group.min_signers(),
+            group.max_signers()
+        );
     }
+    println!("✓ 群組公鑰: {}...", &group.group_public_key_hex()[..32]);
+    println!();
 
-    if signer_ids.len() > 5 {
-        anyhow::bail!("最多只能有 5 個簽署者參與，目前有 {}", signer_ids.len());
+    // 驗證參數
+    let available = group.signer_ids();
+    if signer_ids.len() < group.min_signers() as usize {
+        anyhow::bail!(
+            "至少需要 {} 個簽署者參與，目前只有 {}",
+            group.min_signers(),
+            signer_ids.len()
+        );
     }
 
-    for &id in signer_ids {
-        if id < 1 || id > 5 {
-            anyhow::bail!("簽署者 ID 必須在 1-5 之間，收到: {}", id);
+    for id in signer_ids {
+        if !available.contains(id) {
+            anyhow::bail!("簽署者 {} 不在群組中（可用：{:?}）", id, available);
         }
     }
 
     log_println!(lora_state, "📋 配置:");
     log_println!(lora_state, "   訊息: \"{}\"", message);
     log_println!(lora_state, "   參與簽署者: {:?}", signer_ids);
-    log_println!(lora_state, "   門檻配置: 3-of-5");
+    log_println!(
+        lora_state,
+        "   門檻配置: {}-of-{}",
+        group.min_signers(),
+        group.max_signers()
+    );
     log_println!(lora_state, "");
 
     log_println!(lora_state, "🔧 初始化 Transport 抽象層...");
     log_println!(lora_state, "   ✓ 使用 SimulatedLoRaTransport");
-    log_println!(lora_state, "   ✓ 延遲: 500ms per packet");
-    log_println!(lora_state, "   ✓ 掉包率: 10%");
-    log_println!(lora_state, "   ✓ 分片大小: 64 bytes");
+    match &radio {
+        Some(radio) => {
+            log_println!(
+                lora_state,
+                "   ✓ 無線電模型: EU868 SF{} / {} kHz / 距離 {:.0}m",
+                radio.spreading_factor(),
+                radio.bandwidth_hz / 1000,
+                radio.distance_m
+            );
+            log_println!(lora_state, "   ✓ 工作週期: 1%，突發掉包: Gilbert–Elliott");
+            log_println!(lora_state, "   ✓ 最大 payload: {} bytes", radio.max_payload());
+            log_println!(lora_state, "   ✓ 虛擬時鐘：瞬間完成並估算真實延遲");
+        }
+        None => {
+            log_println!(lora_state, "   ✓ 延遲: 500ms per packet");
+            log_println!(lora_state, "   ✓ 掉包率: 10%");
+            log_println!(lora_state, "   ✓ 分片大小: 64 bytes");
+        }
+    }
     println!();
 
     // ========================================================================
-    // 啟動 HTTP Server（背景執行）
+    // 把簽署者接上 LoRa 網路
     // ========================================================================
-    println!("🌐 啟動 HTTP API Server...");
-
-    let lora_state_clone = Arc::clone(&lora_state);
-    let server_handle = tokio::spawn(async move {
-        start_http_server(lora_state_clone).await
-    });
+    println!("🏗️  建立協調者和簽署者...");
 
-    println!("   ✓ Server 運行在 http://127.0.0.1:3000");
-    println!("   ✓ Dashboard: 在瀏覽器開啟 dashboard.html");
-    println!("   ✓ API 端點: GET /status");
+    // 所有參與者都經由同一條模擬 LoRa 鏈路通訊，Demo 與 HTTP /sign 共用
+    let lora = Arc::new(LoRaSimulation::new(&group, transport));
+    for signer_id in &available {
+        println!("   ✓ 簽署者 {} 已就緒（已連上 LoRa 網路）", signer_id);
+    }
     println!();
 
-    // 等待一下讓 server 啟動
-    tokio::time::sleep(tokio::time::Duration::from_millis(500)).await;
+    let state = AppState::new(group, audit_log, session_store);
+    let group = state.default_group();
 
     // ========================================================================
-    // Setup: Trusted Dealer 金鑰生成
+    // 啟動 HTTP Server（背景執行）
     // ========================================================================
-    println!("╔════════════════════════════════════════════════════════════════╗");
-    println!("║  階段 1: Setup - Trusted Dealer 金鑰生成                       ║");
-    println!("╚════════════════════════════════════════════════════════════════╝\n");
-
-    let max_signers = 5;
-    let min_signers = 3;
-
-    let mut rng = thread_rng();
+    println!("🌐 啟動 HTTP API Server...");
 
-    println!("🔑 生成 FROST 金鑰分片...");
-    let (shares, pubkey_package) = frost::keys::generate_with_dealer(
-        max_signers,
-        min_signers,
-        frost::keys::IdentifierList::Default,
-        &mut rng,
-    )
-    .context("金鑰生成失敗")?;
+    // 本地開啟的 dashboard.html 需要跨來源存取；展示用途不啟用認證
+    let app = ServerBuilder::new(state)
+        .with_cors(AllowOrigin::any())
+        .with_dashboard(Arc::clone(&lora_state))
+        .with_lora_simulation(Arc::clone(&lora))
+        .with_round_timeout(lora::DEFAULT_ROUND_TIMEOUT)
+        .with_metrics(metrics)
+        .build();
+    let server_handle = tokio::spawn(start_http_server(app));
 
-    let group_pubkey = pubkey_package.verifying_key();
-    println!("✓ 已生成 {} 個金鑰分片（門檻值：{}）", max_signers, min_signers);
-    println!("✓ 群組公鑰: {}...", &hex::encode(group_pubkey.serialize().unwrap())[..32]);
+    println!("   ✓ Server 運行在 http://127.0.0.1:3000");
+    println!("   ✓ Dashboard: 在瀏覽器開啟 dashboard.html");
+    println!("   ✓ API 端點: GET /status、GET /events、GET /metrics、POST /sign（與 API 服務相同）");
     println!();
 
-    tokio::time::sleep(tokio::time::Duration::from_millis(800)).await;
-
-    // ========================================================================
-    // 建立 Coordinator 和 Signers
-    // ========================================================================
-    println!("🏗️  建立協調者和簽署者...");
-
-    let coordinator = Coordinator::new(pubkey_package.clone(), min_signers);
-
-    // Create signers map with sequential IDs (1, 2, 3, 4, 5)
-    let mut signers = BTreeMap::new();
-    let mut signer_id_counter = 1u16;
-    for (_identifier, secret_share) in shares {
-        signers.insert(signer_id_counter, Signer::new(secret_share));
-        println!("   ✓ 簽署者 {} 已就緒", signer_id_counter);
-        signer_id_counter += 1;
-    }
-
-    println!();
+    // 等待一下讓 server 啟動
     tokio::time::sleep(tokio::time::Duration::from_millis(500)).await;
 
     // ========================================================================
-    // Round 1: 生成承諾
+    // 在 LoRa 上執行 Round 1、Round 2 與聚合
     // ========================================================================
     println!("╔════════════════════════════════════════════════════════════════╗");
-    println!("║  階段 2: Round 1 - 生成 Nonce 承諾                            ║");
+    println!("║  階段 2: 在 LoRa 上執行 FROST 協議                            ║");
     println!("╚════════════════════════════════════════════════════════════════╝\n");
 
-    println!("📝 為什麼需要 Round 1？");
-    println!("   FROST 使用 Commitment-Reveal 模式防止惡意簽署者操縱 nonce：");
-    println!("   1. 所有簽署者先提交承諾（無法修改）");
-    println!("   2. 然後在 Round 2 才揭露如何使用 nonce");
-    println!("   3. 這確保了簽章的不可偽造性");
-    println!();
-
-    let session_id = SessionId::new();
-    println!("🎲 Session ID: {}", session_id);
+    println!("📝 協調者只透過傳輸層與簽署者交換訊息：");
+    println!("   1. Round 1：發送承諾請求，收集所有簽署者的 Nonce 承諾");
+    println!("      （Commitment-Reveal 模式防止惡意簽署者操縱 nonce）");
+    println!("   2. 廣播簽章套件（包含所有承諾和訊息）");
+    println!("   3. Round 2：收集簽章分片（每個 nonce 只使用一次）");
+    println!("   4. 聚合、驗證並廣播最終簽章");
     println!();
 
     tokio::time::sleep(tokio::time::Duration::from_millis(800)).await;
 
-    let mut commitments_map = BTreeMap::new();
-    let mut commitment_data_vec = Vec::new();
-
-    for &signer_id in signer_ids {
-        let signer = signers.get(&signer_id).unwrap();
-
-        println!("👤 簽署者 {} 正在生成承諾...", signer_id);
-        let commitment = signer
-            .commit(session_id)
-            .context(format!("簽署者 {} 生成承諾失敗", signer_id))?;
-
-        let commitment_hex = hex::encode(commitment.serialize().unwrap());
-
-        // 模擬傳輸：Signer -> Coordinator
-        transport.send(
-            MessageMetadata {
-                from: format!("signer_{}", signer_id),
-                to: "coordinator".to_string(),
-                message_type: MessageType::Round1Commitment,
-                timestamp: Some(chrono::Utc::now()),
-            },
-            &commitment_hex,
-        );
-
-        commitments_map.insert(frost::Identifier::try_from(signer_id).unwrap(), commitment);
-        commitment_data_vec.push(CommitmentData {
-            signer_id,
-            commitment: commitment_hex,
-        });
-
-        tokio::time::sleep(tokio::time::Duration::from_millis(100)).await;
-    }
-
-    println!("✓ Round 1 完成！已收集 {} 個承諾\n", commitments_map.len());
-    std::thread::sleep(std::time::Duration::from_millis(500));
-
-    // ========================================================================
-    // 建立簽章套件
-    // ========================================================================
-    println!("╔════════════════════════════════════════════════════════════════╗");
-    println!("║  階段 3: 建立簽章套件                                         ║");
-    println!("╚════════════════════════════════════════════════════════════════╝\n");
-
-    let message_bytes = message.as_bytes();
-
-    println!("📦 協調者正在建立簽章套件...");
-    let signing_package = frost::SigningPackage::new(commitments_map.clone(), message_bytes);
-
-    let signing_package_data = SigningPackageData {
-        commitments: commitment_data_vec.clone(),
-        message: message_bytes.to_vec(),
+    let payload = SignPayload::Raw {
+        message: hex::encode(message.as_bytes()),
     };
-
-    // 模擬傳輸：Coordinator -> Signers (廣播)
-    let package_json = serde_json::to_string(&signing_package_data)
-        .context("無法序列化簽章套件")?;
-
-    for &signer_id in signer_ids {
-        transport.send(
-            MessageMetadata {
-                from: "coordinator".to_string(),
-                to: format!("signer_{}", signer_id),
-                message_type: MessageType::SigningPackage,
-                timestamp: Some(chrono::Utc::now()),
-            },
-            &package_json[..64.min(package_json.len())],  // 只顯示部分內容
-        );
-        tokio::time::sleep(tokio::time::Duration::from_millis(100)).await;
-    }
-
-    println!("✓ 簽章套件已分發給所有參與的簽署者\n");
-    std::thread::sleep(std::time::Duration::from_millis(500));
+    let outcome = lora
+        .sign(group.coordinator(), signer_ids, &payload, lora::DEFAULT_ROUND_TIMEOUT)
+        .await
+        .context("簽章流程失敗")?;
+
+    let signature_hex = outcome.signature_hex();
+    println!("🎲 Session ID: {}", outcome.session_id);
+    println!("✓ 簽章聚合並驗證成功！");
+    println!("   簽章 (hex): {}", signature_hex);
+    println!(
+        "   耗時: Round 1 {}ms / Round 2 {}ms / 總計 {}ms",
+        outcome.timings.round1_ms, outcome.timings.round2_ms, outcome.timings.total_ms
+    );
+    println!();
 
     // ========================================================================
-    // Round 2: 生成簽章分片
+    // 統計資訊
     // ========================================================================
+    let stats = lora.stats();
     println!("╔════════════════════════════════════════════════════════════════╗");
-    println!("║  階段 4: Round 2 - 生成簽章分片                               ║");
+    println!("║  傳輸統計                                                     ║");
     println!("╚════════════════════════════════════════════════════════════════╝\n");
 
-    println!("📝 Round 2 做什麼？");
-    println!("   每個簽署者使用：");
-    println!("   - 自己的金鑰分片（私密）");
-    println!("   - Round 1 的秘密 nonce（一次性使用）");
-    println!("   - 簽章套件（包含所有承諾和訊息）");
-    println!("   生成一個簽章分片，傳回給協調者。");
-    println!();
-
-    tokio::time::sleep(tokio::time::Duration::from_millis(800)).await;
-
-    let mut signature_shares_map = BTreeMap::new();
-
-    for &signer_id in signer_ids {
-        let signer = signers.get(&signer_id).unwrap();
-
-        println!("✍️  簽署者 {} 正在生成簽章分片...", signer_id);
-        let signature_share = signer
-            .sign(session_id, &signing_package_data)
-            .context(format!("簽署者 {} 生成簽章分片失敗", signer_id))?;
-
-        let share_hex = hex::encode(signature_share.serialize());
-
-        // 模擬傳輸：Signer -> Coordinator
-        transport.send(
-            MessageMetadata {
-                from: format!("signer_{}", signer_id),
-                to: "coordinator".to_string(),
-                message_type: MessageType::Round2SignatureShare,
-                timestamp: Some(chrono::Utc::now()),
-            },
-            &share_hex,
+    println!("📊 總訊息數: {}", stats.total_messages);
+    println!("📊 總位元組數: {}（二進位編碼）", stats.total_bytes);
+    if let Some(reduction) = stats.size_reduction() {
+        println!(
+            "📊 JSON 編碼需要: {} bytes（節省 {:.1}%）",
+            stats.baseline_bytes,
+            reduction * 100.0
         );
-
-        signature_shares_map.insert(frost::Identifier::try_from(signer_id).unwrap(), signature_share);
-
-        tokio::time::sleep(tokio::time::Duration::from_millis(100)).await;
     }
-
-    println!("✓ Round 2 完成！已收集 {} 個簽章分片\n", signature_shares_map.len());
-    std::thread::sleep(std::time::Duration::from_millis(500));
-
-    // ========================================================================
-    // 聚合簽章
-    // ========================================================================
-    println!("╔════════════════════════════════════════════════════════════════╗");
-    println!("║  階段 5: 聚合簽章                                             ║");
-    println!("╚════════════════════════════════════════════════════════════════╝\n");
-
-    println!("🔗 協調者正在聚合簽章分片...");
-    let group_signature = coordinator
-        .aggregate_signature(&signing_package, &signature_shares_map)
-        .context("聚合簽章失敗")?;
-
-    let signature_hex = hex::encode(group_signature.serialize().unwrap());
-    println!("✓ 簽章聚合成功！");
-    println!("   簽章 (hex): {}", signature_hex);
+    println!("\n訊息類型分布:");
+    for (msg_type, count) in &stats.by_type {
+        println!("   - {:?}: {} 個", msg_type, count);
+    }
     println!();
 
-    // 模擬傳輸：Coordinator -> 廣播
-    transport.send(
-        MessageMetadata {
-            from: "coordinator".to_string(),
-            to: "broadcast".to_string(),
-            message_type: MessageType::FinalSignature,
-            timestamp: Some(chrono::Utc::now()),
-        },
-        &signature_hex,
-    );
-
-    std::thread::sleep(std::time::Duration::from_millis(500));
-
-    // ========================================================================
-    // 驗證簽章
-    // ========================================================================
-    println!("╔════════════════════════════════════════════════════════════════╗");
-    println!("║  階段 6: 驗證簽章                                             ║");
-    println!("╚════════════════════════════════════════════════════════════════╝\n");
-
-    println!("🔍 使用群組公鑰驗證簽章...");
-    coordinator
-        .verify_signature(message_bytes, &group_signature)
-        .context("簽章驗證失敗")?;
-
-    println!("✓ 簽章驗證通過！\n");
-    std::thread::sleep(std::time::Duration::from_millis(500));
-
-    // ========================================================================
-    // 統計資訊
-    // ========================================================================
-    if let Some(stats) = transport.get_stats() {
-        println!("╔════════════════════════════════════════════════════════════════╗");
-        println!("║  傳輸統計                                                     ║");
-        println!("╚════════════════════════════════════════════════════════════════╝\n");
-
-        println!("📊 總訊息數: {}", stats.total_messages);
-        println!("📊 總位元組數: {}", stats.total_bytes);
-        println!("\n訊息類型分布:");
-        for (msg_type, count) in &stats.by_type {
-            println!("   - {:?}: {} 個", msg_type, count);
+    {
+        let state = lora_state.lock().unwrap();
+        println!("📡 空中時間: {}ms", state.total_airtime_ms);
+        if radio.is_some() {
+            println!("📡 工作週期等待: {}ms", state.duty_cycle_wait_ms);
+            println!(
+                "📡 估計真實 LoRa 簽章延遲: {:.1}s",
+                state.simulated_time_ms as f64 / 1000.0
+            );
         }
         println!();
     }
@@ -872,12 +1041,18 @@ async fn cmd_demo_basic(message: &str, signer_ids: &[u16], full_payload: bool) -
     // ========================================================================
     println!("╔════════════════════════════════════════════════════════════════╗");
     println!("║                                                                ║");
-    println!("║   🎉 FROST 3-of-5 門檻簽章展示完成！                          ║");
+    println!("║   🎉 FROST 門檻簽章展示完成！                                 ║");
     println!("║                                                                ║");
     println!("╚════════════════════════════════════════════════════════════════╝\n");
 
     println!("✅ 成功完成以下步驟:");
-    println!("   1. ✓ 生成 5 個金鑰分片（3-of-5 門檻）");
+    println!(
+        "   1. ✓ {} {}-of-{} 群組（{}）",
+        if generated { "生成並儲存" } else { "載入" },
+        group.min_signers(),
+        group.max_signers(),
+        keys_dir.display()
+    );
     println!("   2. ✓ {} 個簽署者參與簽章", signer_ids.len());
     println!("   3. ✓ Round 1: 生成並收集承諾");
     println!("   4. ✓ 建立並分發簽章套件");
@@ -887,22 +1062,28 @@ async fn cmd_demo_basic(message: &str, signer_ids: &[u16], full_payload: bool) -
     println!();
 
     println!("🔐 這就是 FROST 門檻簽章！");
-    println!("   - 任意 3 個簽署者可以合作產生合法的 Schnorr 簽章");
+    println!(
+        "   - 任意 {} 個簽署者可以合作產生合法的 Schnorr 簽章",
+        group.min_signers()
+    );
     println!("   - 協調者永遠不會接觸到任何私鑰分片");
     println!("   - 簽章與單一金鑰產生的簽章無法區分（隱私保護）");
     println!();
 
     println!("💡 已實現:");
     println!("   ✅ SimulatedLoRaTransport（模擬低頻寬傳輸）");
+    println!("   ✅ AsyncTransport（協調者與簽署者只透過傳輸層交換 FrostMessage）");
     println!("   ✅ HTTP Dashboard（即時視覺化傳輸過程）");
     println!();
 
     println!("🌐 HTTP Server 仍在運行...");
+    println!("   POST /sign 使用同一個群組（群組公鑰 {}...）", &group.group_public_key_hex()[..16]);
     println!("   按 Ctrl+C 停止 Server 並結束程式");
     println!();
 
     // 讓 server 繼續運行，等待用戶按 Ctrl+C
     server_handle.await.ok();
+    lora.shutdown();
 
     Ok(())
 }
@@ -911,33 +1092,11 @@ async fn cmd_demo_basic(message: &str, signer_ids: &[u16], full_payload: bool) -
 // HTTP Server - 提供 Dashboard API
 // ============================================================================
 
-/// 啟動 HTTP Server 提供 /status API
-async fn start_http_server(
-    lora_state: Arc<Mutex<LoRaTransportState>>,
-) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
-    use axum::{
-        extract::State,
-        http::Method,
-        response::Json,
-        routing::{get, post},
-        Router,
-    };
-    use tower_http::cors::{Any, CorsLayer};
-
-    // CORS 設定（允許本地 HTML 存取）
-    let cors = CorsLayer::new()
-        .allow_origin(Any)
-        .allow_methods([Method::GET, Method::POST])
-        .allow_headers(Any);
-
-    // 創建 Router
-    let app = Router::new()
-        .route("/health", get(health_check))
-        .route("/status", get(get_status))
-        .route("/sign", post(sign_message))
-        .layer(cors)
-        .with_state(lora_state);
-
+/// 啟動 HTTP Server：與 API 服務（main.rs）相同的路由，加上 Dashboard 與模擬 LoRa 鏈路
+///
+/// `/sign` 使用 Demo 的群組與同一條模擬鏈路，因此 Dashboard 會即時顯示每一次
+/// 簽章的傳輸過程，回應中的 `group_public_key` 也與 Demo 顯示的相同。
+async fn start_http_server(app: axum::Router) -> Result<(), std::io::Error> {
     // 綁定到 0.0.0.0:3000 (允許外部連線)
     let addr = std::net::SocketAddr::from(([0, 0, 0, 0], 3000));
     let listener = tokio::net::TcpListener::bind(addr).await?;
@@ -946,169 +1105,3 @@ async fn start_http_server(
 
     Ok(())
 }
-
-/// GET /health - 健康檢查
-async fn health_check() -> Json<serde_json::Value> {
-    Json(serde_json::json!({
-        "status": "ok",
-        "service": "frost-threshold-signature",
-        "version": env!("CARGO_PKG_VERSION")
-    }))
-}
-
-/// GET /status - 回傳當前 LoRa 傳輸狀態
-async fn get_status(
-    State(lora_state): State<Arc<Mutex<LoRaTransportState>>>,
-) -> Json<LoRaTransportState> {
-    let state = lora_state.lock().unwrap();
-    Json(state.clone())
-}
-
-/// POST /sign - 執行簽章操作
-///
-/// Request Body:
-/// ```json
-/// {
-///   "message": "test message",
-///   "signer_ids": [1, 2, 3]
-/// }
-/// ```
-async fn sign_message(
-    State(lora_state): State<Arc<Mutex<LoRaTransportState>>>,
-    axum::extract::Json(payload): axum::extract::Json<SignRequest>,
-) -> Json<SignResponse> {
-    use frost_secp256k1 as frost;
-    use rand::SeedableRng;
-
-    // 重置狀態
-    {
-        let mut state = lora_state.lock().unwrap();
-        state.current_phase = "Starting".to_string();
-        state.progress = 0.0;
-        state.recent_events.clear();
-        state.total_retries = 0;
-    }
-
-    let mut rng = rand::rngs::StdRng::from_entropy();
-    let message_bytes = payload.message.as_bytes().to_vec();
-
-    // 執行簽章流程
-    let result: Result<SignResponse> = async {
-        // Step 1: 生成金鑰
-        {
-            let mut state = lora_state.lock().unwrap();
-            state.current_phase = "KeyGeneration".to_string();
-            state.progress = 0.1;
-        }
-
-        let (shares, pubkey_package) = frost::keys::generate_with_dealer(
-            5,
-            3,
-            frost::keys::IdentifierList::Default,
-            &mut rng,
-        )
-        .map_err(|e| anyhow::anyhow!("金鑰生成失敗: {:?}", e))?;
-
-        // Step 2: Round 1
-        {
-            let mut state = lora_state.lock().unwrap();
-            state.current_phase = "Round1".to_string();
-            state.progress = 0.3;
-        }
-
-        let mut nonces = std::collections::BTreeMap::new();
-        let mut commitments = std::collections::BTreeMap::new();
-
-        for &id in &payload.signer_ids {
-            let identifier = frost::Identifier::try_from(id)
-                .map_err(|e| anyhow::anyhow!("無效的簽署者 ID {}: {:?}", id, e))?;
-
-            let share = shares.get(&identifier)
-                .ok_or_else(|| anyhow::anyhow!("找不到簽署者 {} 的金鑰分片", id))?;
-
-            let (nonce, commitment) = frost::round1::commit(share.signing_share(), &mut rng);
-            nonces.insert(identifier, nonce);
-            commitments.insert(identifier, commitment);
-
-            tokio::time::sleep(tokio::time::Duration::from_millis(100)).await;
-        }
-
-        // Step 3: Round 2
-        {
-            let mut state = lora_state.lock().unwrap();
-            state.current_phase = "Round2".to_string();
-            state.progress = 0.6;
-        }
-
-        let signing_package = frost::SigningPackage::new(commitments, &message_bytes);
-        let mut signature_shares = std::collections::BTreeMap::new();
-
-        for &id in &payload.signer_ids {
-            let identifier = frost::Identifier::try_from(id).unwrap();
-            let share = shares.get(&identifier).unwrap();
-            let nonce = nonces.get(&identifier).unwrap();
-
-            let key_package = frost::keys::KeyPackage::try_from(share.clone())
-                .map_err(|e| anyhow::anyhow!("KeyPackage 轉換失敗: {:?}", e))?;
-            let signature_share = frost::round2::sign(&signing_package, nonce, &key_package)
-                .map_err(|e| anyhow::anyhow!("簽章分片生成失敗: {:?}", e))?;
-
-            signature_shares.insert(identifier, signature_share);
-
-            tokio::time::sleep(tokio::time::Duration::from_millis(100)).await;
-        }
-
-        // Step 4: 聚合簽章
-        {
-            let mut state = lora_state.lock().unwrap();
-            state.current_phase = "Aggregating".to_string();
-            state.progress = 0.9;
-        }
-
-        let group_signature = frost::aggregate(&signing_package, &signature_shares, &pubkey_package)
-            .map_err(|e| anyhow::anyhow!("簽章聚合失敗: {:?}", e))?;
-
-        // Step 5: 驗證簽章
-        let verifying_key = pubkey_package.verifying_key();
-        let is_valid = verifying_key.verify(&message_bytes, &group_signature).is_ok();
-
-        {
-            let mut state = lora_state.lock().unwrap();
-            state.current_phase = "Complete".to_string();
-            state.progress = 1.0;
-        }
-
-        Ok(SignResponse {
-            signature: hex::encode(group_signature.serialize().unwrap()),
-            verified: is_valid,
-            message: payload.message.clone(),
-            signer_ids: payload.signer_ids.clone(),
-        })
-    }.await;
-
-    match result {
-        Ok(response) => Json(response),
-        Err(e) => Json(SignResponse {
-            signature: format!("Error: {}", e),
-            verified: false,
-            message: payload.message,
-            signer_ids: payload.signer_ids,
-        }),
-    }
-}
-
-/// 簽章請求
-#[derive(serde::Deserialize)]
-struct SignRequest {
-    message: String,
-    signer_ids: Vec<u16>,
-}
-
-/// 簽章回應
-#[derive(serde::Serialize)]
-struct SignResponse {
-    signature: String,
-    verified: bool,
-    message: String,
-    signer_ids: Vec<u16>,
-}
@@ -11,9 +11,15 @@
 //! ├── create-pkg   - 建立簽章套件（Coordinator 角色）
 //! ├── round2       - Round 2: 生成簽章分片（Signer 角色）
 //! ├── aggregate    - 聚合最終簽章（Coordinator 角色）
-//! └── verify       - 驗證簽章（任何人）
+//! ├── verify       - 驗證簽章（任何人）
+//! ├── participate  - 自動處理 inbox 中的協議訊息（Signer 角色，air-gapped）
+//! ├── qr-export    - 把 JSON 檔匯出成多段 QR Code（任何人）
+//! ├── qr-import    - 從掃描的 QR Code 影像還原 JSON 檔（任何人）
+//! └── audit
+//!     └── verify   - 驗證稽核日誌的雜湊鏈（任何人）
 //! ```
 
+use crate::transport::QrFormat;
 use clap::{Parser, Subcommand};
 use std::path::PathBuf;
 
@@ -213,7 +219,7 @@ pub enum Commands {
     /// 使用 Transport 抽象層展示訊息傳遞的過程。
     ///
     /// 這個命令會：
-    /// 1. 生成 5 個金鑰分片（3-of-5 門檻）
+    /// 1. 從金鑰目錄載入群組（不存在時生成 3-of-5 並儲存，之後重複使用）
     /// 2. 選擇 3 個簽署者參與簽章
     /// 3. 執行 Round 1（生成承諾）
     /// 4. 建立簽章套件
@@ -236,6 +242,120 @@ pub enum Commands {
         /// 是否顯示完整的 payload（預設：false）
         #[arg(long)]
         full_payload: bool,
+
+        /// 使用 LoRa 物理層模型（EU868）的擴頻因子 7-12；以虛擬時鐘估算真實延遲
+        #[arg(long, value_parser = clap::value_parser!(u8).range(7..=12))]
+        radio_sf: Option<u8>,
+
+        /// 使用物理層模型時，收發兩端的距離（公尺）
+        #[arg(long, default_value_t = 2000.0, requires = "radio_sf")]
+        distance_m: f64,
+
+        /// 群組金鑰目錄（與 keygen 的輸出格式相同；Demo 與 HTTP /sign 共用此群組）
+        #[arg(long, default_value = "frost-data")]
+        keys_dir: PathBuf,
+    },
+
+    /// 【Signer】自動處理 inbox 中的協議訊息（air-gapped 儀式）
+    ///
+    /// 持續輪詢 inbox 目錄，回應協調者的承諾請求與簽章套件，
+    /// 回覆寫到 outbox 下的 coordinator/ 目錄。操作者只需在機器之間傳遞
+    /// USB 隨身碟，不必逐步輸入命令。
+    ///
+    /// Nonce 只保存在記憶體中：Round 1 與 Round 2 之間不可結束此程式。
+    ///
+    /// 目錄慣例：
+    /// - {outbox}/coordinator/ - 寫給協調者的訊息
+    /// - {inbox} - 通常是 {outbox}/signer_{id}/
+    Participate {
+        /// 此簽署者的金鑰分片檔案
+        #[arg(short, long)]
+        share_file: PathBuf,
+
+        /// 讀取訊息的目錄
+        #[arg(long)]
+        inbox: PathBuf,
+
+        /// 寫出訊息的根目錄（每個接收者一個子目錄）
+        #[arg(long)]
+        outbox: PathBuf,
+
+        /// 輪詢 inbox 的間隔（毫秒）
+        #[arg(long, default_value_t = 500)]
+        poll_ms: u64,
+
+        /// 閒置多久沒有新訊息後結束（秒，預設持續執行）
+        #[arg(long)]
+        idle_timeout_secs: Option<u64>,
+    },
+
+    /// 【Air-gap】把承諾、簽章套件或簽章分片 JSON 檔匯出成動畫 QR Code
+    ///
+    /// 以噴泉編碼產生多段 QR Code：掃描端可以亂序、漏掉部分 frame。
+    ///
+    /// 輸出檔案：
+    /// - {output-dir}/frame-0001.png ...（或 .svg；terminal 格式直接播放）
+    QrExport {
+        /// 要匯出的 JSON 檔（commitment / package / share）
+        #[arg(short, long)]
+        input: PathBuf,
+
+        /// 輸出目錄
+        #[arg(short, long, default_value = "qr-frames")]
+        output_dir: PathBuf,
+
+        /// 輸出格式：png、svg 或 terminal
+        #[arg(short, long, default_value = "png")]
+        format: QrFormat,
+
+        /// 每段的最大片段大小（bytes）
+        #[arg(long, default_value_t = 200)]
+        max_fragment_len: usize,
+
+        /// 額外產生的混合分段數（預設與原始片段數相同）
+        #[arg(long)]
+        extra_parts: Option<usize>,
+    },
+
+    /// 【Air-gap】從掃描的 QR Code 影像還原 JSON 檔
+    ///
+    /// 依序讀取影像（或目錄中的所有影像），收集到足夠的分段即停止。
+    QrImport {
+        /// 掃描的影像檔或包含影像的目錄
+        #[arg(long, num_args = 1.., required = true)]
+        frames: Vec<PathBuf>,
+
+        /// 還原後的 JSON 檔
+        #[arg(short, long)]
+        output: PathBuf,
+    },
+
+    /// 【Auditor】稽核日誌工具
+    Audit {
+        #[command(subcommand)]
+        action: AuditAction,
+    },
+}
+
+/// 稽核日誌子命令
+#[derive(Subcommand, Debug)]
+pub enum AuditAction {
+    /// 驗證稽核日誌的雜湊鏈
+    ///
+    /// 逐行重新計算雜湊並驗證檢查點簽章，回報被竄改的紀錄與序號缺口。
+    /// 日誌完整時以 0 結束，否則返回錯誤。
+    Verify {
+        /// 稽核日誌檔案（JSONL）
+        #[arg(default_value = "frost-audit.jsonl")]
+        file: PathBuf,
+
+        /// 檢查點必須由此公鑰簽署（hex，見 `GET /audit/head`）
+        #[arg(long)]
+        checkpoint_key: Option<String>,
+
+        /// 先前記錄的鏈頭雜湊，必須仍在日誌中（偵測截斷或重寫）
+        #[arg(long)]
+        expect_head: Option<String>,
     },
 }
 
@@ -261,6 +381,10 @@ impl Commands {
             Commands::Aggregate { .. } => "aggregate",
             Commands::Verify { .. } => "verify",
             Commands::DemoBasic { .. } => "demo-basic",
+            Commands::Participate { .. } => "participate",
+            Commands::QrExport { .. } => "qr-export",
+            Commands::QrImport { .. } => "qr-import",
+            Commands::Audit { .. } => "audit",
         }
     }
 }
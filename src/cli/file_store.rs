@@ -1,7 +1,7 @@
 //! # 檔案儲存 - JSON 序列化與反序列化
 //!
 //! 此模組處理所有中間結果的檔案操作：
-//! - 金鑰分片
+//! - 金鑰分片與簽署者身分金鑰（單一檔案或整個群組的金鑰目錄）
 //! - 承諾
 //! - 簽章套件
 //! - 簽章分片
@@ -12,7 +12,10 @@
 //! 2. 二進位資料（金鑰、簽章等）使用 hex 編碼
 //! 3. 提供友善的錯誤訊息
 
-use crate::api::{CommitmentData, SigningPackageData};
+use crate::api::{signer_id_from_identifier, CommitmentData, GroupId, SigningPackageData};
+use crate::envelope::{IdentityKey, Roster};
+use crate::group::{GroupServices, SigningGroup};
+use crate::signer::Signer;
 use anyhow::{Context, Result};
 use frost_secp256k1 as frost;
 use serde::{Deserialize, Serialize};
@@ -33,6 +36,10 @@ pub struct KeyShareFile {
     /// 序列化的金鑰分片（hex 編碼）
     pub key_package_hex: String,
 
+    /// 簽署者的身分私鑰（hex 編碼，對應 `pubkey.json` 公布的名冊）
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub identity_key_hex: Option<String>,
+
     /// 元資訊
     pub metadata: KeyShareMetadata,
 }
@@ -53,10 +60,43 @@ pub struct PublicKeyFile {
     /// 群組公鑰（hex 編碼，方便驗證）
     pub group_pubkey_hex: String,
 
+    /// 群組 ID（金鑰目錄載入時沿用，讓持久化的 Session 能對應回群組）
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub group_id: Option<GroupId>,
+
+    /// 公布的身分名冊：簽署者 ID → 身分公鑰（hex 編碼）
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub identity_keys: BTreeMap<u16, String>,
+
     /// 元資訊
     pub metadata: KeyShareMetadata,
 }
 
+/// 從金鑰目錄載入的整個群組
+#[derive(Debug, Clone)]
+pub struct GroupKeys {
+    /// 群組公鑰套件
+    pub pubkey_package: frost::keys::PublicKeyPackage,
+
+    /// 所有簽署者的金鑰分片
+    pub key_packages: BTreeMap<frost::Identifier, frost::keys::KeyPackage>,
+
+    /// 門檻值
+    pub threshold: u16,
+
+    /// 總簽署者數
+    pub max_signers: u16,
+
+    /// 群組 ID（舊版金鑰目錄沒有記錄）
+    pub group_id: Option<GroupId>,
+
+    /// 本機簽署者的身分金鑰（舊版金鑰目錄沒有記錄時為空）
+    pub identity_keys: BTreeMap<u16, IdentityKey>,
+
+    /// 公布的身分名冊（可包含不在此目錄中的簽署者）
+    pub roster: Roster,
+}
+
 /// 承諾檔案格式
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct CommitmentFile {
@@ -136,10 +176,22 @@ impl FileStore {
         key_package: &frost::keys::KeyPackage,
         threshold: u16,
         max_signers: u16,
+    ) -> Result<()> {
+        Self::write_key_share_file(path, signer_id, key_package, None, threshold, max_signers)
+    }
+
+    fn write_key_share_file(
+        path: &Path,
+        signer_id: u16,
+        key_package: &frost::keys::KeyPackage,
+        identity: Option<&IdentityKey>,
+        threshold: u16,
+        max_signers: u16,
     ) -> Result<()> {
         let key_share_file = KeyShareFile {
             signer_id,
             key_package_hex: hex::encode(key_package.serialize()?),
+            identity_key_hex: identity.map(IdentityKey::to_secret_hex),
             metadata: KeyShareMetadata {
                 created_at: chrono::Utc::now().to_rfc3339(),
                 threshold,
@@ -155,6 +207,13 @@ impl FileStore {
 
     /// 載入金鑰分片
     pub fn load_key_share(path: &Path) -> Result<frost::keys::KeyPackage> {
+        Self::load_key_share_with_identity(path).map(|(key_package, _)| key_package)
+    }
+
+    /// 載入金鑰分片與其中保存的身分金鑰（舊版金鑰檔沒有身分金鑰）
+    pub fn load_key_share_with_identity(
+        path: &Path,
+    ) -> Result<(frost::keys::KeyPackage, Option<IdentityKey>)> {
         let json = fs::read_to_string(path)
             .context(format!("Failed to read key share file: {}", path.display()))?;
 
@@ -164,8 +223,27 @@ impl FileStore {
         let key_package_bytes = hex::decode(&key_share_file.key_package_hex)
             .context("Failed to decode key package hex")?;
 
-        frost::keys::KeyPackage::deserialize(&key_package_bytes)
-            .map_err(|e| anyhow::anyhow!("Failed to deserialize key package: {:?}", e))
+        let key_package = frost::keys::KeyPackage::deserialize(&key_package_bytes)
+            .map_err(|e| anyhow::anyhow!("Failed to deserialize key package: {:?}", e))?;
+
+        let identity = key_share_file
+            .identity_key_hex
+            .as_deref()
+            .map(IdentityKey::from_secret_hex)
+            .transpose()
+            .context("Failed to decode identity key")?;
+
+        Ok((key_package, identity))
+    }
+
+    /// 由金鑰檔建立簽署者（有保存身分金鑰時沿用，否則生成新的）
+    pub fn load_signer(path: &Path) -> Result<Signer> {
+        let (key_package, identity) = Self::load_key_share_with_identity(path)?;
+        let signer = Signer::from_key_package(key_package);
+        Ok(match identity {
+            Some(identity) => signer.with_identity_key(identity),
+            None => signer,
+        })
     }
 
     /// 儲存群組公鑰
@@ -175,17 +253,30 @@ impl FileStore {
         threshold: u16,
         max_signers: u16,
     ) -> Result<()> {
-        let pubkey_file = PublicKeyFile {
+        let pubkey_file = Self::public_key_file(pubkey_package, threshold, max_signers)?;
+        Self::write_public_key_file(path, &pubkey_file)
+    }
+
+    fn public_key_file(
+        pubkey_package: &frost::keys::PublicKeyPackage,
+        threshold: u16,
+        max_signers: u16,
+    ) -> Result<PublicKeyFile> {
+        Ok(PublicKeyFile {
             pubkey_package_hex: hex::encode(pubkey_package.serialize()?),
             group_pubkey_hex: hex::encode(pubkey_package.verifying_key().serialize()?),
+            group_id: None,
+            identity_keys: BTreeMap::new(),
             metadata: KeyShareMetadata {
                 created_at: chrono::Utc::now().to_rfc3339(),
                 threshold,
                 max_signers,
             },
-        };
+        })
+    }
 
-        let json = serde_json::to_string_pretty(&pubkey_file)?;
+    fn write_public_key_file(path: &Path, pubkey_file: &PublicKeyFile) -> Result<()> {
+        let json = serde_json::to_string_pretty(pubkey_file)?;
         fs::write(path, json).context("Failed to write public key file")?;
 
         Ok(())
@@ -206,6 +297,232 @@ impl FileStore {
             .map_err(|e| anyhow::anyhow!("Failed to deserialize public key package: {:?}", e))
     }
 
+    // ========================================================================
+    // 群組金鑰目錄（`frost-cli keygen` 的輸出格式）
+    // ========================================================================
+
+    /// 儲存整個群組：每個簽署者一個 `share_{id}.json`，加上 `pubkey.json`
+    ///
+    /// 每個簽署者會生成一把身分金鑰，私鑰寫入自己的金鑰檔，公鑰則與群組 ID
+    /// 一起公布在 `pubkey.json` 中，載入時以此組成名冊。
+    pub fn save_group_keys(
+        dir: &Path,
+        group_id: GroupId,
+        pubkey_package: &frost::keys::PublicKeyPackage,
+        key_packages: &BTreeMap<frost::Identifier, frost::keys::KeyPackage>,
+        threshold: u16,
+        max_signers: u16,
+    ) -> Result<Vec<(u16, std::path::PathBuf)>> {
+        Self::ensure_dir(dir)?;
+
+        let mut pubkey_file = Self::public_key_file(pubkey_package, threshold, max_signers)?;
+        pubkey_file.group_id = Some(group_id);
+
+        let mut share_paths = Vec::new();
+        for (identifier, key_package) in key_packages {
+            let signer_id = signer_id_from_identifier(identifier);
+            let identity = IdentityKey::generate();
+            let share_path = dir.join(format!("share_{}.json", signer_id));
+            Self::write_key_share_file(
+                &share_path,
+                signer_id,
+                key_package,
+                Some(&identity),
+                threshold,
+                max_signers,
+            )?;
+            pubkey_file
+                .identity_keys
+                .insert(signer_id, identity.public_key().to_hex());
+            share_paths.push((signer_id, share_path));
+        }
+
+        Self::write_public_key_file(&dir.join("pubkey.json"), &pubkey_file)?;
+
+        Ok(share_paths)
+    }
+
+    /// 載入整個群組
+    ///
+    /// 以分片內的 Identifier 為準（不依賴檔名），並確認每個分片都屬於同一個群組公鑰、
+    /// 每把身分金鑰都與 `pubkey.json` 公布的名冊相符。
+    pub fn load_group_keys(dir: &Path) -> Result<GroupKeys> {
+        let pubkey_path = dir.join("pubkey.json");
+        let json = fs::read_to_string(&pubkey_path)
+            .context(format!("Failed to read public key file: {}", pubkey_path.display()))?;
+        let pubkey_file: PublicKeyFile = serde_json::from_str(&json)
+            .context("Failed to parse public key JSON")?;
+        let pubkey_package_bytes = hex::decode(&pubkey_file.pubkey_package_hex)
+            .context("Failed to decode public key hex")?;
+        let pubkey_package = frost::keys::PublicKeyPackage::deserialize(&pubkey_package_bytes)
+            .map_err(|e| anyhow::anyhow!("Failed to deserialize public key package: {:?}", e))?;
+        let roster = Roster::from_hex_map(&pubkey_file.identity_keys)
+            .context("Failed to decode published identity keys")?;
+
+        let mut share_paths: Vec<_> = fs::read_dir(dir)
+            .context(format!("Failed to read key directory: {}", dir.display()))?
+            .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+            .filter(|path| {
+                path.file_name()
+                    .and_then(|name| name.to_str())
+                    .is_some_and(|name| name.starts_with("share_") && name.ends_with(".json"))
+            })
+            .collect();
+        share_paths.sort();
+
+        let mut key_packages = BTreeMap::new();
+        let mut identity_keys = BTreeMap::new();
+        for path in share_paths {
+            let (key_package, identity) = Self::load_key_share_with_identity(&path)?;
+            if key_package.verifying_key() != pubkey_package.verifying_key() {
+                anyhow::bail!(
+                    "Key share {} does not belong to the group in {}",
+                    path.display(),
+                    pubkey_path.display()
+                );
+            }
+
+            // 舊版目錄（沒有公布名冊）不檢查身分金鑰，由呼叫者決定是否補上
+            let signer_id = signer_id_from_identifier(key_package.identifier());
+            if !roster.is_empty() {
+                let published = roster.get(signer_id);
+                match identity {
+                    Some(identity) if published == Some(&identity.public_key()) => {
+                        identity_keys.insert(signer_id, identity);
+                    }
+                    _ => anyhow::bail!(
+                        "Identity key in {} does not match the roster in {}",
+                        path.display(),
+                        pubkey_path.display()
+                    ),
+                }
+            }
+            key_packages.insert(*key_package.identifier(), key_package);
+        }
+
+        let KeyShareMetadata {
+            threshold,
+            max_signers,
+            ..
+        } = pubkey_file.metadata;
+        if key_packages.len() < threshold as usize {
+            anyhow::bail!(
+                "Key directory {} holds {} shares, fewer than the threshold {}",
+                dir.display(),
+                key_packages.len(),
+                threshold
+            );
+        }
+
+        Ok(GroupKeys {
+            pubkey_package,
+            key_packages,
+            threshold,
+            max_signers,
+            group_id: pubkey_file.group_id,
+            identity_keys,
+            roster,
+        })
+    }
+
+    /// 從金鑰目錄建立簽章群組；目錄中還沒有群組時以 Trusted Dealer 生成並儲存
+    ///
+    /// 載入既有群組時使用目錄中的門檻值、簽署者數量、群組 ID 與身分金鑰
+    /// （`max_signers` / `min_signers` 只用於生成）。沒有群組 ID 或身分名冊的
+    /// 舊目錄會補上後重新寫入。返回群組與是否為新生成。
+    pub fn load_or_generate_group(
+        dir: &Path,
+        max_signers: u16,
+        min_signers: u16,
+        services: &GroupServices,
+    ) -> Result<(SigningGroup, bool)> {
+        let generated = !dir.join("pubkey.json").exists();
+        if generated {
+            let (shares, pubkey_package) = frost::keys::generate_with_dealer(
+                max_signers,
+                min_signers,
+                frost::keys::IdentifierList::Default,
+                rand::thread_rng(),
+            )
+            .context("Key generation failed")?;
+
+            let key_packages = Self::key_packages_from_shares(shares)?;
+            Self::save_group_keys(
+                dir,
+                GroupId::new(),
+                &pubkey_package,
+                &key_packages,
+                min_signers,
+                max_signers,
+            )?;
+        }
+
+        let load = || {
+            Self::load_group_keys(dir)
+                .context(format!("Failed to load key directory {}", dir.display()))
+        };
+        let mut keys = load()?;
+        if keys.group_id.is_none() || keys.roster.is_empty() {
+            Self::save_group_keys(
+                dir,
+                keys.group_id.unwrap_or_else(GroupId::new),
+                &keys.pubkey_package,
+                &keys.key_packages,
+                keys.threshold,
+                keys.max_signers,
+            )?;
+            keys = load()?;
+        }
+
+        let GroupKeys {
+            pubkey_package,
+            key_packages,
+            threshold,
+            max_signers,
+            group_id,
+            mut identity_keys,
+            roster,
+        } = keys;
+        let signers = key_packages
+            .into_values()
+            .map(|key_package| {
+                let signer_id = signer_id_from_identifier(key_package.identifier());
+                let signer = Signer::from_key_package(key_package);
+                match identity_keys.remove(&signer_id) {
+                    Some(identity) => signer.with_identity_key(identity),
+                    None => signer,
+                }
+            })
+            .collect();
+
+        let group = SigningGroup::from_signers(
+            group_id.context("Key directory has no group ID")?,
+            pubkey_package,
+            signers,
+            roster,
+            threshold,
+            max_signers,
+            services,
+        );
+        Ok((group, generated))
+    }
+
+    /// 驗證 Dealer 產生的 SecretShare 並轉換為可儲存的 KeyPackage
+    pub fn key_packages_from_shares(
+        shares: BTreeMap<frost::Identifier, frost::keys::SecretShare>,
+    ) -> Result<BTreeMap<frost::Identifier, frost::keys::KeyPackage>> {
+        shares
+            .into_iter()
+            .map(|(identifier, secret_share)| {
+                frost::keys::KeyPackage::try_from(secret_share)
+                    .map(|key_package| (identifier, key_package))
+                    .map_err(|e| {
+                        anyhow::anyhow!("Failed to convert SecretShare to KeyPackage: {:?}", e)
+                    })
+            })
+            .collect()
+    }
+
     // ========================================================================
     // 承諾相關
     // ========================================================================
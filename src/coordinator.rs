@@ -11,14 +11,47 @@
 //! - 協調者**永不持有**私鑰分片
 //! - 協調者**永不接觸**秘密 nonces
 //! - 協調者可以是不受信任的（它無法偽造簽章）
-
-use crate::api::{CommitmentData, SessionId, SigningPackageData};
+//!
+//! ## Session 持久化
+//! Session 狀態存放在可插拔的 `SessionStore` 中（預設為記憶體），
+//! 使用持久化後端時，協調者重新啟動後可透過 `session_store::recover_sessions`
+//! 恢復或中止未完成的 Session。
+//!
+//! ## 在 Transport 上執行
+//! `orchestrate_over_transport` 只透過 `AsyncTransport` 與簽署者交換
+//! `FrostMessage`（簽署者端見 `Signer::serve`），可用於任何傳輸層；
+//! `orchestrate_signing` 把同一個行程內的簽署者接上 `ChannelNetwork` 後走同一條路徑。
+//! 每個會話在 `signing_session` span 中執行，送給簽署者的請求附帶該 span 的
+//! trace context，簽署者的日誌因此可以接上同一個 trace（見 `telemetry` 模組）。
+//!
+//! ## Session 狀態機
+//! 每個 Session 都有明確的階段（`SessionStatus`），不符合目前階段的操作
+//! （例如在發出簽章套件後才提交承諾）會被拒絕並返回 `InvalidSessionState`。
+//! 終止狀態的 Session 會保留一段時間供查詢，之後由 `prune_finished_sessions` 清除。
+
+use crate::api::{
+    signer_id_from_identifier, ActiveSessionCounts, CommitmentData, GroupId, MessageKind,
+    RoundTimings, SessionId, SessionInfo, SessionStatus, SignPayload, SignatureShareData,
+    SigningPackageData,
+};
+use crate::audit::{AuditEvent, AuditLog};
+use crate::envelope::{EnvelopeKind, Roster};
+use crate::message::MessageError;
+use crate::metrics::Metrics;
+use crate::session_store::{MemorySessionStore, SessionStore, SessionStoreError};
 use crate::signer::Signer;
-use dashmap::DashMap;
+use crate::telemetry::{self, TraceContext};
+use crate::transport::{
+    AsyncTransport, ChannelNetwork, FrostMessage, Participant, TransportEnvelope, TransportError,
+};
 use frost_secp256k1 as frost;
-use std::collections::BTreeMap;
-use std::sync::Arc;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::collections::{BTreeMap, BTreeSet};
+use std::sync::{Arc, RwLock};
+use std::time::{Duration, Instant};
 use thiserror::Error;
+use tracing::Instrument;
 
 // ============================================================================
 // 錯誤定義
@@ -52,39 +85,237 @@ pub enum CoordinatorError {
 
     #[error("Signature share deserialization failed: {0}")]
     ShareDeserializationFailed(String),
+
+    #[error("Unauthenticated message: {0}")]
+    UnauthenticatedMessage(String),
+
+    #[error("Session store error: {0}")]
+    SessionStore(#[from] SessionStoreError),
+
+    #[error("Invalid signing request: {0}")]
+    InvalidMessage(#[from] MessageError),
+
+    #[error("Session {session_id} is {status}, cannot move to {next}")]
+    InvalidSessionState {
+        session_id: SessionId,
+        status: SessionStatus,
+        next: SessionStatus,
+    },
+
+    #[error("Transport error: {0}")]
+    Transport(#[from] TransportError),
+
+    #[error("{round} timed out in session {session_id}, missing signers {missing:?}")]
+    RoundTimeout {
+        session_id: SessionId,
+        round: &'static str,
+        missing: Vec<u16>,
+    },
+
+    #[error("Coordinator is no longer accepting new sessions")]
+    NotAcceptingSessions,
 }
 
+/// 未完成 Session 的超時時間（秒）
+pub const SESSION_TIMEOUT_SECS: i64 = 300;
+
+/// 已終止 Session 保留供查詢的時間（秒）
+pub const FINISHED_SESSION_RETENTION_SECS: i64 = 3600;
+
+/// 每一輪等待簽署者回覆的預設上限（所有傳輸層共用，可由 `timeouts.round_secs` 設定）
+pub const DEFAULT_ROUND_TIMEOUT: Duration = Duration::from_secs(30);
+
 // ============================================================================
 // Session 狀態管理
 // ============================================================================
 
 /// 簽章會話的狀態
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct SessionState {
     /// Session ID
     pub session_id: SessionId,
 
-    /// 要簽署的訊息
+    /// 所屬群組（共用持久化 Store 時用於區分與恢復）
+    #[serde(default)]
+    pub group_id: Option<GroupId>,
+
+    /// 目前階段
+    #[serde(default)]
+    pub status: SessionStatus,
+
+    /// 要簽署的訊息（typed payload 時為簽署者計算出的摘要）
     pub message: Vec<u8>,
 
+    /// 訊息類型
+    #[serde(default)]
+    pub message_kind: MessageKind,
+
+    /// 結構化的簽章請求（raw 訊息時為 None）
+    #[serde(default)]
+    pub payload: Option<SignPayload>,
+
     /// Round 1 收集的承諾
     pub commitments: Vec<CommitmentData>,
 
+    /// 已提交 Round 2 分片的簽署者
+    #[serde(default)]
+    pub shares_received: Vec<u16>,
+
+    /// 最終簽章（hex，僅 Aggregated）
+    #[serde(default)]
+    pub signature: Option<String>,
+
+    /// 失敗原因（僅 Failed / Expired）
+    #[serde(default)]
+    pub failure_reason: Option<String>,
+
     /// 建立時間（用於超時處理）
     pub created_at: chrono::DateTime<chrono::Utc>,
+
+    /// 最後一次狀態變更時間
+    #[serde(default = "chrono::Utc::now")]
+    pub updated_at: chrono::DateTime<chrono::Utc>,
 }
 
 impl SessionState {
     pub fn new(session_id: SessionId, message: Vec<u8>) -> Self {
+        let now = chrono::Utc::now();
         Self {
             session_id,
+            group_id: None,
+            status: SessionStatus::Created,
             message,
+            message_kind: MessageKind::Raw,
+            payload: None,
             commitments: Vec::new(),
-            created_at: chrono::Utc::now(),
+            shares_received: Vec::new(),
+            signature: None,
+            failure_reason: None,
+            created_at: now,
+            updated_at: now,
+        }
+    }
+
+    /// 轉換為 `GET /sessions/:id` 的回應
+    pub fn to_info(&self) -> SessionInfo {
+        SessionInfo {
+            session_id: self.session_id,
+            group_id: self.group_id,
+            status: self.status,
+            message_kind: self.message_kind,
+            message_sha256: hex::encode(Sha256::digest(&self.message)),
+            commitments_received: self.commitments.iter().map(|c| c.signer_id).collect(),
+            shares_received: self.shares_received.clone(),
+            signature: self.signature.clone(),
+            failure_reason: self.failure_reason.clone(),
+            created_at: self.created_at,
+            updated_at: self.updated_at,
         }
     }
 }
 
+// ============================================================================
+// 簽章結果
+// ============================================================================
+
+/// `orchestrate_signing` 的結果
+///
+/// 攜帶實際使用的 Session ID，讓呼叫者能把簽章與稽核日誌對應起來。
+#[derive(Debug, Clone)]
+pub struct SigningOutcome {
+    /// 此次簽章使用的 Session ID
+    pub session_id: SessionId,
+
+    /// 最終的群組簽章
+    pub signature: frost::Signature,
+
+    /// 參與簽署的簽署者 ID（已排序）
+    pub signer_ids: Vec<u16>,
+
+    /// 訊息類型
+    pub message_kind: MessageKind,
+
+    /// 實際被簽署的 bytes（typed payload 時為摘要）
+    pub message: Vec<u8>,
+
+    /// 各階段耗時
+    pub timings: RoundTimings,
+}
+
+impl SigningOutcome {
+    /// hex 編碼的簽章
+    pub fn signature_hex(&self) -> String {
+        hex::encode(self.signature.serialize().unwrap())
+    }
+
+    /// 以群組公鑰重新驗證簽章
+    ///
+    /// 產生 `SigningOutcome` 前已驗證過一次；此方法供回應或外部檢查時獨立確認。
+    pub fn verify(&self, group_public_key: &frost::VerifyingKey) -> bool {
+        group_public_key.verify(&self.message, &self.signature).is_ok()
+    }
+}
+
+/// 在期限前接收下一則訊息，逾時返回 `RoundTimeout`
+async fn recv_before<T: AsyncTransport>(
+    transport: &mut T,
+    deadline: tokio::time::Instant,
+    session_id: SessionId,
+    round: &'static str,
+    pending: &BTreeSet<u16>,
+) -> Result<TransportEnvelope, CoordinatorError> {
+    match tokio::time::timeout_at(deadline, transport.recv()).await {
+        Ok(envelope) => Ok(envelope?),
+        Err(_) => Err(CoordinatorError::RoundTimeout {
+            session_id,
+            round,
+            missing: pending.iter().copied().collect(),
+        }),
+    }
+}
+
+/// 訊息是否來自此會話中仍在等待回覆的簽署者；其他訊息（過期、重複）會被忽略
+fn expected_sender(
+    envelope: &TransportEnvelope,
+    session_id: SessionId,
+    pending: &BTreeSet<u16>,
+) -> Option<u16> {
+    match envelope.from {
+        Participant::Signer(signer_id)
+            if envelope.message.session_id() == session_id && pending.contains(&signer_id) =>
+        {
+            Some(signer_id)
+        }
+        _ => {
+            tracing::debug!(
+                session_id = %session_id,
+                from = %envelope.from,
+                message_type = %envelope.message.message_type(),
+                "Ignoring unexpected message"
+            );
+            None
+        }
+    }
+}
+
+/// 反序列化一個 Round 1 承諾
+pub(crate) fn decode_commitment(
+    commitment: &CommitmentData,
+) -> Result<(frost::Identifier, frost::round1::SigningCommitments), CoordinatorError> {
+    let identifier = frost::Identifier::try_from(commitment.signer_id)
+        .map_err(|e| CoordinatorError::CommitmentDeserializationFailed(format!("{:?}", e)))?;
+    let bytes = hex::decode(&commitment.commitment)
+        .map_err(|e| CoordinatorError::CommitmentDeserializationFailed(e.to_string()))?;
+    let commitment = frost::round1::SigningCommitments::deserialize(&bytes)
+        .map_err(|e| CoordinatorError::CommitmentDeserializationFailed(format!("{:?}", e)))?;
+    Ok((identifier, commitment))
+}
+
+/// 自某個時間點起經過的毫秒數
+fn elapsed_ms(since: Instant) -> u64 {
+    since.elapsed().as_millis() as u64
+}
+
 // ============================================================================
 // Coordinator 結構
 // ============================================================================
@@ -104,7 +335,24 @@ pub struct Coordinator {
     threshold: u16,
 
     /// 當前活動的會話狀態
-    sessions: Arc<DashMap<SessionId, SessionState>>,
+    sessions: Arc<dyn SessionStore>,
+
+    /// 所屬群組（寫入 SessionState，共用 Store 時只看自己群組的 Session）
+    group_id: Option<GroupId>,
+
+    /// 簽署者身分名冊（設定後所有承諾與分片都必須附有效的信封簽章）
+    roster: Option<Roster>,
+
+    /// 稽核日誌（actor 名稱, 日誌）
+    audit_log: Option<(String, Arc<AuditLog>)>,
+
+    /// Prometheus 指標
+    metrics: Option<Arc<Metrics>>,
+
+    /// 是否接受新的會話（關機排空時關閉）
+    ///
+    /// 建立會話時全程持有讀鎖，`stop_accepting_sessions` 返回後不會再有新的會話。
+    accepting: RwLock<bool>,
 }
 
 impl Coordinator {
@@ -121,48 +369,254 @@ impl Coordinator {
         Self {
             pubkey_package,
             threshold,
-            sessions: Arc::new(DashMap::new()),
+            sessions: Arc::new(MemorySessionStore::new()),
+            group_id: None,
+            roster: None,
+            audit_log: None,
+            metrics: None,
+            accepting: RwLock::new(true),
         }
     }
 
+    /// 設定 Session Store
+    ///
+    /// # 參數
+    /// - `store`: Session 儲存後端（可多個群組共用）
+    /// - `group_id`: 此協調者所屬的群組
+    pub fn with_session_store(mut self, store: Arc<dyn SessionStore>, group_id: GroupId) -> Self {
+        self.sessions = store;
+        self.group_id = Some(group_id);
+        self
+    }
+
+    /// 設定稽核日誌
+    ///
+    /// # 參數
+    /// - `audit_log`: 共用的稽核日誌
+    /// - `actor`: 寫入紀錄時使用的角色名稱
+    pub fn with_audit_log(mut self, audit_log: Arc<AuditLog>, actor: impl Into<String>) -> Self {
+        self.audit_log = Some((actor.into(), audit_log));
+        self
+    }
+
+    /// 寫入稽核事件（未設定稽核日誌時不做任何事）
+    fn audit(&self, event: AuditEvent) {
+        if let Some((actor, audit_log)) = &self.audit_log {
+            audit_log.record(actor, event);
+        }
+    }
+
+    /// 設定 Prometheus 指標
+    pub fn with_metrics(mut self, metrics: Arc<Metrics>) -> Self {
+        self.metrics = Some(metrics);
+        self
+    }
+
+    /// 記錄指標（未設定指標時不做任何事）
+    fn record(&self, observe: impl FnOnce(&Metrics)) {
+        if let Some(metrics) = &self.metrics {
+            observe(metrics);
+        }
+    }
+
+    /// 記錄輪次逾時的簽署者（P2P 模式自行判斷逾時時使用）
+    pub(crate) fn record_timeout(&self, error: &CoordinatorError) {
+        self.record(|metrics| metrics.observe_timeout(error));
+    }
+
+    /// 設定簽署者身分名冊（啟用訊息來源驗證）
+    pub fn with_roster(mut self, roster: Roster) -> Self {
+        self.roster = Some(roster);
+        self
+    }
+
+    /// 獲取簽署者身分名冊
+    pub fn roster(&self) -> Option<&Roster> {
+        self.roster.as_ref()
+    }
+
     /// 獲取群組公鑰
     pub fn group_public_key(&self) -> &frost::VerifyingKey {
         self.pubkey_package.verifying_key()
     }
 
+    /// 獲取門檻值
+    pub fn threshold(&self) -> u16 {
+        self.threshold
+    }
+
     // ========================================================================
     // Session 管理
     // ========================================================================
 
-    /// 建立新的簽章會話
-    pub fn create_session(&self, message: Vec<u8>) -> SessionId {
-        let session_id = SessionId::new();
-        let state = SessionState::new(session_id, message);
+    /// 建立新的簽章會話（raw 訊息，簽署其 tagged hash，見 `message` 模組）
+    pub fn create_session(&self, message: Vec<u8>) -> Result<SessionId, CoordinatorError> {
+        let payload = SignPayload::Raw {
+            message: hex::encode(message),
+        };
+        self.create_payload_session(payload).map(|(session_id, _)| session_id)
+    }
+
+    /// 建立新的簽章會話（結構化請求）
+    ///
+    /// 返回 Session ID 與要簽署的摘要。
+    pub fn create_payload_session(
+        &self,
+        payload: SignPayload,
+    ) -> Result<(SessionId, Vec<u8>), CoordinatorError> {
+        let digest = payload.signing_digest()?;
+        let session_id = self.insert_session(SessionId::new(), digest.clone(), payload)?;
+        Ok((session_id, digest))
+    }
+
+    /// 以他人提議的 Session ID 建立會話（P2P 模式，見 `peer` 模組）
+    ///
+    /// 返回要簽署的摘要。
+    pub fn join_payload_session(
+        &self,
+        session_id: SessionId,
+        payload: SignPayload,
+    ) -> Result<Vec<u8>, CoordinatorError> {
+        let digest = payload.signing_digest()?;
+        self.insert_session(session_id, digest.clone(), payload)?;
+        Ok(digest)
+    }
+
+    fn insert_session(
+        &self,
+        session_id: SessionId,
+        message: Vec<u8>,
+        payload: SignPayload,
+    ) -> Result<SessionId, CoordinatorError> {
+        // 持有讀鎖直到會話寫入，與 `stop_accepting_sessions` 互斥
+        let accepting = self.accepting.read().unwrap();
+        if !*accepting {
+            return Err(CoordinatorError::NotAcceptingSessions);
+        }
 
-        self.sessions.insert(session_id, state);
+        let message_sha256 = hex::encode(Sha256::digest(&message));
+        let message_kind = payload.kind();
+        let mut state = SessionState::new(session_id, message);
+        state.group_id = self.group_id;
+        state.message_kind = message_kind;
+        state.payload = Some(payload);
+
+        self.sessions.insert(&state)?;
+        self.record(Metrics::session_started);
+
+        self.audit(AuditEvent::SessionCreated {
+            session_id,
+            message_kind,
+            message_sha256,
+        });
 
         tracing::info!(
             session_id = %session_id,
             "Created new signing session"
         );
 
-        session_id
+        Ok(session_id)
+    }
+
+    /// 將會話推進到下一個階段
+    ///
+    /// 狀態機不允許的轉換會返回 `InvalidSessionState`，且不修改會話。
+    fn advance(
+        &self,
+        session_id: SessionId,
+        next: SessionStatus,
+        mut apply: impl FnMut(&mut SessionState),
+    ) -> Result<SessionState, CoordinatorError> {
+        let mut rejected = None;
+        let state = self
+            .sessions
+            .update(&session_id, &mut |state| {
+                if !state.status.can_transition_to(next) {
+                    rejected = Some(state.status);
+                    return;
+                }
+                rejected = None;
+                state.status = next;
+                state.updated_at = chrono::Utc::now();
+                apply(state);
+            })?
+            .ok_or(CoordinatorError::SessionNotFound(session_id))?;
+
+        if let Some(status) = rejected {
+            return Err(CoordinatorError::InvalidSessionState {
+                session_id,
+                status,
+                next,
+            });
+        }
+
+        Ok(state)
+    }
+
+    /// 中止會話：標記為 Failed，並通知參與的簽署者丟棄 Nonce
+    pub fn abort_session(&self, session_id: SessionId, signers: &[Arc<Signer>], reason: &str) {
+        let result = self.advance(session_id, SessionStatus::Failed, |state| {
+            state.failure_reason = Some(reason.to_string());
+        });
+        match result {
+            Ok(_) => self.record(|metrics| metrics.session_failed("failed")),
+            Err(e) => tracing::error!(
+                session_id = %session_id,
+                error = %e,
+                "Failed to mark session as failed"
+            ),
+        }
+
+        for signer in signers {
+            signer.clear_session(&session_id);
+        }
+
+        self.audit(AuditEvent::SessionAborted {
+            session_id,
+            reason: reason.to_string(),
+        });
+
+        tracing::warn!(session_id = %session_id, reason, "Aborted signing session");
     }
 
     /// 添加承諾到會話
+    ///
+    /// 若已設定名冊，承諾必須附有該簽署者的有效信封簽章。
     pub fn add_commitment(
         &self,
         session_id: SessionId,
         commitment: CommitmentData,
     ) -> Result<usize, CoordinatorError> {
-        let mut session = self
-            .sessions
-            .get_mut(&session_id)
-            .ok_or(CoordinatorError::SessionNotFound(session_id))?;
+        if let Some(roster) = &self.roster {
+            if let Err(e) = roster.verify(
+                EnvelopeKind::Round1Commitment,
+                session_id,
+                commitment.signer_id,
+                &commitment.commitment,
+                commitment.envelope_signature.as_deref(),
+            ) {
+                self.audit(AuditEvent::RequestRejected {
+                    session_id: Some(session_id),
+                    signer_id: Some(commitment.signer_id),
+                    reason: e.to_string(),
+                });
+                return Err(CoordinatorError::UnauthenticatedMessage(e.to_string()));
+            }
+        }
 
-        session.commitments.push(commitment);
+        let signer_id = commitment.signer_id;
+        let session = self.advance(session_id, SessionStatus::CommitmentsCollecting, |state| {
+            // 同一簽署者重送時以最新的承諾為準
+            state.commitments.retain(|c| c.signer_id != signer_id);
+            state.commitments.push(commitment.clone());
+        })?;
         let count = session.commitments.len();
 
+        self.audit(AuditEvent::CommitmentReceived {
+            session_id,
+            signer_id,
+        });
+
         tracing::debug!(
             session_id = %session_id,
             commitments_count = count,
@@ -173,13 +627,16 @@ impl Coordinator {
     }
 
     /// 獲取會話的簽章套件（用於 Round 2）
+    ///
+    /// 第一次呼叫會把會話推進到 `PackageIssued`，之後不再接受新的承諾；
+    /// 在 Round 2 期間重複呼叫會返回相同的套件。
     pub fn get_signing_package(
         &self,
         session_id: SessionId,
     ) -> Result<SigningPackageData, CoordinatorError> {
         let session = self
             .sessions
-            .get(&session_id)
+            .get(&session_id)?
             .ok_or(CoordinatorError::SessionNotFound(session_id))?;
 
         // 檢查是否收集了足夠的承諾
@@ -190,165 +647,462 @@ impl Coordinator {
             });
         }
 
+        let session = match session.status {
+            SessionStatus::PackageIssued | SessionStatus::SharesCollecting => session,
+            _ => self.advance(session_id, SessionStatus::PackageIssued, |_| {})?,
+        };
+
         Ok(SigningPackageData {
-            commitments: session.commitments.clone(),
-            message: session.message.clone(),
+            commitments: session.commitments,
+            message: session.message,
+            payload: session.payload,
         })
     }
 
+    /// 驗證、記錄並反序列化一個簽章分片
+    ///
+    /// 若已設定名冊，分片必須附有該簽署者的有效信封簽章。
+    /// 會話必須已發出簽章套件（`PackageIssued` 或 `SharesCollecting`）。
+    pub fn verify_share(
+        &self,
+        session_id: SessionId,
+        share: &SignatureShareData,
+    ) -> Result<(frost::Identifier, frost::round2::SignatureShare), CoordinatorError> {
+        if let Some(roster) = &self.roster {
+            if let Err(e) = roster.verify(
+                EnvelopeKind::Round2SignatureShare,
+                session_id,
+                share.signer_id,
+                &share.signature_share,
+                share.envelope_signature.as_deref(),
+            ) {
+                self.audit(AuditEvent::RequestRejected {
+                    session_id: Some(session_id),
+                    signer_id: Some(share.signer_id),
+                    reason: e.to_string(),
+                });
+                return Err(CoordinatorError::UnauthenticatedMessage(e.to_string()));
+            }
+        }
+
+        let identifier = frost::Identifier::try_from(share.signer_id)
+            .map_err(|e| CoordinatorError::ShareDeserializationFailed(format!("{:?}", e)))?;
+        let bytes = hex::decode(&share.signature_share)
+            .map_err(|e| CoordinatorError::ShareDeserializationFailed(e.to_string()))?;
+        let signature_share = frost::round2::SignatureShare::deserialize(&bytes)
+            .map_err(|e| CoordinatorError::ShareDeserializationFailed(format!("{:?}", e)))?;
+
+        self.advance(session_id, SessionStatus::SharesCollecting, |state| {
+            if !state.shares_received.contains(&share.signer_id) {
+                state.shares_received.push(share.signer_id);
+            }
+        })?;
+
+        Ok((identifier, signature_share))
+    }
+
+    /// 將會話標記為完成（簽章已聚合並驗證）
+    pub fn complete_session(
+        &self,
+        session_id: SessionId,
+        signature_hex: &str,
+    ) -> Result<(), CoordinatorError> {
+        self.advance(session_id, SessionStatus::Aggregated, |state| {
+            state.signature = Some(signature_hex.to_string());
+        })?;
+        self.record(Metrics::session_completed);
+        Ok(())
+    }
+
     // ========================================================================
     // 完整簽章流程（高階 API）
     // ========================================================================
 
     /// 執行完整的簽章流程
     ///
-    /// 這是一個高階方法，展示了如何使用協調者編排整個 FROST 流程。
-    /// 在實際的 HTTP API 中，每個步驟會是獨立的端點。
+    /// 同一個行程內的簽署者接上 `ChannelNetwork`，協議本身與遠端簽署者相同，
+    /// 都由 `orchestrate_over_transport` 執行。
     ///
     /// # 參數
     /// - `signers`: 參與簽署的簽署者列表（必須 >= threshold）
-    /// - `message`: 要簽署的訊息
+    /// - `payload`: 結構化的簽章請求（每個簽署者會自己重新計算摘要）
+    /// - `round_timeout`: 每一輪等待回覆的上限
     ///
     /// # 返回
-    /// - `Ok(Signature)`: 最終的群組簽章
-    /// - `Err(CoordinatorError)`: 流程中的任何錯誤
+    /// - `Ok(SigningOutcome)`: 已驗證的群組簽章，以及 Session ID、參與者與耗時
+    /// - `Err(CoordinatorError)`: 流程中的任何錯誤（驗證失敗的簽章不會被返回）
     pub async fn orchestrate_signing(
         &self,
         signers: &[Arc<Signer>],
+        payload: &SignPayload,
+        round_timeout: Duration,
+    ) -> Result<SigningOutcome, CoordinatorError> {
+        let network = ChannelNetwork::new();
+        let mut transport = network.endpoint(Participant::Coordinator);
+        let serving: Vec<_> = signers
+            .iter()
+            .map(|signer| {
+                let signer = Arc::clone(signer);
+                let mut endpoint = network.endpoint(Participant::Signer(signer.numeric_id()));
+                tokio::spawn(async move { signer.serve(&mut endpoint).await })
+            })
+            .collect();
+
+        let signer_ids: Vec<u16> = signers.iter().map(|signer| signer.numeric_id()).collect();
+        let result = self
+            .orchestrate_over_transport(&mut transport, &signer_ids, payload, round_timeout)
+            .await;
+
+        // 簽署者處理完剩餘訊息（包含失敗時的 Abort）後結束
+        network.shutdown();
+        for task in serving {
+            match task.await {
+                Ok(Ok(())) => {}
+                Ok(Err(e)) => tracing::warn!(error = %e, "In-process signer stopped with error"),
+                Err(e) => tracing::error!(error = %e, "In-process signer task panicked"),
+            }
+        }
+
+        result
+    }
+
+    /// 聚合、驗證並完成會話
+    ///
+    /// 返回已驗證的簽章與參與者 ID（已排序）；失敗時寫入 `AggregationFailed` 稽核事件。
+    pub(crate) fn finalize_session(
+        &self,
+        session_id: SessionId,
+        signing_package: &frost::SigningPackage,
+        signature_shares: &BTreeMap<frost::Identifier, frost::round2::SignatureShare>,
         message: &[u8],
-    ) -> Result<frost::Signature, CoordinatorError> {
+    ) -> Result<(frost::Signature, Vec<u16>), CoordinatorError> {
+        // ====================================================================
+        // 聚合簽章
+        // ====================================================================
+        let group_signature = match self.aggregate_signature(signing_package, signature_shares) {
+            Ok(signature) => signature,
+            Err(e) => {
+                self.record(|metrics| metrics.observe_aggregation_failure(&e));
+                self.audit(AuditEvent::AggregationFailed {
+                    session_id,
+                    reason: e.to_string(),
+                });
+                return Err(e);
+            }
+        };
+
+        let signature_hex = hex::encode(group_signature.serialize().unwrap());
         tracing::info!(
-            signer_count = signers.len(),
-            threshold = self.threshold,
-            "Starting FROST signing orchestration"
+            session_id = %session_id,
+            signature = %signature_hex,
+            "Signature aggregated successfully"
         );
 
-        // 驗證簽署者數量
-        if signers.len() < self.threshold as usize {
-            return Err(CoordinatorError::InsufficientCommitments {
-                expected: self.threshold as usize,
-                actual: signers.len(),
+        // ====================================================================
+        // 驗證簽章
+        // ====================================================================
+        if let Err(e) = self.verify_signature(message, &group_signature) {
+            self.record(|metrics| metrics.observe_aggregation_failure(&e));
+            self.audit(AuditEvent::AggregationFailed {
+                session_id,
+                reason: e.to_string(),
             });
+            return Err(e);
         }
 
-        // 建立新的簽章會話
-        let session_id = self.create_session(message.to_vec());
-
-        // ====================================================================
-        // Round 1: 並行收集所有簽署者的承諾
-        // ====================================================================
-        tracing::info!(session_id = %session_id, "Round 1: Collecting commitments");
+        tracing::info!(session_id = %session_id, "Signature verified successfully");
 
-        let round1_futures = signers.iter().map(|signer| {
-            let signer = Arc::clone(signer);
-            let session_id = session_id;
+        self.complete_session(session_id, &signature_hex)?;
 
-            // 為每個簽署者 spawn 一個異步任務
-            tokio::spawn(async move {
-                let commitment = signer
-                    .commit(session_id)
-                    .map_err(|e| CoordinatorError::SignerError(e.to_string()))?;
+        // BTreeMap 的 key 已排序
+        let signer_ids: Vec<u16> = signature_shares
+            .keys()
+            .map(signer_id_from_identifier)
+            .collect();
 
-                Ok::<_, CoordinatorError>((signer.id(), commitment))
-            })
+        self.audit(AuditEvent::AggregationSucceeded {
+            session_id,
+            signer_ids: signer_ids.clone(),
+            signature: signature_hex,
         });
 
-        // 等待所有承諾
-        let round1_results = futures::future::join_all(round1_futures).await;
+        Ok((group_signature, signer_ids))
+    }
 
-        // 收集承諾
-        let mut commitments_map = BTreeMap::new();
-        for result in round1_results {
-            let (identifier, commitment) = result
-                .map_err(|e| CoordinatorError::SignerError(e.to_string()))??;
+    // ========================================================================
+    // 在 Transport 上執行完整簽章流程
+    // ========================================================================
 
-            // Convert Identifier back to u16 by serializing and taking first 2 bytes
-            let id_bytes = identifier.serialize();
-            let signer_id = u16::from_le_bytes([id_bytes[0], id_bytes[1]]);
+    /// 透過 `AsyncTransport` 執行完整的簽章流程
+    ///
+    /// 協調者只經由 transport 與簽署者交換訊息：
+    /// 1. 發送 `CommitmentRequest`，收集 `Round1Commitment`
+    /// 2. 發送 `SigningPackage`，收集 `Round2SignatureShare`
+    /// 3. 聚合、驗證後發送 `FinalSignature`
+    ///
+    /// 任何一步失敗時，會話被標記為 failed，並向所有簽署者發送 `Abort`。
+    ///
+    /// # 參數
+    /// - `transport`: 協調者的端點
+    /// - `signer_ids`: 參與簽署的簽署者 ID（必須 >= threshold）
+    /// - `payload`: 結構化的簽章請求
+    /// - `round_timeout`: 每一輪等待回覆的上限
+    pub async fn orchestrate_over_transport<T: AsyncTransport>(
+        &self,
+        transport: &mut T,
+        signer_ids: &[u16],
+        payload: &SignPayload,
+        round_timeout: Duration,
+    ) -> Result<SigningOutcome, CoordinatorError> {
+        // 呼叫者的 trace（例如 HTTP 請求的 traceparent）成為會話的父節點
+        let span = tracing::info_span!("signing_session", trace_id = tracing::field::Empty);
+        let trace = telemetry::attach(&span, TraceContext::current().as_ref());
+
+        self.orchestrate_traced(transport, signer_ids, payload, round_timeout, trace)
+            .instrument(span)
+            .await
+    }
 
-            let commitment_data = CommitmentData {
-                signer_id,
-                commitment: hex::encode(commitment.serialize().unwrap()),
-            };
+    /// `orchestrate_over_transport` 在 `signing_session` span 中執行的部分
+    async fn orchestrate_traced<T: AsyncTransport>(
+        &self,
+        transport: &mut T,
+        signer_ids: &[u16],
+        payload: &SignPayload,
+        round_timeout: Duration,
+        trace: TraceContext,
+    ) -> Result<SigningOutcome, CoordinatorError> {
+        tracing::info!(
+            signer_count = signer_ids.len(),
+            threshold = self.threshold,
+            local = %transport.local(),
+            "Starting FROST signing over transport"
+        );
 
-            self.add_commitment(session_id, commitment_data)?;
-            commitments_map.insert(identifier, commitment);
+        let signer_ids: BTreeSet<u16> = signer_ids.iter().copied().collect();
+        if signer_ids.len() < self.threshold as usize {
+            return Err(CoordinatorError::InsufficientCommitments {
+                expected: self.threshold as usize,
+                actual: signer_ids.len(),
+            });
         }
 
-        tracing::info!(
-            session_id = %session_id,
-            commitments_count = commitments_map.len(),
-            "Round 1 complete: All commitments collected"
-        );
+        let (session_id, message) = self.create_payload_session(payload.clone())?;
+
+        match self
+            .run_transport_session(
+                transport,
+                session_id,
+                &signer_ids,
+                &message,
+                round_timeout,
+                trace,
+            )
+            .await
+        {
+            Ok(outcome) => Ok(outcome),
+            Err(e) => {
+                self.record_timeout(&e);
+
+                // 遠端簽署者的 Nonce 無法直接清除：標記失敗後以 Abort 訊息通知
+                let reason = e.to_string();
+                self.abort_session(session_id, &[], &reason);
+                for &signer_id in &signer_ids {
+                    let message = FrostMessage::Abort {
+                        session_id,
+                        reason: reason.clone(),
+                    };
+                    if let Err(e) = transport.send(Participant::Signer(signer_id), message).await {
+                        tracing::warn!(
+                            session_id = %session_id,
+                            signer_id,
+                            error = %e,
+                            "Failed to notify signer of aborted session"
+                        );
+                    }
+                }
+                Err(e)
+            }
+        }
+    }
 
-        // ====================================================================
-        // 建立簽章套件
-        // ====================================================================
-        let signing_package_data = self.get_signing_package(session_id)?;
-        let signing_package = frost::SigningPackage::new(commitments_map, message);
+    /// 在已建立的會話上，經由 transport 執行兩輪協議與聚合
+    async fn run_transport_session<T: AsyncTransport>(
+        &self,
+        transport: &mut T,
+        session_id: SessionId,
+        signer_ids: &BTreeSet<u16>,
+        message: &[u8],
+        round_timeout: Duration,
+        trace: TraceContext,
+    ) -> Result<SigningOutcome, CoordinatorError> {
+        let started = Instant::now();
 
         // ====================================================================
-        // Round 2: 並行收集所有簽署者的簽章分片
+        // Round 1: 請求並收集承諾
         // ====================================================================
-        tracing::info!(session_id = %session_id, "Round 2: Collecting signature shares");
-
-        let round2_futures = signers.iter().map(|signer| {
-            let signer = Arc::clone(signer);
-            let session_id = session_id;
-            let signing_package_data = signing_package_data.clone();
-
-            tokio::spawn(async move {
-                let signature_share = signer
-                    .sign(session_id, &signing_package_data)
-                    .map_err(|e| CoordinatorError::SignerError(e.to_string()))?;
-
-                Ok::<_, CoordinatorError>((signer.id(), signature_share))
-            })
-        });
-
-        // 等待所有簽章分片
-        let round2_results = futures::future::join_all(round2_futures).await;
-
-        // 收集簽章分片
-        let mut signature_shares = BTreeMap::new();
-        for result in round2_results {
-            let (identifier, share) = result
-                .map_err(|e| CoordinatorError::SignerError(e.to_string()))??;
+        tracing::info!(session_id = %session_id, "Round 1: Requesting commitments over transport");
+
+        for &signer_id in signer_ids {
+            transport
+                .send(
+                    Participant::Signer(signer_id),
+                    FrostMessage::CommitmentRequest {
+                        session_id,
+                        trace: Some(trace),
+                    },
+                )
+                .await?;
+        }
 
-            signature_shares.insert(identifier, share);
+        let mut pending = signer_ids.clone();
+        let deadline = tokio::time::Instant::now() + round_timeout;
+        while !pending.is_empty() {
+            let envelope = recv_before(transport, deadline, session_id, "Round 1", &pending).await?;
+            let Some(signer_id) = expected_sender(&envelope, session_id, &pending) else {
+                continue;
+            };
+            if let FrostMessage::Abort { reason, .. } = &envelope.message {
+                return Err(CoordinatorError::SignerError(format!(
+                    "signer {} aborted: {}",
+                    signer_id, reason
+                )));
+            }
+            let FrostMessage::Round1Commitment { commitment, .. } = envelope.message else {
+                continue;
+            };
+            if commitment.signer_id != signer_id {
+                tracing::warn!(
+                    session_id = %session_id,
+                    from = signer_id,
+                    claimed = commitment.signer_id,
+                    "Ignoring commitment with mismatched signer ID"
+                );
+                continue;
+            }
+
+            // add_commitment 會驗證信封簽章
+            self.add_commitment(session_id, commitment)?;
+            self.record(|metrics| metrics.observe_round(signer_id, 1, started.elapsed()));
+            pending.remove(&signer_id);
         }
 
-        tracing::info!(
-            session_id = %session_id,
-            shares_count = signature_shares.len(),
-            "Round 2 complete: All signature shares collected"
-        );
+        let round1_ms = elapsed_ms(started);
+        tracing::info!(session_id = %session_id, round1_ms, "Round 1 complete over transport");
 
         // ====================================================================
-        // 聚合簽章
+        // 建立並分發簽章套件
         // ====================================================================
-        let group_signature = self.aggregate_signature(&signing_package, &signature_shares)?;
-
-        tracing::info!(
-            session_id = %session_id,
-            signature = %hex::encode(group_signature.serialize().unwrap()),
-            "Signature aggregated successfully"
-        );
+        let signing_package_data = self.get_signing_package(session_id)?;
+        let message_kind = signing_package_data.message_kind();
+        let commitments = signing_package_data
+            .commitments
+            .iter()
+            .map(decode_commitment)
+            .collect::<Result<BTreeMap<_, _>, _>>()?;
+        let signing_package = frost::SigningPackage::new(commitments, message);
 
         // ====================================================================
-        // 驗證簽章
+        // Round 2: 收集簽章分片
         // ====================================================================
-        self.verify_signature(message, &group_signature)?;
-
         tracing::info!(
             session_id = %session_id,
-            "Signature verified successfully"
+            "Round 2: Distributing signing package over transport"
         );
+        let round2_started = Instant::now();
+
+        for &signer_id in signer_ids {
+            transport
+                .send(
+                    Participant::Signer(signer_id),
+                    FrostMessage::SigningPackage {
+                        session_id,
+                        package: signing_package_data.clone(),
+                        trace: Some(trace),
+                    },
+                )
+                .await?;
+        }
+
+        let mut pending = signer_ids.clone();
+        let mut signature_shares = BTreeMap::new();
+        let deadline = tokio::time::Instant::now() + round_timeout;
+        while !pending.is_empty() {
+            let envelope = recv_before(transport, deadline, session_id, "Round 2", &pending).await?;
+            let Some(signer_id) = expected_sender(&envelope, session_id, &pending) else {
+                continue;
+            };
+            if let FrostMessage::Abort { reason, .. } = &envelope.message {
+                return Err(CoordinatorError::SignerError(format!(
+                    "signer {} aborted: {}",
+                    signer_id, reason
+                )));
+            }
+            let FrostMessage::Round2SignatureShare { share, .. } = envelope.message else {
+                continue;
+            };
+            if share.signer_id != signer_id {
+                tracing::warn!(
+                    session_id = %session_id,
+                    from = signer_id,
+                    claimed = share.signer_id,
+                    "Ignoring signature share with mismatched signer ID"
+                );
+                continue;
+            }
+
+            let (identifier, share) = self.verify_share(session_id, &share)?;
+            self.record(|metrics| metrics.observe_round(signer_id, 2, round2_started.elapsed()));
+            signature_shares.insert(identifier, share);
+            pending.remove(&signer_id);
+        }
 
-        // 清理會話
-        self.sessions.remove(&session_id);
+        let round2_ms = elapsed_ms(round2_started);
+        tracing::info!(session_id = %session_id, round2_ms, "Round 2 complete over transport");
 
-        Ok(group_signature)
+        // ====================================================================
+        // 聚合、驗證並廣播最終簽章
+        // ====================================================================
+        let aggregation_started = Instant::now();
+        let (group_signature, participants) =
+            self.finalize_session(session_id, &signing_package, &signature_shares, message)?;
+        let aggregation_ms = elapsed_ms(aggregation_started);
+
+        let signature_hex = hex::encode(group_signature.serialize().unwrap());
+        for &signer_id in signer_ids {
+            let result = transport
+                .send(
+                    Participant::Signer(signer_id),
+                    FrostMessage::FinalSignature {
+                        session_id,
+                        signature: signature_hex.clone(),
+                    },
+                )
+                .await;
+            // 簽章已完成，通知失敗不影響結果
+            if let Err(e) = result {
+                tracing::warn!(
+                    session_id = %session_id,
+                    signer_id,
+                    error = %e,
+                    "Failed to deliver final signature"
+                );
+            }
+        }
+
+        Ok(SigningOutcome {
+            session_id,
+            signature: group_signature,
+            signer_ids: participants,
+            message_kind,
+            message: message.to_vec(),
+            timings: RoundTimings {
+                round1_ms,
+                round2_ms,
+                aggregation_ms,
+                total_ms: elapsed_ms(started),
+            },
+        })
     }
 
     // ========================================================================
@@ -398,19 +1152,124 @@ impl Coordinator {
     // 管理方法
     // ========================================================================
 
-    /// 獲取活動會話數量
+    /// 獲取此協調者（所屬群組）的所有會話
+    pub fn sessions(&self) -> Result<Vec<SessionState>, CoordinatorError> {
+        Ok(self
+            .sessions
+            .list()?
+            .into_iter()
+            .filter(|state| state.group_id == self.group_id)
+            .collect())
+    }
+
+    /// 查詢此協調者的指定會話
+    pub fn get_session(
+        &self,
+        session_id: &SessionId,
+    ) -> Result<Option<SessionState>, CoordinatorError> {
+        Ok(self
+            .sessions
+            .get(session_id)?
+            .filter(|state| state.group_id == self.group_id))
+    }
+
+    /// 各階段的進行中會話數量
+    ///
+    /// 只讀取 Store 的階段索引（`SessionStore::statuses`），不載入完整的會話狀態。
+    pub fn active_session_counts(&self) -> ActiveSessionCounts {
+        let mut counts = ActiveSessionCounts::default();
+        for summary in self.sessions.statuses().unwrap_or_default() {
+            if summary.group_id == self.group_id {
+                counts.record(summary.status);
+            }
+        }
+        counts
+    }
+
+    /// 獲取活動（未終止）會話數量
     pub fn active_sessions_count(&self) -> usize {
-        self.sessions.len()
+        self.active_session_counts().total
+    }
+
+    /// 將超時的未完成會話標記為 Expired，返回被標記的 Session ID
+    ///
+    /// 呼叫者應通知簽署者丟棄這些 Session 的 Nonce。
+    pub fn expire_stale_sessions(&self, timeout: chrono::Duration) -> Vec<SessionId> {
+        let deadline = chrono::Utc::now() - timeout;
+        let mut expired = Vec::new();
+
+        for state in self.sessions().unwrap_or_default() {
+            if state.status.is_terminal() || state.created_at > deadline {
+                continue;
+            }
+
+            let reason = format!("session timed out after {}s", timeout.num_seconds());
+            let result = self.advance(state.session_id, SessionStatus::Expired, |state| {
+                state.failure_reason = Some(reason.clone());
+            });
+            if result.is_ok() {
+                self.record(|metrics| metrics.session_failed("expired"));
+                self.audit(AuditEvent::SessionAborted {
+                    session_id: state.session_id,
+                    reason,
+                });
+                tracing::warn!(session_id = %state.session_id, "Signing session expired");
+                expired.push(state.session_id);
+            }
+        }
+
+        expired
+    }
+
+    /// 不再接受新的會話（關機排空時使用）
+    ///
+    /// 返回時正在建立的會話都已寫入，之後的 `create_session` 等呼叫返回
+    /// `NotAcceptingSessions`，因此 `abort_active_sessions` 不會漏掉任何會話。
+    pub fn stop_accepting_sessions(&self) {
+        *self.accepting.write().unwrap() = false;
+    }
+
+    /// 是否仍接受新的會話
+    pub fn is_accepting_sessions(&self) -> bool {
+        *self.accepting.read().unwrap()
+    }
+
+    /// 中止所有未終止的會話（例如關機期限已到），返回被中止的 Session ID
+    ///
+    /// 呼叫者應通知簽署者丟棄這些 Session 的 Nonce。
+    pub fn abort_active_sessions(&self, reason: &str) -> Vec<SessionId> {
+        self.sessions()
+            .unwrap_or_default()
+            .into_iter()
+            .filter(|state| !state.status.is_terminal())
+            .map(|state| {
+                self.abort_session(state.session_id, &[], reason);
+                state.session_id
+            })
+            .collect()
+    }
+
+    /// 清除保留時間已過的終止會話，返回清除數量
+    pub fn prune_finished_sessions(&self, retention: chrono::Duration) -> usize {
+        let deadline = chrono::Utc::now() - retention;
+        self.sessions()
+            .unwrap_or_default()
+            .into_iter()
+            .filter(|state| state.status.is_terminal() && state.updated_at < deadline)
+            .filter(|state| self.clear_session(&state.session_id))
+            .count()
     }
 
     /// 清除指定會話
     pub fn clear_session(&self, session_id: &SessionId) -> bool {
-        self.sessions.remove(session_id).is_some()
+        matches!(self.sessions.remove(session_id), Ok(Some(_)))
     }
 
     /// 清除所有會話
     pub fn clear_all_sessions(&self) {
-        self.sessions.clear();
+        for state in self.sessions().unwrap_or_default() {
+            self.clear_session(&state.session_id);
+        }
     }
 }
 
@@ -420,7 +1279,8 @@ impl Coordinator {
 
 // Coordinator 自動實現 Send + Sync，因為：
 // - frost::keys::PublicKeyPackage 實現了 Send + Sync
-// - DashMap 實現了 Send + Sync
+// - SessionStore trait 要求 Send + Sync
+// - Roster 只包含不可變的公鑰
 // - 所有基本類型（u16）都實現了 Send + Sync
 // 因此不需要手動實現，編譯器會自動派生
 
@@ -431,8 +1291,171 @@ impl Coordinator {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use rand::thread_rng;
+
+    #[test]
+    fn test_session_rejects_out_of_order_operations() {
+        let (shares, pubkey_package) = frost::keys::generate_with_dealer(
+            3,
+            2,
+            frost::keys::IdentifierList::Default,
+            thread_rng(),
+        )
+        .unwrap();
+        let signers: Vec<Signer> = shares
+            .into_values()
+            .map(|share| Signer::new(share).unwrap())
+            .collect();
+        let coordinator = Coordinator::new(pubkey_package, 2);
+
+        let session_id = coordinator.create_session(b"state machine".to_vec()).unwrap();
+        assert!(matches!(
+            coordinator.get_signing_package(session_id),
+            Err(CoordinatorError::InsufficientCommitments { .. })
+        ));
+
+        for signer in &signers[..2] {
+            let commitment = signer.commit_signed(session_id).unwrap();
+            coordinator.add_commitment(session_id, commitment).unwrap();
+        }
+        let state = coordinator.get_session(&session_id).unwrap().unwrap();
+        assert_eq!(state.status, SessionStatus::CommitmentsCollecting);
+
+        coordinator.get_signing_package(session_id).unwrap();
+        let counts = coordinator.active_session_counts();
+        assert_eq!((counts.total, counts.package_issued), (1, 1));
+
+        // 套件發出後不再接受承諾，也不能跳過 Round 2 直接完成
+        let late = signers[2].commit_signed(session_id).unwrap();
+        assert!(matches!(
+            coordinator.add_commitment(session_id, late),
+            Err(CoordinatorError::InvalidSessionState { .. })
+        ));
+        assert!(coordinator.complete_session(session_id, "00").is_err());
+
+        coordinator.abort_session(session_id, &[], "test");
+        let state = coordinator.get_session(&session_id).unwrap().unwrap();
+        assert_eq!(state.status, SessionStatus::Failed);
+        assert_eq!(coordinator.active_sessions_count(), 0);
+    }
+
+    #[tokio::test]
+    async fn test_signing_over_channel_transport() {
+        use crate::transport::ChannelNetwork;
+
+        let (shares, pubkey_package) = frost::keys::generate_with_dealer(
+            3,
+            2,
+            frost::keys::IdentifierList::Default,
+            thread_rng(),
+        )
+        .unwrap();
+        let coordinator = Coordinator::new(pubkey_package, 2);
+
+        let network = ChannelNetwork::new();
+        let mut handles = Vec::new();
+        for share in shares.into_values() {
+            let signer = Signer::new(share).unwrap();
+            let mut endpoint = network.endpoint(Participant::Signer(signer.numeric_id()));
+            handles.push(tokio::spawn(async move { signer.serve(&mut endpoint).await }));
+        }
+
+        let mut transport = network.endpoint(Participant::Coordinator);
+        let payload = SignPayload::Raw {
+            message: hex::encode(b"over the wire"),
+        };
+        let outcome = coordinator
+            .orchestrate_over_transport(&mut transport, &[1, 3], &payload, Duration::from_secs(5))
+            .await
+            .unwrap();
+        assert_eq!(outcome.signer_ids, vec![1, 3]);
+        assert!(outcome.verify(coordinator.group_public_key()));
+
+        // 送給未上線簽署者的請求失敗，會話被中止
+        network.disconnect(Participant::Signer(2));
+        let result = coordinator
+            .orchestrate_over_transport(
+                &mut transport,
+                &[1, 2],
+                &payload,
+                Duration::from_millis(100),
+            )
+            .await;
+        assert!(matches!(result, Err(CoordinatorError::Transport(_))));
+
+        // 簽署者 1 已收到承諾請求：先收下它多餘的回覆，才不會在網路關閉後回覆失敗
+        let stray = transport.recv().await.unwrap();
+        assert!(matches!(stray.message, FrostMessage::Round1Commitment { .. }));
+
+        network.shutdown();
+        for handle in handles {
+            handle.await.unwrap().unwrap();
+        }
+    }
 
-    // 可以加入單元測試，例如：
-    // - 測試並發的多個會話
-    // - 測試錯誤情況（不足的簽署者、無效的簽章等）
+    /// 在固定種子的有損 LoRa 鏈路上完成一次簽章，返回鏈路的事件記錄與模擬時間
+    async fn sign_over_lossy_link(seed: u64) -> (String, u64, u32) {
+        use crate::transport::{ChannelNetwork, LoRaConfig, SimulatedLoRaTransport};
+        use rand::{rngs::StdRng, SeedableRng};
+
+        let (shares, pubkey_package) = frost::keys::generate_with_dealer(
+            3,
+            2,
+            frost::keys::IdentifierList::Default,
+            StdRng::seed_from_u64(seed),
+        )
+        .unwrap();
+        let coordinator = Coordinator::new(pubkey_package, 2);
+
+        let link = SimulatedLoRaTransport::new_with_config(LoRaConfig {
+            jitter_ms: 300,
+            packet_loss_rate: 0.15,
+            duplicate_rate: 0.1,
+            reorder_rate: 0.2,
+            max_retries: 16,
+            ..LoRaConfig::deterministic(seed)
+        });
+        let state = link.get_state();
+        let network = ChannelNetwork::with_lora(link);
+
+        let mut handles = Vec::new();
+        for share in shares.into_values() {
+            let signer = Signer::new(share).unwrap();
+            let mut endpoint = network.endpoint(Participant::Signer(signer.numeric_id()));
+            handles.push(tokio::spawn(async move { signer.serve(&mut endpoint).await }));
+        }
+
+        let mut transport = network.endpoint(Participant::Coordinator);
+        let payload = SignPayload::Raw {
+            message: hex::encode(b"over a lossy link"),
+        };
+        let outcome = coordinator
+            .orchestrate_over_transport(&mut transport, &[1, 3], &payload, Duration::from_secs(5))
+            .await
+            .unwrap();
+        assert!(outcome.verify(coordinator.group_public_key()));
+
+        network.shutdown();
+        for handle in handles {
+            handle.await.unwrap().unwrap();
+        }
+
+        let state = state.lock().unwrap();
+        (
+            serde_json::to_string(&state.recent_events).unwrap(),
+            state.simulated_time_ms,
+            state.total_retries,
+        )
+    }
+
+    #[tokio::test]
+    async fn test_signing_over_lossy_link_is_reproducible() {
+        let first = sign_over_lossy_link(2024).await;
+        let second = sign_over_lossy_link(2024).await;
+
+        // 同樣的種子產生同樣的掉包、重傳與模擬時間
+        assert_eq!(first, second);
+        assert!(first.0.contains("PacketLost") || first.0.contains("AckLost"));
+        assert!(first.1 > 0);
+    }
 }
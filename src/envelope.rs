@@ -0,0 +1,319 @@
+//! # 訊息信封 - 簽署者身分認證
+//!
+//! 協調者只是一個中繼者：它轉送承諾、簽章套件與簽章分片，但不應被信任。
+//! 如果中繼者（或 LoRa 等不可信鏈路）被攻破，攻擊者可能以其他簽署者的 ID
+//! 注入偽造的承諾或分片。
+//!
+//! 此模組為每個簽署者提供長期的**身分金鑰**（BIP-340 Schnorr，與 FROST 金鑰分片
+//! 完全獨立），所有 Round 1 / Round 2 的輸出都會以身分金鑰簽署。協調者與其他
+//! 簽署者依據**名冊 (Roster)** 驗證簽章，拒絕任何無法認證的訊息。
+//!
+//! ## 簽署內容
+//! ```text
+//! SHA256(tag || tag || kind || session_id || signer_id || payload)
+//! tag = SHA256("FROST-TS/envelope/v1")
+//! ```
+//! 其中 `payload` 是 hex 編碼的承諾、簽章分片、P2P 提議內容或 WebSocket 登記的群組 ID。
+
+use crate::api::SessionId;
+use k256::schnorr::signature::hazmat::{PrehashSigner, PrehashVerifier};
+use k256::schnorr::{Signature, SigningKey, VerifyingKey};
+use rand::thread_rng;
+use sha2::{Digest, Sha256};
+use std::collections::BTreeMap;
+use std::fmt;
+use thiserror::Error;
+
+// ============================================================================
+// 錯誤定義
+// ============================================================================
+
+#[derive(Debug, Error)]
+pub enum EnvelopeError {
+    #[error("Signer {0} is not in the roster")]
+    UnknownSigner(u16),
+
+    #[error("Missing envelope signature from signer {0}")]
+    MissingSignature(u16),
+
+    #[error("Invalid envelope signature from signer {0}")]
+    InvalidSignature(u16),
+
+    #[error("Invalid identity key: {0}")]
+    InvalidKey(String),
+}
+
+// ============================================================================
+// 信封類型
+// ============================================================================
+
+/// 被簽署的訊息類型（納入簽署內容，防止跨類型重放）
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EnvelopeKind {
+    /// Round 1 承諾
+    Round1Commitment,
+
+    /// Round 2 簽章分片
+    Round2SignatureShare,
+
+    /// P2P 模式的簽章提議（見 `peer` 模組）
+    SessionProposal,
+
+    /// WebSocket 登記挑戰（見 `transport::websocket` 模組）
+    WsRegistration,
+}
+
+impl EnvelopeKind {
+    fn tag(&self) -> u8 {
+        match self {
+            EnvelopeKind::Round1Commitment => 1,
+            EnvelopeKind::Round2SignatureShare => 2,
+            EnvelopeKind::SessionProposal => 3,
+            EnvelopeKind::WsRegistration => 4,
+        }
+    }
+}
+
+/// 計算信封簽署內容的雜湊
+fn envelope_digest(
+    kind: EnvelopeKind,
+    session_id: SessionId,
+    signer_id: u16,
+    payload_hex: &str,
+) -> [u8; 32] {
+    let tag = Sha256::digest(b"FROST-TS/envelope/v1");
+
+    let mut hasher = Sha256::new();
+    hasher.update(tag);
+    hasher.update(tag);
+    hasher.update([kind.tag()]);
+    hasher.update(session_id.0.as_bytes());
+    hasher.update(signer_id.to_be_bytes());
+    hasher.update(payload_hex.as_bytes());
+    hasher.finalize().into()
+}
+
+// ============================================================================
+// 身分金鑰
+// ============================================================================
+
+/// 簽署者的長期身分金鑰（私鑰）
+///
+/// 與金鑰分片一起保存在簽署者的金鑰檔中（見 `FileStore::save_group_keys`），
+/// 重新啟動後沿用同一把金鑰，名冊才不會失效。協調者也以同一型別的金鑰簽署
+/// 稽核日誌的檢查點（見 `audit` 模組）。
+#[derive(Clone)]
+pub struct IdentityKey {
+    signing_key: SigningKey,
+}
+
+impl fmt::Debug for IdentityKey {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        // 只顯示公鑰
+        f.debug_struct("IdentityKey")
+            .field("public_key", &self.public_key().to_hex())
+            .finish_non_exhaustive()
+    }
+}
+
+impl IdentityKey {
+    /// 生成新的隨機身分金鑰
+    pub fn generate() -> Self {
+        Self {
+            signing_key: SigningKey::random(&mut thread_rng()),
+        }
+    }
+
+    /// hex 編碼的私鑰（只寫入簽署者自己的金鑰檔）
+    pub fn to_secret_hex(&self) -> String {
+        hex::encode(self.signing_key.to_bytes())
+    }
+
+    /// 由 hex 編碼的私鑰還原
+    pub fn from_secret_hex(s: &str) -> Result<Self, EnvelopeError> {
+        let bytes = hex::decode(s).map_err(|e| EnvelopeError::InvalidKey(e.to_string()))?;
+        let signing_key =
+            SigningKey::from_bytes(&bytes).map_err(|e| EnvelopeError::InvalidKey(e.to_string()))?;
+        Ok(Self { signing_key })
+    }
+
+    /// 對應的身分公鑰
+    pub fn public_key(&self) -> IdentityPublicKey {
+        IdentityPublicKey {
+            verifying_key: *self.signing_key.verifying_key(),
+        }
+    }
+
+    /// 簽署一個協議訊息，返回 hex 編碼的 64 bytes Schnorr 簽章
+    pub fn sign(
+        &self,
+        kind: EnvelopeKind,
+        session_id: SessionId,
+        signer_id: u16,
+        payload_hex: &str,
+    ) -> String {
+        self.sign_digest(&envelope_digest(kind, session_id, signer_id, payload_hex))
+    }
+
+    /// 簽署任意 32 bytes 摘要（例如稽核日誌檢查點），返回 hex 編碼的 Schnorr 簽章
+    pub fn sign_digest(&self, digest: &[u8; 32]) -> String {
+        let signature: Signature = self
+            .signing_key
+            .sign_prehash(digest)
+            .expect("signing a 32-byte digest cannot fail");
+        hex::encode(signature.to_bytes())
+    }
+}
+
+/// 簽署者的身分公鑰（x-only，32 bytes）
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct IdentityPublicKey {
+    verifying_key: VerifyingKey,
+}
+
+impl IdentityPublicKey {
+    /// hex 編碼
+    pub fn to_hex(&self) -> String {
+        hex::encode(self.verifying_key.to_bytes())
+    }
+
+    /// 由 hex 解碼
+    pub fn from_hex(s: &str) -> Result<Self, EnvelopeError> {
+        let bytes = hex::decode(s).map_err(|e| EnvelopeError::InvalidKey(e.to_string()))?;
+        let verifying_key = VerifyingKey::from_bytes(&bytes)
+            .map_err(|e| EnvelopeError::InvalidKey(e.to_string()))?;
+        Ok(Self { verifying_key })
+    }
+
+    /// 驗證 `IdentityKey::sign_digest` 產生的簽章
+    pub fn verify_digest(&self, digest: &[u8; 32], signature_hex: &str) -> bool {
+        hex::decode(signature_hex)
+            .ok()
+            .and_then(|bytes| Signature::try_from(bytes.as_slice()).ok())
+            .is_some_and(|signature| self.verifying_key.verify_prehash(digest, &signature).is_ok())
+    }
+}
+
+// ============================================================================
+// 名冊 (Roster)
+// ============================================================================
+
+/// 簽署者 ID → 身分公鑰 的名冊
+///
+/// 名冊在群組建立時分發給協調者與所有簽署者，之後用於驗證每個訊息的來源。
+#[derive(Debug, Clone, Default)]
+pub struct Roster {
+    keys: BTreeMap<u16, IdentityPublicKey>,
+}
+
+impl Roster {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// 登記簽署者的身分公鑰
+    pub fn insert(&mut self, signer_id: u16, key: IdentityPublicKey) {
+        self.keys.insert(signer_id, key);
+    }
+
+    /// 查詢簽署者的身分公鑰
+    pub fn get(&self, signer_id: u16) -> Option<&IdentityPublicKey> {
+        self.keys.get(&signer_id)
+    }
+
+    /// 以 hex 形式列出名冊（用於 API 回應與公布到金鑰目錄）
+    pub fn to_hex_map(&self) -> BTreeMap<u16, String> {
+        self.keys
+            .iter()
+            .map(|(id, key)| (*id, key.to_hex()))
+            .collect()
+    }
+
+    /// 由公布的 hex 名冊還原
+    pub fn from_hex_map(keys: &BTreeMap<u16, String>) -> Result<Self, EnvelopeError> {
+        let keys = keys
+            .iter()
+            .map(|(id, key)| Ok((*id, IdentityPublicKey::from_hex(key)?)))
+            .collect::<Result<_, EnvelopeError>>()?;
+        Ok(Self { keys })
+    }
+
+    /// 名冊是否為空
+    pub fn is_empty(&self) -> bool {
+        self.keys.is_empty()
+    }
+
+    /// 驗證信封簽章
+    ///
+    /// # 錯誤
+    /// - 簽署者不在名冊中 → `UnknownSigner`
+    /// - 未附簽章 → `MissingSignature`
+    /// - 簽章無效（偽造、竄改或屬於其他 Session）→ `InvalidSignature`
+    pub fn verify(
+        &self,
+        kind: EnvelopeKind,
+        session_id: SessionId,
+        signer_id: u16,
+        payload_hex: &str,
+        signature_hex: Option<&str>,
+    ) -> Result<(), EnvelopeError> {
+        let key = self
+            .keys
+            .get(&signer_id)
+            .ok_or(EnvelopeError::UnknownSigner(signer_id))?;
+        let signature_hex = signature_hex.ok_or(EnvelopeError::MissingSignature(signer_id))?;
+
+        let signature = hex::decode(signature_hex)
+            .ok()
+            .and_then(|bytes| Signature::try_from(bytes.as_slice()).ok())
+            .ok_or(EnvelopeError::InvalidSignature(signer_id))?;
+
+        let digest = envelope_digest(kind, session_id, signer_id, payload_hex);
+        key.verifying_key
+            .verify_prehash(&digest, &signature)
+            .map_err(|_| EnvelopeError::InvalidSignature(signer_id))
+    }
+}
+
+// ============================================================================
+// 測試
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_envelope_rejects_impersonation() {
+        let signer_1 = IdentityKey::generate();
+        let signer_2 = IdentityKey::generate();
+
+        let mut roster = Roster::new();
+        roster.insert(1, signer_1.public_key());
+        roster.insert(2, signer_2.public_key());
+
+        let session_id = SessionId::new();
+        let kind = EnvelopeKind::Round1Commitment;
+        let signature = signer_1.sign(kind, session_id, 1, "abcd");
+
+        assert!(roster.verify(kind, session_id, 1, "abcd", Some(&signature)).is_ok());
+
+        // 竄改 payload、冒充其他 ID、重放到其他 Session 或其他訊息類型都必須失敗
+        assert!(roster.verify(kind, session_id, 1, "abce", Some(&signature)).is_err());
+        assert!(roster.verify(kind, session_id, 2, "abcd", Some(&signature)).is_err());
+        assert!(roster.verify(kind, SessionId::new(), 1, "abcd", Some(&signature)).is_err());
+        assert!(roster
+            .verify(EnvelopeKind::Round2SignatureShare, session_id, 1, "abcd", Some(&signature))
+            .is_err());
+        assert!(matches!(
+            roster.verify(kind, session_id, 1, "abcd", None),
+            Err(EnvelopeError::MissingSignature(1))
+        ));
+
+        // 保存後還原的私鑰與公布的名冊仍然相符
+        let restored = IdentityKey::from_secret_hex(&signer_1.to_secret_hex()).unwrap();
+        let published = Roster::from_hex_map(&roster.to_hex_map()).unwrap();
+        let signature = restored.sign(kind, session_id, 1, "abcd");
+        assert!(published.verify(kind, session_id, 1, "abcd", Some(&signature)).is_ok());
+    }
+}
@@ -0,0 +1,465 @@
+//! # Signing Group - 多租戶簽章群組
+//!
+//! 一個 `SigningGroup` 代表一組獨立的金鑰分片與門檻設定（例如一個金庫），
+//! 包含：
+//! - 自己的 `Coordinator`（持有群組公鑰與 Session 狀態）
+//! - 自己的 `Signer` 集合（持有金鑰分片與 Nonce 狀態）
+//!
+//! ## 隔離性
+//! 每個群組的 Session 與 Nonce 都儲存在群組自己的 Coordinator / Signer 實例中，
+//! 不同群組之間不共享任何可變狀態：
+//! - 在群組 A 產生的 Session ID 無法在群組 B 的簽署者上消費 Nonce
+//! - 群組 A 的承諾即使被混入群組 B 的簽章套件，也會因為找不到對應 Nonce 或
+//!   承諾不符而被 FROST 拒絕
+//! - 每個群組有自己的身分名冊，群組 A 簽署者的信封簽章無法通過群組 B 的驗證
+//!
+//! 稽核日誌與 Session Store 可由多個群組共用（`GroupServices`），
+//! 紀錄中會標註所屬群組。
+
+use crate::api::{GroupId, SessionId, SetupResponse};
+use crate::audit::AuditLog;
+use crate::coordinator::Coordinator;
+use crate::envelope::Roster;
+use crate::metrics::Metrics;
+use crate::session_store::SessionStore;
+use crate::signer::{Signer, SignerError};
+use dashmap::DashMap;
+use frost_secp256k1 as frost;
+use rand::thread_rng;
+use std::collections::BTreeMap;
+use std::sync::Arc;
+use thiserror::Error;
+
+// ============================================================================
+// 錯誤定義
+// ============================================================================
+
+#[derive(Debug, Error)]
+pub enum GroupError {
+    #[error("Invalid group parameters: threshold {min_signers} with {max_signers} signers")]
+    InvalidParameters { min_signers: u16, max_signers: u16 },
+
+    #[error("Key generation failed: {0}")]
+    KeyGenerationFailed(String),
+
+    #[error("Invalid key share: {0}")]
+    InvalidShare(#[from] SignerError),
+}
+
+// ============================================================================
+// 共用服務
+// ============================================================================
+
+/// 群組可共用的基礎服務
+#[derive(Clone, Default)]
+pub struct GroupServices {
+    /// 群組內 Coordinator 與 Signers 共用的稽核日誌
+    pub audit_log: Option<Arc<AuditLog>>,
+
+    /// 協調者的 Session Store（未設定時使用記憶體）
+    pub session_store: Option<Arc<dyn SessionStore>>,
+
+    /// 協調者記錄的 Prometheus 指標
+    pub metrics: Option<Arc<Metrics>>,
+}
+
+// ============================================================================
+// SigningGroup 結構
+// ============================================================================
+
+/// 一個獨立的 FROST 簽章群組
+pub struct SigningGroup {
+    /// 群組 ID
+    group_id: GroupId,
+
+    /// 此群組專屬的協調者
+    coordinator: Arc<Coordinator>,
+
+    /// 此群組的簽署者
+    /// Key: Signer ID (u16)
+    signers: DashMap<u16, Arc<Signer>>,
+
+    /// 門檻值
+    min_signers: u16,
+
+    /// 簽署者總數
+    max_signers: u16,
+}
+
+impl SigningGroup {
+    // ========================================================================
+    // 建構函數
+    // ========================================================================
+
+    /// 使用 Trusted Dealer 生成一個新的群組
+    ///
+    /// # 參數
+    /// - `max_signers`: 簽署者總數
+    /// - `min_signers`: 門檻值
+    /// - `services`: 稽核日誌與 Session Store
+    pub fn generate_with_dealer(
+        max_signers: u16,
+        min_signers: u16,
+        services: &GroupServices,
+    ) -> Result<Self, GroupError> {
+        if min_signers < 2 || min_signers > max_signers {
+            return Err(GroupError::InvalidParameters {
+                min_signers,
+                max_signers,
+            });
+        }
+
+        let (shares, pubkey_package) = frost::keys::generate_with_dealer(
+            max_signers,
+            min_signers,
+            frost::keys::IdentifierList::Default,
+            thread_rng(),
+        )
+        .map_err(|e| GroupError::KeyGenerationFailed(format!("{:?}", e)))?;
+
+        Self::from_shares(
+            GroupId::new(),
+            pubkey_package,
+            shares,
+            min_signers,
+            max_signers,
+            services,
+        )
+    }
+
+    /// 由既有的金鑰分片建立群組
+    pub fn from_shares(
+        group_id: GroupId,
+        pubkey_package: frost::keys::PublicKeyPackage,
+        shares: BTreeMap<frost::Identifier, frost::keys::SecretShare>,
+        min_signers: u16,
+        max_signers: u16,
+        services: &GroupServices,
+    ) -> Result<Self, GroupError> {
+        let signers = shares
+            .into_values()
+            .map(Signer::new)
+            .collect::<Result<Vec<_>, _>>()?;
+        let roster = roster_of(&signers);
+        Ok(Self::from_signers(
+            group_id,
+            pubkey_package,
+            signers,
+            roster,
+            min_signers,
+            max_signers,
+            services,
+        ))
+    }
+
+    /// 由已驗證的 KeyPackage 建立群組（例如從 `frost-cli keygen` 的金鑰目錄載入）
+    pub fn from_key_packages(
+        group_id: GroupId,
+        pubkey_package: frost::keys::PublicKeyPackage,
+        key_packages: BTreeMap<frost::Identifier, frost::keys::KeyPackage>,
+        min_signers: u16,
+        max_signers: u16,
+        services: &GroupServices,
+    ) -> Self {
+        let signers: Vec<Signer> = key_packages
+            .into_values()
+            .map(Signer::from_key_package)
+            .collect();
+        let roster = roster_of(&signers);
+        Self::from_signers(
+            group_id,
+            pubkey_package,
+            signers,
+            roster,
+            min_signers,
+            max_signers,
+            services,
+        )
+    }
+
+    /// 由已建立的簽署者與公布的身分名冊建立群組（例如從金鑰目錄載入）
+    ///
+    /// 名冊可以包含不在此行程中的簽署者；呼叫者應確認本機簽署者的身分金鑰
+    /// 與名冊相符，否則它們的訊息會被拒絕。
+    pub fn from_signers(
+        group_id: GroupId,
+        pubkey_package: frost::keys::PublicKeyPackage,
+        signers: Vec<Signer>,
+        roster: Roster,
+        min_signers: u16,
+        max_signers: u16,
+        services: &GroupServices,
+    ) -> Self {
+        // 將名冊分發給所有簽署者
+        let signers_by_id = DashMap::new();
+        for signer in signers {
+            let signer_id = signer.numeric_id();
+            let mut signer = signer.with_roster(roster.clone());
+            if let Some(audit_log) = &services.audit_log {
+                signer = signer.with_audit_log(
+                    Arc::clone(audit_log),
+                    format!("group/{}/signer_{}", group_id, signer_id),
+                );
+            }
+            signers_by_id.insert(signer_id, Arc::new(signer));
+        }
+
+        let mut coordinator = Coordinator::new(pubkey_package, min_signers).with_roster(roster);
+        if let Some(audit_log) = &services.audit_log {
+            coordinator = coordinator.with_audit_log(
+                Arc::clone(audit_log),
+                format!("group/{}/coordinator", group_id),
+            );
+        }
+        if let Some(session_store) = &services.session_store {
+            coordinator = coordinator.with_session_store(Arc::clone(session_store), group_id);
+        }
+        if let Some(metrics) = &services.metrics {
+            coordinator = coordinator.with_metrics(Arc::clone(metrics));
+        }
+
+        tracing::info!(
+            group_id = %group_id,
+            min_signers,
+            max_signers,
+            "Created signing group"
+        );
+
+        Self {
+            group_id,
+            coordinator: Arc::new(coordinator),
+            signers: signers_by_id,
+            min_signers,
+            max_signers,
+        }
+    }
+
+    // ========================================================================
+    // 存取方法
+    // ========================================================================
+
+    /// 獲取群組 ID
+    pub fn id(&self) -> GroupId {
+        self.group_id
+    }
+
+    /// 獲取此群組的協調者
+    pub fn coordinator(&self) -> &Arc<Coordinator> {
+        &self.coordinator
+    }
+
+    /// 獲取指定的簽署者
+    pub fn get_signer(&self, signer_id: u16) -> Option<Arc<Signer>> {
+        self.signers.get(&signer_id).map(|s| Arc::clone(&s))
+    }
+
+    /// 獲取所有簽署者 ID（已排序）
+    pub fn signer_ids(&self) -> Vec<u16> {
+        let mut ids: Vec<u16> = self.signers.iter().map(|entry| *entry.key()).collect();
+        ids.sort_unstable();
+        ids
+    }
+
+    /// 通知所有簽署者丟棄指定 Session 的 Nonce（用於中止會話）
+    pub fn abort_session_nonces(&self, session_id: &SessionId) {
+        for signer in self.signers.iter() {
+            signer.clear_session(session_id);
+        }
+    }
+
+    /// 定期維護：讓超時的會話過期並通知簽署者丟棄 Nonce，清除過舊的終止會話
+    ///
+    /// # 參數
+    /// - `timeout`: 未完成會話的存活時間（預設 `coordinator::SESSION_TIMEOUT_SECS`）
+    /// - `retention`: 終止會話保留供查詢的時間（預設 `coordinator::FINISHED_SESSION_RETENTION_SECS`）
+    ///
+    /// 返回本次過期的會話數量。
+    pub fn expire_stale_sessions(
+        &self,
+        timeout: chrono::Duration,
+        retention: chrono::Duration,
+    ) -> usize {
+        let expired = self.coordinator.expire_stale_sessions(timeout);
+        for session_id in &expired {
+            self.abort_session_nonces(session_id);
+        }
+
+        self.coordinator.prune_finished_sessions(retention);
+
+        expired.len()
+    }
+
+    /// 中止所有進行中的會話並讓簽署者丟棄其 Nonce（關機時使用）
+    ///
+    /// 返回被中止的 Session ID；遠端簽署者需由呼叫者另行通知。
+    pub fn abort_active_sessions(&self, reason: &str) -> Vec<SessionId> {
+        let aborted = self.coordinator.abort_active_sessions(reason);
+        for session_id in &aborted {
+            self.abort_session_nonces(session_id);
+        }
+        aborted
+    }
+
+    /// 丟棄所有簽署者仍持有的 Nonce，返回丟棄的數量
+    ///
+    /// 包含外部協調者透過 Round 1 端點取得承諾、但還沒完成 Round 2 的 Nonce。
+    pub fn discard_all_nonces(&self) -> usize {
+        self.signers
+            .iter()
+            .map(|signer| {
+                let count = signer.active_sessions_count();
+                signer.clear_all_sessions();
+                count
+            })
+            .sum()
+    }
+
+    /// 簽署者數量
+    pub fn signers_count(&self) -> usize {
+        self.signers.len()
+    }
+
+    /// 門檻值
+    pub fn min_signers(&self) -> u16 {
+        self.min_signers
+    }
+
+    /// 簽署者總數
+    pub fn max_signers(&self) -> u16 {
+        self.max_signers
+    }
+
+    /// 群組公鑰（hex 編碼）
+    pub fn group_public_key_hex(&self) -> String {
+        hex::encode(self.coordinator.group_public_key().serialize().unwrap())
+    }
+
+    /// 建立 `POST /groups` 的回應
+    pub fn to_setup_response(&self) -> SetupResponse {
+        SetupResponse {
+            group_id: self.group_id,
+            min_signers: self.min_signers,
+            group_public_key: self.group_public_key_hex(),
+            signer_ids: self.signer_ids(),
+            identity_keys: self
+                .coordinator
+                .roster()
+                .map(|roster| roster.to_hex_map())
+                .unwrap_or_default(),
+            message: format!(
+                "Created {}-of-{} signing group",
+                self.min_signers, self.max_signers
+            ),
+        }
+    }
+}
+
+/// 以簽署者的身分公鑰組成名冊（金鑰剛生成、尚未公布時使用）
+fn roster_of(signers: &[Signer]) -> Roster {
+    let mut roster = Roster::new();
+    for signer in signers {
+        roster.insert(signer.numeric_id(), signer.identity_public_key());
+    }
+    roster
+}
+
+// ============================================================================
+// 測試
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::coordinator::DEFAULT_ROUND_TIMEOUT;
+    use crate::message::raw_digest;
+
+    #[test]
+    fn test_groups_do_not_share_nonces() {
+        let group_a = SigningGroup::generate_with_dealer(5, 3, &GroupServices::default()).unwrap();
+        let group_b = SigningGroup::generate_with_dealer(5, 3, &GroupServices::default()).unwrap();
+
+        assert_ne!(group_a.group_public_key_hex(), group_b.group_public_key_hex());
+        assert_eq!(group_a.signer_ids(), vec![1, 2, 3, 4, 5]);
+
+        // 在群組 A 建立 Session 並生成 Nonce
+        let session_id = group_a.coordinator().create_session(b"vault A".to_vec()).unwrap();
+        let signer_a = group_a.get_signer(1).unwrap();
+        signer_a.commit(session_id).unwrap();
+
+        // 同一個 Session ID 在群組 B 中不存在任何 Nonce
+        let signer_b = group_b.get_signer(1).unwrap();
+        assert_eq!(signer_b.active_sessions_count(), 0);
+        assert!(!signer_b.clear_session(&session_id));
+        assert_eq!(group_b.coordinator().active_sessions_count(), 0);
+    }
+
+    #[tokio::test]
+    async fn test_group_reloaded_from_key_directory_keeps_public_key() {
+        use crate::cli::file_store::FileStore;
+        use crate::message::SignPayload;
+
+        let (shares, pubkey_package) = frost::keys::generate_with_dealer(
+            5,
+            3,
+            frost::keys::IdentifierList::Default,
+            thread_rng(),
+        )
+        .unwrap();
+        let key_packages = shares
+            .into_iter()
+            .map(|(id, share)| (id, frost::keys::KeyPackage::try_from(share).unwrap()))
+            .collect();
+
+        let group_id = GroupId::new();
+        let dir = std::env::temp_dir().join(format!("frost-keys-{}", group_id));
+        FileStore::save_group_keys(&dir, group_id, &pubkey_package, &key_packages, 3, 5).unwrap();
+
+        // 重新載入後是同一個群組：相同的群組 ID、公鑰與簽署者
+        let (group, generated) =
+            FileStore::load_or_generate_group(&dir, 5, 3, &GroupServices::default()).unwrap();
+        assert!(!generated);
+        assert_eq!(group.id(), group_id);
+
+        // 身分金鑰來自金鑰檔：再次載入時名冊不變，且與公布的名冊一致
+        let (reloaded, _) =
+            FileStore::load_or_generate_group(&dir, 5, 3, &GroupServices::default()).unwrap();
+        let roster = group.coordinator().roster().unwrap().to_hex_map();
+        assert_eq!(roster, reloaded.coordinator().roster().unwrap().to_hex_map());
+        assert_eq!(
+            roster[&1],
+            reloaded.get_signer(1).unwrap().identity_public_key().to_hex()
+        );
+        assert_eq!(
+            group.group_public_key_hex(),
+            hex::encode(pubkey_package.verifying_key().serialize().unwrap())
+        );
+        assert_eq!(group.signer_ids(), vec![1, 2, 3, 4, 5]);
+
+        let signers: Vec<_> = [1, 3, 5]
+            .into_iter()
+            .filter_map(|id| group.get_signer(id))
+            .collect();
+        let payload = SignPayload::Raw {
+            message: hex::encode("persisted group"),
+        };
+        let outcome = group
+            .coordinator()
+            .orchestrate_signing(&signers, &payload, DEFAULT_ROUND_TIMEOUT)
+            .await
+            .unwrap();
+        assert!(pubkey_package
+            .verifying_key()
+            .verify(&raw_digest(b"persisted group"), &outcome.signature)
+            .is_ok());
+
+        std::fs::remove_dir_all(&dir).ok();
+    }
+
+    #[test]
+    fn test_invalid_group_parameters() {
+        assert!(matches!(
+            SigningGroup::generate_with_dealer(3, 5, &GroupServices::default()),
+            Err(GroupError::InvalidParameters { .. })
+        ));
+    }
+}
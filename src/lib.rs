@@ -7,7 +7,16 @@
 //!
 //! - `coordinator`: 協調者邏輯 - 編排簽章流程，不持有私鑰
 //! - `signer`: 簽署者邏輯 - 管理金鑰分片和 Nonce 狀態
+//! - `group`: 簽章群組 - 多租戶的 Coordinator + Signers 組合
+//! - `peer`: P2P 模式 - 無協調者行程，每個參與者互相廣播並在本地聚合
+//! - `envelope`: 訊息信封 - 簽署者身分金鑰與名冊驗證
+//! - `message`: 簽章訊息類型 - 結構化請求與 domain separation
+//! - `audit`: 稽核日誌 - 雜湊鏈 JSONL 簽章事件紀錄
+//! - `session_store`: Session 持久化 - 可插拔的協調者 Session 儲存與啟動恢復
+//! - `metrics`: Prometheus 指標 - 會話、簽署者延遲與逾時、HTTP 回應碼與傳輸統計
+//! - `telemetry`: 日誌與追蹤 - `RUST_LOG` / JSON 日誌、W3C traceparent 傳遞與 OpenTelemetry 匯出
 //! - `api`: API 合約 - 共用的資料結構（用於序列化）
+//! - `server`: HTTP 服務 - 路由組裝（`ServerBuilder`）、處理器、認證、TLS 與分層設定
 //! - `cli`: CLI 工具相關模組（條件編譯）
 //!
 //! ## 使用範例
@@ -44,8 +53,16 @@ pub use frost_secp256k1 as frost;
 // ============================================================================
 
 pub mod api;
+pub mod audit;
 pub mod coordinator;
+pub mod envelope;
+pub mod group;
+pub mod message;
+pub mod metrics;
+pub mod peer;
+pub mod session_store;
 pub mod signer;
+pub mod telemetry;
 
 // ============================================================================
 // Transport 抽象層 - 訊息傳遞介面
@@ -53,6 +70,12 @@ pub mod signer;
 
 pub mod transport;
 
+// ============================================================================
+// HTTP 服務 - 路由、處理器、認證與 TLS
+// ============================================================================
+
+pub mod server;
+
 // ============================================================================
 // CLI 模組
 // ============================================================================
@@ -63,9 +86,15 @@ pub mod cli;
 // 常用型別重新匯出 - 簡化外部使用
 // ============================================================================
 
-pub use api::{CommitmentData, SessionId, SignatureShareData, SigningPackageData};
-pub use coordinator::{Coordinator, CoordinatorError};
+pub use api::{CommitmentData, GroupId, SessionId, SignatureShareData, SigningPackageData};
+pub use coordinator::{Coordinator, CoordinatorError, SigningOutcome};
+pub use group::{GroupError, GroupServices, SigningGroup};
+pub use message::{MessageError, MessageKind, SignPayload};
+pub use metrics::Metrics;
+pub use peer::{Peer, PeerError};
+pub use session_store::{MemorySessionStore, SessionStore, SessionStoreError, SledSessionStore};
 pub use signer::{Signer, SignerError};
+pub use telemetry::TraceContext;
 
 // ============================================================================
 // 版本資訊
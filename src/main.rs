@@ -8,45 +8,35 @@
 //! - **HTTP API**: RESTful API 提供簽章服務
 //!
 //! ## API 端點
-//! - `GET  /health` - 健康檢查
-//! - `GET  /pubkey` - 獲取群組公鑰
-//! - `POST /signer/:id/round1` - Round 1: 生成承諾
-//! - `POST /signer/:id/round2` - Round 2: 生成簽章分片
-//! - `POST /sign` - 完整簽章流程（示範用）
+//! 路由由 `server::ServerBuilder` 組裝，完整的端點列表見 `server` 模組。
 //!
 //! ## 運行方式
 //! ```bash
 //! cargo run --release
+//! cargo run --release -- --config frost.toml --port 8080
+//! cargo run --release -- --print-config
 //! ```
 //!
-//! 服務將在 http://127.0.0.1:3000 啟動
-
-// ============================================================================
-// 模組聲明
-// ============================================================================
-
-mod api;
-mod coordinator;
-mod handlers;
-mod signer;
+//! 服務預設在 http://0.0.0.0:3000 啟動。設定檔、環境變數與命令列參數的合併方式
+//! 見 `server::config` 模組。
+//!
+//! 收到 SIGINT / SIGTERM 時停止接受新會話，等待進行中的會話完成（最多
+//! `timeouts.shutdown_secs`），中止其餘會話並寫入稽核日誌，見 `server::shutdown` 模組。
+//!
+//! 除了 `/health` 與公鑰查詢外，所有端點都需要 `Authorization: Bearer <token>`，
+//! 詳見 `server::auth` 模組。
 
 // ============================================================================
 // 導入
 // ============================================================================
 
-use axum::{
-    routing::{get, post},
-    Router,
-};
-use frost_secp256k1 as frost;
-use handlers::AppState;
-use rand::thread_rng;
-use std::net::SocketAddr;
-use tower_http::{
-    cors::{Any, CorsLayer},
-    trace::TraceLayer,
-};
-use tracing::Level;
+// 核心邏輯與 HTTP 處理器都在 library crate 中
+use clap::Parser;
+use frost_threshold_signature::cli::FileStore;
+use frost_threshold_signature::server::config::{ServerArgs, ServerConfig};
+use frost_threshold_signature::server::{self, auth, shutdown, tls, AppState, ServerBuilder};
+use frost_threshold_signature::{audit, group, session_store, telemetry, Metrics};
+use std::sync::Arc;
 
 // ============================================================================
 // 主程式
@@ -54,117 +44,219 @@ use tracing::Level;
 
 #[tokio::main]
 async fn main() -> anyhow::Result<()> {
+    // ========================================================================
+    // 載入設定（無效的值在啟動前就失敗）
+    // ========================================================================
+    let args = ServerArgs::parse();
+    let config = ServerConfig::load(&args)?;
+    if args.print_config {
+        print!("{}", config.to_toml()?);
+        return Ok(());
+    }
+
     // ========================================================================
     // 初始化日誌系統
     // ========================================================================
-    tracing_subscriber::fmt()
-        .with_max_level(Level::INFO)
-        .with_target(false)
-        .with_thread_ids(false)
-        .with_file(false)
-        .init();
+    // RUST_LOG 指定過濾條件（預設 info），FROST_LOG_FORMAT=json 輸出 JSON 日誌；
+    // 以 `otel` feature 編譯並設定 OTEL_EXPORTER_OTLP_ENDPOINT 時匯出 span
+    let _telemetry = telemetry::init_tracing("frost-api", "info")?;
 
-    print_banner();
+    print_banner(config.group.threshold, config.group.signers);
 
     // ========================================================================
-    // Setup: Trusted Dealer 金鑰生成
+    // Setup: 稽核日誌、Session Store 與預設群組
     // ========================================================================
-    tracing::info!("🔑 Initializing FROST setup (Trusted Dealer)");
+    tracing::info!("🔑 Initializing FROST setup");
 
-    let max_signers = 5;
-    let min_signers = 3;
+    // 稽核日誌：JSONL 檔案（storage.audit_log），檢查點以 storage.audit_key 簽署
+    let audit_path = &config.storage.audit_log;
+    let checkpoint_key = audit::load_or_generate_checkpoint_key(&config.storage.audit_key)?;
+    let audit_log =
+        Arc::new(audit::AuditLog::open(audit_path)?.with_checkpoint_key(checkpoint_key));
+    let audit_head = audit_log.head();
+    tracing::info!("📜 Audit log: {}", audit_path.display());
+    tracing::info!("   Head: {}", audit_head.hash);
+    tracing::info!(
+        "   Checkpoint key: {}",
+        audit_head.checkpoint_key.unwrap_or_default()
+    );
 
-    let mut rng = thread_rng();
+    // Session Store：sled 資料庫目錄（storage.session_store），設為 "memory" 時不持久化
+    let store_path = &config.storage.session_store;
+    let session_store: Arc<dyn session_store::SessionStore> = if store_path == "memory" {
+        Arc::new(session_store::MemorySessionStore::new())
+    } else {
+        Arc::new(session_store::SledSessionStore::open(store_path)?)
+    };
+    tracing::info!(
+        "💾 Session store: {} ({})",
+        session_store.backend_name(),
+        store_path
+    );
 
-    let (shares, pubkey_package) = frost::keys::generate_with_dealer(
-        max_signers,
-        min_signers,
-        frost::keys::IdentifierList::Default,
-        &mut rng,
-    )?;
+    // Prometheus 指標：所有群組共用，由 GET /metrics 輸出
+    let metrics = Arc::new(Metrics::new());
+
+    let services = group::GroupServices {
+        audit_log: Some(Arc::clone(&audit_log)),
+        session_store: Some(Arc::clone(&session_store)),
+        metrics: Some(Arc::clone(&metrics)),
+    };
+    let max_signers = config.group.signers;
+    let min_signers = config.group.threshold;
+    let default_group = match &config.group.key_dir {
+        // 金鑰目錄：載入既有群組（必須與設定一致），不存在時生成並儲存
+        Some(key_dir) => {
+            let (group, generated) =
+                FileStore::load_or_generate_group(key_dir, max_signers, min_signers, &services)?;
+            if (group.min_signers(), group.max_signers()) != (min_signers, max_signers) {
+                anyhow::bail!(
+                    "Key directory {} holds a {}-of-{} group, but the config asks for {}-of-{}",
+                    key_dir.display(),
+                    group.min_signers(),
+                    group.max_signers(),
+                    min_signers,
+                    max_signers
+                );
+            }
+            let action = if generated { "Generated" } else { "Loaded" };
+            tracing::info!("✓ {} key shares in {}", action, key_dir.display());
+            group
+        }
+        // 未設定金鑰目錄：每次啟動以 Trusted Dealer 生成新群組（不保存）
+        None => {
+            tracing::warn!(
+                "⚠️  group.key_dir is not set: the default group is regenerated on every start, \
+                 so its public key changes and signing sessions will not survive a restart"
+            );
+            group::SigningGroup::generate_with_dealer(max_signers, min_signers, &services)?
+        }
+    };
 
     tracing::info!(
-        "✓ Generated {} key shares with threshold {}",
+        "✓ Using {} key shares with threshold {}",
         max_signers,
         min_signers
     );
-
-    let group_pubkey = pubkey_package.verifying_key();
+    tracing::info!("✓ Default group ID: {}", default_group.id());
     tracing::info!(
         "✓ Group public key: {}...",
-        &hex::encode(group_pubkey.serialize().unwrap())[..32]
+        &default_group.group_public_key_hex()[..32]
     );
 
+    for signer_id in default_group.signer_ids() {
+        tracing::info!("✓ Created Signer {}", signer_id);
+    }
+
     // ========================================================================
-    // 建立 Coordinator 和 Signers
+    // 建立應用狀態
     // ========================================================================
-    let coordinator = coordinator::Coordinator::new(pubkey_package, min_signers);
-
-    let app_state = AppState::new(coordinator);
-
-    // 為每個金鑰分片建立 Signer
-    for (identifier, key_package) in shares {
-        let signer = signer::Signer::new(key_package);
+    let app_state = AppState::new(
+        default_group,
+        Arc::clone(&audit_log),
+        Arc::clone(&session_store),
+    );
 
-        // Convert Identifier to u16 by serializing
-        let id_bytes = identifier.serialize();
-        let signer_id = u16::from_le_bytes([id_bytes[0], id_bytes[1]]);
+    // ========================================================================
+    // 恢復上次執行時未完成的 Session
+    // ========================================================================
+    let recovery = session_store::recover_sessions(
+        &*session_store,
+        &app_state.groups,
+        Some(&audit_log),
+        Some(&app_state.ws_hub),
+    )?;
+    if !recovery.aborted.is_empty() {
+        tracing::info!(
+            "♻️  Session recovery: aborted {} incomplete sessions",
+            recovery.aborted.len()
+        );
+    }
 
-        app_state.add_signer(signer_id, signer);
+    // ========================================================================
+    // 定期讓超時的會話過期（並通知簽署者丟棄 Nonce）
+    // ========================================================================
+    let groups = Arc::clone(&app_state.groups);
+    let timeouts = config.timeouts.clone();
+    tokio::spawn(async move {
+        let mut interval = tokio::time::interval(timeouts.expiry_interval());
+        loop {
+            interval.tick().await;
+            for group in groups.iter() {
+                let expired = group
+                    .expire_stale_sessions(timeouts.session(), timeouts.finished_session_retention());
+                if expired > 0 {
+                    tracing::warn!(group_id = %group.id(), expired, "Expired stale sessions");
+                }
+            }
+        }
+    });
 
-        tracing::info!("✓ Created Signer {}", signer_id);
+    // ========================================================================
+    // 認證與 CORS
+    // ========================================================================
+    let auth_config = auth::AuthConfig::from_env()?;
+    let cert_bound_tokens = auth_config.cert_bound_tokens();
+    if auth_config.disabled {
+        tracing::warn!("⚠️  Authentication DISABLED (FROST_AUTH=disabled) - development only!");
+    } else {
+        tracing::info!("🔒 Authentication enabled ({} API tokens)", auth_config.tokens.len());
+        if let Some(path) = auth_config.write_ephemeral_token()? {
+            tracing::warn!(
+                "🔑 No FROST_AUTH_FILE configured, generated admin token written to {}",
+                path.display()
+            );
+        }
     }
 
     // ========================================================================
     // 建立 HTTP 路由
     // ========================================================================
-    // CORS 配置：允許從任何 origin 訪問（適合展示和開發）
-    let cors = CorsLayer::new()
-        .allow_origin(Any)
-        .allow_methods(Any)
-        .allow_headers(Any);
-
-    let app = Router::new()
-        // 健康檢查與資訊端點
-        .route("/health", get(handlers::health))
-        .route("/pubkey", get(handlers::get_pubkey))
-        // Round 1: Commitment 生成
-        .route(
-            "/signer/:signer_id/round1",
-            post(handlers::signer_round1),
-        )
-        // Round 2: Signature Share 生成
-        .route(
-            "/signer/:signer_id/round2",
-            post(handlers::signer_round2),
-        )
-        // 完整簽章流程（示範用）
-        .route("/sign", post(handlers::sign))
-        // 添加共享狀態
-        .with_state(app_state)
-        // 添加 CORS 中間件（必須在 TraceLayer 之前）
-        .layer(cors)
-        // 添加日誌中間件
-        .layer(TraceLayer::new_for_http());
+    // CORS 配置：cors_origins 指定允許的 origin（"*" 代表全部）
+    let timeouts = &config.timeouts;
+    let builder = ServerBuilder::new(app_state)
+        .with_auth(auth_config)
+        .with_cors(server::cors_origins(&config.cors_origins)?)
+        .with_metrics(metrics)
+        .with_round_timeout(timeouts.round())
+        .with_websocket_heartbeat(timeouts.heartbeat_interval(), timeouts.heartbeat_timeout());
+    // 關機時排空會話用（與路由共用同一組群組、WebSocket 連線與儲存）
+    let shutdown_state = builder.state().clone();
+    let app = builder.build();
 
     // ========================================================================
     // 啟動 HTTP 服務
     // ========================================================================
-    // 支援環境變數配置 HOST 和 PORT
-    // 預設為 0.0.0.0:3000 (允許外部訪問，適合展示)
-    // 可透過環境變數自訂：
-    //   HOST=127.0.0.1 PORT=3000 cargo run (僅本地)
+    // 預設為 0.0.0.0:3000 (允許外部訪問，適合展示)，可自訂：
+    //   cargo run -- --host 127.0.0.1 (僅本地)
     //   HOST=0.0.0.0 PORT=8080 cargo run (所有網路介面)
-    let host = std::env::var("HOST").unwrap_or_else(|_| "0.0.0.0".to_string());
-    let port = std::env::var("PORT")
-        .unwrap_or_else(|_| "3000".to_string())
-        .parse::<u16>()
-        .unwrap_or(3000);
+    let addr = config.addr();
 
-    let addr: SocketAddr = format!("{}:{}", host, port).parse()?;
+    // TLS：設定 TLS_CERT_PATH / TLS_KEY_PATH 後改用 HTTPS
+    let tls_settings = tls::TlsSettings::from_env()?;
+    let scheme = if tls_settings.is_some() { "https" } else { "http" };
 
-    tracing::info!("🚀 FROST API Server starting on http://{}", addr);
-    tracing::info!("💡 Network Access: {}", if host == "0.0.0.0" {
+    tracing::info!("🚀 FROST API Server starting on {}://{}", scheme, addr);
+    match &tls_settings {
+        Some(settings) if settings.client_ca_path.is_some() => {
+            tracing::info!("🔐 TLS: ENABLED with client certificate verification (mTLS)")
+        }
+        Some(_) => tracing::info!("🔐 TLS: ENABLED"),
+        None => tracing::warn!("⚠️  TLS: DISABLED - signing traffic is sent in cleartext"),
+    }
+    // 綁定客戶端憑證的 Token 只能經由 mTLS 使用
+    if cert_bound_tokens > 0 {
+        if tls_settings.as_ref().is_some_and(|s| s.client_ca_path.is_some()) {
+            tracing::info!("🔐 {} API tokens bound to client certificates", cert_bound_tokens);
+        } else {
+            tracing::warn!(
+                "⚠️  {} API tokens are bound to client certificates but mTLS is not enabled \
+                 (TLS_CLIENT_CA_PATH); they will be rejected",
+                cert_bound_tokens
+            );
+        }
+    }
+    tracing::info!("💡 Network Access: {}", if !addr.ip().is_loopback() {
         "ENABLED - Accessible from other devices"
     } else {
         "DISABLED - Localhost only"
@@ -174,15 +266,73 @@ async fn main() -> anyhow::Result<()> {
     tracing::info!("   GET  /pubkey                    - Get group public key");
     tracing::info!("   POST /signer/:id/round1         - Round 1: Generate commitment");
     tracing::info!("   POST /signer/:id/round2         - Round 2: Generate signature share");
+    tracing::info!("   GET  /signer/:id/ws             - Signer WebSocket (push-based rounds)");
     tracing::info!("   POST /sign                      - Complete signing flow");
+    tracing::info!("   GET  /groups                    - List signing groups");
+    tracing::info!("   POST /groups                    - Create a signing group");
+    tracing::info!("   *    /groups/:group_id/...      - Group-scoped endpoints");
+    tracing::info!("   GET  /sessions?status=          - List signing sessions");
+    tracing::info!("   GET  /sessions/:id              - Signing session status");
+    tracing::info!("   GET  /audit                     - Recent audit log entries");
+    tracing::info!("   GET  /audit/head                - Audit log head and checkpoint key");
+    tracing::info!("   GET  /metrics                   - Prometheus metrics");
     tracing::info!("");
     tracing::info!("💡 Try the demo client:");
-    tracing::info!("   cargo run --example demo_client");
+    tracing::info!("   FROST_API_TOKEN=<token> cargo run --example demo_client");
     tracing::info!("");
 
-    let listener = tokio::net::TcpListener::bind(addr).await?;
+    let handle = axum_server::Handle::new();
+    let mut server = match tls_settings {
+        Some(settings) => tokio::spawn(
+            axum_server::bind(addr)
+                .acceptor(settings.acceptor()?)
+                .handle(handle.clone())
+                .serve(app.into_make_service()),
+        ),
+        None => tokio::spawn(
+            axum_server::bind(addr)
+                .handle(handle.clone())
+                .serve(app.into_make_service()),
+        ),
+    };
+
+    // ========================================================================
+    // 優雅關機（SIGINT / SIGTERM）
+    // ========================================================================
+    // 1. 拒絕新的會話（503 SHUTTING_DOWN），進行中的會話在期限內完成
+    // 2. 中止其餘會話並通知簽署者丟棄 Nonce，寫入稽核日誌與 Session Store
+    // 3. 停止 HTTP 服務（仍未結束的連線在 SHUTDOWN_GRACE 後關閉）
+    let signal = tokio::select! {
+        result = &mut server => {
+            // 服務在收到訊號前結束（例如無法綁定位址）
+            result??;
+            return Ok(());
+        }
+        signal = shutdown::wait_for_signal() => signal,
+    };
+
+    let deadline = config.timeouts.shutdown();
+    tracing::info!(
+        "🛑 Received {}, draining in-flight sessions (deadline {}s)",
+        signal,
+        deadline.as_secs()
+    );
+    let summary = shutdown::drain(&shutdown_state, deadline).await;
+
+    handle.graceful_shutdown(Some(shutdown::SHUTDOWN_GRACE));
+    match tokio::time::timeout(shutdown::SHUTDOWN_GRACE * 2, server).await {
+        Ok(result) => result??,
+        Err(_) => tracing::warn!("HTTP server did not stop in time"),
+    }
+
+    // 最後的檢查點已在 drain 中寫入，記錄鏈頭供之後驗證日誌未被截斷
+    tracing::info!("📜 Audit log head: {}", audit_log.head().hash);
 
-    axum::serve(listener, app).await?;
+    if summary.flush_errors.is_empty() {
+        tracing::info!("✓ Shutdown complete: {}", summary);
+    } else {
+        tracing::error!("✗ Shutdown complete with errors: {}", summary);
+    }
 
     Ok(())
 }
@@ -191,10 +341,11 @@ async fn main() -> anyhow::Result<()> {
 // 輔助函數
 // ============================================================================
 
-fn print_banner() {
+fn print_banner(min_signers: u16, max_signers: u16) {
+    let title = format!("FROST {}-of-{} 門檻簽章服務", min_signers, max_signers);
     println!("╔════════════════════════════════════════════════════════════════╗");
     println!("║                                                                ║");
-    println!("║   FROST 3-of-5 門檻簽章服務                                    ║");
+    println!("║   {:<55}║", title);
     println!("║   Level 2: HTTP API Architecture                              ║");
     println!("║                                                                ║");
     println!("║   Bitcoin-Compatible Schnorr Threshold Signatures             ║");
@@ -0,0 +1,442 @@
+//! # 簽章訊息類型 - Domain Separation
+//!
+//! 原本的 API 直接簽署呼叫者提供的 hex bytes，同一把群組金鑰可能在「測試」情境下
+//! 被騙去簽署一段看起來像交易的資料。
+//!
+//! 此模組定義結構化的簽章請求（`SignPayload`），簽署者**自己**從結構化輸入計算
+//! 要簽署的摘要，並依類型套用不同的 domain separation：
+//!
+//! | 類型 | 簽署內容 |
+//! |------|----------|
+//! | `raw` | `tagged_hash("FROST-TS/raw/v1", bytes)`（供相容舊客戶端；結果不可能是其他類型的摘要） |
+//! | `document` | `tagged_hash("FROST-TS/document/v1", SHA256(document))` |
+//! | `bip340_tagged` | `tagged_hash(tag, message)`（保留給其他類型的 tag 會被拒絕） |
+//! | `bitcoin_sighash` | BIP-341 Taproot key-path sighash（`TapSighash` tag） |
+//! | `nostr_event` | NIP-01 event ID：`SHA256([0, pubkey, created_at, kind, tags, content])` |
+//!
+//! 其中 `tagged_hash(tag, m) = SHA256(SHA256(tag) || SHA256(tag) || m)`（BIP-340）。
+//!
+//! 群組以 `frost-secp256k1`（FROST(secp256k1, SHA-256)）簽署，產生的 65 bytes 簽章
+//! **不是** BIP-340 Schnorr 簽章，無法通過 Bitcoin 或 Nostr 的驗證規則。因此
+//! `bip340_tagged`、`bitcoin_sighash`、`nostr_event` 只計算 domain-separated 摘要
+//! （`SignPayload::digest`），`SignPayload::signing_digest` 會以
+//! `MessageError::NotBip340` 拒絕簽署。
+
+use bitcoin::hashes::Hash;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::str::FromStr;
+use thiserror::Error;
+
+// ============================================================================
+// 錯誤定義
+// ============================================================================
+
+#[derive(Debug, Error)]
+pub enum MessageError {
+    #[error("Invalid hex in {field}: {reason}")]
+    InvalidHex { field: &'static str, reason: String },
+
+    #[error("Message must not be empty")]
+    EmptyMessage,
+
+    #[error("Provide exactly one of message or payload")]
+    AmbiguousInput,
+
+    #[error("Tag '{0}' is reserved and cannot be used with bip340_tagged")]
+    ReservedTag(String),
+
+    #[error("Invalid Bitcoin transaction: {0}")]
+    InvalidTransaction(String),
+
+    #[error("Invalid sighash request: {0}")]
+    InvalidSighash(String),
+
+    #[error("Invalid Nostr event: {0}")]
+    InvalidNostrEvent(String),
+
+    #[error("Message kind '{0}' requires a BIP-340 signature, which this group cannot produce")]
+    NotBip340(MessageKind),
+}
+
+// ============================================================================
+// 訊息類型
+// ============================================================================
+
+/// 簽章訊息的類型（記錄於 SessionState 與稽核日誌）
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum MessageKind {
+    /// 原始 bytes
+    #[default]
+    Raw,
+
+    /// 文件的 SHA-256
+    Document,
+
+    /// BIP-340 tagged hash（domain-separated digest only, signature is not BIP-340）
+    Bip340Tagged,
+
+    /// Bitcoin Taproot sighash（domain-separated digest only, signature is not BIP-340）
+    BitcoinSighash,
+
+    /// Nostr event ID（domain-separated digest only, signature is not BIP-340）
+    NostrEvent,
+}
+
+impl MessageKind {
+    /// 此類型的簽章是否有意義
+    ///
+    /// 需要 BIP-340 簽章的類型回傳 false：FROST(secp256k1, SHA-256) 簽章無法被
+    /// Bitcoin 或 Nostr 驗證。
+    pub fn is_signable(&self) -> bool {
+        matches!(self, MessageKind::Raw | MessageKind::Document)
+    }
+}
+
+impl std::fmt::Display for MessageKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let name = match self {
+            MessageKind::Raw => "raw",
+            MessageKind::Document => "document",
+            MessageKind::Bip340Tagged => "bip340_tagged",
+            MessageKind::BitcoinSighash => "bitcoin_sighash",
+            MessageKind::NostrEvent => "nostr_event",
+        };
+        write!(f, "{}", name)
+    }
+}
+
+/// 文件內容的編碼方式
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum DocumentEncoding {
+    /// UTF-8 文字
+    #[default]
+    Utf8,
+
+    /// hex 編碼的二進位資料
+    Hex,
+}
+
+/// 被花費的輸出（計算 Taproot sighash 需要所有輸入的 prevout）
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PrevOut {
+    /// 金額（satoshi）
+    pub value_sat: u64,
+
+    /// scriptPubKey（hex 編碼）
+    pub script_pubkey: String,
+}
+
+/// 結構化的簽章請求
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum SignPayload {
+    /// 原始 bytes（hex 編碼），簽署 `tagged_hash("FROST-TS/raw/v1", bytes)`
+    Raw { message: String },
+
+    /// 文件：簽署 `tagged_hash("FROST-TS/document/v1", SHA256(document))`
+    Document {
+        content: String,
+        #[serde(default)]
+        encoding: DocumentEncoding,
+    },
+
+    /// BIP-340 tagged hash：`tagged_hash(tag, message)`
+    ///
+    /// domain-separated digest only, signature is not BIP-340
+    Bip340Tagged {
+        tag: String,
+        /// hex 編碼的訊息
+        message: String,
+    },
+
+    /// Bitcoin Taproot key-path sighash（BIP-341）
+    ///
+    /// domain-separated digest only, signature is not BIP-340
+    BitcoinSighash {
+        /// 未簽署交易（hex 編碼的 consensus serialization）
+        transaction: String,
+        /// 要簽署的輸入索引
+        input_index: usize,
+        /// 所有輸入的 prevout（依輸入順序）
+        prevouts: Vec<PrevOut>,
+        /// sighash 類型（例如 "SIGHASH_DEFAULT"、"SIGHASH_ALL"），預設 SIGHASH_DEFAULT
+        #[serde(default)]
+        sighash_type: Option<String>,
+    },
+
+    /// Nostr event（NIP-01）
+    ///
+    /// domain-separated digest only, signature is not BIP-340
+    NostrEvent {
+        /// x-only 公鑰（64 個 hex 字元）
+        pubkey: String,
+        created_at: u64,
+        kind: u32,
+        #[serde(default)]
+        tags: Vec<Vec<String>>,
+        content: String,
+    },
+}
+
+/// `bip340_tagged` 不得使用的 tag（其他類型或協議專用）
+const RESERVED_TAGS: &[&str] = &[
+    "BIP0340/challenge",
+    "BIP0340/aux",
+    "BIP0340/nonce",
+    "TapSighash",
+    "TapLeaf",
+    "TapBranch",
+    "TapTweak",
+];
+
+/// 本專案專用 tag 的前綴
+const FROST_TAG_PREFIX: &str = "FROST-TS/";
+
+/// 文件類型使用的 tag
+const DOCUMENT_TAG: &str = "FROST-TS/document/v1";
+
+/// raw 類型使用的 tag（原始 bytes 不會被直接簽署，無法偽裝成 sighash 等摘要）
+const RAW_TAG: &str = "FROST-TS/raw/v1";
+
+impl SignPayload {
+    /// 訊息類型
+    pub fn kind(&self) -> MessageKind {
+        match self {
+            SignPayload::Raw { .. } => MessageKind::Raw,
+            SignPayload::Document { .. } => MessageKind::Document,
+            SignPayload::Bip340Tagged { .. } => MessageKind::Bip340Tagged,
+            SignPayload::BitcoinSighash { .. } => MessageKind::BitcoinSighash,
+            SignPayload::NostrEvent { .. } => MessageKind::NostrEvent,
+        }
+    }
+
+    /// 計算實際要簽署的 bytes
+    ///
+    /// 拒絕需要 BIP-340 簽章的類型；協調者與簽署者都必須透過此方法取得摘要。
+    pub fn signing_digest(&self) -> Result<Vec<u8>, MessageError> {
+        let kind = self.kind();
+        if !kind.is_signable() {
+            return Err(MessageError::NotBip340(kind));
+        }
+        self.digest()
+    }
+
+    /// 計算此類型的 domain-separated 摘要
+    ///
+    /// 不檢查類型是否可簽署；要簽署時請使用 `signing_digest`。
+    pub fn digest(&self) -> Result<Vec<u8>, MessageError> {
+        match self {
+            SignPayload::Raw { message } => {
+                let bytes = decode_hex("message", message)?;
+                if bytes.is_empty() {
+                    return Err(MessageError::EmptyMessage);
+                }
+                Ok(raw_digest(&bytes).to_vec())
+            }
+
+            SignPayload::Document { content, encoding } => {
+                let document = match encoding {
+                    DocumentEncoding::Utf8 => content.as_bytes().to_vec(),
+                    DocumentEncoding::Hex => decode_hex("content", content)?,
+                };
+                Ok(tagged_hash(DOCUMENT_TAG, &Sha256::digest(&document)).to_vec())
+            }
+
+            SignPayload::Bip340Tagged { tag, message } => {
+                if tag.is_empty()
+                    || tag.starts_with(FROST_TAG_PREFIX)
+                    || RESERVED_TAGS.contains(&tag.as_str())
+                {
+                    return Err(MessageError::ReservedTag(tag.clone()));
+                }
+                let message = decode_hex("message", message)?;
+                Ok(tagged_hash(tag, &message).to_vec())
+            }
+
+            SignPayload::BitcoinSighash {
+                transaction,
+                input_index,
+                prevouts,
+                sighash_type,
+            } => taproot_sighash(transaction, *input_index, prevouts, sighash_type.as_deref()),
+
+            SignPayload::NostrEvent {
+                pubkey,
+                created_at,
+                kind,
+                tags,
+                content,
+            } => {
+                let pubkey_bytes = decode_hex("pubkey", pubkey)
+                    .map_err(|e| MessageError::InvalidNostrEvent(e.to_string()))?;
+                if pubkey_bytes.len() != 32 {
+                    return Err(MessageError::InvalidNostrEvent(
+                        "pubkey must be a 32-byte x-only key".to_string(),
+                    ));
+                }
+
+                let serialized = serde_json::json!([
+                    0,
+                    pubkey.to_lowercase(),
+                    created_at,
+                    kind,
+                    tags,
+                    content
+                ])
+                .to_string();
+                Ok(Sha256::digest(serialized.as_bytes()).to_vec())
+            }
+        }
+    }
+}
+
+// ============================================================================
+// 輔助函數
+// ============================================================================
+
+/// BIP-340 tagged hash
+pub fn tagged_hash(tag: &str, message: &[u8]) -> [u8; 32] {
+    let tag_hash = Sha256::digest(tag.as_bytes());
+    let mut hasher = Sha256::new();
+    hasher.update(tag_hash);
+    hasher.update(tag_hash);
+    hasher.update(message);
+    hasher.finalize().into()
+}
+
+/// raw 訊息實際被簽署的摘要（`frost-cli` 的檔案流程也使用）
+pub fn raw_digest(message: &[u8]) -> [u8; 32] {
+    tagged_hash(RAW_TAG, message)
+}
+
+fn decode_hex(field: &'static str, value: &str) -> Result<Vec<u8>, MessageError> {
+    hex::decode(value).map_err(|e| MessageError::InvalidHex {
+        field,
+        reason: e.to_string(),
+    })
+}
+
+/// 計算 BIP-341 Taproot key-path sighash
+fn taproot_sighash(
+    transaction: &str,
+    input_index: usize,
+    prevouts: &[PrevOut],
+    sighash_type: Option<&str>,
+) -> Result<Vec<u8>, MessageError> {
+    use bitcoin::sighash::{Prevouts, SighashCache, TapSighashType};
+
+    let tx_bytes = decode_hex("transaction", transaction)?;
+    let tx: bitcoin::Transaction = bitcoin::consensus::deserialize(&tx_bytes)
+        .map_err(|e| MessageError::InvalidTransaction(e.to_string()))?;
+
+    if input_index >= tx.input.len() {
+        return Err(MessageError::InvalidSighash(format!(
+            "input_index {} out of range ({} inputs)",
+            input_index,
+            tx.input.len()
+        )));
+    }
+    if prevouts.len() != tx.input.len() {
+        return Err(MessageError::InvalidSighash(format!(
+            "expected {} prevouts, got {}",
+            tx.input.len(),
+            prevouts.len()
+        )));
+    }
+
+    let prevouts = prevouts
+        .iter()
+        .map(|prevout| {
+            Ok(bitcoin::TxOut {
+                value: bitcoin::Amount::from_sat(prevout.value_sat),
+                script_pubkey: bitcoin::ScriptBuf::from_bytes(decode_hex(
+                    "script_pubkey",
+                    &prevout.script_pubkey,
+                )?),
+            })
+        })
+        .collect::<Result<Vec<_>, MessageError>>()?;
+
+    let sighash_type = match sighash_type {
+        Some(name) => TapSighashType::from_str(name)
+            .map_err(|e| MessageError::InvalidSighash(e.to_string()))?,
+        None => TapSighashType::Default,
+    };
+
+    let sighash = SighashCache::new(&tx)
+        .taproot_key_spend_signature_hash(input_index, &Prevouts::All(&prevouts), sighash_type)
+        .map_err(|e| MessageError::InvalidSighash(e.to_string()))?;
+
+    Ok(sighash.to_byte_array().to_vec())
+}
+
+// ============================================================================
+// 測試
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_domain_separation() {
+        let raw = SignPayload::Raw {
+            message: hex::encode(b"hello"),
+        };
+        let document = SignPayload::Document {
+            content: "hello".to_string(),
+            encoding: DocumentEncoding::Utf8,
+        };
+        let tagged = SignPayload::Bip340Tagged {
+            tag: "example/app".to_string(),
+            message: hex::encode(b"hello"),
+        };
+
+        // 同樣的內容在不同類型下產生不同的摘要
+        let digests = [
+            raw.digest().unwrap(),
+            document.digest().unwrap(),
+            tagged.digest().unwrap(),
+        ];
+        assert_eq!(digests[0], tagged_hash(RAW_TAG, b"hello"));
+        assert_ne!(digests[0], digests[1]);
+        assert_ne!(digests[1], digests[2]);
+        assert_eq!(digests[1].len(), 32);
+
+        // 保留的 tag 必須透過對應的類型簽署
+        for tag in ["TapSighash", "FROST-TS/document/v1"] {
+            let payload = SignPayload::Bip340Tagged {
+                tag: tag.to_string(),
+                message: "00".to_string(),
+            };
+            assert!(matches!(payload.digest(), Err(MessageError::ReservedTag(_))));
+        }
+    }
+
+    #[test]
+    fn test_nostr_event_id() {
+        // NIP-01 序列化：[0, pubkey, created_at, kind, tags, content]
+        let pubkey = "a".repeat(64);
+        let payload = SignPayload::NostrEvent {
+            pubkey: pubkey.clone(),
+            created_at: 1_700_000_000,
+            kind: 1,
+            tags: vec![],
+            content: "gm".to_string(),
+        };
+
+        let expected = Sha256::digest(
+            format!("[0,\"{}\",1700000000,1,[],\"gm\"]", pubkey).as_bytes(),
+        );
+        assert_eq!(payload.digest().unwrap(), expected.to_vec());
+
+        // FROST(secp256k1, SHA-256) 簽章不是 BIP-340，拒絕簽署
+        assert!(matches!(
+            payload.signing_digest(),
+            Err(MessageError::NotBip340(MessageKind::NostrEvent))
+        ));
+    }
+}
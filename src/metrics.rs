@@ -0,0 +1,348 @@
+//! # Metrics - Prometheus 指標
+//!
+//! 協調者在簽章流程中即時記錄會話數量、每位簽署者的回覆延遲與失敗原因；
+//! 簽署者持有的 Nonce、傳輸統計等狀態則在每次 `GET /metrics` 抓取時取樣。
+//!
+//! ## 指標
+//!
+//! | 名稱 | 類型 | 標籤 | 說明 |
+//! |------|------|------|------|
+//! | `frost_sessions_started_total` | counter | | 建立的簽章會話 |
+//! | `frost_sessions_completed_total` | counter | | 聚合並驗證成功的會話 |
+//! | `frost_sessions_failed_total` | counter | `status`（failed / expired） | 中止或逾時的會話 |
+//! | `frost_signer_round_duration_seconds` | histogram | `signer_id`, `round` | 從發出請求到收到該簽署者的回覆 |
+//! | `frost_signer_timeouts_total` | counter | `signer_id`, `round` | 在期限內沒有回覆 |
+//! | `frost_aggregation_failures_total` | counter | `reason` | 聚合或驗證失敗 |
+//! | `frost_signer_outstanding_nonces` | gauge | `group_id`, `signer_id` | 已承諾但尚未消費的 Nonce |
+//! | `frost_http_requests_total` | counter | `method`, `path`, `status` | HTTP 回應碼 |
+//! | `frost_transport_messages_total` | counter | `type` | 模擬 LoRa 鏈路上的訊息 |
+//! | `frost_transport_bytes_total` | counter | | 模擬 LoRa 鏈路上的位元組 |
+//! | `frost_lora_retries_total` | counter | | 模擬 LoRa 鏈路的重傳次數 |
+//!
+//! 例如對持續逾時的簽署者告警：
+//!
+//! ```text
+//! increase(frost_signer_timeouts_total[15m]) >= 3
+//! ```
+
+use crate::coordinator::CoordinatorError;
+use crate::group::SigningGroup;
+use crate::transport::TransportStats;
+use prometheus::{
+    HistogramOpts, HistogramVec, IntCounter, IntCounterVec, IntGaugeVec, Opts, Registry,
+    TextEncoder,
+};
+use std::time::Duration;
+
+/// Prometheus 文字格式的 Content-Type
+pub const CONTENT_TYPE: &str = prometheus::TEXT_FORMAT;
+
+/// 簽署者回覆延遲的 bucket（秒）：涵蓋本機呼叫到低速 LoRa 鏈路
+const ROUND_LATENCY_BUCKETS: &[f64] = &[
+    0.001, 0.005, 0.01, 0.05, 0.1, 0.5, 1.0, 5.0, 15.0, 30.0, 60.0, 120.0, 300.0,
+];
+
+// ============================================================================
+// Metrics
+// ============================================================================
+
+/// 服務的 Prometheus 指標（以 `Arc` 在群組與 HTTP 層之間共用）
+pub struct Metrics {
+    registry: Registry,
+    sessions_started: IntCounter,
+    sessions_completed: IntCounter,
+    sessions_failed: IntCounterVec,
+    round_duration: HistogramVec,
+    signer_timeouts: IntCounterVec,
+    aggregation_failures: IntCounterVec,
+    outstanding_nonces: IntGaugeVec,
+    http_requests: IntCounterVec,
+    transport_messages: IntCounterVec,
+    transport_bytes: IntCounter,
+    lora_retries: IntCounter,
+}
+
+impl Metrics {
+    /// 建立並註冊所有指標
+    pub fn new() -> Self {
+        let registry = Registry::new();
+
+        let metrics = Self {
+            sessions_started: IntCounter::new(
+                "frost_sessions_started_total",
+                "Signing sessions created",
+            )
+            .unwrap(),
+            sessions_completed: IntCounter::new(
+                "frost_sessions_completed_total",
+                "Signing sessions aggregated and verified",
+            )
+            .unwrap(),
+            sessions_failed: IntCounterVec::new(
+                Opts::new("frost_sessions_failed_total", "Signing sessions aborted or expired"),
+                &["status"],
+            )
+            .unwrap(),
+            round_duration: HistogramVec::new(
+                HistogramOpts::new(
+                    "frost_signer_round_duration_seconds",
+                    "Time from the coordinator's request to the signer's reply",
+                )
+                .buckets(ROUND_LATENCY_BUCKETS.to_vec()),
+                &["signer_id", "round"],
+            )
+            .unwrap(),
+            signer_timeouts: IntCounterVec::new(
+                Opts::new(
+                    "frost_signer_timeouts_total",
+                    "Rounds in which a signer did not reply before the deadline",
+                ),
+                &["signer_id", "round"],
+            )
+            .unwrap(),
+            aggregation_failures: IntCounterVec::new(
+                Opts::new(
+                    "frost_aggregation_failures_total",
+                    "Signature aggregation or verification failures",
+                ),
+                &["reason"],
+            )
+            .unwrap(),
+            outstanding_nonces: IntGaugeVec::new(
+                Opts::new(
+                    "frost_signer_outstanding_nonces",
+                    "Nonces committed by a signer and not yet consumed",
+                ),
+                &["group_id", "signer_id"],
+            )
+            .unwrap(),
+            http_requests: IntCounterVec::new(
+                Opts::new("frost_http_requests_total", "HTTP responses by status code"),
+                &["method", "path", "status"],
+            )
+            .unwrap(),
+            transport_messages: IntCounterVec::new(
+                Opts::new(
+                    "frost_transport_messages_total",
+                    "Messages sent over the simulated LoRa link",
+                ),
+                &["type"],
+            )
+            .unwrap(),
+            transport_bytes: IntCounter::new(
+                "frost_transport_bytes_total",
+                "Encoded bytes sent over the simulated LoRa link",
+            )
+            .unwrap(),
+            lora_retries: IntCounter::new(
+                "frost_lora_retries_total",
+                "Fragment retransmissions on the simulated LoRa link",
+            )
+            .unwrap(),
+            registry,
+        };
+
+        let collectors: [Box<dyn prometheus::core::Collector>; 11] = [
+            Box::new(metrics.sessions_started.clone()),
+            Box::new(metrics.sessions_completed.clone()),
+            Box::new(metrics.sessions_failed.clone()),
+            Box::new(metrics.round_duration.clone()),
+            Box::new(metrics.signer_timeouts.clone()),
+            Box::new(metrics.aggregation_failures.clone()),
+            Box::new(metrics.outstanding_nonces.clone()),
+            Box::new(metrics.http_requests.clone()),
+            Box::new(metrics.transport_messages.clone()),
+            Box::new(metrics.transport_bytes.clone()),
+            Box::new(metrics.lora_retries.clone()),
+        ];
+        for collector in collectors {
+            metrics
+                .registry
+                .register(collector)
+                .expect("metric names are unique");
+        }
+
+        metrics
+    }
+
+    /// 底層的 Registry（可註冊額外的指標）
+    pub fn registry(&self) -> &Registry {
+        &self.registry
+    }
+
+    // ========================================================================
+    // 簽章流程（由 Coordinator 記錄）
+    // ========================================================================
+
+    /// 建立了新的會話
+    pub fn session_started(&self) {
+        self.sessions_started.inc();
+    }
+
+    /// 會話已聚合並驗證
+    pub fn session_completed(&self) {
+        self.sessions_completed.inc();
+    }
+
+    /// 會話被中止（`failed`）或逾時（`expired`）
+    pub fn session_failed(&self, status: &str) {
+        self.sessions_failed.with_label_values(&[status]).inc();
+    }
+
+    /// 記錄簽署者在某一輪的回覆延遲
+    pub fn observe_round(&self, signer_id: u16, round: u8, elapsed: Duration) {
+        self.round_duration
+            .with_label_values(&[&signer_id.to_string(), &round.to_string()])
+            .observe(elapsed.as_secs_f64());
+    }
+
+    /// 若錯誤是輪次逾時，為每個沒有回覆的簽署者計數
+    pub fn observe_timeout(&self, error: &CoordinatorError) {
+        let CoordinatorError::RoundTimeout { round, missing, .. } = error else {
+            return;
+        };
+        // "Round 1" → "1"，與延遲 histogram 的標籤一致
+        let round = round.trim_start_matches("Round ");
+        for signer_id in missing {
+            self.signer_timeouts
+                .with_label_values(&[&signer_id.to_string(), round])
+                .inc();
+        }
+    }
+
+    /// 依錯誤類型記錄聚合失敗
+    pub fn observe_aggregation_failure(&self, error: &CoordinatorError) {
+        let reason = match error {
+            CoordinatorError::InsufficientShares { .. } => "insufficient_shares",
+            CoordinatorError::AggregationFailed(_) => "aggregation_error",
+            CoordinatorError::VerificationFailed(_) => "verification_failed",
+            _ => "other",
+        };
+        self.aggregation_failures.with_label_values(&[reason]).inc();
+    }
+
+    // ========================================================================
+    // HTTP 與抓取時取樣的狀態
+    // ========================================================================
+
+    /// 記錄一個 HTTP 回應（`path` 應為路由樣板，避免 Session ID 造成標籤爆量）
+    pub fn observe_http(&self, method: &str, path: &str, status: u16) {
+        self.http_requests
+            .with_label_values(&[method, path, &status.to_string()])
+            .inc();
+    }
+
+    /// 清除上一次取樣的 Nonce 數量（已刪除的群組不再出現）
+    pub fn reset_outstanding_nonces(&self) {
+        self.outstanding_nonces.reset();
+    }
+
+    /// 取樣群組內每個簽署者目前持有的 Nonce 數量
+    pub fn observe_group(&self, group: &SigningGroup) {
+        let group_id = group.id().to_string();
+        for signer_id in group.signer_ids() {
+            if let Some(signer) = group.get_signer(signer_id) {
+                self.outstanding_nonces
+                    .with_label_values(&[&group_id, &signer_id.to_string()])
+                    .set(signer.active_sessions_count() as i64);
+            }
+        }
+    }
+
+    /// 以鏈路的累計統計更新傳輸計數器
+    ///
+    /// 來源數值只增不減，因此只把差額加到 counter 上。
+    pub fn observe_transport(&self, stats: &TransportStats, lora_retries: u64) {
+        for (message_type, count) in &stats.by_type {
+            let counter = self
+                .transport_messages
+                .with_label_values(&[&message_type.to_string()]);
+            advance_to(&counter, *count as u64);
+        }
+        advance_to(&self.transport_bytes, stats.total_bytes as u64);
+        advance_to(&self.lora_retries, lora_retries);
+    }
+
+    /// 以 Prometheus 文字格式輸出所有指標
+    pub fn render(&self) -> String {
+        TextEncoder::new()
+            .encode_to_string(&self.registry.gather())
+            .unwrap_or_default()
+    }
+}
+
+impl Default for Metrics {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// 把 counter 推進到 `total`（小於目前值時不變）
+fn advance_to(counter: &IntCounter, total: u64) {
+    let current = counter.get();
+    if total > current {
+        counter.inc_by(total - current);
+    }
+}
+
+// ============================================================================
+// 測試
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::coordinator::DEFAULT_ROUND_TIMEOUT;
+    use crate::group::GroupServices;
+    use crate::message::SignPayload;
+    use std::sync::Arc;
+
+    #[tokio::test]
+    async fn test_coordinator_records_sessions_and_signer_latency() {
+        let metrics = Arc::new(Metrics::new());
+        let services = GroupServices {
+            metrics: Some(Arc::clone(&metrics)),
+            ..GroupServices::default()
+        };
+        let group = SigningGroup::generate_with_dealer(3, 2, &services).unwrap();
+
+        let signers: Vec<_> = [1, 3].into_iter().filter_map(|id| group.get_signer(id)).collect();
+        let payload = SignPayload::Raw {
+            message: hex::encode("metrics"),
+        };
+        group
+            .coordinator()
+            .orchestrate_signing(&signers, &payload, DEFAULT_ROUND_TIMEOUT)
+            .await
+            .unwrap();
+
+        // 只有一個簽署者：不足門檻，不會建立會話
+        assert!(group
+            .coordinator()
+            .orchestrate_signing(&signers[..1], &payload, DEFAULT_ROUND_TIMEOUT)
+            .await
+            .is_err());
+
+        // 承諾後尚未簽署的 Nonce 會在取樣時出現
+        let session_id = group.coordinator().create_session(b"pending".to_vec()).unwrap();
+        group.get_signer(2).unwrap().commit(session_id).unwrap();
+        metrics.observe_group(&group);
+
+        metrics.observe_timeout(&CoordinatorError::RoundTimeout {
+            session_id,
+            round: "Round 2",
+            missing: vec![3],
+        });
+
+        let text = metrics.render();
+        assert!(text.contains("frost_sessions_started_total 2"));
+        assert!(text.contains("frost_sessions_completed_total 1"));
+        assert!(text.contains(r#"frost_signer_round_duration_seconds_count{round="1",signer_id="1"} 1"#));
+        assert!(text.contains(r#"frost_signer_round_duration_seconds_count{round="2",signer_id="3"} 1"#));
+        assert!(text.contains(r#"frost_signer_timeouts_total{round="2",signer_id="3"} 1"#));
+        assert!(text.contains(&format!(
+            r#"frost_signer_outstanding_nonces{{group_id="{}",signer_id="2"}} 1"#,
+            group.id()
+        )));
+    }
+}
@@ -0,0 +1,982 @@
+//! # Peer - 無協調者的 P2P 簽章模式
+//!
+//! FROST 的協調者只是中繼者：它不持有任何秘密，只負責轉送承諾與分片並聚合。
+//! 在 P2P 模式中，每個簽署者都內嵌一個本地 `Coordinator`，參與者之間直接廣播：
+//!
+//! 1. 任一參與者（提議者）廣播 `SessionProposal`（Session ID、參與者、簽章請求）
+//! 2. 每個參與者自行計算摘要並生成承諾，廣播 `Round1Commitment`
+//! 3. 收齊所有承諾後，每個參與者在本地組出相同的簽章套件，廣播 `Round2SignatureShare`
+//! 4. 收齊所有分片後，每個參與者在本地聚合並以群組公鑰驗證
+//!
+//! 所有參與者都會得到同一個簽章；提議者送出提議後離線，其他參與者仍能完成，
+//! 因此沒有任何單一行程是活性 (liveness) 依賴。承諾與分片的來源仍由名冊的
+//! 信封簽章驗證。
+//!
+//! ## 提議的授權
+//! 參與者不會自動簽署任何收到的提議：
+//! - 提議必須附有提議者的身分金鑰簽章，並依名冊驗證（未設定名冊時拒絕所有遠端提議）
+//! - 驗證通過後交給 `with_approval` 設定的核准策略；未設定策略時拒絕所有遠端提議
+//!
+//! 訊息經由任何 `AsyncTransport` 傳遞（以 `Participant::Signer` 定址），
+//! 廣播即逐一發送給其他參與者。gossip 不保證順序：提議之前先到達的訊息會暫存，
+//! 收到提議後再處理。
+
+use crate::api::{RoundTimings, SessionId, SignatureShareData};
+use crate::coordinator::{decode_commitment, Coordinator, CoordinatorError, SigningOutcome};
+use crate::envelope::{EnvelopeKind, Roster};
+use crate::message::{MessageKind, SignPayload};
+use crate::metrics::Metrics;
+use crate::signer::{Signer, SignerError};
+use crate::transport::{
+    AsyncTransport, FrostMessage, Participant, TransportEnvelope, TransportError,
+};
+use frost_secp256k1 as frost;
+use std::collections::{BTreeMap, BTreeSet, HashMap, VecDeque};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use thiserror::Error;
+
+/// 每一輪等待其他參與者的預設上限
+pub const DEFAULT_ROUND_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// 暫存「提議之前先到達」訊息的上限
+const MAX_EARLY_MESSAGES: usize = 256;
+
+/// 保留已結束 Session 結果的上限（超過時移除最舊的）
+const MAX_FINISHED_SESSIONS: usize = 1024;
+
+// ============================================================================
+// 錯誤定義
+// ============================================================================
+
+#[derive(Debug, Error)]
+pub enum PeerError {
+    #[error("Invalid proposal for session {session_id}: {reason}")]
+    InvalidProposal { session_id: SessionId, reason: String },
+
+    #[error("Session {session_id} aborted by signer {signer_id}: {reason}")]
+    Aborted {
+        session_id: SessionId,
+        signer_id: u16,
+        reason: String,
+    },
+
+    #[error("Coordinator error: {0}")]
+    Coordinator(#[from] CoordinatorError),
+
+    #[error("Signer error: {0}")]
+    Signer(#[from] SignerError),
+
+    #[error("Transport error: {0}")]
+    Transport(#[from] TransportError),
+}
+
+// ============================================================================
+// Session 狀態
+// ============================================================================
+
+/// 一個進行中的 P2P Session
+struct PeerSession {
+    /// 所有參與者（包含自己）
+    participants: BTreeSet<u16>,
+
+    /// 要簽署的摘要
+    message: Vec<u8>,
+    message_kind: MessageKind,
+
+    /// 尚未收到承諾的其他參與者
+    pending_commitments: BTreeSet<u16>,
+
+    /// Round 1 完成後在本地組出的簽章套件
+    signing_package: Option<frost::SigningPackage>,
+
+    /// 尚未收到分片的參與者（包含自己）
+    pending_shares: BTreeSet<u16>,
+    shares: BTreeMap<frost::Identifier, frost::round2::SignatureShare>,
+
+    /// Round 1 完成前就到達的分片
+    early_shares: Vec<SignatureShareData>,
+
+    started: Instant,
+    round1_ms: u64,
+    round2_started: Instant,
+
+    /// 目前這一輪的期限
+    deadline: tokio::time::Instant,
+}
+
+/// 交給核准策略的遠端提議（提議者簽章已驗證）
+#[derive(Debug, Clone)]
+pub struct Proposal {
+    pub session_id: SessionId,
+    pub proposer: u16,
+    pub signer_ids: Vec<u16>,
+    pub payload: SignPayload,
+}
+
+/// 核准策略：返回 `Err(原因)` 拒絕提議
+pub type ApprovalPolicy = Arc<dyn Fn(&Proposal) -> Result<(), String> + Send + Sync>;
+
+/// 待發送給其他參與者的訊息
+struct Broadcast {
+    to: Vec<u16>,
+    message: FrostMessage,
+}
+
+// ============================================================================
+// Peer 結構
+// ============================================================================
+
+/// P2P 模式的參與者：簽署者 + 本地協調者
+pub struct Peer {
+    signer: Arc<Signer>,
+
+    /// 本地協調者（只保存自己看到的 Session，用於驗證與聚合）
+    coordinator: Coordinator,
+
+    round_timeout: Duration,
+
+    /// 進行中的 Session
+    sessions: HashMap<SessionId, PeerSession>,
+
+    /// 提議之前先到達的訊息
+    early: HashMap<SessionId, Vec<TransportEnvelope>>,
+
+    /// 已結束的 Session 結果（最多 `MAX_FINISHED_SESSIONS` 個）
+    finished: HashMap<SessionId, Result<SigningOutcome, PeerError>>,
+    finished_order: VecDeque<SessionId>,
+
+    /// 遠端提議的核准策略（未設定時拒絕所有遠端提議）
+    approval: Option<ApprovalPolicy>,
+}
+
+impl Peer {
+    // ========================================================================
+    // 建構函數
+    // ========================================================================
+
+    /// 建立 P2P 參與者
+    ///
+    /// # 參數
+    /// - `signer`: 此參與者的簽署者（持有金鑰分片）
+    /// - `pubkey_package`: 群組公鑰套件（用於本地聚合與驗證）
+    /// - `threshold`: 門檻值
+    pub fn new(
+        signer: Arc<Signer>,
+        pubkey_package: frost::keys::PublicKeyPackage,
+        threshold: u16,
+    ) -> Self {
+        Self {
+            signer,
+            coordinator: Coordinator::new(pubkey_package, threshold),
+            round_timeout: DEFAULT_ROUND_TIMEOUT,
+            sessions: HashMap::new(),
+            early: HashMap::new(),
+            finished: HashMap::new(),
+            finished_order: VecDeque::new(),
+            approval: None,
+        }
+    }
+
+    /// 設定群組名冊（驗證其他參與者的承諾與分片來源）
+    pub fn with_roster(mut self, roster: Roster) -> Self {
+        self.coordinator = self.coordinator.with_roster(roster);
+        self
+    }
+
+    /// 設定 Prometheus 指標（會話數量、聚合失敗與逾時的簽署者）
+    pub fn with_metrics(mut self, metrics: Arc<Metrics>) -> Self {
+        self.coordinator = self.coordinator.with_metrics(metrics);
+        self
+    }
+
+    /// 設定遠端提議的核准策略（在生成承諾之前呼叫）
+    ///
+    /// 策略決定此參與者願意簽署什麼，例如只接受特定的訊息類型或人工確認。
+    pub fn with_approval(
+        mut self,
+        policy: impl Fn(&Proposal) -> Result<(), String> + Send + Sync + 'static,
+    ) -> Self {
+        self.approval = Some(Arc::new(policy));
+        self
+    }
+
+    /// 設定每一輪等待的上限（預設 30 秒）
+    pub fn with_round_timeout(mut self, timeout: Duration) -> Self {
+        self.round_timeout = timeout;
+        self
+    }
+
+    // ========================================================================
+    // 存取方法
+    // ========================================================================
+
+    /// 此參與者的簽署者 ID
+    pub fn signer_id(&self) -> u16 {
+        self.signer.numeric_id()
+    }
+
+    /// 本地協調者（可查詢 Session 狀態）
+    pub fn coordinator(&self) -> &Coordinator {
+        &self.coordinator
+    }
+
+    /// 已在本地完成聚合與驗證的簽章
+    pub fn outcome(&self, session_id: SessionId) -> Option<&SigningOutcome> {
+        match self.finished.get(&session_id) {
+            Some(Ok(outcome)) => Some(outcome),
+            _ => None,
+        }
+    }
+
+    /// 進行中的 Session 數量
+    pub fn active_sessions_count(&self) -> usize {
+        self.sessions.len()
+    }
+
+    // ========================================================================
+    // 協議流程
+    // ========================================================================
+
+    /// 以提議者身分發起一次簽章，等待本地聚合完成
+    ///
+    /// 提議者本身必須是參與者之一。等待期間也會處理其他 Session 的訊息。
+    pub async fn propose<T: AsyncTransport>(
+        &mut self,
+        transport: &mut T,
+        signer_ids: &[u16],
+        payload: &SignPayload,
+    ) -> Result<SigningOutcome, PeerError> {
+        let session_id = SessionId::new();
+        let participants: BTreeSet<u16> = signer_ids.iter().copied().collect();
+        self.validate_proposal(self.signer_id(), &participants)
+            .map_err(|reason| PeerError::InvalidProposal { session_id, reason })?;
+
+        tracing::info!(
+            session_id = %session_id,
+            proposer = self.signer_id(),
+            participants = ?participants,
+            "Proposing peer-to-peer signing session"
+        );
+
+        let signer_ids: Vec<u16> = participants.iter().copied().collect();
+        let proposer_signature = self.signer.sign_envelope(
+            EnvelopeKind::SessionProposal,
+            session_id,
+            &proposal_payload_hex(&signer_ids, payload),
+        );
+        let proposal = Broadcast {
+            to: self.others(&participants),
+            message: FrostMessage::SessionProposal {
+                session_id,
+                signer_ids,
+                payload: payload.clone(),
+                proposer_signature: Some(proposer_signature),
+            },
+        };
+        self.send_all(transport, vec![proposal]).await;
+
+        let outgoing = self.begin(session_id, participants, payload.clone());
+        self.send_all(transport, outgoing).await;
+
+        loop {
+            if let Some(result) = self.finished.remove(&session_id) {
+                return result;
+            }
+            self.step(transport).await?;
+        }
+    }
+
+    /// 作為參與者持續處理其他人的提議，直到傳輸關閉
+    ///
+    /// 完成的簽章可透過 `outcome` 查詢。
+    pub async fn serve<T: AsyncTransport>(&mut self, transport: &mut T) -> Result<(), PeerError> {
+        loop {
+            match self.step(transport).await {
+                Ok(()) => {}
+                Err(PeerError::Transport(TransportError::Closed)) => return Ok(()),
+                Err(e) => return Err(e),
+            }
+        }
+    }
+
+    /// 接收並處理一則訊息；期限到時讓逾時的 Session 失敗
+    async fn step<T: AsyncTransport>(&mut self, transport: &mut T) -> Result<(), PeerError> {
+        let deadline = self.sessions.values().map(|session| session.deadline).min();
+        let received = match deadline {
+            Some(deadline) => tokio::time::timeout_at(deadline, transport.recv()).await.ok(),
+            None => Some(transport.recv().await),
+        };
+
+        let outgoing = match received {
+            Some(envelope) => self.process(envelope?),
+            None => self.expire_sessions(),
+        };
+        self.send_all(transport, outgoing).await;
+        Ok(())
+    }
+
+    /// 逐一發送；個別參與者無法送達時只記錄警告（其他人仍可完成）
+    async fn send_all<T: AsyncTransport>(&self, transport: &mut T, outgoing: Vec<Broadcast>) {
+        for broadcast in outgoing {
+            for signer_id in broadcast.to {
+                let result = transport
+                    .send(Participant::Signer(signer_id), broadcast.message.clone())
+                    .await;
+                if let Err(e) = result {
+                    tracing::warn!(
+                        local = self.signer_id(),
+                        to = signer_id,
+                        message_type = %broadcast.message.message_type(),
+                        error = %e,
+                        "Failed to deliver peer message"
+                    );
+                }
+            }
+        }
+    }
+
+    /// 處理一則訊息，返回要廣播的回覆
+    fn process(&mut self, envelope: TransportEnvelope) -> Vec<Broadcast> {
+        let session_id = envelope.message.session_id();
+        let Participant::Signer(from) = envelope.from else {
+            tracing::debug!(from = %envelope.from, "Ignoring message from non-peer participant");
+            return Vec::new();
+        };
+
+        // 已結束的 Session 的遲到訊息
+        if self.finished.contains_key(&session_id) {
+            return Vec::new();
+        }
+
+        if let FrostMessage::SessionProposal {
+            signer_ids,
+            payload,
+            proposer_signature,
+            ..
+        } = envelope.message
+        {
+            let proposal = Proposal {
+                session_id,
+                proposer: from,
+                signer_ids,
+                payload,
+            };
+            return self.on_proposal(proposal, proposer_signature);
+        }
+
+        if !self.sessions.contains_key(&session_id) {
+            self.buffer_early(envelope);
+            return Vec::new();
+        }
+
+        let result = match envelope.message {
+            FrostMessage::Round1Commitment { commitment, .. } => {
+                self.on_commitment(session_id, from, commitment)
+            }
+            FrostMessage::Round2SignatureShare { share, .. } => {
+                self.on_share(session_id, from, share)
+            }
+            FrostMessage::Abort { reason, .. } => {
+                let participants = self.sessions[&session_id].participants.clone();
+                if !participants.contains(&from) {
+                    return Vec::new();
+                }
+                let error = PeerError::Aborted {
+                    session_id,
+                    signer_id: from,
+                    reason,
+                };
+                return self.fail(session_id, &participants, error, false);
+            }
+            _ => Ok(Vec::new()),
+        };
+
+        match result {
+            Ok(outgoing) => outgoing,
+            Err(e) => {
+                let participants = self.sessions[&session_id].participants.clone();
+                self.fail(session_id, &participants, e, true)
+            }
+        }
+    }
+
+    /// 檢查提議：參與者數量達門檻、提議者與自己都是參與者、且都在名冊中
+    fn validate_proposal(
+        &self,
+        proposer: u16,
+        participants: &BTreeSet<u16>,
+    ) -> Result<(), String> {
+        let threshold = self.coordinator.threshold() as usize;
+        if participants.len() < threshold {
+            return Err(format!(
+                "{} participants is below the threshold of {}",
+                participants.len(),
+                threshold
+            ));
+        }
+        if !participants.contains(&proposer) {
+            return Err(format!("proposer {} is not a participant", proposer));
+        }
+        if !participants.contains(&self.signer_id()) {
+            return Err(format!("signer {} is not a participant", self.signer_id()));
+        }
+        if let Some(roster) = self.coordinator.roster() {
+            if let Some(unknown) = participants.iter().find(|id| roster.get(**id).is_none()) {
+                return Err(format!("signer {} is not in the roster", unknown));
+            }
+        }
+        Ok(())
+    }
+
+    /// 驗證提議者的身分金鑰簽章
+    fn authenticate_proposal(
+        &self,
+        proposal: &Proposal,
+        signature: Option<&str>,
+    ) -> Result<(), String> {
+        let roster = self
+            .coordinator
+            .roster()
+            .ok_or_else(|| "no roster to authenticate the proposer".to_string())?;
+        roster
+            .verify(
+                EnvelopeKind::SessionProposal,
+                proposal.session_id,
+                proposal.proposer,
+                &proposal_payload_hex(&proposal.signer_ids, &proposal.payload),
+                signature,
+            )
+            .map_err(|e| e.to_string())
+    }
+
+    fn on_proposal(&mut self, proposal: Proposal, signature: Option<String>) -> Vec<Broadcast> {
+        let session_id = proposal.session_id;
+        let proposer = proposal.proposer;
+        if self.sessions.contains_key(&session_id) {
+            tracing::debug!(session_id = %session_id, "Ignoring duplicate proposal");
+            return Vec::new();
+        }
+
+        // 無效、無法認證或未核准的提議直接忽略，不佔用 Nonce
+        let participants: BTreeSet<u16> = proposal.signer_ids.iter().copied().collect();
+        let checked = self
+            .validate_proposal(proposer, &participants)
+            .and_then(|()| self.authenticate_proposal(&proposal, signature.as_deref()))
+            .and_then(|()| match &self.approval {
+                Some(policy) => policy(&proposal),
+                None => Err("no approval policy configured".to_string()),
+            });
+        if let Err(reason) = checked {
+            tracing::warn!(
+                session_id = %session_id,
+                proposer,
+                reason = %reason,
+                "Rejecting peer proposal"
+            );
+            return Vec::new();
+        }
+
+        tracing::info!(
+            session_id = %session_id,
+            proposer,
+            local = self.signer_id(),
+            "Joining peer-to-peer signing session"
+        );
+        self.begin(session_id, participants, proposal.payload)
+    }
+
+    /// 加入 Session：建立本地會話、生成承諾並廣播，然後處理先到達的訊息
+    fn begin(
+        &mut self,
+        session_id: SessionId,
+        participants: BTreeSet<u16>,
+        payload: SignPayload,
+    ) -> Vec<Broadcast> {
+        let message_kind = payload.kind();
+        let message = match self.coordinator.join_payload_session(session_id, payload) {
+            Ok(message) => message,
+            Err(e) => return self.fail(session_id, &participants, e.into(), true),
+        };
+
+        let local = self.signer_id();
+        let now = Instant::now();
+        let mut pending_commitments = participants.clone();
+        pending_commitments.remove(&local);
+        self.sessions.insert(
+            session_id,
+            PeerSession {
+                participants: participants.clone(),
+                message,
+                message_kind,
+                pending_commitments,
+                signing_package: None,
+                pending_shares: participants.clone(),
+                shares: BTreeMap::new(),
+                early_shares: Vec::new(),
+                started: now,
+                round1_ms: 0,
+                round2_started: now,
+                deadline: tokio::time::Instant::now() + self.round_timeout,
+            },
+        );
+
+        let commitment = match self.signer.commit_signed(session_id) {
+            Ok(commitment) => commitment,
+            Err(e) => return self.fail(session_id, &participants, e.into(), true),
+        };
+        if let Err(e) = self.coordinator.add_commitment(session_id, commitment.clone()) {
+            return self.fail(session_id, &participants, e.into(), true);
+        }
+
+        let mut outgoing = vec![Broadcast {
+            to: self.others(&participants),
+            message: FrostMessage::Round1Commitment {
+                session_id,
+                commitment,
+            },
+        }];
+
+        // 處理提議之前先到達的承諾與分片
+        for envelope in self.early.remove(&session_id).unwrap_or_default() {
+            outgoing.extend(self.process(envelope));
+        }
+        outgoing
+    }
+
+    fn on_commitment(
+        &mut self,
+        session_id: SessionId,
+        from: u16,
+        commitment: crate::api::CommitmentData,
+    ) -> Result<Vec<Broadcast>, PeerError> {
+        let Some(session) = self.sessions.get(&session_id) else {
+            return Ok(Vec::new());
+        };
+        if !session.pending_commitments.contains(&from) || commitment.signer_id != from {
+            tracing::debug!(
+                session_id = %session_id,
+                from,
+                "Ignoring unexpected or duplicate commitment"
+            );
+            return Ok(Vec::new());
+        }
+
+        // 偽造或無效的承諾只會被忽略，不影響其他參與者
+        if let Err(e) = self.coordinator.add_commitment(session_id, commitment) {
+            tracing::warn!(session_id = %session_id, from, error = %e, "Rejected peer commitment");
+            return Ok(Vec::new());
+        }
+
+        let Some(session) = self.sessions.get_mut(&session_id) else {
+            return Ok(Vec::new());
+        };
+        session.pending_commitments.remove(&from);
+        if !session.pending_commitments.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        // ====================================================================
+        // Round 1 完成：在本地組出簽章套件並簽署
+        // ====================================================================
+        let package_data = self.coordinator.get_signing_package(session_id)?;
+        let commitments = package_data
+            .commitments
+            .iter()
+            .map(decode_commitment)
+            .collect::<Result<BTreeMap<_, _>, _>>()?;
+
+        let session = self
+            .sessions
+            .get_mut(&session_id)
+            .expect("session is still active");
+        session.signing_package = Some(frost::SigningPackage::new(commitments, &session.message));
+        session.round1_ms = session.started.elapsed().as_millis() as u64;
+        session.round2_started = Instant::now();
+        session.deadline = tokio::time::Instant::now() + self.round_timeout;
+        let participants = session.participants.clone();
+        let early_shares = std::mem::take(&mut session.early_shares);
+
+        tracing::info!(session_id = %session_id, local = self.signer_id(), "Peer round 1 complete");
+
+        let share = self.signer.sign_signed(session_id, &package_data)?;
+        let mut outgoing = vec![Broadcast {
+            to: self.others(&participants),
+            message: FrostMessage::Round2SignatureShare {
+                session_id,
+                share: share.clone(),
+            },
+        }];
+
+        // 自己的分片，以及 Round 1 完成前就到達的分片
+        // 與一般的分片相同，單一分片出錯只記錄警告，不影響其他分片與已產生的廣播
+        for share in std::iter::once(share).chain(early_shares) {
+            let from = share.signer_id;
+            match self.on_share(session_id, from, share) {
+                Ok(broadcasts) => outgoing.extend(broadcasts),
+                Err(e) => tracing::warn!(
+                    session_id = %session_id,
+                    from,
+                    error = %e,
+                    "Failed to process replayed signature share"
+                ),
+            }
+        }
+        Ok(outgoing)
+    }
+
+    fn on_share(
+        &mut self,
+        session_id: SessionId,
+        from: u16,
+        share: SignatureShareData,
+    ) -> Result<Vec<Broadcast>, PeerError> {
+        let Some(session) = self.sessions.get_mut(&session_id) else {
+            return Ok(Vec::new());
+        };
+        if !session.pending_shares.contains(&from) || share.signer_id != from {
+            tracing::debug!(
+                session_id = %session_id,
+                from,
+                "Ignoring unexpected or duplicate signature share"
+            );
+            return Ok(Vec::new());
+        }
+        if session.signing_package.is_none() {
+            if !session.early_shares.iter().any(|s| s.signer_id == from) {
+                session.early_shares.push(share);
+            }
+            return Ok(Vec::new());
+        }
+
+        let (identifier, signature_share) = match self.coordinator.verify_share(session_id, &share)
+        {
+            Ok(verified) => verified,
+            Err(e) => {
+                tracing::warn!(session_id = %session_id, from, error = %e, "Rejected peer share");
+                return Ok(Vec::new());
+            }
+        };
+
+        let session = self
+            .sessions
+            .get_mut(&session_id)
+            .expect("session is still active");
+        session.shares.insert(identifier, signature_share);
+        session.pending_shares.remove(&from);
+        if !session.pending_shares.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        // ====================================================================
+        // 所有分片到齊：在本地聚合並驗證
+        // ====================================================================
+        let round2_ms = session.round2_started.elapsed().as_millis() as u64;
+        let aggregation_started = Instant::now();
+        let signing_package = session
+            .signing_package
+            .clone()
+            .expect("signing package is built after round 1");
+        let (signature, signer_ids) = self.coordinator.finalize_session(
+            session_id,
+            &signing_package,
+            &session.shares,
+            &session.message,
+        )?;
+
+        let session = self
+            .sessions
+            .remove(&session_id)
+            .expect("session is still active");
+        let outcome = SigningOutcome {
+            session_id,
+            signature,
+            signer_ids,
+            message_kind: session.message_kind,
+            message: session.message,
+            timings: RoundTimings {
+                round1_ms: session.round1_ms,
+                round2_ms,
+                aggregation_ms: aggregation_started.elapsed().as_millis() as u64,
+                total_ms: session.started.elapsed().as_millis() as u64,
+            },
+        };
+
+        tracing::info!(
+            session_id = %session_id,
+            local = self.signer_id(),
+            signature = %outcome.signature_hex(),
+            "Peer aggregated and verified signature locally"
+        );
+        self.finish(session_id, Ok(outcome));
+        Ok(Vec::new())
+    }
+
+    /// 讓 Session 失敗：丟棄 Nonce、記錄結果，並視需要通知其他參與者
+    fn fail(
+        &mut self,
+        session_id: SessionId,
+        participants: &BTreeSet<u16>,
+        error: PeerError,
+        notify: bool,
+    ) -> Vec<Broadcast> {
+        let reason = error.to_string();
+        if self.sessions.remove(&session_id).is_some() {
+            self.coordinator
+                .abort_session(session_id, &[Arc::clone(&self.signer)], &reason);
+        }
+        self.early.remove(&session_id);
+        self.finish(session_id, Err(error));
+
+        if !notify {
+            return Vec::new();
+        }
+        vec![Broadcast {
+            to: self.others(participants),
+            message: FrostMessage::Abort { session_id, reason },
+        }]
+    }
+
+    /// 讓超過期限的 Session 失敗（並通知其他參與者不必再等待）
+    fn expire_sessions(&mut self) -> Vec<Broadcast> {
+        let now = tokio::time::Instant::now();
+        let expired: Vec<(SessionId, BTreeSet<u16>, &'static str, Vec<u16>)> = self
+            .sessions
+            .iter()
+            .filter(|(_, session)| session.deadline <= now)
+            .map(|(session_id, session)| {
+                let (round, missing) = match session.signing_package {
+                    None => ("Round 1", &session.pending_commitments),
+                    Some(_) => ("Round 2", &session.pending_shares),
+                };
+                (
+                    *session_id,
+                    session.participants.clone(),
+                    round,
+                    missing.iter().copied().collect(),
+                )
+            })
+            .collect();
+
+        let mut outgoing = Vec::new();
+        for (session_id, participants, round, missing) in expired {
+            let error = CoordinatorError::RoundTimeout {
+                session_id,
+                round,
+                missing,
+            };
+            self.coordinator.record_timeout(&error);
+            outgoing.extend(self.fail(session_id, &participants, error.into(), true));
+        }
+        outgoing
+    }
+
+    /// 記錄 Session 結果，超過上限時移除最舊的結果
+    fn finish(&mut self, session_id: SessionId, result: Result<SigningOutcome, PeerError>) {
+        if self.finished.insert(session_id, result).is_none() {
+            self.finished_order.push_back(session_id);
+        }
+        while self.finished_order.len() > MAX_FINISHED_SESSIONS {
+            if let Some(oldest) = self.finished_order.pop_front() {
+                self.finished.remove(&oldest);
+            }
+        }
+    }
+
+    /// 暫存提議之前先到達的訊息
+    fn buffer_early(&mut self, envelope: TransportEnvelope) {
+        let buffered: usize = self.early.values().map(Vec::len).sum();
+        if buffered >= MAX_EARLY_MESSAGES {
+            tracing::warn!(
+                session_id = %envelope.message.session_id(),
+                from = %envelope.from,
+                "Early message buffer full, dropping message"
+            );
+            return;
+        }
+        self.early
+            .entry(envelope.message.session_id())
+            .or_default()
+            .push(envelope);
+    }
+
+    /// 除了自己以外的參與者
+    fn others(&self, participants: &BTreeSet<u16>) -> Vec<u16> {
+        let local = self.signer_id();
+        participants.iter().copied().filter(|&id| id != local).collect()
+    }
+}
+
+/// 提議信封的簽署內容：參與者與簽章請求的 JSON（hex 編碼）
+fn proposal_payload_hex(signer_ids: &[u16], payload: &SignPayload) -> String {
+    let encoded = serde_json::to_vec(&(signer_ids, payload))
+        .expect("serializing a sign payload cannot fail");
+    hex::encode(encoded)
+}
+
+// ============================================================================
+// 測試
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::transport::ChannelNetwork;
+    use rand::thread_rng;
+
+    #[tokio::test]
+    async fn test_every_participant_aggregates_without_coordinator() {
+        let (shares, pubkey_package) = frost::keys::generate_with_dealer(
+            3,
+            2,
+            frost::keys::IdentifierList::Default,
+            thread_rng(),
+        )
+        .unwrap();
+        let signers: Vec<Signer> = shares
+            .into_values()
+            .map(|share| Signer::new(share).unwrap())
+            .collect();
+        let mut roster = Roster::new();
+        for signer in &signers {
+            roster.insert(signer.numeric_id(), signer.identity_public_key());
+        }
+
+        let network = ChannelNetwork::new();
+        let mut peers: Vec<_> = signers
+            .into_iter()
+            .map(|signer| {
+                let transport = network.endpoint(Participant::Signer(signer.numeric_id()));
+                let signer = Arc::new(signer.with_roster(roster.clone()));
+                let peer = Peer::new(signer, pubkey_package.clone(), 2)
+                    .with_roster(roster.clone())
+                    .with_approval(|proposal| match proposal.payload {
+                        SignPayload::Document { .. } => Ok(()),
+                        _ => Err("only documents are approved".to_string()),
+                    });
+                (peer, transport)
+            })
+            .collect();
+
+        // 簽署者 3 提議；網路上沒有任何協調者行程
+        let (mut proposer, mut proposer_transport) = peers.pop().unwrap();
+        let handles: Vec<_> = peers
+            .into_iter()
+            .map(|(mut peer, mut transport)| {
+                tokio::spawn(async move {
+                    peer.serve(&mut transport).await.unwrap();
+                    peer
+                })
+            })
+            .collect();
+
+        let payload = SignPayload::Document {
+            content: "no coordinator needed".to_string(),
+            encoding: Default::default(),
+        };
+        let outcome = proposer
+            .propose(&mut proposer_transport, &[1, 3], &payload)
+            .await
+            .unwrap();
+        assert!(outcome.verify(pubkey_package.verifying_key()));
+        assert_eq!(outcome.signer_ids, vec![1, 3]);
+
+        network.shutdown();
+        let mut others = Vec::new();
+        for handle in handles {
+            others.push(handle.await.unwrap());
+        }
+
+        // 參與者 1 在本地聚合出同一個簽章；未被選中的參與者 2 沒有參與
+        let peer_1 = &others[0];
+        assert_eq!(peer_1.signer_id(), 1);
+        assert_eq!(
+            peer_1.outcome(outcome.session_id).unwrap().signature_hex(),
+            outcome.signature_hex()
+        );
+        assert!(others[1].outcome(outcome.session_id).is_none());
+        assert_eq!(others[1].coordinator().active_sessions_count(), 0);
+    }
+
+    #[tokio::test]
+    async fn test_forged_or_unapproved_proposals_are_not_signed() {
+        let (shares, pubkey_package) = frost::keys::generate_with_dealer(
+            3,
+            2,
+            frost::keys::IdentifierList::Default,
+            thread_rng(),
+        )
+        .unwrap();
+        let mut signers: Vec<Signer> = shares
+            .into_values()
+            .map(|share| Signer::new(share).unwrap())
+            .collect();
+        let mut roster = Roster::new();
+        for signer in &signers {
+            roster.insert(signer.numeric_id(), signer.identity_public_key());
+        }
+        let signer_1 = Arc::new(signers.remove(0).with_roster(roster.clone()));
+        let mut peer = Peer::new(Arc::clone(&signer_1), pubkey_package, 2)
+            .with_roster(roster)
+            .with_approval(|proposal| match proposal.payload {
+                SignPayload::Document { .. } => Ok(()),
+                _ => Err("only documents are approved".to_string()),
+            });
+
+        let document = SignPayload::Document {
+            content: "quarterly report".to_string(),
+            encoding: Default::default(),
+        };
+        let raw = SignPayload::Raw {
+            message: hex::encode([0u8; 32]),
+        };
+        let proposal = |proposer: u16, payload: &SignPayload, signature: Option<String>| {
+            TransportEnvelope {
+                from: Participant::Signer(proposer),
+                to: Participant::Signer(1),
+                message: FrostMessage::SessionProposal {
+                    session_id: SessionId::new(),
+                    signer_ids: vec![1, proposer],
+                    payload: payload.clone(),
+                    proposer_signature: signature,
+                },
+            }
+        };
+        let signed_by = |signer: &Signer, envelope: &mut TransportEnvelope| {
+            if let FrostMessage::SessionProposal {
+                session_id,
+                signer_ids,
+                payload,
+                proposer_signature,
+            } = &mut envelope.message
+            {
+                *proposer_signature = Some(signer.sign_envelope(
+                    EnvelopeKind::SessionProposal,
+                    *session_id,
+                    &proposal_payload_hex(signer_ids, payload),
+                ));
+            }
+        };
+
+        // 未簽署、或由其他簽署者的身分金鑰冒充簽署：忽略，不生成承諾
+        assert!(peer.process(proposal(2, &document, None)).is_empty());
+        let mut forged = proposal(2, &document, None);
+        signed_by(&signers[1], &mut forged);
+        assert!(peer.process(forged).is_empty());
+
+        // 簽章有效但核准策略拒絕（原始 bytes）
+        let mut unapproved = proposal(2, &raw, None);
+        signed_by(&signers[0], &mut unapproved);
+        assert!(peer.process(unapproved).is_empty());
+        assert_eq!(peer.active_sessions_count(), 0);
+        assert_eq!(signer_1.active_sessions_count(), 0);
+
+        // 簽章有效且已核准：生成承諾並廣播
+        let mut approved = proposal(2, &document, None);
+        signed_by(&signers[0], &mut approved);
+        let outgoing = peer.process(approved);
+        assert_eq!(outgoing.len(), 1);
+        assert!(matches!(outgoing[0].message, FrostMessage::Round1Commitment { .. }));
+        assert_eq!(signer_1.active_sessions_count(), 1);
+    }
+}
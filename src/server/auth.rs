@@ -0,0 +1,486 @@
+//! # 認證與授權
+//!
+//! 此模組為 HTTP API 提供 Bearer Token 認證與角色權限檢查。
+//!
+//! ## 角色
+//! - `admin`: 可呼叫所有端點（包含 `POST /groups`）
+//! - `coordinator`: 可呼叫 `/sign`、`/sessions/*`、Dashboard 端點、`/metrics` 以及群組內的對應端點
+//! - `signer_operator`: 只能呼叫自己負責的 `/signer/:id/*` 端點；其他群組的
+//!   `/groups/:group_id/signer/:id/*` 端點需在 `group_signers` 中個別授權
+//!
+//! `GET /health` 與公鑰查詢端點為公開端點，不需要 Token。
+//!
+//! ## 客戶端憑證綁定（mTLS）
+//! Token 設定 `client_cert` 後，只有出示 SAN（DNS）或 CN 與之相符的客戶端憑證的
+//! 連線能使用該 Token（見 `tls` 模組）；被竊取的 Token 無法從其他機器使用。
+//!
+//! ## 設定方式
+//! - `FROST_AUTH_FILE=auth.json`：從 JSON 檔案載入 Token 清單
+//! - `FROST_AUTH=disabled`：停用認證（僅適合本機開發與展示）
+//! - 兩者都未設定時，啟動時會產生一個臨時的 admin Token，寫入
+//!   `FROST_ADMIN_TOKEN_FILE`（預設 `frost-admin-token`，權限 0600），不會出現在日誌中
+//!
+//! ```json
+//! {
+//!   "tokens": [
+//!     { "name": "ops", "token": "…", "role": "admin" },
+//!     { "name": "vault-coordinator", "token": "…", "role": "coordinator" },
+//!     { "name": "signer-1", "token": "…", "role": "signer_operator", "signer_ids": [1] },
+//!     {
+//!       "name": "vault-b-signer-2", "token": "…", "role": "signer_operator",
+//!       "group_signers": { "0b7e…": [2] }
+//!     }
+//!   ]
+//! }
+//! ```
+
+use crate::api::{ErrorResponse, GroupId};
+use crate::audit::{AuditEvent, AuditLog};
+use crate::server::tls::ClientCertificate;
+use axum::{
+    extract::{Request, State},
+    http::{header, Method, StatusCode},
+    middleware::Next,
+    response::{IntoResponse, Response},
+    Json,
+};
+use rand::RngCore;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::path::PathBuf;
+use std::sync::Arc;
+
+/// 未設定任何 Token 時產生的臨時 admin Token 名稱
+pub const EPHEMERAL_ADMIN: &str = "ephemeral-admin";
+
+// ============================================================================
+// 設定結構
+// ============================================================================
+
+/// 呼叫者的角色
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Role {
+    /// 管理者 - 可呼叫所有端點
+    Admin,
+
+    /// 協調者 - 可發起與編排簽章流程
+    Coordinator,
+
+    /// 簽署者操作員 - 只能操作指定的簽署者
+    SignerOperator,
+}
+
+/// 單一 API Token 設定
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ApiToken {
+    /// 身分名稱（用於日誌）
+    pub name: String,
+
+    /// Bearer Token
+    pub token: String,
+
+    /// 角色
+    pub role: Role,
+
+    /// `signer_operator` 可操作的預設群組簽署者 ID（`/signer/:id/*`）
+    #[serde(default)]
+    pub signer_ids: Vec<u16>,
+
+    /// `signer_operator` 可操作的其他群組簽署者（`/groups/:group_id/signer/:id/*`）
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub group_signers: HashMap<GroupId, Vec<u16>>,
+
+    /// 必須出示的客戶端憑證名稱（SAN DNS 或 CN）；未設定時不檢查
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub client_cert: Option<String>,
+}
+
+/// 認證設定
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct AuthConfig {
+    /// 是否停用認證
+    #[serde(default)]
+    pub disabled: bool,
+
+    /// 所有有效的 Token
+    #[serde(default)]
+    pub tokens: Vec<ApiToken>,
+}
+
+impl AuthConfig {
+    /// 從環境變數載入認證設定
+    pub fn from_env() -> anyhow::Result<Self> {
+        if std::env::var("FROST_AUTH").map(|v| v == "disabled").unwrap_or(false) {
+            return Ok(Self {
+                disabled: true,
+                tokens: Vec::new(),
+            });
+        }
+
+        if let Ok(path) = std::env::var("FROST_AUTH_FILE") {
+            let json = std::fs::read_to_string(&path)
+                .map_err(|e| anyhow::anyhow!("Failed to read auth file {}: {}", path, e))?;
+            let config: AuthConfig = serde_json::from_str(&json)
+                .map_err(|e| anyhow::anyhow!("Failed to parse auth file {}: {}", path, e))?;
+            return Ok(config);
+        }
+
+        // 未設定任何 Token：產生一個臨時的 admin Token（安全預設）
+        let mut bytes = [0u8; 24];
+        rand::thread_rng().fill_bytes(&mut bytes);
+        Ok(Self {
+            disabled: false,
+            tokens: vec![ApiToken {
+                name: EPHEMERAL_ADMIN.to_string(),
+                token: hex::encode(bytes),
+                role: Role::Admin,
+                signer_ids: Vec::new(),
+                group_signers: HashMap::new(),
+                client_cert: None,
+            }],
+        })
+    }
+
+    /// 將臨時 admin Token 寫入 `FROST_ADMIN_TOKEN_FILE`（只有擁有者可讀），返回檔案路徑
+    ///
+    /// 沒有臨時 Token 時返回 `None`。
+    pub fn write_ephemeral_token(&self) -> anyhow::Result<Option<PathBuf>> {
+        let Some(token) = self.tokens.iter().find(|t| t.name == EPHEMERAL_ADMIN) else {
+            return Ok(None);
+        };
+        let path = PathBuf::from(
+            std::env::var("FROST_ADMIN_TOKEN_FILE").unwrap_or_else(|_| "frost-admin-token".into()),
+        );
+
+        let mut options = std::fs::OpenOptions::new();
+        options.write(true).create(true).truncate(true);
+        #[cfg(unix)]
+        std::os::unix::fs::OpenOptionsExt::mode(&mut options, 0o600);
+        let mut file = options
+            .open(&path)
+            .map_err(|e| anyhow::anyhow!("Failed to write {}: {}", path.display(), e))?;
+        std::io::Write::write_all(&mut file, format!("{}\n", token.token).as_bytes())?;
+        Ok(Some(path))
+    }
+
+    /// 綁定客戶端憑證的 Token 數量
+    pub fn cert_bound_tokens(&self) -> usize {
+        self.tokens.iter().filter(|t| t.client_cert.is_some()).count()
+    }
+
+    /// 依 Token 查找身分（常數時間比較）
+    fn authenticate(&self, presented: &str) -> Option<&ApiToken> {
+        self.tokens
+            .iter()
+            .find(|t| constant_time_eq(t.token.as_bytes(), presented.as_bytes()))
+    }
+}
+
+/// 常數時間的位元組比較，避免透過時間差猜測 Token
+fn constant_time_eq(a: &[u8], b: &[u8]) -> bool {
+    if a.len() != b.len() {
+        return false;
+    }
+    a.iter().zip(b).fold(0u8, |acc, (x, y)| acc | (x ^ y)) == 0
+}
+
+// ============================================================================
+// 權限判斷
+// ============================================================================
+
+/// 端點所需的權限
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Permission {
+    /// 公開端點
+    Public,
+
+    /// 協調者（或 admin）
+    Coordinator,
+
+    /// 指定簽署者的操作員（或 admin）；`group_id` 為 `None` 時是預設群組
+    Signer {
+        group_id: Option<GroupId>,
+        signer_id: u16,
+    },
+
+    /// 僅限 admin
+    Admin,
+}
+
+/// 根據 HTTP 方法與路徑判斷所需權限
+fn required_permission(method: &Method, path: &str) -> Permission {
+    let segments: Vec<&str> = path.trim_matches('/').split('/').collect();
+
+    // 群組端點：/groups/:group_id/<rest> 與預設群組端點的權限規則相同
+    // 簽署者權限依 (群組, 簽署者) 授權
+    let (group, rest): (Option<&str>, &[&str]) = match segments.as_slice() {
+        ["groups"] if method == Method::GET => return Permission::Coordinator,
+        ["groups"] => return Permission::Admin,
+        ["groups", group_id, rest @ ..] => (Some(*group_id), rest),
+        other => (None, other),
+    };
+
+    match rest {
+        ["health"] | ["pubkey"] if method == Method::GET => Permission::Public,
+        ["signer", signer_id, ..] => {
+            let group_id = match group.map(uuid::Uuid::parse_str) {
+                None => None,
+                Some(Ok(uuid)) => Some(GroupId(uuid)),
+                Some(Err(_)) => return Permission::Admin,
+            };
+            match signer_id.parse::<u16>() {
+                Ok(signer_id) => Permission::Signer {
+                    group_id,
+                    signer_id,
+                },
+                Err(_) => Permission::Admin,
+            }
+        }
+        ["sign"] | ["sessions", ..] | ["status"] | ["events"] | ["metrics"] => {
+            Permission::Coordinator
+        }
+        _ => Permission::Admin,
+    }
+}
+
+impl ApiToken {
+    /// 連線出示的客戶端憑證是否符合此 Token 的綁定
+    fn accepts_certificate(&self, certificate: Option<&ClientCertificate>) -> bool {
+        match &self.client_cert {
+            Some(name) => certificate.is_some_and(|cert| cert.matches(name)),
+            None => true,
+        }
+    }
+
+    fn allows(&self, permission: Permission) -> bool {
+        match (self.role, permission) {
+            (_, Permission::Public) => true,
+            (Role::Admin, _) => true,
+            (Role::Coordinator, Permission::Coordinator) => true,
+            (
+                Role::SignerOperator,
+                Permission::Signer {
+                    group_id: None,
+                    signer_id,
+                },
+            ) => self.signer_ids.contains(&signer_id),
+            (
+                Role::SignerOperator,
+                Permission::Signer {
+                    group_id: Some(group_id),
+                    signer_id,
+                },
+            ) => self
+                .group_signers
+                .get(&group_id)
+                .is_some_and(|ids| ids.contains(&signer_id)),
+            _ => false,
+        }
+    }
+}
+
+/// 中間件狀態
+pub struct AuthState {
+    /// 認證設定
+    pub config: AuthConfig,
+
+    /// 稽核日誌（記錄被拒絕的請求）
+    pub audit_log: Arc<AuditLog>,
+}
+
+/// 通過認證的呼叫者身分（放入 request extensions 供後續使用）
+#[derive(Debug, Clone)]
+pub struct Identity {
+    pub name: String,
+    pub role: Role,
+}
+
+// ============================================================================
+// 中間件
+// ============================================================================
+
+/// 認證與授權中間件
+///
+/// - 缺少或無效的 Token → `401 UNAUTHORIZED`
+/// - Token 綁定的客戶端憑證與連線不符 → `401 UNAUTHORIZED`
+/// - Token 有效但角色不允許 → `403 FORBIDDEN`
+pub async fn require_auth(
+    State(state): State<Arc<AuthState>>,
+    mut request: Request,
+    next: Next,
+) -> Response {
+    let config = &state.config;
+    if config.disabled {
+        return next.run(request).await;
+    }
+
+    let permission = required_permission(request.method(), request.uri().path());
+    if permission == Permission::Public {
+        return next.run(request).await;
+    }
+
+    let presented = request
+        .headers()
+        .get(header::AUTHORIZATION)
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| value.strip_prefix("Bearer "));
+
+    let Some(token) = presented.and_then(|t| config.authenticate(t.trim())) else {
+        tracing::warn!(
+            path = %request.uri().path(),
+            "Rejected request: missing or invalid API token"
+        );
+        state.audit_log.record("auth", AuditEvent::RequestRejected {
+            session_id: None,
+            signer_id: None,
+            reason: format!(
+                "401 {} {}: missing or invalid API token",
+                request.method(),
+                request.uri().path()
+            ),
+        });
+        return auth_error(
+            StatusCode::UNAUTHORIZED,
+            "UNAUTHORIZED",
+            "Missing or invalid API token",
+        );
+    };
+
+    if !token.accepts_certificate(request.extensions().get::<ClientCertificate>()) {
+        tracing::warn!(
+            identity = %token.name,
+            path = %request.uri().path(),
+            "Rejected request: client certificate does not match API token"
+        );
+        state.audit_log.record("auth", AuditEvent::RequestRejected {
+            session_id: None,
+            signer_id: None,
+            reason: format!(
+                "401 {} {}: identity '{}' presented without its client certificate",
+                request.method(),
+                request.uri().path(),
+                token.name
+            ),
+        });
+        return auth_error(
+            StatusCode::UNAUTHORIZED,
+            "UNAUTHORIZED",
+            "API token is not valid for this client certificate",
+        );
+    }
+
+    if !token.allows(permission) {
+        tracing::warn!(
+            identity = %token.name,
+            role = ?token.role,
+            path = %request.uri().path(),
+            "Rejected request: insufficient permissions"
+        );
+        state.audit_log.record("auth", AuditEvent::RequestRejected {
+            session_id: None,
+            signer_id: None,
+            reason: format!(
+                "403 {} {}: identity '{}' not permitted",
+                request.method(),
+                request.uri().path(),
+                token.name
+            ),
+        });
+        return auth_error(
+            StatusCode::FORBIDDEN,
+            "FORBIDDEN",
+            format!("Identity '{}' is not allowed to call this endpoint", token.name),
+        );
+    }
+
+    request.extensions_mut().insert(Identity {
+        name: token.name.clone(),
+        role: token.role,
+    });
+
+    next.run(request).await
+}
+
+fn auth_error(status: StatusCode, code: &str, message: impl Into<String>) -> Response {
+    (status, Json(ErrorResponse::new(code, message))).into_response()
+}
+
+// ============================================================================
+// 測試
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn token(role: Role, signer_ids: Vec<u16>) -> ApiToken {
+        ApiToken {
+            name: "test".to_string(),
+            token: "t".to_string(),
+            role,
+            signer_ids,
+            group_signers: HashMap::new(),
+            client_cert: None,
+        }
+    }
+
+    #[test]
+    fn test_role_permissions() {
+        let coordinator = token(Role::Coordinator, vec![]);
+        let signer_1 = token(Role::SignerOperator, vec![1]);
+
+        let sign = required_permission(&Method::POST, "/groups/abc/sign");
+        let round2_1 = required_permission(&Method::POST, "/signer/1/round2");
+        let round2_2 = required_permission(&Method::POST, "/groups/abc/signer/2/round2");
+
+        assert!(coordinator.allows(sign));
+        assert!(!coordinator.allows(round2_1));
+        assert!(signer_1.allows(round2_1));
+        assert!(!signer_1.allows(round2_2));
+
+        // 預設群組的簽署者 1 不等於其他群組的簽署者 1
+        let vault_b = GroupId::new();
+        let vault_b_round1 = required_permission(
+            &Method::POST,
+            &format!("/groups/{}/signer/1/round1", vault_b),
+        );
+        let other_round1 = required_permission(
+            &Method::POST,
+            &format!("/groups/{}/signer/1/round1", GroupId::new()),
+        );
+        assert!(!signer_1.allows(vault_b_round1));
+        let mut vault_b_signer_1 = token(Role::SignerOperator, vec![]);
+        vault_b_signer_1.group_signers.insert(vault_b, vec![1]);
+        assert!(vault_b_signer_1.allows(vault_b_round1));
+        assert!(!vault_b_signer_1.allows(other_round1));
+        assert!(!vault_b_signer_1.allows(round2_1));
+        assert_eq!(
+            required_permission(&Method::GET, "/groups/not-a-uuid/signer/1/ws"),
+            Permission::Admin
+        );
+        assert!(!signer_1.allows(sign));
+        assert!(coordinator.allows(required_permission(&Method::GET, "/metrics")));
+        assert!(!signer_1.allows(required_permission(&Method::GET, "/metrics")));
+        assert_eq!(
+            required_permission(&Method::POST, "/groups"),
+            Permission::Admin
+        );
+        assert_eq!(
+            required_permission(&Method::GET, "/health"),
+            Permission::Public
+        );
+
+        // 綁定憑證的 Token 只接受相符的客戶端憑證
+        let mut bound = token(Role::SignerOperator, vec![1]);
+        bound.client_cert = Some("signer-1".to_string());
+        let cert = |name: &str| ClientCertificate {
+            names: vec![name.to_string()],
+        };
+        assert!(bound.accepts_certificate(Some(&cert("signer-1"))));
+        assert!(!bound.accepts_certificate(Some(&cert("signer-2"))));
+        assert!(!bound.accepts_certificate(Some(&ClientCertificate::default())));
+        assert!(!bound.accepts_certificate(None));
+        assert!(signer_1.accepts_certificate(None));
+    }
+}
@@ -0,0 +1,494 @@
+//! # Server Config - API 服務的分層設定
+//!
+//! 設定依下列順序合併，後者覆寫前者：
+//! 1. 內建預設值
+//! 2. TOML 設定檔（`--config` 或 `FROST_CONFIG`）
+//! 3. 環境變數（例如 `PORT`、`FROST_THRESHOLD`）
+//! 4. 命令列參數（例如 `--port`）
+//!
+//! 合併後立即驗證，任何無效值（無法解析的埠號、門檻值大於簽署者數量等）都會讓服務
+//! 在啟動前失敗，而不是默默改用預設值。`--print-config` 輸出合併後的設定（TOML）。
+//!
+//! 認證（`FROST_AUTH*`）與 TLS（`TLS_*`）仍由 `auth` / `tls` 模組從環境變數讀取。
+//!
+//! ## 設定檔範例
+//!
+//! ```toml
+//! host = "127.0.0.1"
+//! port = 3000
+//! cors_origins = ["https://dashboard.example.com"]
+//!
+//! [group]
+//! threshold = 3
+//! signers = 5
+//! key_dir = "frost-data"
+//!
+//! [storage]
+//! audit_log = "frost-audit.jsonl"
+//! audit_key = "frost-audit.key"
+//! session_store = "frost-sessions.db"
+//!
+//! [timeouts]
+//! session_secs = 300
+//! finished_session_retention_secs = 3600
+//! expiry_interval_secs = 30
+//! round_secs = 30
+//! heartbeat_interval_secs = 15
+//! heartbeat_timeout_secs = 45
+//! shutdown_secs = 30
+//! ```
+
+use crate::coordinator::{
+    DEFAULT_ROUND_TIMEOUT, FINISHED_SESSION_RETENTION_SECS, SESSION_TIMEOUT_SECS,
+};
+use crate::transport::websocket;
+use anyhow::{Context, Result};
+use clap::Parser;
+use serde::{Deserialize, Serialize};
+use std::net::{IpAddr, Ipv4Addr, SocketAddr};
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+/// 逾時與保留時間的上限（30 天）
+const MAX_DURATION_SECS: u64 = 30 * 24 * 3600;
+
+// ============================================================================
+// 命令列參數
+// ============================================================================
+
+/// FROST 門檻簽章 HTTP API 服務
+///
+/// 每個參數都可以改用對應的環境變數或設定檔指定（命令列優先）。
+#[derive(Parser, Debug, Default)]
+#[command(name = "frost-threshold-signature", version)]
+pub struct ServerArgs {
+    /// TOML 設定檔
+    #[arg(short, long, env = "FROST_CONFIG")]
+    pub config: Option<PathBuf>,
+
+    /// 輸出合併後的設定（TOML）並結束
+    #[arg(long)]
+    pub print_config: bool,
+
+    /// 監聽位址（0.0.0.0 允許外部存取，127.0.0.1 僅限本機）
+    #[arg(long, env = "HOST")]
+    pub host: Option<IpAddr>,
+
+    /// 監聽埠
+    #[arg(long, env = "PORT")]
+    pub port: Option<u16>,
+
+    /// 預設群組的門檻值
+    #[arg(long, env = "FROST_THRESHOLD")]
+    pub threshold: Option<u16>,
+
+    /// 預設群組的簽署者總數
+    #[arg(long, env = "FROST_SIGNERS")]
+    pub signers: Option<u16>,
+
+    /// 預設群組的金鑰目錄（`frost-cli keygen` 格式；不存在時生成並儲存）
+    #[arg(long, env = "FROST_KEYS_DIR")]
+    pub keys_dir: Option<PathBuf>,
+
+    /// 允許的 CORS origin（逗號分隔，"*" 代表全部）
+    #[arg(long, env = "FROST_CORS_ORIGINS", value_delimiter = ',')]
+    pub cors_origins: Option<Vec<String>>,
+
+    /// 稽核日誌（JSONL）路徑
+    #[arg(long, env = "FROST_AUDIT_LOG")]
+    pub audit_log: Option<PathBuf>,
+
+    /// 簽署稽核日誌檢查點的金鑰檔（不存在時生成）
+    #[arg(long, env = "FROST_AUDIT_KEY")]
+    pub audit_key: Option<PathBuf>,
+
+    /// Session Store 的 sled 資料庫目錄（"memory" 代表不持久化）
+    #[arg(long, env = "FROST_SESSION_STORE")]
+    pub session_store: Option<String>,
+
+    /// 未完成會話的存活時間（秒）
+    #[arg(long, env = "FROST_SESSION_TIMEOUT_SECS")]
+    pub session_timeout_secs: Option<u64>,
+
+    /// 終止會話保留供查詢的時間（秒）
+    #[arg(long, env = "FROST_SESSION_RETENTION_SECS")]
+    pub session_retention_secs: Option<u64>,
+
+    /// 檢查會話逾時的間隔（秒）
+    #[arg(long, env = "FROST_EXPIRY_INTERVAL_SECS")]
+    pub expiry_interval_secs: Option<u64>,
+
+    /// WebSocket 簽章時每一輪等待簽署者回覆的上限（秒）
+    #[arg(long, env = "FROST_ROUND_TIMEOUT_SECS")]
+    pub round_timeout_secs: Option<u64>,
+
+    /// WebSocket 心跳間隔（秒）
+    #[arg(long, env = "FROST_HEARTBEAT_INTERVAL_SECS")]
+    pub heartbeat_interval_secs: Option<u64>,
+
+    /// WebSocket 斷線逾時（秒）
+    #[arg(long, env = "FROST_HEARTBEAT_TIMEOUT_SECS")]
+    pub heartbeat_timeout_secs: Option<u64>,
+
+    /// 關機時等待進行中會話完成的期限（秒）
+    #[arg(long, env = "FROST_SHUTDOWN_TIMEOUT_SECS")]
+    pub shutdown_timeout_secs: Option<u64>,
+}
+
+// ============================================================================
+// 設定結構
+// ============================================================================
+
+/// API 服務的完整設定
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct ServerConfig {
+    /// 監聽位址
+    pub host: IpAddr,
+
+    /// 監聽埠
+    pub port: u16,
+
+    /// 允許的 CORS origin（`"*"` 代表全部，空列表代表不允許跨來源存取）
+    pub cors_origins: Vec<String>,
+
+    /// 啟動時建立的預設群組
+    pub group: GroupConfig,
+
+    /// 稽核日誌與 Session Store
+    pub storage: StorageConfig,
+
+    /// 逾時與保留時間
+    pub timeouts: TimeoutConfig,
+}
+
+/// 預設群組
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct GroupConfig {
+    /// 門檻值
+    pub threshold: u16,
+
+    /// 簽署者總數
+    pub signers: u16,
+
+    /// 金鑰目錄（未設定時每次啟動都以 Trusted Dealer 生成新群組）
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub key_dir: Option<PathBuf>,
+}
+
+/// 持久化
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct StorageConfig {
+    /// 稽核日誌（JSONL）路徑
+    pub audit_log: PathBuf,
+
+    /// 簽署稽核日誌檢查點的協調者金鑰檔（hex 私鑰，不存在時生成）
+    pub audit_key: PathBuf,
+
+    /// Session Store 的 sled 資料庫目錄（`"memory"` 代表不持久化）
+    pub session_store: String,
+}
+
+/// 逾時與保留時間（秒）
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct TimeoutConfig {
+    /// 未完成會話的存活時間
+    pub session_secs: u64,
+
+    /// 終止會話保留供查詢的時間
+    pub finished_session_retention_secs: u64,
+
+    /// 檢查會話逾時的間隔
+    pub expiry_interval_secs: u64,
+
+    /// WebSocket 簽章時每一輪的逾時
+    pub round_secs: u64,
+
+    /// WebSocket 心跳間隔
+    pub heartbeat_interval_secs: u64,
+
+    /// WebSocket 斷線逾時
+    pub heartbeat_timeout_secs: u64,
+
+    /// 關機時等待進行中會話完成的期限
+    pub shutdown_secs: u64,
+}
+
+impl Default for ServerConfig {
+    fn default() -> Self {
+        Self {
+            host: IpAddr::V4(Ipv4Addr::UNSPECIFIED),
+            port: 3000,
+            cors_origins: Vec::new(),
+            group: GroupConfig::default(),
+            storage: StorageConfig::default(),
+            timeouts: TimeoutConfig::default(),
+        }
+    }
+}
+
+impl Default for GroupConfig {
+    fn default() -> Self {
+        Self {
+            threshold: 3,
+            signers: 5,
+            key_dir: None,
+        }
+    }
+}
+
+impl Default for StorageConfig {
+    fn default() -> Self {
+        Self {
+            audit_log: PathBuf::from("frost-audit.jsonl"),
+            audit_key: PathBuf::from("frost-audit.key"),
+            session_store: "frost-sessions.db".to_string(),
+        }
+    }
+}
+
+impl Default for TimeoutConfig {
+    fn default() -> Self {
+        Self {
+            session_secs: SESSION_TIMEOUT_SECS as u64,
+            finished_session_retention_secs: FINISHED_SESSION_RETENTION_SECS as u64,
+            expiry_interval_secs: 30,
+            round_secs: DEFAULT_ROUND_TIMEOUT.as_secs(),
+            heartbeat_interval_secs: websocket::DEFAULT_HEARTBEAT_INTERVAL.as_secs(),
+            heartbeat_timeout_secs: websocket::DEFAULT_HEARTBEAT_TIMEOUT.as_secs(),
+            shutdown_secs: 30,
+        }
+    }
+}
+
+// ============================================================================
+// 載入與驗證
+// ============================================================================
+
+impl ServerConfig {
+    /// 合併設定檔、環境變數與命令列參數，並驗證結果
+    ///
+    /// 環境變數已由 clap 併入 `args`（命令列優先）。
+    pub fn load(args: &ServerArgs) -> Result<Self> {
+        let mut config = match &args.config {
+            Some(path) => Self::from_file(path)?,
+            None => Self::default(),
+        };
+        config.apply(args);
+        config.validate()?;
+        Ok(config)
+    }
+
+    /// 讀取 TOML 設定檔（未列出的欄位使用預設值，未知的欄位視為錯誤）
+    pub fn from_file(path: &Path) -> Result<Self> {
+        let text = std::fs::read_to_string(path)
+            .with_context(|| format!("Failed to read config file {}", path.display()))?;
+        toml::from_str(&text)
+            .with_context(|| format!("Failed to parse config file {}", path.display()))
+    }
+
+    /// 以命令列參數（含環境變數）覆寫
+    pub fn apply(&mut self, args: &ServerArgs) {
+        fn set<T: Clone>(target: &mut T, value: &Option<T>) {
+            if let Some(value) = value {
+                *target = value.clone();
+            }
+        }
+
+        set(&mut self.host, &args.host);
+        set(&mut self.port, &args.port);
+        set(&mut self.cors_origins, &args.cors_origins);
+        set(&mut self.group.threshold, &args.threshold);
+        set(&mut self.group.signers, &args.signers);
+        if args.keys_dir.is_some() {
+            self.group.key_dir = args.keys_dir.clone();
+        }
+        set(&mut self.storage.audit_log, &args.audit_log);
+        set(&mut self.storage.audit_key, &args.audit_key);
+        set(&mut self.storage.session_store, &args.session_store);
+
+        let timeouts = &mut self.timeouts;
+        set(&mut timeouts.session_secs, &args.session_timeout_secs);
+        set(&mut timeouts.finished_session_retention_secs, &args.session_retention_secs);
+        set(&mut timeouts.expiry_interval_secs, &args.expiry_interval_secs);
+        set(&mut timeouts.round_secs, &args.round_timeout_secs);
+        set(&mut timeouts.heartbeat_interval_secs, &args.heartbeat_interval_secs);
+        set(&mut timeouts.heartbeat_timeout_secs, &args.heartbeat_timeout_secs);
+        set(&mut timeouts.shutdown_secs, &args.shutdown_timeout_secs);
+    }
+
+    /// 檢查所有設定值，一次列出全部問題
+    pub fn validate(&self) -> Result<()> {
+        let mut problems = Vec::new();
+
+        if self.port == 0 {
+            problems.push("port must be between 1 and 65535".to_string());
+        }
+        if let Err(e) = super::cors_origins(&self.cors_origins) {
+            problems.push(e.to_string());
+        }
+
+        let GroupConfig {
+            threshold, signers, ..
+        } = self.group;
+        if threshold < 2 {
+            problems.push(format!("group.threshold must be at least 2, got {threshold}"));
+        }
+        if threshold > signers {
+            problems.push(format!(
+                "group.threshold ({threshold}) cannot exceed group.signers ({signers})"
+            ));
+        }
+
+        if self.storage.audit_log.as_os_str().is_empty() {
+            problems.push("storage.audit_log must not be empty".to_string());
+        }
+        if self.storage.audit_key.as_os_str().is_empty() {
+            problems.push("storage.audit_key must not be empty".to_string());
+        }
+        if self.storage.session_store.trim().is_empty() {
+            problems.push("storage.session_store must not be empty".to_string());
+        }
+
+        let timeouts = &self.timeouts;
+        for (name, secs) in [
+            ("session_secs", timeouts.session_secs),
+            ("finished_session_retention_secs", timeouts.finished_session_retention_secs),
+            ("expiry_interval_secs", timeouts.expiry_interval_secs),
+            ("round_secs", timeouts.round_secs),
+            ("heartbeat_interval_secs", timeouts.heartbeat_interval_secs),
+            ("heartbeat_timeout_secs", timeouts.heartbeat_timeout_secs),
+            ("shutdown_secs", timeouts.shutdown_secs),
+        ] {
+            if secs == 0 || secs > MAX_DURATION_SECS {
+                problems.push(format!(
+                    "timeouts.{name} must be between 1 and {MAX_DURATION_SECS}, got {secs}"
+                ));
+            }
+        }
+        // 兩輪都用滿逾時的會話不應在完成前被過期
+        if timeouts.session_secs < 2 * timeouts.round_secs {
+            problems.push(format!(
+                "timeouts.session_secs ({}) must be at least twice timeouts.round_secs ({})",
+                timeouts.session_secs, timeouts.round_secs
+            ));
+        }
+        if timeouts.heartbeat_timeout_secs <= timeouts.heartbeat_interval_secs {
+            problems.push(format!(
+                "timeouts.heartbeat_timeout_secs ({}) must exceed timeouts.heartbeat_interval_secs ({})",
+                timeouts.heartbeat_timeout_secs, timeouts.heartbeat_interval_secs
+            ));
+        }
+
+        if problems.is_empty() {
+            Ok(())
+        } else {
+            anyhow::bail!("Invalid configuration:\n  - {}", problems.join("\n  - "))
+        }
+    }
+
+    /// 輸出為 TOML（`--print-config`）
+    pub fn to_toml(&self) -> Result<String> {
+        toml::to_string_pretty(self).context("Failed to serialize config")
+    }
+
+    /// 監聽位址
+    pub fn addr(&self) -> SocketAddr {
+        SocketAddr::new(self.host, self.port)
+    }
+}
+
+impl TimeoutConfig {
+    /// 未完成會話的存活時間
+    pub fn session(&self) -> chrono::Duration {
+        chrono::Duration::seconds(self.session_secs as i64)
+    }
+
+    /// 終止會話保留供查詢的時間
+    pub fn finished_session_retention(&self) -> chrono::Duration {
+        chrono::Duration::seconds(self.finished_session_retention_secs as i64)
+    }
+
+    /// 檢查會話逾時的間隔
+    pub fn expiry_interval(&self) -> Duration {
+        Duration::from_secs(self.expiry_interval_secs)
+    }
+
+    /// WebSocket 簽章時每一輪的逾時
+    pub fn round(&self) -> Duration {
+        Duration::from_secs(self.round_secs)
+    }
+
+    /// WebSocket 心跳間隔
+    pub fn heartbeat_interval(&self) -> Duration {
+        Duration::from_secs(self.heartbeat_interval_secs)
+    }
+
+    /// WebSocket 斷線逾時
+    pub fn heartbeat_timeout(&self) -> Duration {
+        Duration::from_secs(self.heartbeat_timeout_secs)
+    }
+
+    /// 關機時等待進行中會話完成的期限
+    pub fn shutdown(&self) -> Duration {
+        Duration::from_secs(self.shutdown_secs)
+    }
+}
+
+// ============================================================================
+// 測試
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_flags_override_file_and_invalid_values_fail() {
+        let path = std::env::temp_dir().join(format!("frost-config-{}.toml", uuid::Uuid::new_v4()));
+        std::fs::write(
+            &path,
+            "port = 8080\ncors_origins = [\"*\"]\n\n[group]\nthreshold = 2\nsigners = 3\n\n[timeouts]\nround_secs = 60\n",
+        )
+        .unwrap();
+
+        // 命令列（含環境變數）覆寫設定檔，設定檔覆寫預設值
+        let args = ServerArgs {
+            config: Some(path.clone()),
+            port: Some(9090),
+            signers: Some(4),
+            ..Default::default()
+        };
+        let config = ServerConfig::load(&args).unwrap();
+        assert_eq!(config.port, 9090);
+        assert_eq!(config.group.threshold, 2);
+        assert_eq!(config.group.signers, 4);
+        assert_eq!(config.cors_origins, vec!["*".to_string()]);
+        assert_eq!(config.timeouts.round(), Duration::from_secs(60));
+        assert_eq!(config.storage, StorageConfig::default());
+
+        // --print-config 的輸出可以再被讀回
+        let printed: ServerConfig = toml::from_str(&config.to_toml().unwrap()).unwrap();
+        assert_eq!(printed, config);
+
+        // 無效值在啟動前就失敗，並列出所有問題
+        let args = ServerArgs {
+            config: Some(path.clone()),
+            threshold: Some(5),
+            heartbeat_timeout_secs: Some(1),
+            ..Default::default()
+        };
+        let error = ServerConfig::load(&args).unwrap_err().to_string();
+        assert!(error.contains("group.threshold (5) cannot exceed group.signers (3)"));
+        assert!(error.contains("heartbeat_timeout_secs"));
+
+        // 設定檔中的未知欄位（例如拼錯）不會被默默忽略
+        std::fs::write(&path, "prot = 8080\n").unwrap();
+        assert!(ServerConfig::from_file(&path).is_err());
+
+        std::fs::remove_file(&path).unwrap();
+    }
+}
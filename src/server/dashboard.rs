@@ -0,0 +1,48 @@
+//! # Dashboard - LoRa 傳輸狀態端點
+//!
+//! - `GET /status`：目前的 `LoRaTransportState` 快照（含 `last_event_id`）
+//! - `GET /events`：之後的狀態變化（Server-Sent Events，見 `transport::events`）
+//!
+//! `dashboard.html` 先抓取一次快照，再從快照的序號訂閱事件流。
+
+use crate::transport::events::{self, EventsQuery};
+use crate::transport::LoRaTransportState;
+use axum::{
+    extract::{Query, State},
+    http::HeaderMap,
+    response::IntoResponse,
+    routing::get,
+    Json, Router,
+};
+use std::sync::{Arc, Mutex};
+
+/// Dashboard 端點共用的狀態
+pub type DashboardState = Arc<Mutex<LoRaTransportState>>;
+
+/// 建立 Dashboard 路由
+pub fn router(state: DashboardState) -> Router {
+    Router::new()
+        .route("/status", get(get_status))
+        .route("/events", get(stream_events))
+        .with_state(state)
+}
+
+/// GET /status - 回傳當前 LoRa 傳輸狀態
+pub async fn get_status(State(state): State<DashboardState>) -> Json<LoRaTransportState> {
+    let state = state.lock().unwrap();
+    Json(state.clone())
+}
+
+/// GET /events - 以 Server-Sent Events 推送狀態變化
+///
+/// 首次連線以 `?last_event_id=` 接續 /status 快照；
+/// 重連時瀏覽器帶上 `Last-Event-ID`，從該序號之後續傳；
+/// 無法續傳時送出 `resync` 事件，客戶端應重新抓取 /status。
+pub async fn stream_events(
+    State(state): State<DashboardState>,
+    headers: HeaderMap,
+    Query(query): Query<EventsQuery>,
+) -> impl IntoResponse {
+    let stream = state.lock().unwrap().events.clone();
+    stream.sse(events::last_event_id(&headers, &query))
+}
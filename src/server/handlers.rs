@@ -0,0 +1,789 @@
+//! # HTTP 處理器
+//!
+//! 此模組實作了所有 HTTP 端點的處理邏輯。
+//! 使用 Axum 框架提供 RESTful API，路由由 `ServerBuilder` 組裝。
+
+use crate::api::*;
+use crate::audit::{AuditEntry, AuditHead, AuditLog};
+use crate::coordinator::{SigningOutcome, DEFAULT_ROUND_TIMEOUT};
+use crate::group::{GroupError, GroupServices, SigningGroup};
+use crate::metrics::Metrics;
+use crate::session_store::SessionStore;
+use crate::telemetry::TraceContext;
+use crate::transport::websocket::WebSocketHub;
+use axum::{
+    extract::{Path, Query, State, WebSocketUpgrade},
+    http::StatusCode,
+    response::{IntoResponse, Response},
+    Json,
+};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+
+// ============================================================================
+// 共享應用狀態
+// ============================================================================
+
+/// 應用的共享狀態
+///
+/// 使用 Arc 包裝，可以在多個請求處理器之間安全共享。
+///
+/// 服務可以同時管理多個簽章群組（`/groups/:group_id/...`），
+/// 未帶群組 ID 的舊端點（`/pubkey`、`/sign`、`/signer/:id/...`）使用預設群組。
+#[derive(Clone)]
+pub struct AppState {
+    /// 所有簽章群組
+    /// Key: Group ID
+    /// Value: 群組實例（各自擁有 Coordinator 與 Signers）
+    pub groups: Arc<dashmap::DashMap<GroupId, Arc<SigningGroup>>>,
+
+    /// 預設群組（供未帶群組 ID 的端點使用）
+    pub default_group: GroupId,
+
+    /// 所有群組共用的稽核日誌
+    pub audit_log: Arc<AuditLog>,
+
+    /// 所有群組共用的 Session Store
+    pub session_store: Arc<dyn SessionStore>,
+
+    /// 透過 WebSocket 主動連線的簽署者（`/signer/:id/ws`）
+    pub ws_hub: WebSocketHub,
+
+    /// 每一輪等待簽署者回覆的上限（WebSocket、LoRa 與行程內簽章共用）
+    pub round_timeout: Duration,
+
+    /// 關機中：不再接受新的簽章會話（見 `server::shutdown`）
+    pub draining: Arc<AtomicBool>,
+
+    /// Prometheus 指標（設定後提供 `GET /metrics`，新建的群組也會記錄指標）
+    pub metrics: Option<Arc<Metrics>>,
+
+    /// 模擬 LoRa 鏈路（設定後，該群組的 `/sign` 經由鏈路執行）
+    #[cfg(feature = "lora-sim")]
+    pub lora: Option<Arc<super::lora::LoRaSimulation>>,
+}
+
+impl AppState {
+    pub fn new(
+        default_group: SigningGroup,
+        audit_log: Arc<AuditLog>,
+        session_store: Arc<dyn SessionStore>,
+    ) -> Self {
+        let default_group_id = default_group.id();
+        let groups = dashmap::DashMap::new();
+        groups.insert(default_group_id, Arc::new(default_group));
+
+        Self {
+            groups: Arc::new(groups),
+            default_group: default_group_id,
+            audit_log,
+            session_store,
+            ws_hub: WebSocketHub::new(),
+            round_timeout: DEFAULT_ROUND_TIMEOUT,
+            draining: Arc::new(AtomicBool::new(false)),
+            metrics: None,
+            #[cfg(feature = "lora-sim")]
+            lora: None,
+        }
+    }
+
+    /// 新建群組時使用的共用服務
+    pub fn group_services(&self) -> GroupServices {
+        GroupServices {
+            audit_log: Some(Arc::clone(&self.audit_log)),
+            session_store: Some(Arc::clone(&self.session_store)),
+            metrics: self.metrics.clone(),
+        }
+    }
+
+    /// 開始關機：之後的 `/sign` 與 Round 1 請求返回 503
+    ///
+    /// 各群組的協調者也停止建立會話，已通過 `ensure_accepting_sessions` 但還沒建立
+    /// 會話的請求同樣返回 503，不會在排空之後才出現新的會話。
+    pub fn begin_draining(&self) {
+        self.draining.store(true, Ordering::SeqCst);
+        for group in self.groups.iter() {
+            group.coordinator().stop_accepting_sessions();
+        }
+    }
+
+    /// 新的簽章會話是否仍被接受
+    pub fn ensure_accepting_sessions(&self) -> Result<(), ApiError> {
+        if self.draining.load(Ordering::SeqCst) {
+            return Err(ApiError::ShuttingDown);
+        }
+        Ok(())
+    }
+
+    pub fn add_group(&self, group: SigningGroup) -> Arc<SigningGroup> {
+        let group = Arc::new(group);
+        self.groups.insert(group.id(), Arc::clone(&group));
+        // 與 `begin_draining` 同時發生時，至少其中一方會關閉此群組
+        if self.draining.load(Ordering::SeqCst) {
+            group.coordinator().stop_accepting_sessions();
+        }
+        group
+    }
+
+    pub fn get_group(&self, group_id: GroupId) -> Result<Arc<SigningGroup>, ApiError> {
+        self.groups
+            .get(&group_id)
+            .map(|g| Arc::clone(&g))
+            .ok_or(ApiError::GroupNotFound(group_id))
+    }
+
+    pub fn default_group(&self) -> Arc<SigningGroup> {
+        self.groups
+            .get(&self.default_group)
+            .map(|g| Arc::clone(&g))
+            .expect("default group is never removed")
+    }
+}
+
+// ============================================================================
+// 錯誤處理
+// ============================================================================
+
+/// 統一的錯誤類型
+pub enum ApiError {
+    GroupNotFound(GroupId),
+    GroupError(GroupError),
+    SignerNotFound(u16),
+    SignerError(crate::signer::SignerError),
+    CoordinatorError(crate::coordinator::CoordinatorError),
+    InvalidMessage(String),
+    InternalError(String),
+    ShuttingDown,
+}
+
+impl IntoResponse for ApiError {
+    fn into_response(self) -> Response {
+        let (status, error_response) = match self {
+            ApiError::GroupNotFound(id) => (
+                StatusCode::NOT_FOUND,
+                ErrorResponse::new("GROUP_NOT_FOUND", format!("Group {} not found", id)),
+            ),
+            ApiError::GroupError(e) => (
+                StatusCode::BAD_REQUEST,
+                ErrorResponse::new("INVALID_GROUP", e.to_string()),
+            ),
+            ApiError::SignerNotFound(id) => (
+                StatusCode::NOT_FOUND,
+                ErrorResponse::new(
+                    "SIGNER_NOT_FOUND",
+                    format!("Signer {} not found", id),
+                ),
+            ),
+            ApiError::SignerError(e) => {
+                let (code, message) = match e {
+                    crate::signer::SignerError::SessionNotFound(session_id) => (
+                        "SESSION_NOT_FOUND",
+                        format!(
+                            "Session {} not found - nonce may have been used or never generated",
+                            session_id
+                        ),
+                    ),
+                    _ => ("SIGNER_ERROR", e.to_string()),
+                };
+                (StatusCode::BAD_REQUEST, ErrorResponse::new(code, message))
+            }
+            ApiError::CoordinatorError(e) => match e {
+                crate::coordinator::CoordinatorError::SessionNotFound(_) => (
+                    StatusCode::NOT_FOUND,
+                    ErrorResponse::new("SESSION_NOT_FOUND", e.to_string()),
+                ),
+                crate::coordinator::CoordinatorError::InvalidMessage(_) => (
+                    StatusCode::BAD_REQUEST,
+                    ErrorResponse::new("INVALID_MESSAGE", e.to_string()),
+                ),
+                crate::coordinator::CoordinatorError::InvalidSessionState { .. } => (
+                    StatusCode::CONFLICT,
+                    ErrorResponse::new("INVALID_SESSION_STATE", e.to_string()),
+                ),
+                _ => (
+                    StatusCode::BAD_REQUEST,
+                    ErrorResponse::new("COORDINATOR_ERROR", e.to_string()),
+                ),
+            },
+            ApiError::InvalidMessage(msg) => (
+                StatusCode::BAD_REQUEST,
+                ErrorResponse::new("INVALID_MESSAGE", msg),
+            ),
+            ApiError::InternalError(msg) => (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                ErrorResponse::new("INTERNAL_ERROR", msg),
+            ),
+            ApiError::ShuttingDown => (
+                StatusCode::SERVICE_UNAVAILABLE,
+                ErrorResponse::new(
+                    "SHUTTING_DOWN",
+                    "Server is shutting down and no longer accepts new sessions",
+                ),
+            ),
+        };
+
+        (status, Json(error_response)).into_response()
+    }
+}
+
+impl From<GroupError> for ApiError {
+    fn from(e: GroupError) -> Self {
+        ApiError::GroupError(e)
+    }
+}
+
+impl From<crate::signer::SignerError> for ApiError {
+    fn from(e: crate::signer::SignerError) -> Self {
+        ApiError::SignerError(e)
+    }
+}
+
+impl From<crate::coordinator::CoordinatorError> for ApiError {
+    fn from(e: crate::coordinator::CoordinatorError) -> Self {
+        match e {
+            crate::coordinator::CoordinatorError::NotAcceptingSessions => ApiError::ShuttingDown,
+            e => ApiError::CoordinatorError(e),
+        }
+    }
+}
+
+// ============================================================================
+// Handler: Round 1 - Commitment
+// ============================================================================
+
+/// POST /signer/:signer_id/round1
+///
+/// 簽署者生成並返回 Round 1 承諾（預設群組）
+pub async fn signer_round1(
+    State(state): State<AppState>,
+    Path(signer_id): Path<u16>,
+    Json(request): Json<Round1Request>,
+) -> Result<Json<Round1Response>, ApiError> {
+    state.ensure_accepting_sessions()?;
+    round1_in_group(&state.default_group(), signer_id, request).map(Json)
+}
+
+/// POST /groups/:group_id/signer/:signer_id/round1
+///
+/// 指定群組的簽署者生成並返回 Round 1 承諾
+pub async fn group_signer_round1(
+    State(state): State<AppState>,
+    Path((group_id, signer_id)): Path<(GroupId, u16)>,
+    Json(request): Json<Round1Request>,
+) -> Result<Json<Round1Response>, ApiError> {
+    state.ensure_accepting_sessions()?;
+    let group = state.get_group(group_id)?;
+    round1_in_group(&group, signer_id, request).map(Json)
+}
+
+fn round1_in_group(
+    group: &SigningGroup,
+    signer_id: u16,
+    request: Round1Request,
+) -> Result<Round1Response, ApiError> {
+    tracing::info!(
+        group_id = %group.id(),
+        signer_id = signer_id,
+        session_id = %request.session_id,
+        "Received Round 1 request"
+    );
+
+    // 結構化請求必須能計算出摘要，否則不浪費 Nonce
+    if let Some(payload) = &request.payload {
+        payload
+            .signing_digest()
+            .map_err(|e| ApiError::InvalidMessage(e.to_string()))?;
+    }
+
+    // 獲取簽署者
+    let signer = group
+        .get_signer(signer_id)
+        .ok_or(ApiError::SignerNotFound(signer_id))?;
+
+    // 接在請求的 trace（`traceparent`）之下，與外部協調者的會話串在一起
+    let span = signer.request_span(request.session_id, TraceContext::current().as_ref());
+    let _entered = span.enter();
+
+    // 生成承諾（附身分金鑰的信封簽章）
+    let commitment = signer.commit_signed(request.session_id)?;
+
+    // 建立回應
+    let response = Round1Response {
+        signer_id,
+        session_id: request.session_id,
+        commitment: commitment.commitment,
+        envelope_signature: commitment.envelope_signature.unwrap_or_default(),
+        timestamp: chrono::Utc::now(),
+    };
+
+    tracing::info!(
+        signer_id = signer_id,
+        session_id = %request.session_id,
+        "Round 1 commitment generated"
+    );
+
+    Ok(response)
+}
+
+// ============================================================================
+// Handler: Round 2 - Signature Share
+// ============================================================================
+
+/// POST /signer/:signer_id/round2
+///
+/// 簽署者生成並返回簽章分片（預設群組）
+pub async fn signer_round2(
+    State(state): State<AppState>,
+    Path(signer_id): Path<u16>,
+    Json(request): Json<Round2Request>,
+) -> Result<Json<Round2Response>, ApiError> {
+    round2_in_group(&state.default_group(), signer_id, request).map(Json)
+}
+
+/// POST /groups/:group_id/signer/:signer_id/round2
+///
+/// 指定群組的簽署者生成並返回簽章分片
+pub async fn group_signer_round2(
+    State(state): State<AppState>,
+    Path((group_id, signer_id)): Path<(GroupId, u16)>,
+    Json(request): Json<Round2Request>,
+) -> Result<Json<Round2Response>, ApiError> {
+    let group = state.get_group(group_id)?;
+    round2_in_group(&group, signer_id, request).map(Json)
+}
+
+fn round2_in_group(
+    group: &SigningGroup,
+    signer_id: u16,
+    request: Round2Request,
+) -> Result<Round2Response, ApiError> {
+    tracing::info!(
+        group_id = %group.id(),
+        signer_id = signer_id,
+        session_id = %request.session_id,
+        "Received Round 2 request"
+    );
+
+    // 獲取簽署者
+    let signer = group
+        .get_signer(signer_id)
+        .ok_or(ApiError::SignerNotFound(signer_id))?;
+
+    let span = signer.request_span(request.session_id, TraceContext::current().as_ref());
+    let _entered = span.enter();
+
+    // 生成簽章分片
+    let signature_share = signer.sign_signed(request.session_id, &request.signing_package)?;
+
+    // 建立回應
+    let response = Round2Response {
+        signer_id,
+        session_id: request.session_id,
+        signature_share: signature_share.signature_share,
+        envelope_signature: signature_share.envelope_signature.unwrap_or_default(),
+        timestamp: chrono::Utc::now(),
+    };
+
+    tracing::info!(
+        signer_id = signer_id,
+        session_id = %request.session_id,
+        "Round 2 signature share generated"
+    );
+
+    Ok(response)
+}
+
+// ============================================================================
+// Handler: WebSocket 簽署者連線
+// ============================================================================
+
+/// GET /signer/:signer_id/ws
+///
+/// 簽署者主動建立 WebSocket 連線並登記（預設群組），之後由協調者推送請求
+pub async fn signer_websocket(
+    State(state): State<AppState>,
+    Path(signer_id): Path<u16>,
+    ws: WebSocketUpgrade,
+) -> Result<Response, ApiError> {
+    connect_signer(&state, &state.default_group(), signer_id, ws)
+}
+
+/// GET /groups/:group_id/signer/:signer_id/ws
+///
+/// 指定群組的簽署者建立 WebSocket 連線
+pub async fn group_signer_websocket(
+    State(state): State<AppState>,
+    Path((group_id, signer_id)): Path<(GroupId, u16)>,
+    ws: WebSocketUpgrade,
+) -> Result<Response, ApiError> {
+    let group = state.get_group(group_id)?;
+    connect_signer(&state, &group, signer_id, ws)
+}
+
+fn connect_signer(
+    state: &AppState,
+    group: &SigningGroup,
+    signer_id: u16,
+    ws: WebSocketUpgrade,
+) -> Result<Response, ApiError> {
+    // 只接受群組中存在的簽署者 ID（升級前驗證，錯誤以一般 HTTP 回應返回）
+    if !group.signer_ids().contains(&signer_id) {
+        return Err(ApiError::SignerNotFound(signer_id));
+    }
+
+    // 名冊用於驗證登記挑戰：只有持有身分金鑰的連線能取代既有連線
+    let hub = state.ws_hub.clone();
+    let group_id = group.id();
+    let roster = group.coordinator().roster().cloned();
+    Ok(ws.on_upgrade(move |socket| hub.accept(socket, group_id, signer_id, roster)))
+}
+
+// ============================================================================
+// Handler: 完整簽章流程（示範用）
+// ============================================================================
+
+/// POST /sign
+///
+/// 執行完整的簽章流程（示範用高階 API）
+///
+/// 在生產環境中，客戶端通常會分別呼叫 Round 1 和 Round 2 端點。
+/// 這個端點展示了如何使用協調者編排整個流程。
+///
+/// 請求與回應格式見 `api::SignRequest` / `api::SignResponse`。
+pub async fn sign(
+    State(state): State<AppState>,
+    Json(request): Json<SignRequest>,
+) -> Result<Json<SignResponse>, ApiError> {
+    sign_in_group(&state, &state.default_group(), request).await.map(Json)
+}
+
+/// POST /groups/:group_id/sign
+///
+/// 在指定群組中執行完整的簽章流程
+pub async fn group_sign(
+    State(state): State<AppState>,
+    Path(group_id): Path<GroupId>,
+    Json(request): Json<SignRequest>,
+) -> Result<Json<SignResponse>, ApiError> {
+    let group = state.get_group(group_id)?;
+    sign_in_group(&state, &group, request).await.map(Json)
+}
+
+async fn sign_in_group(
+    state: &AppState,
+    group: &SigningGroup,
+    request: SignRequest,
+) -> Result<SignResponse, ApiError> {
+    state.ensure_accepting_sessions()?;
+
+    tracing::info!(
+        group_id = %group.id(),
+        signer_ids = ?request.signer_ids,
+        "Received complete signing request"
+    );
+
+    let signer_ids = request.signer_ids.clone();
+    let payload = request
+        .into_payload()
+        .map_err(|e| ApiError::InvalidMessage(e.to_string()))?;
+
+    let (outcome, signing_path) = orchestrate(state, group, &signer_ids, &payload).await?;
+
+    // 建立回應
+    let response = SignResponse {
+        session_id: outcome.session_id,
+        group_id: group.id(),
+        signature: outcome.signature_hex(),
+        message_kind: outcome.message_kind,
+        message_digest: hex::encode(&outcome.message),
+        verified: outcome.verify(group.coordinator().group_public_key()),
+        signer_ids: outcome.signer_ids,
+        signing_path,
+        timings: outcome.timings,
+        group_public_key: group.group_public_key_hex(),
+    };
+
+    tracing::info!(
+        group_id = %group.id(),
+        session_id = %response.session_id,
+        signature = %response.signature,
+        total_ms = response.timings.total_ms,
+        "Complete signing flow finished"
+    );
+
+    Ok(response)
+}
+
+/// 選擇簽章通道並執行完整的簽章流程，返回結果與實際使用的通道
+///
+/// 1. 所有簽署者都已透過 WebSocket 連線時，改由推播通道執行
+/// 2. 群組接上模擬 LoRa 鏈路時（`lora-sim` feature），經由鏈路執行
+/// 3. 否則直接在行程內呼叫簽署者
+///
+/// 只有部分簽署者以 WebSocket 連線時不會混用通道，而是記錄警告並改用 2 或 3。
+async fn orchestrate(
+    state: &AppState,
+    group: &SigningGroup,
+    signer_ids: &[u16],
+    payload: &SignPayload,
+) -> Result<(SigningOutcome, SigningPath), ApiError> {
+    // 收集簽署者
+    let mut signers = Vec::new();
+    for signer_id in signer_ids {
+        let signer = group
+            .get_signer(*signer_id)
+            .ok_or(ApiError::SignerNotFound(*signer_id))?;
+        signers.push(signer);
+    }
+
+    let connected = state.ws_hub.connected_signers(group.id());
+    let missing: Vec<u16> = signer_ids
+        .iter()
+        .copied()
+        .filter(|signer_id| !connected.contains(signer_id))
+        .collect();
+    if missing.is_empty() {
+        tracing::info!(group_id = %group.id(), "Signing over WebSocket-connected signers");
+        let mut transport = state.ws_hub.endpoint(group.id());
+        let outcome = group
+            .coordinator()
+            .orchestrate_over_transport(&mut transport, signer_ids, payload, state.round_timeout)
+            .await?;
+        return Ok((outcome, SigningPath::WebSocket));
+    }
+    if missing.len() < signer_ids.len() {
+        tracing::warn!(
+            group_id = %group.id(),
+            missing = ?missing,
+            "Not every requested signer is connected over WebSocket, not using the WebSocket path"
+        );
+    }
+
+    #[cfg(feature = "lora-sim")]
+    if let Some(lora) = state.lora.as_ref().filter(|lora| lora.group_id() == group.id()) {
+        tracing::info!(group_id = %group.id(), "Signing over the simulated LoRa link");
+        let outcome = lora
+            .sign(group.coordinator(), signer_ids, payload, state.round_timeout)
+            .await?;
+        return Ok((outcome, SigningPath::LoRa));
+    }
+
+    tracing::info!(group_id = %group.id(), "Signing with in-process signers");
+    let outcome = group
+        .coordinator()
+        .orchestrate_signing(&signers, payload, state.round_timeout)
+        .await?;
+    Ok((outcome, SigningPath::InProcess))
+}
+
+// ============================================================================
+// Handler: 健康檢查
+// ============================================================================
+
+/// GET /health
+///
+/// 健康檢查端點
+#[derive(serde::Serialize)]
+pub struct HealthResponse {
+    pub status: String,
+    pub groups_count: usize,
+    pub signers_count: usize,
+    /// 各階段的進行中會話數量
+    pub active_sessions: ActiveSessionCounts,
+}
+
+pub async fn health(State(state): State<AppState>) -> Json<HealthResponse> {
+    let mut signers_count = 0;
+    let mut active_sessions = ActiveSessionCounts::default();
+    for group in state.groups.iter() {
+        signers_count += group.signers_count();
+        active_sessions.merge(&group.coordinator().active_session_counts());
+    }
+
+    Json(HealthResponse {
+        status: "ok".to_string(),
+        groups_count: state.groups.len(),
+        signers_count,
+        active_sessions,
+    })
+}
+
+// ============================================================================
+// Handler: 獲取群組公鑰
+// ============================================================================
+
+/// GET /pubkey
+///
+/// 獲取群組公鑰（用於驗證簽章）
+#[derive(serde::Serialize)]
+pub struct PubkeyResponse {
+    pub group_public_key: String,
+}
+
+pub async fn get_pubkey(State(state): State<AppState>) -> Json<PubkeyResponse> {
+    Json(PubkeyResponse {
+        group_public_key: state.default_group().group_public_key_hex(),
+    })
+}
+
+/// GET /groups/:group_id/pubkey
+///
+/// 獲取指定群組的公鑰
+pub async fn get_group_pubkey(
+    State(state): State<AppState>,
+    Path(group_id): Path<GroupId>,
+) -> Result<Json<PubkeyResponse>, ApiError> {
+    Ok(Json(PubkeyResponse {
+        group_public_key: state.get_group(group_id)?.group_public_key_hex(),
+    }))
+}
+
+// ============================================================================
+// Handler: 群組管理
+// ============================================================================
+
+/// POST /groups
+///
+/// 使用 Trusted Dealer 建立新的簽章群組
+pub async fn create_group(
+    State(state): State<AppState>,
+    Json(request): Json<SetupRequest>,
+) -> Result<(StatusCode, Json<SetupResponse>), ApiError> {
+    tracing::info!(
+        max_signers = request.max_signers,
+        min_signers = request.min_signers,
+        "Received group setup request"
+    );
+
+    let group = SigningGroup::generate_with_dealer(
+        request.max_signers,
+        request.min_signers,
+        &state.group_services(),
+    )?;
+    let group = state.add_group(group);
+
+    Ok((StatusCode::CREATED, Json(group.to_setup_response())))
+}
+
+/// GET /groups
+///
+/// 列出所有簽章群組
+pub async fn list_groups(State(state): State<AppState>) -> Json<Vec<SetupResponse>> {
+    let mut groups: Vec<SetupResponse> = state
+        .groups
+        .iter()
+        .map(|group| group.to_setup_response())
+        .collect();
+    groups.sort_by_key(|g| g.group_id.0);
+
+    Json(groups)
+}
+
+// ============================================================================
+// Handler: 會話查詢
+// ============================================================================
+
+/// GET /sessions/:session_id
+///
+/// 查詢會話目前的階段（在所有群組中搜尋）
+pub async fn get_session(
+    State(state): State<AppState>,
+    Path(session_id): Path<SessionId>,
+) -> Result<Json<SessionInfo>, ApiError> {
+    for group in state.groups.iter() {
+        if let Some(session) = group.coordinator().get_session(&session_id)? {
+            return Ok(Json(session.to_info()));
+        }
+    }
+
+    Err(crate::coordinator::CoordinatorError::SessionNotFound(session_id).into())
+}
+
+/// GET /sessions?status=...
+///
+/// 列出所有群組的會話（可依階段過濾）
+pub async fn list_sessions(
+    State(state): State<AppState>,
+    Query(query): Query<SessionListQuery>,
+) -> Result<Json<Vec<SessionInfo>>, ApiError> {
+    let mut sessions = Vec::new();
+    for group in state.groups.iter() {
+        sessions.extend(group.coordinator().sessions()?);
+    }
+    Ok(Json(filter_sessions(sessions, &query)))
+}
+
+/// GET /groups/:group_id/sessions/:session_id
+pub async fn get_group_session(
+    State(state): State<AppState>,
+    Path((group_id, session_id)): Path<(GroupId, SessionId)>,
+) -> Result<Json<SessionInfo>, ApiError> {
+    let session = state
+        .get_group(group_id)?
+        .coordinator()
+        .get_session(&session_id)?
+        .ok_or(crate::coordinator::CoordinatorError::SessionNotFound(session_id))?;
+    Ok(Json(session.to_info()))
+}
+
+/// GET /groups/:group_id/sessions?status=...
+pub async fn list_group_sessions(
+    State(state): State<AppState>,
+    Path(group_id): Path<GroupId>,
+    Query(query): Query<SessionListQuery>,
+) -> Result<Json<Vec<SessionInfo>>, ApiError> {
+    let sessions = state.get_group(group_id)?.coordinator().sessions()?;
+    Ok(Json(filter_sessions(sessions, &query)))
+}
+
+/// 依查詢條件過濾並依建立時間排序（新的在前）
+fn filter_sessions(
+    sessions: Vec<crate::coordinator::SessionState>,
+    query: &SessionListQuery,
+) -> Vec<SessionInfo> {
+    let mut sessions: Vec<_> = sessions
+        .into_iter()
+        .filter(|session| match query.status {
+            Some(status) => session.status == status,
+            None => true,
+        })
+        .collect();
+    sessions.sort_by_key(|session| std::cmp::Reverse(session.created_at));
+    sessions.iter().map(|session| session.to_info()).collect()
+}
+
+// ============================================================================
+// Handler: 稽核日誌
+// ============================================================================
+
+/// GET /audit 查詢參數
+#[derive(serde::Deserialize)]
+pub struct AuditQuery {
+    /// 只返回序號 >= since 的紀錄
+    pub since: Option<u64>,
+
+    /// 最多返回的筆數（預設 100）
+    pub limit: Option<usize>,
+}
+
+/// GET /audit
+///
+/// 查詢最近的稽核紀錄（完整歷史請直接讀取 JSONL 檔案）
+pub async fn get_audit(
+    State(state): State<AppState>,
+    Query(query): Query<AuditQuery>,
+) -> Json<Vec<AuditEntry>> {
+    Json(
+        state
+            .audit_log
+            .recent_entries(query.since, query.limit.unwrap_or(100)),
+    )
+}
+
+/// GET /audit/head
+///
+/// 目前的鏈頭與檢查點公鑰（記錄在日誌以外，供 `frost-cli audit verify --expect-head` 使用）
+pub async fn get_audit_head(State(state): State<AppState>) -> Json<AuditHead> {
+    Json(state.audit_log.head())
+}
@@ -0,0 +1,131 @@
+//! # LoRa Simulation - 經由模擬 LoRa 鏈路執行 `/sign`
+//!
+//! 把一個簽章群組的所有簽署者接到 `ChannelNetwork::with_lora` 上，讓 HTTP 的
+//! `/sign` 請求以真正的 `Coordinator` / `Signer` 在模擬鏈路上交換 `FrostMessage`。
+//! 鏈路的狀態（`LoRaTransportState`）同時供 Dashboard 的 `/status` 與 `/events` 使用。
+//!
+//! 所有參與者共用一條鏈路與同一個協調者端點，因此同一時間只執行一個簽章流程，
+//! 後到的請求會排隊等待。
+
+use crate::api::{GroupId, SignPayload};
+use crate::coordinator::{Coordinator, CoordinatorError, SigningOutcome};
+use crate::group::SigningGroup;
+use crate::transport::{
+    ChannelNetwork, ChannelTransport, LoRaTransportState, Participant, SimulatedLoRaTransport,
+    TransportStats,
+};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+/// 模擬鏈路建議的每輪逾時（可能需要多次重傳，交給 `ServerBuilder::with_round_timeout`）
+pub const DEFAULT_ROUND_TIMEOUT: Duration = Duration::from_secs(300);
+
+/// 接上模擬 LoRa 鏈路的簽章群組
+pub struct LoRaSimulation {
+    /// 接上鏈路的群組
+    group_id: GroupId,
+
+    /// 模擬網路（簽署者任務在網路關閉後結束）
+    network: ChannelNetwork,
+
+    /// 鏈路狀態（供 Dashboard 使用）
+    state: Arc<Mutex<LoRaTransportState>>,
+
+    /// 協調者端點（同一時間只有一個簽章流程使用）
+    coordinator: tokio::sync::Mutex<ChannelTransport>,
+
+    /// 累計的重傳次數（鏈路狀態的 `total_retries` 每次簽章都會歸零）
+    total_retries: AtomicU64,
+}
+
+impl LoRaSimulation {
+    /// 把群組的所有簽署者接到模擬鏈路上
+    ///
+    /// 每個簽署者在自己的 Tokio 任務中服務協調者的請求，因此必須在 runtime 內呼叫。
+    pub fn new(group: &SigningGroup, link: SimulatedLoRaTransport) -> Self {
+        let state = link.get_state();
+        let network = ChannelNetwork::with_lora(link);
+
+        for signer_id in group.signer_ids() {
+            let Some(signer) = group.get_signer(signer_id) else {
+                continue;
+            };
+            let mut endpoint = network.endpoint(Participant::Signer(signer_id));
+            tokio::spawn(async move {
+                if let Err(e) = signer.serve(&mut endpoint).await {
+                    tracing::warn!(signer_id, error = %e, "LoRa signer stopped");
+                }
+            });
+        }
+
+        let coordinator = network.endpoint(Participant::Coordinator);
+        Self {
+            group_id: group.id(),
+            network,
+            state,
+            coordinator: tokio::sync::Mutex::new(coordinator),
+            total_retries: AtomicU64::new(0),
+        }
+    }
+
+    /// 接上鏈路的群組
+    pub fn group_id(&self) -> GroupId {
+        self.group_id
+    }
+
+    /// 鏈路狀態（供 Dashboard 使用）
+    pub fn state(&self) -> Arc<Mutex<LoRaTransportState>> {
+        Arc::clone(&self.state)
+    }
+
+    /// 鏈路上累計的訊息統計
+    pub fn stats(&self) -> TransportStats {
+        self.network.stats()
+    }
+
+    /// 啟動以來累計的重傳次數
+    pub fn total_retries(&self) -> u64 {
+        self.total_retries.load(Ordering::Relaxed)
+    }
+
+    /// 經由模擬鏈路執行完整的簽章流程
+    ///
+    /// `round_timeout` 為每一輪等待簽署者回應的上限。
+    pub async fn sign(
+        &self,
+        coordinator: &Coordinator,
+        signer_ids: &[u16],
+        payload: &SignPayload,
+        round_timeout: Duration,
+    ) -> Result<SigningOutcome, CoordinatorError> {
+        let mut transport = self.coordinator.lock().await;
+
+        // 新的簽章流程：Dashboard 只顯示這一次的傳輸事件
+        {
+            let mut state = self.state.lock().unwrap();
+            state.recent_events.clear();
+            state.total_retries = 0;
+            state.set_phase("Starting", 0.0);
+        }
+
+        let outcome = coordinator
+            .orchestrate_over_transport(&mut *transport, signer_ids, payload, round_timeout)
+            .await;
+
+        let phase = if outcome.is_ok() { "Complete" } else { "Failed" };
+        let retries = {
+            let mut state = self.state.lock().unwrap();
+            state.set_phase(phase, 1.0);
+            state.total_retries
+        };
+        self.total_retries
+            .fetch_add(u64::from(retries), Ordering::Relaxed);
+        outcome
+    }
+
+    /// 關閉模擬網路，結束所有簽署者任務
+    pub fn shutdown(&self) {
+        self.network.shutdown();
+    }
+}
@@ -0,0 +1,54 @@
+//! # Metrics - Prometheus 抓取端點
+//!
+//! - `GET /metrics`：`crate::metrics` 的所有指標（Prometheus 文字格式）
+//!
+//! 協調者在簽章流程中即時記錄；簽署者持有的 Nonce 與模擬 LoRa 鏈路的統計在每次
+//! 抓取時取樣。HTTP 回應碼由 `track_requests` 中間件記錄，`path` 標籤使用路由樣板
+//! （例如 `/sessions/:session_id`），不會因 Session ID 產生大量時間序列。
+
+use super::AppState;
+use crate::metrics::{self, Metrics};
+use axum::{
+    extract::{MatchedPath, Request, State},
+    http::{header, StatusCode},
+    middleware::Next,
+    response::{IntoResponse, Response},
+};
+use std::sync::Arc;
+
+/// GET /metrics - 取樣目前狀態並輸出所有指標
+pub async fn get_metrics(State(state): State<AppState>) -> Response {
+    let Some(metrics) = state.metrics.as_ref() else {
+        return StatusCode::NOT_FOUND.into_response();
+    };
+
+    metrics.reset_outstanding_nonces();
+    for group in state.groups.iter() {
+        metrics.observe_group(&group);
+    }
+
+    #[cfg(feature = "lora-sim")]
+    if let Some(lora) = &state.lora {
+        metrics.observe_transport(&lora.stats(), lora.total_retries());
+    }
+
+    ([(header::CONTENT_TYPE, metrics::CONTENT_TYPE)], metrics.render()).into_response()
+}
+
+/// 記錄每個 HTTP 回應的 method、路由樣板與狀態碼
+pub async fn track_requests(
+    State(metrics): State<Arc<Metrics>>,
+    request: Request,
+    next: Next,
+) -> Response {
+    let method = request.method().clone();
+    let path = request
+        .extensions()
+        .get::<MatchedPath>()
+        .map(|path| path.as_str().to_string())
+        .unwrap_or_else(|| "unmatched".to_string());
+
+    let response = next.run(request).await;
+    metrics.observe_http(method.as_str(), &path, response.status().as_u16());
+    response
+}
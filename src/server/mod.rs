@@ -0,0 +1,378 @@
+//! # HTTP 服務 - Router Builder
+//!
+//! API 服務（`main.rs`）與 `frost-cli demo-basic` 的 Dashboard 服務共用同一組
+//! 處理器與同一份 API 合約（`api::SignRequest` / `api::SignResponse`），
+//! 差別只在 `ServerBuilder` 啟用了哪些功能。
+//!
+//! ## API 端點
+//! - `GET  /health` - 健康檢查
+//! - `GET  /pubkey` - 獲取群組公鑰
+//! - `POST /signer/:id/round1` - Round 1: 生成承諾
+//! - `POST /signer/:id/round2` - Round 2: 生成簽章分片
+//! - `GET  /signer/:id/ws` - 簽署者主動建立 WebSocket 連線，由協調者推送請求
+//! - `POST /sign` - 完整簽章流程（簽署者皆已透過 WebSocket 連線時改用推播）
+//!
+//! ### 多群組（多租戶）端點
+//! - `GET  /groups` - 列出所有簽章群組
+//! - `POST /groups` - 建立新的簽章群組（Trusted Dealer）
+//! - `GET  /groups/:group_id/pubkey` - 獲取群組公鑰
+//! - `POST /groups/:group_id/signer/:id/round1` - Round 1
+//! - `POST /groups/:group_id/signer/:id/round2` - Round 2
+//! - `GET  /groups/:group_id/signer/:id/ws` - WebSocket 簽署者連線
+//! - `POST /groups/:group_id/sign` - 完整簽章流程
+//!
+//! 未帶群組 ID 的端點操作啟動時建立的預設群組。
+//!
+//! ### 會話查詢
+//! - `GET  /sessions?status=` - 列出會話（可依階段過濾）
+//! - `GET  /sessions/:session_id` - 查詢會話目前的階段
+//! - `GET  /groups/:group_id/sessions[/:session_id]` - 群組內的會話
+//!
+//! ### 稽核
+//! - `GET  /audit?since=&limit=` - 查詢最近的稽核紀錄（雜湊鏈 JSONL）
+//! - `GET  /audit/head` - 目前的鏈頭與檢查點公鑰
+//!
+//! ### 監控
+//! - `GET  /metrics` - Prometheus 指標（呼叫 `with_metrics` 後提供，見 `crate::metrics`）
+//!
+//! ## 追蹤
+//! 所有請求都接受 W3C `traceparent` 標頭，`/sign` 的簽章會話與簽署者的日誌
+//! 會記錄在同一個 trace 之下（見 `trace` 模組與 `crate::telemetry`）。
+//!
+//! ## 關機
+//! `shutdown::drain` 開始排空後，新的 `/sign` 與 Round 1 請求返回
+//! `503 SHUTTING_DOWN`；進行中的會話在期限內完成，其餘會話被中止。
+//!
+//! ## 可選功能
+//!
+//! | Cargo feature | Builder 方法 | 效果 |
+//! |---------------|--------------|------|
+//! | `dashboard` | `with_dashboard` | `GET /status`、`GET /events`（LoRa 傳輸狀態） |
+//! | `lora-sim` | `with_lora_simulation` | 該群組的 `/sign` 經由模擬 LoRa 鏈路執行 |
+//!
+//! 兩者都在預設 features 中。
+//!
+//! ## 使用範例
+//!
+//! ```no_run
+//! use frost_threshold_signature::audit::AuditLog;
+//! use frost_threshold_signature::group::{GroupServices, SigningGroup};
+//! use frost_threshold_signature::server::{AppState, ServerBuilder};
+//! use frost_threshold_signature::MemorySessionStore;
+//! use std::sync::Arc;
+//!
+//! # async fn run() -> anyhow::Result<()> {
+//! let group = SigningGroup::generate_with_dealer(5, 3, &GroupServices::default())?;
+//! let state = AppState::new(
+//!     group,
+//!     Arc::new(AuditLog::in_memory()),
+//!     Arc::new(MemorySessionStore::new()),
+//! );
+//! let app = ServerBuilder::new(state).build();
+//!
+//! let listener = tokio::net::TcpListener::bind("127.0.0.1:3000").await?;
+//! axum::serve(listener, app).await?;
+//! # Ok(())
+//! # }
+//! ```
+
+pub mod auth;
+pub mod config;
+pub mod handlers;
+pub mod metrics;
+pub mod shutdown;
+pub mod tls;
+pub mod trace;
+
+#[cfg(feature = "dashboard")]
+pub mod dashboard;
+#[cfg(feature = "lora-sim")]
+pub mod lora;
+
+pub use handlers::{ApiError, AppState};
+
+#[cfg(feature = "lora-sim")]
+pub use lora::LoRaSimulation;
+
+use axum::{
+    http::HeaderValue,
+    middleware,
+    routing::{get, post},
+    Router,
+};
+use std::sync::Arc;
+use std::time::Duration;
+use tower_http::{
+    cors::{AllowOrigin, Any, CorsLayer},
+    trace::TraceLayer,
+};
+
+// ============================================================================
+// Server Builder
+// ============================================================================
+
+/// 組裝 HTTP 路由
+///
+/// 未呼叫 `with_auth` 時所有端點都不需要 Token（僅適合本機展示）；
+/// 未呼叫 `with_cors` 時不允許跨來源存取。
+pub struct ServerBuilder {
+    state: AppState,
+    auth: Option<auth::AuthConfig>,
+    cors: Option<AllowOrigin>,
+    #[cfg(feature = "dashboard")]
+    dashboard: Option<dashboard::DashboardState>,
+}
+
+impl ServerBuilder {
+    /// 以應用狀態建立 Builder
+    pub fn new(state: AppState) -> Self {
+        Self {
+            state,
+            auth: None,
+            cors: None,
+            #[cfg(feature = "dashboard")]
+            dashboard: None,
+        }
+    }
+
+    /// 啟用 Bearer Token 認證（拒絕的請求寫入應用狀態的稽核日誌）
+    pub fn with_auth(mut self, config: auth::AuthConfig) -> Self {
+        self.auth = Some(config);
+        self
+    }
+
+    /// 允許指定的跨來源存取
+    pub fn with_cors(mut self, origins: AllowOrigin) -> Self {
+        self.cors = Some(origins);
+        self
+    }
+
+    /// 提供 `GET /status` 與 `GET /events`
+    #[cfg(feature = "dashboard")]
+    pub fn with_dashboard(mut self, state: dashboard::DashboardState) -> Self {
+        self.dashboard = Some(state);
+        self
+    }
+
+    /// 讓群組的 `/sign` 經由模擬 LoRa 鏈路執行
+    ///
+    /// 以 `Arc` 傳入，讓呼叫者（例如 `frost-cli demo-basic`）也能在同一條鏈路上簽章。
+    #[cfg(feature = "lora-sim")]
+    pub fn with_lora_simulation(mut self, lora: Arc<LoRaSimulation>) -> Self {
+        self.state.lora = Some(lora);
+        self
+    }
+
+    /// 提供 `GET /metrics` 並記錄 HTTP 回應碼
+    ///
+    /// 啟動前建立的群組必須在 `GroupServices::metrics` 使用同一個 `Metrics`，
+    /// 之後經由 `POST /groups` 建立的群組會自動套用。
+    pub fn with_metrics(mut self, metrics: Arc<crate::metrics::Metrics>) -> Self {
+        self.state.metrics = Some(metrics);
+        self
+    }
+
+    /// 每一輪等待簽署者回覆的上限（WebSocket、LoRa 與行程內簽章共用）
+    pub fn with_round_timeout(mut self, round_timeout: Duration) -> Self {
+        self.state.round_timeout = round_timeout;
+        self
+    }
+
+    /// 設定 WebSocket 簽署者連線的心跳（Ping 間隔與斷線逾時）
+    pub fn with_websocket_heartbeat(mut self, interval: Duration, timeout: Duration) -> Self {
+        self.state.ws_hub = self.state.ws_hub.clone().with_heartbeat(interval, timeout);
+        self
+    }
+
+    /// 應用狀態（例如啟動時恢復 Session、啟動背景任務）
+    pub fn state(&self) -> &AppState {
+        &self.state
+    }
+
+    /// 建立 Router
+    pub fn build(self) -> Router {
+        let audit_log = Arc::clone(&self.state.audit_log);
+        let http_metrics = self.state.metrics.clone();
+
+        let mut app = Router::new()
+            // 健康檢查與資訊端點
+            .route("/health", get(handlers::health))
+            .route("/pubkey", get(handlers::get_pubkey))
+            // Round 1: Commitment 生成
+            .route("/signer/:signer_id/round1", post(handlers::signer_round1))
+            // Round 2: Signature Share 生成
+            .route("/signer/:signer_id/round2", post(handlers::signer_round2))
+            // WebSocket：簽署者主動連線並接收推送的請求
+            .route("/signer/:signer_id/ws", get(handlers::signer_websocket))
+            // 完整簽章流程
+            .route("/sign", post(handlers::sign))
+            // 多群組端點
+            .route("/groups", get(handlers::list_groups).post(handlers::create_group))
+            .route("/groups/:group_id/pubkey", get(handlers::get_group_pubkey))
+            .route(
+                "/groups/:group_id/signer/:signer_id/round1",
+                post(handlers::group_signer_round1),
+            )
+            .route(
+                "/groups/:group_id/signer/:signer_id/round2",
+                post(handlers::group_signer_round2),
+            )
+            .route(
+                "/groups/:group_id/signer/:signer_id/ws",
+                get(handlers::group_signer_websocket),
+            )
+            .route("/groups/:group_id/sign", post(handlers::group_sign))
+            .route("/groups/:group_id/sessions", get(handlers::list_group_sessions))
+            .route(
+                "/groups/:group_id/sessions/:session_id",
+                get(handlers::get_group_session),
+            )
+            // 會話查詢
+            .route("/sessions", get(handlers::list_sessions))
+            .route("/sessions/:session_id", get(handlers::get_session))
+            // 稽核日誌
+            .route("/audit", get(handlers::get_audit))
+            .route("/audit/head", get(handlers::get_audit_head))
+            // Prometheus 指標（未設定時返回 404）
+            .route("/metrics", get(metrics::get_metrics))
+            // 添加共享狀態
+            .with_state(self.state);
+
+        #[cfg(feature = "dashboard")]
+        if let Some(state) = self.dashboard {
+            app = app.merge(dashboard::router(state));
+        }
+
+        // 添加認證中間件（在 CORS 之內，讓 preflight 請求不需要 Token）
+        if let Some(config) = self.auth {
+            app = app.layer(middleware::from_fn_with_state(
+                Arc::new(auth::AuthState { config, audit_log }),
+                auth::require_auth,
+            ));
+        }
+
+        // 記錄 HTTP 回應碼（在認證之外，401 / 403 也會被計入）
+        if let Some(http_metrics) = http_metrics {
+            app = app.layer(middleware::from_fn_with_state(
+                http_metrics,
+                metrics::track_requests,
+            ));
+        }
+
+        // 添加 CORS 中間件（必須在 TraceLayer 之前）
+        if let Some(origins) = self.cors {
+            app = app.layer(
+                CorsLayer::new()
+                    .allow_origin(origins)
+                    .allow_methods(Any)
+                    .allow_headers(Any),
+            );
+        }
+
+        // 添加日誌中間件（最外層：所有日誌都帶有請求的 trace_id）
+        app.layer(TraceLayer::new_for_http())
+            .layer(middleware::from_fn(trace::propagate_trace))
+    }
+}
+
+// ============================================================================
+// 輔助函數
+// ============================================================================
+
+/// 解析允許的 CORS origin（`"*"` 代表全部）
+///
+/// 空列表代表不允許跨來源存取。
+pub fn cors_origins(origins: &[String]) -> anyhow::Result<AllowOrigin> {
+    let origins: Vec<&str> = origins
+        .iter()
+        .map(|o| o.trim())
+        .filter(|o| !o.is_empty())
+        .collect();
+
+    if origins.contains(&"*") {
+        if origins.len() > 1 {
+            anyhow::bail!("CORS origin \"*\" cannot be combined with other origins");
+        }
+        return Ok(AllowOrigin::any());
+    }
+
+    let origins = origins
+        .into_iter()
+        .map(|o| {
+            o.parse::<HeaderValue>()
+                .map_err(|e| anyhow::anyhow!("Invalid CORS origin '{}': {}", o, e))
+        })
+        .collect::<anyhow::Result<Vec<_>>>()?;
+
+    Ok(AllowOrigin::list(origins))
+}
+
+// ============================================================================
+// 測試
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::api::{ErrorResponse, SignRequest, SignResponse, SigningPath};
+    use crate::audit::AuditLog;
+    use crate::group::{GroupServices, SigningGroup};
+    use crate::session_store::MemorySessionStore;
+    use crate::SignPayload;
+
+    #[tokio::test]
+    async fn test_sign_request_contract() {
+        let group = SigningGroup::generate_with_dealer(3, 2, &GroupServices::default()).unwrap();
+        let group_public_key = group.group_public_key_hex();
+        let state = AppState::new(
+            group,
+            Arc::new(AuditLog::in_memory()),
+            Arc::new(MemorySessionStore::new()),
+        );
+        let app = ServerBuilder::new(state).build();
+
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("http://{}/sign", listener.local_addr().unwrap());
+        tokio::spawn(async move { axum::serve(listener, app).await.unwrap() });
+        let client = reqwest::Client::new();
+
+        // hex 訊息簽署其 tagged hash，而不是原始 bytes
+        let response: SignResponse = client
+            .post(&url)
+            .json(&SignRequest {
+                signer_ids: vec![1, 3],
+                message: Some(hex::encode("Hello FROST")),
+                payload: None,
+            })
+            .send()
+            .await
+            .unwrap()
+            .json()
+            .await
+            .unwrap();
+        assert!(response.verified);
+        assert_eq!(
+            response.message_digest,
+            hex::encode(crate::message::raw_digest(b"Hello FROST"))
+        );
+        assert_eq!(response.group_public_key, group_public_key);
+        assert_eq!(response.signing_path, SigningPath::InProcess);
+
+        // 同時提供兩種訊息格式時拒絕
+        let response = client
+            .post(&url)
+            .json(&SignRequest {
+                signer_ids: vec![1, 3],
+                message: Some(hex::encode("Hello FROST")),
+                payload: Some(SignPayload::Raw {
+                    message: hex::encode("Hello FROST"),
+                }),
+            })
+            .send()
+            .await
+            .unwrap();
+        assert_eq!(response.status(), reqwest::StatusCode::BAD_REQUEST);
+        let error: ErrorResponse = response.json().await.unwrap();
+        assert_eq!(error.error_code, "INVALID_MESSAGE");
+    }
+}
@@ -0,0 +1,278 @@
+//! # Shutdown - 優雅關機與會話排空
+//!
+//! 收到 SIGINT / SIGTERM 後，`drain` 依序：
+//! 1. `AppState::begin_draining`：新的 `/sign` 與 Round 1 請求返回 503，
+//!    協調者不再建立會話（已通過入口檢查的請求也一樣）
+//! 2. 等待進行中的會話完成，直到期限
+//! 3. 中止仍未完成的會話：標記為 Failed、本機簽署者丟棄 Nonce、
+//!    以 WebSocket 連線的簽署者收到 `Abort`
+//! 4. 丟棄簽署者剩餘的 Nonce（外部協調者透過 Round 1 端點取得承諾，但未完成 Round 2）
+//! 5. 將稽核日誌與 Session Store 寫入磁碟
+//!
+//! 停止接受新連線由 HTTP 伺服器負責（`main.rs` 使用 `axum_server::Handle`）。
+
+use super::AppState;
+use crate::api::SessionId;
+use crate::group::SigningGroup;
+use crate::transport::{AsyncTransport, FrostMessage, Participant};
+use std::fmt;
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::time::Instant;
+
+/// 中止會話時記錄的原因（也會送給遠端簽署者）
+pub const SHUTDOWN_REASON: &str = "server shutting down";
+
+/// 排空後等待 HTTP 連線（含 WebSocket）關閉的寬限時間
+pub const SHUTDOWN_GRACE: Duration = Duration::from_secs(5);
+
+/// 檢查進行中會話的間隔
+const DRAIN_POLL_INTERVAL: Duration = Duration::from_millis(100);
+
+// ============================================================================
+// 關機訊號
+// ============================================================================
+
+/// 等待 SIGINT（Ctrl+C）或 SIGTERM，返回訊號名稱
+pub async fn wait_for_signal() -> &'static str {
+    let interrupt = async {
+        if let Err(e) = tokio::signal::ctrl_c().await {
+            tracing::warn!(error = %e, "Failed to listen for SIGINT");
+            std::future::pending::<()>().await;
+        }
+        "SIGINT"
+    };
+
+    #[cfg(unix)]
+    let terminate = async {
+        use tokio::signal::unix::{signal, SignalKind};
+        match signal(SignalKind::terminate()) {
+            Ok(mut stream) => {
+                stream.recv().await;
+            }
+            Err(e) => {
+                tracing::warn!(error = %e, "Failed to listen for SIGTERM");
+                std::future::pending::<()>().await;
+            }
+        }
+        "SIGTERM"
+    };
+    #[cfg(not(unix))]
+    let terminate = std::future::pending::<&'static str>();
+
+    tokio::select! {
+        name = interrupt => name,
+        name = terminate => name,
+    }
+}
+
+// ============================================================================
+// 會話排空
+// ============================================================================
+
+/// 關機結果
+#[derive(Debug, Clone, Default)]
+pub struct ShutdownSummary {
+    /// 開始關機時進行中的會話數量（之後不會再有新的會話）
+    pub in_flight: usize,
+
+    /// 在期限內結束（完成或自行失敗）的會話數量
+    pub drained: usize,
+
+    /// 期限到達後被中止的會話
+    pub aborted: Vec<SessionId>,
+
+    /// 送出的 `Abort` 訊息（WebSocket 簽署者）
+    pub abort_notifications: usize,
+
+    /// 丟棄的簽署者 Nonce 數量
+    pub discarded_nonces: usize,
+
+    /// 寫入磁碟失敗的項目（不中斷關機）
+    pub flush_errors: Vec<String>,
+
+    /// 排空花費的時間
+    pub elapsed: Duration,
+}
+
+impl fmt::Display for ShutdownSummary {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} in-flight sessions: {} drained, {} aborted ({} signer notifications); \
+             {} nonces discarded; ",
+            self.in_flight,
+            self.drained,
+            self.aborted.len(),
+            self.abort_notifications,
+            self.discarded_nonces
+        )?;
+        if self.flush_errors.is_empty() {
+            write!(f, "audit log and session store flushed")?;
+        } else {
+            write!(f, "flush failed: {}", self.flush_errors.join("; "))?;
+        }
+        write!(f, " in {:.1}s", self.elapsed.as_secs_f64())
+    }
+}
+
+/// 停止接受新會話，等待進行中的會話直到 `deadline`，中止其餘會話並寫入磁碟
+pub async fn drain(state: &AppState, deadline: Duration) -> ShutdownSummary {
+    let started = Instant::now();
+    state.begin_draining();
+
+    // 不在 await 期間持有 DashMap 的鎖
+    let groups: Vec<Arc<SigningGroup>> = state
+        .groups
+        .iter()
+        .map(|group| Arc::clone(group.value()))
+        .collect();
+    let active = || -> usize {
+        groups
+            .iter()
+            .map(|g| g.coordinator().active_sessions_count())
+            .sum()
+    };
+
+    let in_flight = active();
+    tracing::info!(
+        in_flight,
+        deadline_secs = deadline.as_secs(),
+        "Draining signing sessions"
+    );
+
+    let deadline_at = started + deadline;
+    while active() > 0 && Instant::now() < deadline_at {
+        tokio::time::sleep(DRAIN_POLL_INTERVAL).await;
+    }
+
+    let mut summary = ShutdownSummary {
+        in_flight,
+        ..Default::default()
+    };
+
+    for group in &groups {
+        let aborted = group.abort_active_sessions(SHUTDOWN_REASON);
+        summary.abort_notifications += notify_remote_signers(state, group, &aborted).await;
+        summary.aborted.extend(aborted);
+        summary.discarded_nonces += group.discard_all_nonces();
+    }
+    // 協調者在計數前已停止建立會話，被中止的會話都在 in_flight 之中
+    summary.drained = in_flight.saturating_sub(summary.aborted.len());
+
+    if let Err(e) = state.audit_log.sync() {
+        summary.flush_errors.push(format!("audit log: {}", e));
+    }
+    if let Err(e) = state.session_store.flush() {
+        summary.flush_errors.push(format!("session store: {}", e));
+    }
+
+    summary.elapsed = started.elapsed();
+    summary
+}
+
+/// 通知以 WebSocket 連線的簽署者丟棄被中止會話的 Nonce，返回送出的訊息數
+async fn notify_remote_signers(
+    state: &AppState,
+    group: &SigningGroup,
+    session_ids: &[SessionId],
+) -> usize {
+    let connected = state.ws_hub.connected_signers(group.id());
+    if connected.is_empty() || session_ids.is_empty() {
+        return 0;
+    }
+
+    let mut transport = state.ws_hub.endpoint(group.id());
+    let mut sent = 0;
+    for &session_id in session_ids {
+        for &signer_id in &connected {
+            let message = FrostMessage::Abort {
+                session_id,
+                reason: SHUTDOWN_REASON.to_string(),
+            };
+            match transport
+                .send(Participant::Signer(signer_id), message)
+                .await
+            {
+                Ok(()) => sent += 1,
+                Err(e) => tracing::warn!(
+                    session_id = %session_id,
+                    signer_id,
+                    error = %e,
+                    "Failed to notify signer of shutdown abort"
+                ),
+            }
+        }
+    }
+    sent
+}
+
+// ============================================================================
+// 測試
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::audit::AuditLog;
+    use crate::group::GroupServices;
+    use crate::server::ApiError;
+    use crate::session_store::MemorySessionStore;
+
+    #[tokio::test]
+    async fn test_drain_aborts_sessions_past_deadline() {
+        let group = SigningGroup::generate_with_dealer(3, 2, &GroupServices::default()).unwrap();
+        let state = AppState::new(
+            group,
+            Arc::new(AuditLog::in_memory()),
+            Arc::new(MemorySessionStore::new()),
+        );
+        let group = state.default_group();
+
+        // 一個已發出承諾但不會完成的會話，以及一個外部協調者留下的 Nonce
+        let session_id = group
+            .coordinator()
+            .create_session(b"stuck".to_vec())
+            .unwrap();
+        let signer = group.get_signer(1).unwrap();
+        let commitment = signer.commit_signed(session_id).unwrap();
+        group
+            .coordinator()
+            .add_commitment(session_id, commitment)
+            .unwrap();
+        group
+            .get_signer(2)
+            .unwrap()
+            .commit_signed(SessionId::new())
+            .unwrap();
+
+        let summary = drain(&state, Duration::from_millis(200)).await;
+        assert_eq!(summary.in_flight, 1);
+        assert_eq!(summary.drained, 0);
+        assert_eq!(summary.aborted, vec![session_id]);
+        assert_eq!(summary.discarded_nonces, 1);
+        assert!(summary.flush_errors.is_empty());
+        assert!(!signer.has_nonce(&session_id));
+        assert_eq!(group.coordinator().active_sessions_count(), 0);
+
+        // 關機後不再接受新的會話
+        assert!(matches!(
+            state.ensure_accepting_sessions(),
+            Err(ApiError::ShuttingDown)
+        ));
+
+        // 已通過入口檢查的請求也無法在排空後建立會話
+        let late = group.coordinator().create_session(b"late".to_vec());
+        assert!(matches!(
+            late.map_err(ApiError::from),
+            Err(ApiError::ShuttingDown)
+        ));
+        assert_eq!(group.coordinator().active_sessions_count(), 0);
+
+        // 排空期間加入的群組同樣不接受會話
+        let added = state.add_group(
+            SigningGroup::generate_with_dealer(3, 2, &GroupServices::default()).unwrap(),
+        );
+        assert!(!added.coordinator().is_accepting_sessions());
+    }
+}
@@ -0,0 +1,365 @@
+//! # TLS 設定
+//!
+//! 讓 HTTP 服務可選擇直接以 HTTPS 提供，避免 Round 2 的簽章套件與
+//! 簽章分片在機器之間以明文傳輸。
+//!
+//! ## 環境變數
+//! - `TLS_CERT_PATH`: 伺服器憑證鏈（PEM）
+//! - `TLS_KEY_PATH`: 伺服器私鑰（PEM，PKCS#8 / PKCS#1 / SEC1）
+//! - `TLS_CLIENT_CA_PATH`: （可選）客戶端憑證的 CA；設定後所有連線都必須出示
+//!   由此 CA 簽發的客戶端憑證（mTLS）
+//!
+//! ## 客戶端憑證身分
+//! 以 `acceptor()` 提供服務時，每個請求的 extensions 都帶有該連線的
+//! `ClientCertificate`（憑證的 SAN DNS 名稱與 Subject CN）。API Token 設定
+//! `client_cert` 後只接受出示該名稱憑證的連線（見 `auth` 模組），
+//! 例如只有 `signer-1` 的憑證能使用簽署者 1 的 Token。
+//!
+//! 本機測試可使用 `gen-dev-certs.sh` 產生自簽憑證。
+
+use anyhow::{Context, Result};
+use axum::{middleware::AddExtension, Extension};
+use axum_server::accept::Accept;
+use axum_server::tls_rustls::{RustlsAcceptor, RustlsConfig};
+use futures::future::BoxFuture;
+use rustls::pki_types::{CertificateDer, PrivateKeyDer};
+use std::fs::File;
+use std::io::{self, BufReader};
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use tokio::io::{AsyncRead, AsyncWrite};
+use tower::Layer;
+use x509_parser::prelude::{FromDer, GeneralName, X509Certificate};
+
+/// TLS 設定
+#[derive(Debug, Clone)]
+pub struct TlsSettings {
+    /// 伺服器憑證鏈
+    pub cert_path: PathBuf,
+
+    /// 伺服器私鑰
+    pub key_path: PathBuf,
+
+    /// 客戶端憑證 CA（啟用 mTLS）
+    pub client_ca_path: Option<PathBuf>,
+}
+
+impl TlsSettings {
+    /// 從環境變數讀取 TLS 設定
+    ///
+    /// 兩個路徑都未設定時返回 `Ok(None)`（使用純 HTTP）；
+    /// 只設定其中一個視為設定錯誤。
+    pub fn from_env() -> Result<Option<Self>> {
+        let cert_path = std::env::var_os("TLS_CERT_PATH").map(PathBuf::from);
+        let key_path = std::env::var_os("TLS_KEY_PATH").map(PathBuf::from);
+        let client_ca_path = std::env::var_os("TLS_CLIENT_CA_PATH").map(PathBuf::from);
+
+        match (cert_path, key_path) {
+            (Some(cert_path), Some(key_path)) => Ok(Some(Self {
+                cert_path,
+                key_path,
+                client_ca_path,
+            })),
+            (None, None) => {
+                if client_ca_path.is_some() {
+                    anyhow::bail!("TLS_CLIENT_CA_PATH requires TLS_CERT_PATH and TLS_KEY_PATH");
+                }
+                Ok(None)
+            }
+            _ => anyhow::bail!("TLS_CERT_PATH and TLS_KEY_PATH must be set together"),
+        }
+    }
+
+    /// 建立 axum-server 使用的 rustls 設定
+    pub fn rustls_config(&self) -> Result<RustlsConfig> {
+        let provider = Arc::new(rustls::crypto::ring::default_provider());
+
+        let builder = rustls::ServerConfig::builder_with_provider(Arc::clone(&provider))
+            .with_safe_default_protocol_versions()
+            .context("Failed to select TLS protocol versions")?;
+
+        let builder = match &self.client_ca_path {
+            Some(ca_path) => {
+                let mut roots = rustls::RootCertStore::empty();
+                for cert in load_certs(ca_path)? {
+                    roots
+                        .add(cert)
+                        .context("Failed to add client CA certificate")?;
+                }
+
+                let verifier = rustls::server::WebPkiClientVerifier::builder_with_provider(
+                    Arc::new(roots),
+                    provider,
+                )
+                .build()
+                .context("Failed to build client certificate verifier")?;
+
+                builder.with_client_cert_verifier(verifier)
+            }
+            None => builder.with_no_client_auth(),
+        };
+
+        let mut config = builder
+            .with_single_cert(load_certs(&self.cert_path)?, load_private_key(&self.key_path)?)
+            .context("Invalid TLS certificate or private key")?;
+        config.alpn_protocols = vec![b"h2".to_vec(), b"http/1.1".to_vec()];
+
+        Ok(RustlsConfig::from_config(Arc::new(config)))
+    }
+
+    /// 建立會把客戶端憑證身分放入 request extensions 的 TLS acceptor
+    ///
+    /// 搭配 `axum_server::bind(addr).acceptor(...)` 使用。
+    pub fn acceptor(&self) -> Result<ClientCertAcceptor> {
+        Ok(ClientCertAcceptor {
+            inner: RustlsAcceptor::new(self.rustls_config()?),
+        })
+    }
+}
+
+// ============================================================================
+// 客戶端憑證身分
+// ============================================================================
+
+/// 連線出示的客戶端憑證身分
+///
+/// 未出示憑證（未啟用 mTLS）時 `names` 為空。
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ClientCertificate {
+    /// SAN 中的 DNS 名稱與 Subject CN
+    pub names: Vec<String>,
+}
+
+impl ClientCertificate {
+    /// 從終端實體憑證（DER）讀取身分名稱
+    fn from_der(der: &[u8]) -> Self {
+        let Ok((_, cert)) = X509Certificate::from_der(der) else {
+            return Self::default();
+        };
+
+        let mut names: Vec<String> = cert
+            .subject_alternative_name()
+            .ok()
+            .flatten()
+            .map(|san| {
+                san.value
+                    .general_names
+                    .iter()
+                    .filter_map(|name| match name {
+                        GeneralName::DNSName(dns) => Some(dns.to_string()),
+                        _ => None,
+                    })
+                    .collect()
+            })
+            .unwrap_or_default();
+        names.extend(
+            cert.subject()
+                .iter_common_name()
+                .filter_map(|cn| cn.as_str().ok())
+                .map(str::to_string),
+        );
+
+        Self { names }
+    }
+
+    /// 憑證是否屬於指定名稱（SAN 或 CN 完全相符）
+    pub fn matches(&self, name: &str) -> bool {
+        self.names.iter().any(|n| n == name)
+    }
+}
+
+/// 完成 TLS 握手後，把客戶端憑證身分加入該連線所有請求的 extensions
+#[derive(Debug, Clone)]
+pub struct ClientCertAcceptor {
+    inner: RustlsAcceptor,
+}
+
+impl<I, S> Accept<I, S> for ClientCertAcceptor
+where
+    I: AsyncRead + AsyncWrite + Unpin + Send + 'static,
+    S: Send + 'static,
+{
+    type Stream = <RustlsAcceptor as Accept<I, S>>::Stream;
+    type Service = AddExtension<S, ClientCertificate>;
+    type Future = BoxFuture<'static, io::Result<(Self::Stream, Self::Service)>>;
+
+    fn accept(&self, stream: I, service: S) -> Self::Future {
+        let acceptor = self.inner.clone();
+
+        Box::pin(async move {
+            let (stream, service) = acceptor.accept(stream, service).await?;
+            let certificate = stream
+                .get_ref()
+                .1
+                .peer_certificates()
+                .and_then(|chain| chain.first())
+                .map(|cert| ClientCertificate::from_der(cert))
+                .unwrap_or_default();
+
+            Ok((stream, Extension(certificate).layer(service)))
+        })
+    }
+}
+
+/// 讀取 PEM 格式的憑證鏈
+fn load_certs(path: &Path) -> Result<Vec<CertificateDer<'static>>> {
+    let file = File::open(path)
+        .with_context(|| format!("Failed to open certificate file: {}", path.display()))?;
+
+    let certs = rustls_pemfile::certs(&mut BufReader::new(file))
+        .collect::<Result<Vec<_>, _>>()
+        .with_context(|| format!("Failed to parse certificates: {}", path.display()))?;
+
+    if certs.is_empty() {
+        anyhow::bail!("No certificates found in {}", path.display());
+    }
+
+    Ok(certs)
+}
+
+/// 讀取 PEM 格式的私鑰
+fn load_private_key(path: &Path) -> Result<PrivateKeyDer<'static>> {
+    let file = File::open(path)
+        .with_context(|| format!("Failed to open private key file: {}", path.display()))?;
+
+    rustls_pemfile::private_key(&mut BufReader::new(file))
+        .with_context(|| format!("Failed to parse private key: {}", path.display()))?
+        .ok_or_else(|| anyhow::anyhow!("No private key found in {}", path.display()))
+}
+
+// ============================================================================
+// 測試
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::audit::AuditLog;
+    use crate::group::{GroupServices, SigningGroup};
+    use crate::server::auth::{ApiToken, AuthConfig, Role};
+    use crate::server::{AppState, ServerBuilder};
+    use crate::session_store::MemorySessionStore;
+    use rcgen::{
+        BasicConstraints, CertificateParams, DnType, ExtendedKeyUsagePurpose, IsCa, KeyPair,
+    };
+    use std::collections::HashMap;
+
+    /// 由測試 CA 簽發的憑證，返回 (憑證 PEM, 私鑰 PEM)
+    fn issue(
+        ca: &rcgen::Certificate,
+        ca_key: &KeyPair,
+        name: &str,
+        usage: ExtendedKeyUsagePurpose,
+    ) -> (String, String) {
+        let key = KeyPair::generate().unwrap();
+        let mut params = CertificateParams::new(vec![name.to_string()]).unwrap();
+        params.distinguished_name.push(DnType::CommonName, name);
+        params.extended_key_usages = vec![usage];
+        let cert = params.signed_by(&key, ca, ca_key).unwrap();
+        (cert.pem(), key.serialize_pem())
+    }
+
+    #[tokio::test]
+    async fn test_mtls_handshake_binds_client_certificate_to_token() {
+        // 自簽 CA、伺服器憑證與兩張客戶端憑證
+        let ca_key = KeyPair::generate().unwrap();
+        let mut ca_params = CertificateParams::new(Vec::<String>::new()).unwrap();
+        ca_params.is_ca = IsCa::Ca(BasicConstraints::Unconstrained);
+        ca_params
+            .distinguished_name
+            .push(DnType::CommonName, "FROST-T Test CA");
+        let ca = ca_params.self_signed(&ca_key).unwrap();
+
+        let (server_cert, server_key) =
+            issue(&ca, &ca_key, "localhost", ExtendedKeyUsagePurpose::ServerAuth);
+        let (coordinator_cert, coordinator_key) = issue(
+            &ca,
+            &ca_key,
+            "frost-coordinator",
+            ExtendedKeyUsagePurpose::ClientAuth,
+        );
+        let (signer_cert, signer_key) =
+            issue(&ca, &ca_key, "signer-2", ExtendedKeyUsagePurpose::ClientAuth);
+
+        let dir = std::env::temp_dir().join(format!("frost-tls-{}", uuid::Uuid::new_v4()));
+        std::fs::create_dir_all(&dir).unwrap();
+        std::fs::write(dir.join("ca.pem"), ca.pem()).unwrap();
+        std::fs::write(dir.join("server.pem"), &server_cert).unwrap();
+        std::fs::write(dir.join("server.key"), &server_key).unwrap();
+        let settings = TlsSettings {
+            cert_path: dir.join("server.pem"),
+            key_path: dir.join("server.key"),
+            client_ca_path: Some(dir.join("ca.pem")),
+        };
+
+        // Coordinator Token 只能搭配 frost-coordinator 的客戶端憑證使用
+        let group = SigningGroup::generate_with_dealer(3, 2, &GroupServices::default()).unwrap();
+        let state = AppState::new(
+            group,
+            Arc::new(AuditLog::in_memory()),
+            Arc::new(MemorySessionStore::new()),
+        );
+        let app = ServerBuilder::new(state)
+            .with_auth(AuthConfig {
+                disabled: false,
+                tokens: vec![ApiToken {
+                    name: "vault-coordinator".to_string(),
+                    token: "coordinator-token".to_string(),
+                    role: Role::Coordinator,
+                    signer_ids: Vec::new(),
+                    group_signers: HashMap::new(),
+                    client_cert: Some("frost-coordinator".to_string()),
+                }],
+            })
+            .build();
+
+        let handle = axum_server::Handle::new();
+        let server = axum_server::bind("127.0.0.1:0".parse().unwrap())
+            .acceptor(settings.acceptor().unwrap())
+            .handle(handle.clone())
+            .serve(app.into_make_service());
+        tokio::spawn(async move { server.await.unwrap() });
+        let addr = handle.listening().await.unwrap();
+
+        let client = |identity: Option<(&str, &str)>| {
+            let mut builder = reqwest::Client::builder()
+                .use_rustls_tls()
+                .resolve("localhost", addr)
+                .add_root_certificate(reqwest::Certificate::from_pem(ca.pem().as_bytes()).unwrap());
+            if let Some((cert, key)) = identity {
+                let pem = format!("{}{}", cert, key);
+                builder = builder.identity(reqwest::Identity::from_pem(pem.as_bytes()).unwrap());
+            }
+            builder.build().unwrap()
+        };
+        let url = |path: &str| format!("https://localhost:{}{}", addr.port(), path);
+
+        // 未出示客戶端憑證：握手失敗
+        assert!(client(None).get(url("/health")).send().await.is_err());
+
+        // 正確的客戶端憑證與 Token
+        let coordinator = client(Some((&coordinator_cert, &coordinator_key)));
+        let response = coordinator
+            .get(url("/sessions"))
+            .bearer_auth("coordinator-token")
+            .send()
+            .await
+            .unwrap();
+        assert_eq!(response.status(), reqwest::StatusCode::OK);
+
+        // 其他簽署者的憑證不能使用 Coordinator Token
+        let signer = client(Some((&signer_cert, &signer_key)));
+        let response = signer
+            .get(url("/sessions"))
+            .bearer_auth("coordinator-token")
+            .send()
+            .await
+            .unwrap();
+        assert_eq!(response.status(), reqwest::StatusCode::UNAUTHORIZED);
+        let response = signer.get(url("/health")).send().await.unwrap();
+        assert_eq!(response.status(), reqwest::StatusCode::OK);
+
+        handle.shutdown();
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+}
@@ -0,0 +1,89 @@
+//! # Trace - HTTP 請求的 trace context
+//!
+//! 每個請求在 `http_request` span 中處理。請求帶有 W3C `traceparent` 標頭時，
+//! span 接在該 trace 之下；否則開始新的 trace。處理器內建立的簽章會話
+//! （`Coordinator::orchestrate_over_transport`）以此 span 為父節點，
+//! 並把 trace 傳給簽署者（見 `crate::telemetry`）。Round 1 / Round 2 端點的
+//! `signer_request` span 也接在請求的 trace 之下，外部協調者帶上 `traceparent`
+//! 即可把簽署者的日誌串進自己的會話。
+
+use crate::telemetry::{self, TraceContext, TRACEPARENT_HEADER};
+use axum::{extract::Request, middleware::Next, response::Response};
+use tracing::Instrument;
+
+/// 讀取 `traceparent` 並在對應的 trace 中處理請求
+pub async fn propagate_trace(request: Request, next: Next) -> Response {
+    let parent = request
+        .headers()
+        .get(TRACEPARENT_HEADER)
+        .and_then(|value| value.to_str().ok())
+        .and_then(TraceContext::parse);
+
+    let span = tracing::info_span!(
+        "http_request",
+        method = %request.method(),
+        path = %request.uri().path(),
+        trace_id = tracing::field::Empty,
+    );
+    let context = telemetry::attach(&span, parent.as_ref());
+
+    telemetry::in_context(context, next.run(request))
+        .instrument(span)
+        .await
+}
+
+// ============================================================================
+// 測試
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use crate::api::{Round1Request, SessionId};
+    use crate::audit::AuditLog;
+    use crate::group::{GroupServices, SigningGroup};
+    use crate::server::{AppState, ServerBuilder};
+    use crate::session_store::MemorySessionStore;
+    use crate::telemetry::testing::SpanRecorder;
+    use crate::telemetry::{TraceContext, TRACEPARENT_HEADER};
+    use std::sync::Arc;
+    use tracing_subscriber::layer::SubscriberExt;
+
+    #[tokio::test]
+    async fn test_round_endpoints_join_the_request_trace() {
+        let recorder = SpanRecorder::default();
+        let _guard = tracing::subscriber::set_default(
+            tracing_subscriber::registry().with(recorder.clone()),
+        );
+
+        let group = SigningGroup::generate_with_dealer(3, 2, &GroupServices::default()).unwrap();
+        let state = AppState::new(
+            group,
+            Arc::new(AuditLog::in_memory()),
+            Arc::new(MemorySessionStore::new()),
+        );
+        let app = ServerBuilder::new(state).build();
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("http://{}/signer/2/round1", listener.local_addr().unwrap());
+        tokio::spawn(async move { axum::serve(listener, app).await.unwrap() });
+
+        // 外部協調者以 traceparent 傳入自己的 trace
+        let parent = TraceContext::new_root();
+        let response = reqwest::Client::new()
+            .post(&url)
+            .header(TRACEPARENT_HEADER, parent.to_traceparent())
+            .json(&Round1Request {
+                session_id: SessionId::new(),
+                message: Vec::new(),
+                payload: None,
+            })
+            .send()
+            .await
+            .unwrap();
+        assert!(response.status().is_success());
+
+        assert_eq!(
+            recorder.trace_ids("signer_request"),
+            vec![parent.trace_id_hex()]
+        );
+    }
+}
@@ -0,0 +1,457 @@
+//! # Session Store - 協調者會話的持久化
+//!
+//! 協調者的 Session 狀態（訊息、已收集的承諾）原本只存在記憶體中，
+//! 協調者在簽章途中當機會遺失所有承諾，並讓簽署者持有永遠不會被使用的 Nonce。
+//!
+//! 此模組提供可插拔的 `SessionStore` trait：
+//! - `MemorySessionStore`: 記憶體實作（預設，行為與原本相同）
+//! - `SledSessionStore`: 嵌入式資料庫實作（sled），每次狀態變更都寫入磁碟
+//!
+//! ## 恢復流程
+//! 服務重新啟動時呼叫 `recover_sessions`：
+//! - 已終止（aggregated / failed / expired）的 Session → 保留供查詢
+//! - 所屬群組仍存在的未完成 Session → 標記為 failed，並通知群組內所有簽署者
+//!   丟棄該 Session 的 Nonce：程序內的簽署者直接清除，以 WebSocket 連線的簽署者
+//!   透過 `WebSocketHub::queue_abort` 在重新登記後收到 `Abort`
+//! - 所屬群組已不存在 → 從 Store 移除
+//!
+//! 簽署者的 Nonce 只存在記憶體中（寫入磁碟會讓 Nonce 有被重複使用的風險），
+//! 重新啟動後任何未完成的 Session 都無法再產生簽章分片，因此一律中止。
+//! 群組 ID 與金鑰目錄一起保存（見 `FileStore::load_or_generate_group`），
+//! 重新啟動後 Session 仍能對應到原本的群組並留下中止紀錄。
+
+use crate::api::{GroupId, SessionId, SessionStatus};
+use crate::audit::{AuditEvent, AuditLog};
+use crate::coordinator::SessionState;
+use crate::group::SigningGroup;
+use crate::transport::websocket::WebSocketHub;
+use dashmap::DashMap;
+use std::path::Path;
+use std::sync::Arc;
+use thiserror::Error;
+
+// ============================================================================
+// 錯誤定義
+// ============================================================================
+
+#[derive(Debug, Error)]
+pub enum SessionStoreError {
+    #[error("Session store backend error: {0}")]
+    Backend(String),
+
+    #[error("Session serialization error: {0}")]
+    Serialization(#[from] serde_json::Error),
+}
+
+impl From<sled::Error> for SessionStoreError {
+    fn from(e: sled::Error) -> Self {
+        SessionStoreError::Backend(e.to_string())
+    }
+}
+
+// ============================================================================
+// SessionStore trait
+// ============================================================================
+
+/// Session 的所屬群組與目前階段（不含訊息與承諾）
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SessionSummary {
+    pub session_id: SessionId,
+    pub group_id: Option<GroupId>,
+    pub status: SessionStatus,
+}
+
+impl From<&SessionState> for SessionSummary {
+    fn from(state: &SessionState) -> Self {
+        Self {
+            session_id: state.session_id,
+            group_id: state.group_id,
+            status: state.status,
+        }
+    }
+}
+
+/// 協調者 Session 狀態的儲存後端
+///
+/// 所有方法都是同步的：每次狀態變更都在返回前寫入後端，
+/// 因此協調者當機時最多遺失正在進行中的那一次變更。
+pub trait SessionStore: Send + Sync {
+    /// 新增或覆寫一個 Session
+    fn insert(&self, state: &SessionState) -> Result<(), SessionStoreError>;
+
+    /// 讀取一個 Session
+    fn get(&self, session_id: &SessionId) -> Result<Option<SessionState>, SessionStoreError>;
+
+    /// 原子地修改一個 Session，返回修改後的狀態（Session 不存在時返回 `None`）
+    ///
+    /// `f` 可能被呼叫多次（例如 compare-and-swap 重試），每次都會拿到最新的狀態。
+    fn update(
+        &self,
+        session_id: &SessionId,
+        f: &mut dyn FnMut(&mut SessionState),
+    ) -> Result<Option<SessionState>, SessionStoreError>;
+
+    /// 移除一個 Session，返回被移除的狀態
+    fn remove(&self, session_id: &SessionId) -> Result<Option<SessionState>, SessionStoreError>;
+
+    /// 列出所有 Session
+    fn list(&self) -> Result<Vec<SessionState>, SessionStoreError>;
+
+    /// 列出所有 Session 的所屬群組與目前階段
+    ///
+    /// 用於健康檢查與計數；預設以 `list` 實作，後端應盡量避免反序列化完整狀態。
+    fn statuses(&self) -> Result<Vec<SessionSummary>, SessionStoreError> {
+        Ok(self.list()?.iter().map(SessionSummary::from).collect())
+    }
+
+    /// 確保所有變更都已寫入後端
+    fn flush(&self) -> Result<(), SessionStoreError> {
+        Ok(())
+    }
+
+    /// 後端名稱（用於日誌與健康檢查）
+    fn backend_name(&self) -> &'static str;
+}
+
+// ============================================================================
+// 記憶體實作
+// ============================================================================
+
+/// 記憶體中的 Session Store（重新啟動後遺失）
+#[derive(Default)]
+pub struct MemorySessionStore {
+    sessions: DashMap<SessionId, SessionState>,
+}
+
+impl MemorySessionStore {
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+impl SessionStore for MemorySessionStore {
+    fn insert(&self, state: &SessionState) -> Result<(), SessionStoreError> {
+        self.sessions.insert(state.session_id, state.clone());
+        Ok(())
+    }
+
+    fn get(&self, session_id: &SessionId) -> Result<Option<SessionState>, SessionStoreError> {
+        Ok(self.sessions.get(session_id).map(|s| s.clone()))
+    }
+
+    fn update(
+        &self,
+        session_id: &SessionId,
+        f: &mut dyn FnMut(&mut SessionState),
+    ) -> Result<Option<SessionState>, SessionStoreError> {
+        Ok(self.sessions.get_mut(session_id).map(|mut state| {
+            f(&mut state);
+            state.clone()
+        }))
+    }
+
+    fn remove(&self, session_id: &SessionId) -> Result<Option<SessionState>, SessionStoreError> {
+        Ok(self.sessions.remove(session_id).map(|(_, state)| state))
+    }
+
+    fn list(&self) -> Result<Vec<SessionState>, SessionStoreError> {
+        Ok(self.sessions.iter().map(|entry| entry.value().clone()).collect())
+    }
+
+    fn statuses(&self) -> Result<Vec<SessionSummary>, SessionStoreError> {
+        Ok(self
+            .sessions
+            .iter()
+            .map(|entry| SessionSummary::from(entry.value()))
+            .collect())
+    }
+
+    fn backend_name(&self) -> &'static str {
+        "memory"
+    }
+}
+
+// ============================================================================
+// sled 實作
+// ============================================================================
+
+/// 以 sled 嵌入式資料庫持久化的 Session Store
+///
+/// Key 為 Session UUID 的 16 bytes，Value 為 `SessionState` 的 JSON。
+/// 各 Session 的階段另外保存在記憶體索引中，`statuses` 不需要讀取資料庫。
+pub struct SledSessionStore {
+    db: sled::Db,
+    tree: sled::Tree,
+    index: DashMap<SessionId, SessionSummary>,
+}
+
+impl SledSessionStore {
+    /// 開啟（或建立）資料庫目錄
+    pub fn open(path: impl AsRef<Path>) -> Result<Self, SessionStoreError> {
+        let db = sled::open(path)?;
+        let tree = db.open_tree("sessions")?;
+        let store = Self {
+            db,
+            tree,
+            index: DashMap::new(),
+        };
+        for state in store.list()? {
+            store.index.insert(state.session_id, SessionSummary::from(&state));
+        }
+        Ok(store)
+    }
+
+    fn key(session_id: &SessionId) -> [u8; 16] {
+        *session_id.0.as_bytes()
+    }
+}
+
+impl SessionStore for SledSessionStore {
+    fn insert(&self, state: &SessionState) -> Result<(), SessionStoreError> {
+        let value = serde_json::to_vec(state)?;
+        self.tree.insert(Self::key(&state.session_id), value)?;
+        self.tree.flush()?;
+        self.index.insert(state.session_id, SessionSummary::from(state));
+        Ok(())
+    }
+
+    fn get(&self, session_id: &SessionId) -> Result<Option<SessionState>, SessionStoreError> {
+        match self.tree.get(Self::key(session_id))? {
+            Some(bytes) => Ok(Some(serde_json::from_slice(&bytes)?)),
+            None => Ok(None),
+        }
+    }
+
+    fn update(
+        &self,
+        session_id: &SessionId,
+        f: &mut dyn FnMut(&mut SessionState),
+    ) -> Result<Option<SessionState>, SessionStoreError> {
+        let mut decode_error = None;
+
+        let updated = self
+            .tree
+            .update_and_fetch(Self::key(session_id), |old| {
+                let bytes = old?;
+                let encoded = serde_json::from_slice::<SessionState>(bytes)
+                    .and_then(|mut state| {
+                        f(&mut state);
+                        serde_json::to_vec(&state)
+                    });
+                match encoded {
+                    Ok(encoded) => Some(encoded),
+                    Err(e) => {
+                        // 無法解碼時保留原值，並在外層回報錯誤
+                        decode_error = Some(e);
+                        Some(bytes.to_vec())
+                    }
+                }
+            })?;
+
+        if let Some(e) = decode_error {
+            return Err(e.into());
+        }
+        self.tree.flush()?;
+
+        let Some(bytes) = updated else {
+            return Ok(None);
+        };
+        let state: SessionState = serde_json::from_slice(&bytes)?;
+        self.index.insert(state.session_id, SessionSummary::from(&state));
+        Ok(Some(state))
+    }
+
+    fn remove(&self, session_id: &SessionId) -> Result<Option<SessionState>, SessionStoreError> {
+        let removed = self.tree.remove(Self::key(session_id))?;
+        self.tree.flush()?;
+        self.index.remove(session_id);
+        match removed {
+            Some(bytes) => Ok(Some(serde_json::from_slice(&bytes)?)),
+            None => Ok(None),
+        }
+    }
+
+    fn list(&self) -> Result<Vec<SessionState>, SessionStoreError> {
+        self.tree
+            .iter()
+            .values()
+            .map(|value| Ok(serde_json::from_slice(&value?)?))
+            .collect()
+    }
+
+    fn statuses(&self) -> Result<Vec<SessionSummary>, SessionStoreError> {
+        Ok(self.index.iter().map(|entry| *entry.value()).collect())
+    }
+
+    fn flush(&self) -> Result<(), SessionStoreError> {
+        self.db.flush()?;
+        Ok(())
+    }
+
+    fn backend_name(&self) -> &'static str {
+        "sled"
+    }
+}
+
+// ============================================================================
+// 啟動恢復
+// ============================================================================
+
+/// 恢復結果
+#[derive(Debug, Clone, Default)]
+pub struct RecoveryReport {
+    /// 中止的 Session
+    pub aborted: Vec<SessionId>,
+}
+
+/// 啟動時恢復 Store 中未完成的 Session
+///
+/// # 參數
+/// - `store`: 共用的 Session Store
+/// - `groups`: 目前存在的簽章群組
+/// - `audit_log`: （可選）記錄中止事件
+/// - `ws_hub`: （可選）為以 WebSocket 連線的簽署者保留 `Abort`，於其登記後送出
+pub fn recover_sessions(
+    store: &dyn SessionStore,
+    groups: &DashMap<GroupId, Arc<SigningGroup>>,
+    audit_log: Option<&AuditLog>,
+    ws_hub: Option<&WebSocketHub>,
+) -> Result<RecoveryReport, SessionStoreError> {
+    let mut report = RecoveryReport::default();
+
+    for state in store.list()? {
+        if state.status.is_terminal() {
+            continue;
+        }
+
+        let session_id = state.session_id;
+        let group = state
+            .group_id
+            .and_then(|group_id| groups.get(&group_id).map(|g| Arc::clone(&g)));
+
+        match &group {
+            Some(group) => {
+                // 標記為 failed（由協調者寫入稽核），並通知所有簽署者丟棄 Nonce
+                let reason = "coordinator restarted and signer nonces were lost";
+                group.coordinator().abort_session(session_id, &[], reason);
+                group.abort_session_nonces(&session_id);
+
+                if let Some(ws_hub) = ws_hub {
+                    for signer_id in 1..=group.max_signers() {
+                        if let Err(e) =
+                            ws_hub.queue_abort(group.id(), signer_id, session_id, reason)
+                        {
+                            tracing::warn!(
+                                session_id = %session_id,
+                                signer_id,
+                                error = %e,
+                                "Failed to queue abort for remote signer"
+                            );
+                        }
+                    }
+                }
+            }
+            None => {
+                let reason = "coordinator restarted and the signing group no longer exists";
+                store.remove(&session_id)?;
+
+                if let Some(audit_log) = audit_log {
+                    audit_log.record(
+                        "recovery",
+                        AuditEvent::SessionAborted {
+                            session_id,
+                            reason: reason.to_string(),
+                        },
+                    );
+                }
+                tracing::warn!(
+                    session_id = %session_id,
+                    reason,
+                    "Aborted incomplete signing session"
+                );
+            }
+        }
+
+        report.aborted.push(session_id);
+    }
+
+    Ok(report)
+}
+
+// ============================================================================
+// 測試
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::api::CommitmentData;
+
+    #[test]
+    fn test_sled_store_survives_reopen() {
+        let dir = std::env::temp_dir().join(format!("frost-sessions-{}", SessionId::new()));
+        let session_id = SessionId::new();
+
+        {
+            let store = SledSessionStore::open(&dir).unwrap();
+            store
+                .insert(&SessionState::new(session_id, b"hello".to_vec()))
+                .unwrap();
+            store
+                .update(&session_id, &mut |state| {
+                    state.commitments.push(CommitmentData {
+                        signer_id: 1,
+                        commitment: "00".to_string(),
+                        envelope_signature: None,
+                    })
+                })
+                .unwrap();
+        }
+
+        // sled 的背景 flush 執行緒可能在 drop 後短暫持有檔案鎖
+        let store = (0..50)
+            .find_map(|_| {
+                SledSessionStore::open(&dir).ok().or_else(|| {
+                    std::thread::sleep(std::time::Duration::from_millis(20));
+                    None
+                })
+            })
+            .expect("sled lock was not released");
+        let state = store.get(&session_id).unwrap().unwrap();
+        assert_eq!(state.message, b"hello");
+        assert_eq!(state.commitments.len(), 1);
+        assert_eq!(store.statuses().unwrap(), vec![SessionSummary::from(&state)]);
+
+        // 群組已不存在的 Session 會被中止並移除
+        let report = recover_sessions(&store, &DashMap::new(), None, None).unwrap();
+        assert_eq!(report.aborted, vec![session_id]);
+        assert!(store.list().unwrap().is_empty());
+        assert!(store.statuses().unwrap().is_empty());
+
+        drop(store);
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn test_recovery_aborts_sessions_without_commitments() {
+        use crate::group::GroupServices;
+
+        let store: Arc<dyn SessionStore> = Arc::new(MemorySessionStore::new());
+        let services = GroupServices {
+            session_store: Some(Arc::clone(&store)),
+            ..GroupServices::default()
+        };
+        let group = SigningGroup::generate_with_dealer(3, 2, &services).unwrap();
+        let session_id = group.coordinator().create_session(b"restart".to_vec()).unwrap();
+
+        // 重新啟動後簽署者沒有任何 Nonce：即使還沒有承諾也無法繼續
+        let groups = DashMap::new();
+        groups.insert(group.id(), Arc::new(group));
+        let report = recover_sessions(&*store, &groups, None, None).unwrap();
+        assert_eq!(report.aborted, vec![session_id]);
+        assert_eq!(
+            store.get(&session_id).unwrap().unwrap().status,
+            SessionStatus::Failed
+        );
+    }
+}
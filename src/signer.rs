@@ -9,13 +9,22 @@
 //! 1. **Nonce 一次性使用**: 每個 SessionId 的 Nonce 在 sign() 後會被立即銷毀
 //! 2. **並發安全**: 使用 DashMap 允許多個並發的簽章會話
 //! 3. **無狀態洩漏**: SecretNonces 永遠不會離開此模組
-
-use crate::api::{SessionId, SigningPackageData};
+//! 4. **訊息認證**: 每個簽署者擁有長期身分金鑰，Round 1 / Round 2 的輸出都附上
+//!    信封簽章；設定名冊後，簽章套件中每個承諾的來源都會先被驗證
+
+use crate::api::{
+    signer_id_from_identifier, CommitmentData, SessionId, SignatureShareData, SigningPackageData,
+};
+use crate::audit::{AuditEvent, AuditLog};
+use crate::envelope::{EnvelopeKind, IdentityKey, IdentityPublicKey, Roster};
+use crate::telemetry::{self, TraceContext};
+use crate::transport::{AsyncTransport, FrostMessage, Participant, TransportError};
 use dashmap::DashMap;
 use frost_secp256k1 as frost;
 use rand::thread_rng;
 use std::sync::Arc;
 use thiserror::Error;
+use tracing::{Instrument, Span};
 
 // ============================================================================
 // 錯誤定義
@@ -40,6 +49,15 @@ pub enum SignerError {
 
     #[error("FROST library error: {0}")]
     FrostError(String),
+
+    #[error("Unauthenticated message: {0}")]
+    UnauthenticatedMessage(String),
+
+    #[error("Invalid signing request: {0}")]
+    InvalidMessage(String),
+
+    #[error("Transport error: {0}")]
+    Transport(#[from] TransportError),
 }
 
 // ============================================================================
@@ -63,7 +81,7 @@ pub struct Signer {
     signer_id: frost::Identifier,
 
     /// 此簽署者的金鑰分片（包含私鑰分片）
-    secret_share: frost::keys::SecretShare,
+    key_package: frost::keys::KeyPackage,
 
     /// Nonce 儲存: SessionId -> SecretNonces
     /// 使用 DashMap 提供並發安全且高效能的存取
@@ -73,6 +91,15 @@ pub struct Signer {
     /// - 對於讀多寫少的場景更高效
     /// - API 更簡潔，不需要手動 lock()/unlock()
     nonce_store: Arc<DashMap<SessionId, frost::round1::SigningNonces>>,
+
+    /// 長期身分金鑰（用於簽署訊息信封，與金鑰分片無關）
+    identity: IdentityKey,
+
+    /// 群組名冊（設定後會驗證簽章套件中每個承諾的信封簽章）
+    roster: Option<Roster>,
+
+    /// 稽核日誌（actor 名稱, 日誌）
+    audit_log: Option<(String, Arc<AuditLog>)>,
 }
 
 impl Signer {
@@ -83,22 +110,84 @@ impl Signer {
     /// 建立新的簽署者實例
     ///
     /// # 參數
-    /// - `key_package`: 此簽署者的金鑰分片（由 Setup 階段分發）
-    pub fn new(secret_share: frost::keys::SecretShare) -> Self {
-        let signer_id = *secret_share.identifier();
+    /// - `secret_share`: 此簽署者的金鑰分片（由 Setup 階段分發）
+    ///
+    /// # 返回
+    /// - `Err(SignerError::FrostError)`: 分片無法通過 VSS 承諾驗證
+    pub fn new(secret_share: frost::keys::SecretShare) -> Result<Self, SignerError> {
+        let key_package = frost::keys::KeyPackage::try_from(secret_share)
+            .map_err(|e| SignerError::FrostError(format!("{:?}", e)))?;
+        Ok(Self::from_key_package(key_package))
+    }
+
+    /// 由已驗證的 KeyPackage 建立簽署者（例如從 `frost-cli keygen` 的金鑰檔載入）
+    pub fn from_key_package(key_package: frost::keys::KeyPackage) -> Self {
+        let signer_id = *key_package.identifier();
 
         Self {
             signer_id,
-            secret_share,
+            key_package,
             nonce_store: Arc::new(DashMap::new()),
+            identity: IdentityKey::generate(),
+            roster: None,
+            audit_log: None,
+        }
+    }
+
+    /// 設定稽核日誌
+    ///
+    /// # 參數
+    /// - `audit_log`: 共用的稽核日誌
+    /// - `actor`: 寫入紀錄時使用的角色名稱
+    pub fn with_audit_log(mut self, audit_log: Arc<AuditLog>, actor: impl Into<String>) -> Self {
+        self.audit_log = Some((actor.into(), audit_log));
+        self
+    }
+
+    /// 寫入稽核事件（未設定稽核日誌時不做任何事）
+    fn audit(&self, event: AuditEvent) {
+        if let Some((actor, audit_log)) = &self.audit_log {
+            audit_log.record(actor, event);
         }
     }
 
+    /// 使用既有的身分金鑰（例如從金鑰檔載入），取代建立時生成的金鑰
+    pub fn with_identity_key(mut self, identity: IdentityKey) -> Self {
+        self.identity = identity;
+        self
+    }
+
+    /// 設定群組名冊（啟用承諾來源驗證）
+    pub fn with_roster(mut self, roster: Roster) -> Self {
+        self.roster = Some(roster);
+        self
+    }
+
     /// 獲取簽署者 ID
     pub fn id(&self) -> frost::Identifier {
         self.signer_id
     }
 
+    /// 獲取 API 使用的 u16 簽署者 ID
+    pub fn numeric_id(&self) -> u16 {
+        signer_id_from_identifier(&self.signer_id)
+    }
+
+    /// 獲取身分公鑰（登記到名冊中）
+    pub fn identity_public_key(&self) -> IdentityPublicKey {
+        self.identity.public_key()
+    }
+
+    /// 以身分金鑰簽署其他類型的信封（例如 P2P 提議），返回 hex 編碼的簽章
+    pub fn sign_envelope(
+        &self,
+        kind: EnvelopeKind,
+        session_id: SessionId,
+        payload_hex: &str,
+    ) -> String {
+        self.identity.sign(kind, session_id, self.numeric_id(), payload_hex)
+    }
+
     // ========================================================================
     // Round 1: Commitment 生成
     // ========================================================================
@@ -143,7 +232,7 @@ impl Signer {
         // - SigningNonces: 秘密部分（必須保密）
         // - SigningCommitments: 公開承諾（可以傳輸）
         let (nonces, commitments) = frost::round1::commit(
-            self.secret_share.signing_share(),
+            self.key_package.signing_share(),
             &mut rng,
         );
 
@@ -158,6 +247,11 @@ impl Signer {
         // 儲存秘密 nonce（將在 Round 2 使用）
         self.nonce_store.insert(session_id, nonces);
 
+        self.audit(AuditEvent::CommitmentIssued {
+            session_id,
+            signer_id: self.numeric_id(),
+        });
+
         tracing::debug!(
             signer_id = ?self.signer_id,
             session_id = %session_id,
@@ -168,6 +262,30 @@ impl Signer {
         Ok(commitments)
     }
 
+    /// Round 1（附信封簽章）：生成承諾並以身分金鑰簽署
+    pub fn commit_signed(&self, session_id: SessionId) -> Result<CommitmentData, SignerError> {
+        let commitments = self.commit(session_id)?;
+        let commitment_hex = hex::encode(
+            commitments
+                .serialize()
+                .map_err(|e| SignerError::CommitmentGenerationFailed(format!("{:?}", e)))?,
+        );
+
+        let signer_id = self.numeric_id();
+        let envelope_signature = self.identity.sign(
+            EnvelopeKind::Round1Commitment,
+            session_id,
+            signer_id,
+            &commitment_hex,
+        );
+
+        Ok(CommitmentData {
+            signer_id,
+            commitment: commitment_hex,
+            envelope_signature: Some(envelope_signature),
+        })
+    }
+
     // ========================================================================
     // Round 2: 簽章分片生成
     // ========================================================================
@@ -194,6 +312,7 @@ impl Signer {
     /// - `Ok(SignatureShare)`: 此簽署者的簽章分片
     /// - `Err(SignerError::SessionNotFound)`: Session ID 無效或 nonce 已被使用
     /// - `Err(SignerError::SignatureGenerationFailed)`: FROST 簽章生成失敗
+    /// - `Err(SignerError::UnauthenticatedMessage)`: 簽章套件中有承諾無法通過名冊驗證
     ///
     /// # 錯誤情況
     /// - Session ID 不存在 → 可能原因：
@@ -204,6 +323,31 @@ impl Signer {
         &self,
         session_id: SessionId,
         signing_package_data: &SigningPackageData,
+    ) -> Result<frost::round2::SignatureShare, SignerError> {
+        let result = self.generate_signature_share(session_id, signing_package_data);
+
+        // 記錄稽核事件：成功釋出分片或拒絕請求
+        match &result {
+            Ok(_) => self.audit(AuditEvent::ShareReleased {
+                session_id,
+                signer_id: self.numeric_id(),
+                message_kind: signing_package_data.message_kind(),
+            }),
+            Err(e) => self.audit(AuditEvent::RequestRejected {
+                session_id: Some(session_id),
+                signer_id: Some(self.numeric_id()),
+                reason: e.to_string(),
+            }),
+        }
+
+        result
+    }
+
+    /// Round 2 的實際實作（由 `sign` 包裝並寫入稽核日誌）
+    fn generate_signature_share(
+        &self,
+        session_id: SessionId,
+        signing_package_data: &SigningPackageData,
     ) -> Result<frost::round2::SignatureShare, SignerError> {
         tracing::info!(
             signer_id = ?self.signer_id,
@@ -223,18 +367,47 @@ impl Signer {
             "Secret nonce retrieved and removed from storage"
         );
 
-        // 步驟 2: 反序列化簽章套件
+        // 步驟 2: 驗證每個承諾的來源（若已設定名冊）
+        // 中繼者即使被攻破，也無法以其他簽署者的 ID 注入偽造的承諾
+        if let Some(roster) = &self.roster {
+            for commitment in &signing_package_data.commitments {
+                roster
+                    .verify(
+                        EnvelopeKind::Round1Commitment,
+                        session_id,
+                        commitment.signer_id,
+                        &commitment.commitment,
+                        commitment.envelope_signature.as_deref(),
+                    )
+                    .map_err(|e| SignerError::UnauthenticatedMessage(e.to_string()))?;
+            }
+        }
+
+        // 步驟 3: 自己從結構化請求計算摘要（domain separation）
+        // 協調者無法讓簽署者簽署與 payload 不符、或沒有 payload 的 bytes
+        let payload = signing_package_data.payload.as_ref().ok_or_else(|| {
+            SignerError::InvalidMessage(
+                "signing package has no payload to derive the digest from".to_string(),
+            )
+        })?;
+        let digest = payload
+            .signing_digest()
+            .map_err(|e| SignerError::InvalidMessage(e.to_string()))?;
+        if digest != signing_package_data.message {
+            return Err(SignerError::InvalidMessage(format!(
+                "signing package message does not match the {} payload digest",
+                payload.kind()
+            )));
+        }
+
+        // 步驟 4: 反序列化簽章套件
         let signing_package = self
             .deserialize_signing_package(signing_package_data)
             .map_err(|e| SignerError::InvalidSigningPackage(e.to_string()))?;
 
-        // 步驟 3: 生成簽章分片
+        // 步驟 5: 生成簽章分片
         // 使用：金鑰分片 + 秘密 nonce + 簽章套件
-        // Create a KeyPackage from the secret_share components
-        let key_package = frost::keys::KeyPackage::try_from(self.secret_share.clone())
-            .map_err(|e| SignerError::SignatureGenerationFailed(format!("KeyPackage conversion failed: {:?}", e)))?;
-
-        let signature_share = frost::round2::sign(&signing_package, &nonces, &key_package)
+        let signature_share = frost::round2::sign(&signing_package, &nonces, &self.key_package)
             .map_err(|e| SignerError::SignatureGenerationFailed(format!("{:?}", e)))?;
 
         tracing::info!(
@@ -248,6 +421,30 @@ impl Signer {
         Ok(signature_share)
     }
 
+    /// Round 2（附信封簽章）：生成簽章分片並以身分金鑰簽署
+    pub fn sign_signed(
+        &self,
+        session_id: SessionId,
+        signing_package_data: &SigningPackageData,
+    ) -> Result<SignatureShareData, SignerError> {
+        let signature_share = self.sign(session_id, signing_package_data)?;
+        let share_hex = hex::encode(signature_share.serialize());
+
+        let signer_id = self.numeric_id();
+        let envelope_signature = self.identity.sign(
+            EnvelopeKind::Round2SignatureShare,
+            session_id,
+            signer_id,
+            &share_hex,
+        );
+
+        Ok(SignatureShareData {
+            signer_id,
+            signature_share: share_hex,
+            envelope_signature: Some(envelope_signature),
+        })
+    }
+
     // ========================================================================
     // 輔助方法 - 序列化/反序列化
     // ========================================================================
@@ -284,6 +481,135 @@ impl Signer {
         ))
     }
 
+    // ========================================================================
+    // 在 Transport 上服務協調者
+    // ========================================================================
+
+    /// 在 transport 上回應協調者的請求，直到 transport 關閉
+    ///
+    /// - `CommitmentRequest` → 回覆 `Round1Commitment`
+    /// - `SigningPackage` → 回覆 `Round2SignatureShare`
+    /// - `FinalSignature` → 記錄結果
+    /// - `Abort` → 丟棄該 Session 的 Nonce
+    ///
+    /// 單一請求處理失敗（例如 Nonce 已被使用）時記錄警告並回覆 `Abort`，不會中止服務。
+    /// 只接受來自協調者的訊息。請求附帶的 trace context 會成為 `signer_request`
+    /// span 的父節點。
+    pub async fn serve<T: AsyncTransport>(&self, transport: &mut T) -> Result<(), SignerError> {
+        loop {
+            let envelope = match transport.recv().await {
+                Ok(envelope) => envelope,
+                Err(TransportError::Closed) => return Ok(()),
+                Err(e) => return Err(e.into()),
+            };
+
+            if envelope.from != Participant::Coordinator {
+                tracing::warn!(
+                    signer_id = self.numeric_id(),
+                    from = %envelope.from,
+                    "Ignoring message from non-coordinator participant"
+                );
+                continue;
+            }
+
+            let session_id = envelope.message.session_id();
+            let span = self.request_span(session_id, envelope.message.trace_context());
+
+            let Some(reply) = span.in_scope(|| self.handle_request(envelope.message)) else {
+                continue;
+            };
+
+            match reply {
+                Ok(reply) => {
+                    transport
+                        .send(envelope.from, reply)
+                        .instrument(span)
+                        .await?
+                }
+                Err(e) => {
+                    span.in_scope(|| {
+                        tracing::warn!(
+                            signer_id = self.numeric_id(),
+                            session_id = %session_id,
+                            error = %e,
+                            "Failed to handle coordinator request"
+                        )
+                    });
+                    // 告知協調者此簽署者無法完成，不必等到逾時
+                    let abort = FrostMessage::Abort {
+                        session_id,
+                        reason: e.to_string(),
+                    };
+                    transport
+                        .send(envelope.from, abort)
+                        .instrument(span)
+                        .await?
+                }
+            }
+        }
+    }
+
+    /// 處理一則協調者請求的 `signer_request` span
+    ///
+    /// `parent` 為協調者的 trace context（訊息附帶或 HTTP `traceparent`），
+    /// 有的話這次處理記錄在同一個 trace 之下。`serve` 與 HTTP 的 Round 1 / Round 2
+    /// 端點都使用此 span。
+    pub fn request_span(&self, session_id: SessionId, parent: Option<&TraceContext>) -> Span {
+        let span = tracing::info_span!(
+            "signer_request",
+            signer_id = self.numeric_id(),
+            session_id = %session_id,
+            trace_id = tracing::field::Empty,
+        );
+        if let Some(parent) = parent {
+            telemetry::attach(&span, Some(parent));
+        }
+        span
+    }
+
+    /// 處理一則協調者訊息，需要回覆時返回回覆內容
+    fn handle_request(&self, message: FrostMessage) -> Option<Result<FrostMessage, SignerError>> {
+        let reply = match message {
+            FrostMessage::CommitmentRequest { session_id, .. } => self
+                .commit_signed(session_id)
+                .map(|commitment| FrostMessage::Round1Commitment {
+                    session_id,
+                    commitment,
+                }),
+            FrostMessage::SigningPackage {
+                session_id,
+                package,
+                ..
+            } => self
+                .sign_signed(session_id, &package)
+                .map(|share| FrostMessage::Round2SignatureShare { session_id, share }),
+            FrostMessage::FinalSignature {
+                session_id,
+                signature,
+            } => {
+                tracing::info!(
+                    signer_id = self.numeric_id(),
+                    session_id = %session_id,
+                    signature = %signature,
+                    "Received final signature"
+                );
+                return None;
+            }
+            FrostMessage::Abort { session_id, reason } => {
+                self.clear_session(&session_id);
+                tracing::warn!(
+                    signer_id = self.numeric_id(),
+                    session_id = %session_id,
+                    reason = %reason,
+                    "Coordinator aborted session, discarded nonce"
+                );
+                return None;
+            }
+            _ => return None,
+        };
+        Some(reply)
+    }
+
     // ========================================================================
     // 管理與除錯方法
     // ========================================================================
@@ -293,6 +619,11 @@ impl Signer {
         self.nonce_store.len()
     }
 
+    /// 是否仍持有指定 Session 的 Nonce（用於協調者恢復時判斷能否繼續）
+    pub fn has_nonce(&self, session_id: &SessionId) -> bool {
+        self.nonce_store.contains_key(session_id)
+    }
+
     /// 清除指定的 Session（用於錯誤恢復或超時處理）
     pub fn clear_session(&self, session_id: &SessionId) -> bool {
         self.nonce_store.remove(session_id).is_some()
@@ -0,0 +1,494 @@
+//! # Telemetry - 結構化日誌與分散式追蹤
+//!
+//! ## 日誌
+//!
+//! `init_tracing` 安裝全域 subscriber：
+//! - `RUST_LOG`：過濾條件（`EnvFilter` 語法，例如 `info,frost_threshold_signature=debug`）
+//! - `FROST_LOG_FORMAT`：`text`（預設）或 `json`（每行一個 JSON 物件，包含所在的 span 欄位）
+//!
+//! ## Trace Context
+//!
+//! 協調者為每個簽章會話建立一個 `signing_session` span，並把它的 W3C `traceparent`
+//! （`TraceContext`）放進送給簽署者的 `CommitmentRequest` 與 `SigningPackage`。
+//! 簽署者在同一個 trace 之下建立 `signer_request` span，因此不同行程的日誌可以用
+//! `trace_id` 串起同一個簽章會話。HTTP 請求的 `traceparent` 標頭會成為會話的父節點。
+//!
+//! ## OpenTelemetry（`otel` feature）
+//!
+//! 設定 `OTEL_EXPORTER_OTLP_ENDPOINT`（例如本機 collector 的 `http://localhost:4317`）後，
+//! span 以 OTLP/gRPC 匯出；`OTEL_SERVICE_NAME` 可覆寫服務名稱。此時 `TraceContext`
+//! 直接取自 OpenTelemetry 的 span，collector 中看到的 trace 與日誌中的 `trace_id` 一致。
+
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use std::fmt;
+use std::future::Future;
+use tracing::Span;
+use tracing_subscriber::{
+    fmt as log_fmt, layer::SubscriberExt, util::SubscriberInitExt, EnvFilter,
+};
+
+/// HTTP 請求攜帶父 trace 的標頭
+pub const TRACEPARENT_HEADER: &str = "traceparent";
+
+// ============================================================================
+// Trace Context
+// ============================================================================
+
+/// W3C Trace Context（`traceparent` 的內容）
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TraceContext {
+    /// 整個分散式 trace 的 ID
+    pub trace_id: [u8; 16],
+
+    /// 發送端 span 的 ID（接收端 span 的父節點）
+    pub span_id: [u8; 8],
+
+    /// 是否取樣
+    pub sampled: bool,
+}
+
+impl TraceContext {
+    /// 建立新的 trace（沒有父節點時使用）
+    pub fn new_root() -> Self {
+        Self {
+            trace_id: random_id(),
+            span_id: random_id(),
+            sampled: true,
+        }
+    }
+
+    /// 同一個 trace 中的下一個 span
+    pub fn child(&self) -> Self {
+        Self {
+            span_id: random_id(),
+            ..*self
+        }
+    }
+
+    /// 解析 `traceparent`（`00-{trace_id}-{span_id}-{flags}`）
+    ///
+    /// 格式錯誤或 ID 全為 0 時返回 `None`（依規範視為沒有父節點）。
+    pub fn parse(traceparent: &str) -> Option<Self> {
+        let mut parts = traceparent.trim().split('-');
+        let version = parts.next()?;
+        let trace_id = parts.next()?;
+        let span_id = parts.next()?;
+        let flags = parts.next()?;
+
+        // 版本 00 恰好四段；未來版本可能附加欄位
+        if version.len() != 2 || version == "ff" || (version == "00" && parts.next().is_some()) {
+            return None;
+        }
+
+        let mut context = Self {
+            trace_id: [0; 16],
+            span_id: [0; 8],
+            sampled: false,
+        };
+        hex::decode_to_slice(trace_id, &mut context.trace_id).ok()?;
+        hex::decode_to_slice(span_id, &mut context.span_id).ok()?;
+        let mut flags_byte = [0u8; 1];
+        hex::decode_to_slice(flags, &mut flags_byte).ok()?;
+        context.sampled = flags_byte[0] & 0x01 != 0;
+
+        if context.trace_id == [0; 16] || context.span_id == [0; 8] {
+            return None;
+        }
+        Some(context)
+    }
+
+    /// 格式化為 `traceparent`
+    pub fn to_traceparent(&self) -> String {
+        format!(
+            "00-{}-{}-{:02x}",
+            self.trace_id_hex(),
+            hex::encode(self.span_id),
+            u8::from(self.sampled)
+        )
+    }
+
+    /// Trace ID（32 個 hex 字元，日誌中的 `trace_id` 欄位）
+    pub fn trace_id_hex(&self) -> String {
+        hex::encode(self.trace_id)
+    }
+
+    /// 目前非同步任務所屬的 trace（由 `in_context` 設定）
+    pub fn current() -> Option<Self> {
+        CURRENT.try_with(|context| *context).ok()
+    }
+}
+
+impl fmt::Display for TraceContext {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.to_traceparent())
+    }
+}
+
+// JSON 中以 `traceparent` 字串表示
+impl Serialize for TraceContext {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
+impl<'de> Deserialize<'de> for TraceContext {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let traceparent = String::deserialize(deserializer)?;
+        Self::parse(&traceparent)
+            .ok_or_else(|| serde::de::Error::custom(format!("invalid traceparent: {traceparent}")))
+    }
+}
+
+fn random_id<const N: usize>() -> [u8; N] {
+    loop {
+        let id: [u8; N] = std::array::from_fn(|_| rand::random());
+        if id != [0; N] {
+            return id;
+        }
+    }
+}
+
+tokio::task_local! {
+    static CURRENT: TraceContext;
+}
+
+/// 在指定的 trace 中執行 future（`TraceContext::current` 會返回它）
+pub async fn in_context<F: Future>(context: TraceContext, future: F) -> F::Output {
+    CURRENT.scope(context, future).await
+}
+
+/// 把 span 接到父 trace 之下，返回該 span 自己的 `TraceContext`
+///
+/// span 必須宣告 `trace_id` 欄位（`tracing::field::Empty`），此函數會填入。
+/// 沒有父節點時開始新的 trace。
+pub fn attach(span: &Span, parent: Option<&TraceContext>) -> TraceContext {
+    let fallback = || parent.map_or_else(TraceContext::new_root, TraceContext::child);
+    #[cfg(feature = "otel")]
+    let context = otel::attach(span, parent).unwrap_or_else(fallback);
+    #[cfg(not(feature = "otel"))]
+    let context = fallback();
+
+    span.record("trace_id", context.trace_id_hex().as_str());
+    context
+}
+
+// ============================================================================
+// Subscriber 初始化
+// ============================================================================
+
+/// 日誌輸出格式（`FROST_LOG_FORMAT`）
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LogFormat {
+    Text,
+    Json,
+}
+
+impl LogFormat {
+    /// 從 `FROST_LOG_FORMAT` 讀取（未設定時為 `Text`）
+    pub fn from_env() -> anyhow::Result<Self> {
+        match std::env::var("FROST_LOG_FORMAT").as_deref() {
+            Err(_) | Ok("text") => Ok(Self::Text),
+            Ok("json") => Ok(Self::Json),
+            Ok(other) => anyhow::bail!("FROST_LOG_FORMAT must be 'text' or 'json', got '{other}'"),
+        }
+    }
+}
+
+/// 持有到程式結束：drop 時送出尚未匯出的 span
+#[must_use = "dropping the guard flushes and stops the trace exporter"]
+pub struct TelemetryGuard {
+    _private: (),
+}
+
+impl Drop for TelemetryGuard {
+    fn drop(&mut self) {
+        #[cfg(feature = "otel")]
+        opentelemetry::global::shutdown_tracer_provider();
+    }
+}
+
+/// 安裝全域的 tracing subscriber
+///
+/// # 參數
+/// - `service_name`: OpenTelemetry 的服務名稱（`OTEL_SERVICE_NAME` 優先）
+/// - `default_filter`: 未設定 `RUST_LOG` 時的過濾條件
+pub fn init_tracing(service_name: &str, default_filter: &str) -> anyhow::Result<TelemetryGuard> {
+    let filter =
+        EnvFilter::try_from_default_env().unwrap_or_else(|_| EnvFilter::new(default_filter));
+
+    let format = LogFormat::from_env()?;
+    let text = (format == LogFormat::Text).then(|| log_fmt::layer().with_target(false));
+    let json = (format == LogFormat::Json).then(|| {
+        log_fmt::layer()
+            .json()
+            .with_current_span(true)
+            .with_span_list(true)
+    });
+
+    let registry = tracing_subscriber::registry()
+        .with(filter)
+        .with(text)
+        .with(json);
+
+    #[cfg(feature = "otel")]
+    let registry = registry.with(otel::layer(service_name)?);
+    #[cfg(not(feature = "otel"))]
+    let _ = service_name;
+
+    registry.try_init()?;
+    Ok(TelemetryGuard { _private: () })
+}
+
+// ============================================================================
+// OpenTelemetry 匯出
+// ============================================================================
+
+#[cfg(feature = "otel")]
+mod otel {
+    use super::TraceContext;
+    use opentelemetry::trace::{
+        SpanContext, SpanId, TraceContextExt, TraceFlags, TraceId, TraceState,
+    };
+    use opentelemetry::KeyValue;
+    use opentelemetry_otlp::WithExportConfig;
+    use opentelemetry_sdk::{trace::Tracer, Resource};
+    use tracing::Span;
+    use tracing_opentelemetry::{OpenTelemetryLayer, OpenTelemetrySpanExt};
+    use tracing_subscriber::registry::LookupSpan;
+
+    /// 設定 `OTEL_EXPORTER_OTLP_ENDPOINT` 時建立 OTLP 匯出層
+    pub(super) fn layer<S>(
+        service_name: &str,
+    ) -> anyhow::Result<Option<OpenTelemetryLayer<S, Tracer>>>
+    where
+        S: tracing::Subscriber + for<'span> LookupSpan<'span>,
+    {
+        let Ok(endpoint) = std::env::var("OTEL_EXPORTER_OTLP_ENDPOINT") else {
+            return Ok(None);
+        };
+        let service_name =
+            std::env::var("OTEL_SERVICE_NAME").unwrap_or_else(|_| service_name.to_string());
+
+        let tracer = opentelemetry_otlp::new_pipeline()
+            .tracing()
+            .with_exporter(
+                opentelemetry_otlp::new_exporter()
+                    .tonic()
+                    .with_endpoint(endpoint),
+            )
+            .with_trace_config(
+                opentelemetry_sdk::trace::config()
+                    .with_resource(Resource::new([KeyValue::new("service.name", service_name)])),
+            )
+            .install_batch(opentelemetry_sdk::runtime::Tokio)?;
+
+        Ok(Some(tracing_opentelemetry::layer().with_tracer(tracer)))
+    }
+
+    /// 設定 span 的遠端父節點，並讀回 OpenTelemetry 指派的 ID
+    ///
+    /// 未安裝匯出層時 span 沒有有效的 context，返回 `None`。
+    pub(super) fn attach(span: &Span, parent: Option<&TraceContext>) -> Option<TraceContext> {
+        if let Some(parent) = parent {
+            let flags = if parent.sampled {
+                TraceFlags::SAMPLED
+            } else {
+                TraceFlags::default()
+            };
+            let remote = SpanContext::new(
+                TraceId::from_bytes(parent.trace_id),
+                SpanId::from_bytes(parent.span_id),
+                flags,
+                true,
+                TraceState::default(),
+            );
+            span.set_parent(opentelemetry::Context::new().with_remote_span_context(remote));
+        }
+
+        let context = span.context();
+        let span_context = context.span().span_context().clone();
+        span_context.is_valid().then(|| TraceContext {
+            trace_id: span_context.trace_id().to_bytes(),
+            span_id: span_context.span_id().to_bytes(),
+            sampled: span_context.is_sampled(),
+        })
+    }
+}
+
+// ============================================================================
+// 測試
+// ============================================================================
+
+#[cfg(test)]
+pub(crate) mod testing {
+    //! 測試用的 span 記錄器：記錄每個 span 的名稱與 `trace_id`
+
+    use std::fmt;
+    use std::sync::{Arc, Mutex};
+    use tracing::field::{Field, Visit};
+    use tracing::span::{Attributes, Id, Record};
+    use tracing::Subscriber;
+    use tracing_subscriber::layer::{Context, Layer};
+    use tracing_subscriber::registry::LookupSpan;
+
+    /// span 名稱與 `trace_id`（尚未填入時為 `None`）
+    type RecordedSpan = (&'static str, Option<String>);
+
+    /// 記錄建立過的 span（依建立順序）
+    #[derive(Clone, Default)]
+    pub(crate) struct SpanRecorder {
+        spans: Arc<Mutex<Vec<RecordedSpan>>>,
+    }
+
+    /// span 在記錄中的位置（放在 span extensions 中）
+    struct RecordedIndex(usize);
+
+    impl SpanRecorder {
+        /// 指定名稱、已填入 `trace_id` 的 span 的 trace ID
+        pub(crate) fn trace_ids(&self, name: &str) -> Vec<String> {
+            self.spans
+                .lock()
+                .unwrap()
+                .iter()
+                .filter(|(span_name, _)| *span_name == name)
+                .filter_map(|(_, trace_id)| trace_id.clone())
+                .collect()
+        }
+    }
+
+    impl<S: Subscriber + for<'a> LookupSpan<'a>> Layer<S> for SpanRecorder {
+        fn on_new_span(&self, attrs: &Attributes<'_>, id: &Id, ctx: Context<'_, S>) {
+            let mut spans = self.spans.lock().unwrap();
+            spans.push((attrs.metadata().name(), None));
+            if let Some(span) = ctx.span(id) {
+                span.extensions_mut().insert(RecordedIndex(spans.len() - 1));
+            }
+        }
+
+        fn on_record(&self, id: &Id, values: &Record<'_>, ctx: Context<'_, S>) {
+            let Some(span) = ctx.span(id) else {
+                return;
+            };
+            let Some(&RecordedIndex(index)) = span.extensions().get::<RecordedIndex>() else {
+                return;
+            };
+            let mut visitor = TraceIdVisitor(None);
+            values.record(&mut visitor);
+            if let Some(trace_id) = visitor.0 {
+                self.spans.lock().unwrap()[index].1 = Some(trace_id);
+            }
+        }
+    }
+
+    struct TraceIdVisitor(Option<String>);
+
+    impl Visit for TraceIdVisitor {
+        fn record_str(&mut self, field: &Field, value: &str) {
+            if field.name() == "trace_id" {
+                self.0 = Some(value.to_string());
+            }
+        }
+
+        fn record_debug(&mut self, _field: &Field, _value: &dyn fmt::Debug) {}
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::testing::SpanRecorder;
+    use super::*;
+    use crate::coordinator::DEFAULT_ROUND_TIMEOUT;
+    use crate::group::{GroupServices, SigningGroup};
+    use crate::transport::{
+        AsyncTransport, ChannelNetwork, FrostMessage, LoRaConfig, Participant,
+        SimulatedLoRaTransport,
+    };
+    use crate::SignPayload;
+
+    #[test]
+    fn test_traceparent_round_trip_and_child_span() {
+        let traceparent = "00-4bf92f3577b34da6a3ce929d0e0e4736-00f067aa0ba902b7-01";
+        let parent = TraceContext::parse(traceparent).unwrap();
+        assert!(parent.sampled);
+        assert_eq!(parent.to_traceparent(), traceparent);
+
+        // 子 span 屬於同一個 trace，但有自己的 span ID
+        let child = parent.child();
+        assert_eq!(child.trace_id, parent.trace_id);
+        assert_ne!(child.span_id, parent.span_id);
+
+        // JSON 中為 traceparent 字串
+        let json = serde_json::to_string(&child).unwrap();
+        assert_eq!(json, format!("\"{}\"", child.to_traceparent()));
+        assert_eq!(serde_json::from_str::<TraceContext>(&json).unwrap(), child);
+
+        // 格式錯誤或 ID 全為 0 時視為沒有父節點
+        for invalid in [
+            "",
+            "00-4bf92f3577b34da6a3ce929d0e0e4736-00f067aa0ba902b7",
+            "00-00000000000000000000000000000000-00f067aa0ba902b7-01",
+            "00-4bf92f3577b34da6a3ce929d0e0e4736-0000000000000000-01",
+            "ff-4bf92f3577b34da6a3ce929d0e0e4736-00f067aa0ba902b7-01",
+            "00-4bf92f3577b34da6a3ce929d0e0e473-00f067aa0ba902b7-01",
+        ] {
+            assert!(TraceContext::parse(invalid).is_none(), "{invalid}");
+        }
+    }
+
+    #[tokio::test]
+    async fn test_signer_spans_inherit_the_coordinator_trace() {
+        let recorder = SpanRecorder::default();
+        let _guard = tracing::subscriber::set_default(
+            tracing_subscriber::registry().with(recorder.clone()),
+        );
+
+        // 呼叫者的 trace（例如 HTTP traceparent）→ signing_session → signer_request
+        let group = SigningGroup::generate_with_dealer(3, 2, &GroupServices::default()).unwrap();
+        let signers = vec![group.get_signer(1).unwrap(), group.get_signer(3).unwrap()];
+        let payload = SignPayload::Raw {
+            message: hex::encode("traced"),
+        };
+        let parent = TraceContext::new_root();
+        in_context(
+            parent,
+            group
+                .coordinator()
+                .orchestrate_signing(&signers, &payload, DEFAULT_ROUND_TIMEOUT),
+        )
+            .await
+            .unwrap();
+
+        let trace_id = parent.trace_id_hex();
+        assert_eq!(recorder.trace_ids("signing_session"), vec![trace_id.clone()]);
+        // 兩個簽署者各處理承諾請求與簽章套件
+        let signer_traces = recorder.trace_ids("signer_request");
+        assert_eq!(signer_traces.len(), 4);
+        assert!(signer_traces.iter().all(|id| *id == trace_id));
+
+        // LoRa 鏈路預設不傳遞 trace context，需明確啟用
+        for enabled in [false, true] {
+            let link = SimulatedLoRaTransport::new_with_config(
+                LoRaConfig {
+                    packet_loss_rate: 0.0,
+                    ..LoRaConfig::deterministic(7)
+                }
+                .with_trace_context(enabled),
+            );
+            let network = ChannelNetwork::with_lora(link);
+            let mut coordinator = network.endpoint(Participant::Coordinator);
+            let mut signer = network.endpoint(Participant::Signer(1));
+            coordinator
+                .send(
+                    Participant::Signer(1),
+                    FrostMessage::CommitmentRequest {
+                        session_id: crate::api::SessionId::new(),
+                        trace: Some(parent),
+                    },
+                )
+                .await
+                .unwrap();
+            let received = signer.recv().await.unwrap();
+            assert_eq!(received.message.trace_context().is_some(), enabled);
+        }
+    }
+}
@@ -0,0 +1,191 @@
+//! # Channel Network - 行程內的 AsyncTransport
+//!
+//! 以 Tokio channel 連接同一個行程內的協調者與簽署者，每個參與者擁有一個
+//! `ChannelTransport` 端點。用於 Demo 與測試。
+//!
+//! 訊息一律以 `codec` 的二進位格式編碼後傳遞（接收端會驗證版本與校驗碼）。
+//! 以 `ChannelNetwork::with_lora` 建立時，編碼後的 bytes 會經過共用的
+//! `SimulatedLoRaTransport` 鏈路（延遲、掉包、分片）；未能送達的訊息會被丟棄，
+//! 與真實無線電一樣，發送端不會收到錯誤。除非鏈路設定了
+//! `LoRaConfig::trace_context`，否則訊息中的 trace context 在編碼前會被移除。
+
+use super::{
+    codec, AsyncTransport, FrostMessage, MessageMetadata, Participant, SimulatedLoRaTransport,
+    TransportEnvelope, TransportError, TransportStats,
+};
+use dashmap::DashMap;
+use std::sync::Arc;
+use tokio::sync::{mpsc, Mutex};
+
+// ============================================================================
+// ChannelNetwork - 共用的網路
+// ============================================================================
+
+/// 行程內的訊息網路
+///
+/// Clone 後共用同一組端點與鏈路。
+#[derive(Clone, Default)]
+pub struct ChannelNetwork {
+    /// 每個參與者的收件匣
+    inboxes: Arc<DashMap<Participant, mpsc::UnboundedSender<TransportEnvelope>>>,
+
+    /// （可選）所有訊息共用的模擬 LoRa 鏈路（半雙工：一次只傳一則）
+    link: Option<Arc<Mutex<SimulatedLoRaTransport>>>,
+
+    /// 全網路的累計統計
+    stats: Arc<std::sync::Mutex<TransportStats>>,
+
+    /// 編碼前移除訊息中的 trace context
+    strip_trace: bool,
+}
+
+impl ChannelNetwork {
+    /// 建立直接連接的網路（無延遲、不掉包）
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// 建立經過模擬 LoRa 鏈路的網路
+    pub fn with_lora(lora: SimulatedLoRaTransport) -> Self {
+        Self {
+            strip_trace: !lora.config().trace_context,
+            link: Some(Arc::new(Mutex::new(lora))),
+            ..Self::default()
+        }
+    }
+
+    /// 為參與者建立端點（同一參與者重新連上時取代舊端點）
+    pub fn endpoint(&self, participant: Participant) -> ChannelTransport {
+        let (sender, inbox) = mpsc::unbounded_channel();
+        self.inboxes.insert(participant, sender);
+
+        ChannelTransport {
+            local: participant,
+            inbox,
+            network: self.clone(),
+            stats: TransportStats::default(),
+        }
+    }
+
+    /// 讓參與者離線：其端點在處理完剩餘訊息後 `recv` 會返回 `Closed`
+    pub fn disconnect(&self, participant: Participant) {
+        self.inboxes.remove(&participant);
+    }
+
+    /// 關閉整個網路（所有端點的 `recv` 最終都會返回 `Closed`）
+    pub fn shutdown(&self) {
+        self.inboxes.clear();
+    }
+
+    /// 全網路所有端點的累計統計（含相對於 JSON 的大小比較）
+    pub fn stats(&self) -> TransportStats {
+        self.stats.lock().unwrap().clone()
+    }
+
+    /// 模擬 LoRa 鏈路的累計統計（直接連接的網路返回 `None`）
+    pub async fn link_stats(&self) -> Option<TransportStats> {
+        match &self.link {
+            Some(link) => Some(link.lock().await.stats().clone()),
+            None => None,
+        }
+    }
+
+    /// 將已編碼的訊息送到接收者的收件匣
+    async fn deliver(
+        &self,
+        metadata: MessageMetadata,
+        wire: Vec<u8>,
+    ) -> Result<(), TransportError> {
+        // 經過鏈路時，接收端拿到的是重組後的 bytes
+        let wire = match &self.link {
+            Some(link) => match link.lock().await.transmit(&metadata, &wire).await {
+                Some(reassembled) => reassembled,
+                None => {
+                    tracing::warn!(
+                        from = %metadata.from,
+                        to = %metadata.to,
+                        message_type = %metadata.message_type,
+                        "Message lost on the simulated LoRa link"
+                    );
+                    return Ok(());
+                }
+            },
+            None => wire,
+        };
+
+        let envelope = codec::decode(&wire)?;
+        let to = envelope.to;
+
+        let sender = self
+            .inboxes
+            .get(&to)
+            .map(|sender| sender.clone())
+            .ok_or(TransportError::UnknownRecipient(to))?;
+        sender
+            .send(envelope)
+            .map_err(|_| TransportError::UnknownRecipient(to))
+    }
+}
+
+// ============================================================================
+// ChannelTransport - 單一參與者的端點
+// ============================================================================
+
+/// `ChannelNetwork` 上的一個端點
+pub struct ChannelTransport {
+    local: Participant,
+    inbox: mpsc::UnboundedReceiver<TransportEnvelope>,
+    network: ChannelNetwork,
+    stats: TransportStats,
+}
+
+impl AsyncTransport for ChannelTransport {
+    fn local(&self) -> Participant {
+        self.local
+    }
+
+    async fn send(
+        &mut self,
+        to: Participant,
+        mut message: FrostMessage,
+    ) -> Result<(), TransportError> {
+        if self.network.strip_trace {
+            message.clear_trace_context();
+        }
+        let metadata = MessageMetadata {
+            from: self.local.to_string(),
+            to: to.to_string(),
+            message_type: message.message_type(),
+            timestamp: Some(chrono::Utc::now()),
+        };
+        let envelope = TransportEnvelope {
+            from: self.local,
+            to,
+            message,
+        };
+        let wire = codec::encode(&envelope)?;
+
+        // 以 JSON 信封的大小作為比較基準
+        let baseline =
+            serde_json::to_vec(&envelope).map_err(|e| TransportError::Encoding(e.to_string()))?;
+
+        // 更新統計
+        self.stats
+            .record(metadata.message_type, wire.len(), baseline.len());
+        self.network
+            .stats
+            .lock()
+            .unwrap()
+            .record(metadata.message_type, wire.len(), baseline.len());
+
+        self.network.deliver(metadata, wire).await
+    }
+
+    async fn recv(&mut self) -> Result<TransportEnvelope, TransportError> {
+        self.inbox.recv().await.ok_or(TransportError::Closed)
+    }
+
+    fn get_stats(&self) -> Option<TransportStats> {
+        Some(self.stats.clone())
+    }
+}
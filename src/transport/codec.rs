@@ -0,0 +1,653 @@
+//! # Wire Codec - FrostMessage 的精簡二進位編碼
+//!
+//! JSON 信封會把 FROST 的序列化結果再做一次 hex 編碼，位元組數約為原本的兩倍，
+//! 在 64 bytes 分片的 LoRa 鏈路上代價很高。此模組定義版本化的二進位格式：
+//!
+//! ```text
+//! offset  長度  欄位
+//! 0       1     版本（目前為 1）
+//! 1       1     訊息類型
+//! 2       16    Session ID（UUID bytes）
+//! 18      3     發送者（種類 u8 + ID u16 BE）
+//! 21      3     接收者（同上）
+//! 24      ...   TLV 欄位：tag u8 + 長度（LEB128 varint）+ 值
+//! n-4     4     校驗碼：SHA-256(前面所有 bytes) 的前 4 bytes
+//! ```
+//!
+//! 承諾、分片、簽章等欄位以原始 bytes 傳送（不做 hex 編碼）。
+//! 簽章套件中的每個承諾以 `SIGNER_ID` 開頭，之後的 `COMMITMENT` /
+//! `ENVELOPE_SIGNATURE` 屬於最近一個 `SIGNER_ID`。Session 提議以重複的 `SIGNER_ID`
+//! 列出參與者，`PAYLOAD` 為 JSON。
+//!
+//! 承諾請求與簽章套件可附帶 `TRACE_CONTEXT`（trace ID 16 bytes + span ID 8 bytes +
+//! flags 1 byte），讓簽署者的日誌接上協調者的 trace（見 `telemetry` 模組）。
+
+use super::{FrostMessage, Participant, TransportEnvelope};
+use crate::api::{CommitmentData, SessionId, SignatureShareData, SigningPackageData};
+use crate::telemetry::TraceContext;
+use sha2::{Digest, Sha256};
+use thiserror::Error;
+use uuid::Uuid;
+
+/// 目前的編碼版本
+pub const WIRE_VERSION: u8 = 1;
+
+/// 固定標頭長度（版本 + 類型 + Session ID + 發送者 + 接收者）
+const HEADER_LEN: usize = 1 + 1 + 16 + 3 + 3;
+
+/// 校驗碼長度
+const CHECKSUM_LEN: usize = 4;
+
+// 訊息類型
+const TYPE_COMMITMENT_REQUEST: u8 = 0x01;
+const TYPE_ROUND1_COMMITMENT: u8 = 0x02;
+const TYPE_SIGNING_PACKAGE: u8 = 0x03;
+const TYPE_ROUND2_SIGNATURE_SHARE: u8 = 0x04;
+const TYPE_FINAL_SIGNATURE: u8 = 0x05;
+const TYPE_ABORT: u8 = 0x06;
+const TYPE_SESSION_PROPOSAL: u8 = 0x07;
+
+// 參與者種類
+const PARTICIPANT_COORDINATOR: u8 = 0x00;
+const PARTICIPANT_SIGNER: u8 = 0x01;
+
+// TLV 欄位
+const TAG_SIGNER_ID: u8 = 0x01;
+const TAG_COMMITMENT: u8 = 0x02;
+const TAG_ENVELOPE_SIGNATURE: u8 = 0x03;
+const TAG_SIGNATURE_SHARE: u8 = 0x04;
+const TAG_MESSAGE: u8 = 0x05;
+const TAG_PAYLOAD: u8 = 0x06;
+const TAG_SIGNATURE: u8 = 0x07;
+const TAG_REASON: u8 = 0x08;
+const TAG_TRACE_CONTEXT: u8 = 0x09;
+
+/// `TRACE_CONTEXT` 欄位長度
+const TRACE_CONTEXT_LEN: usize = 16 + 8 + 1;
+
+// ============================================================================
+// 錯誤定義
+// ============================================================================
+
+#[derive(Debug, Error)]
+pub enum CodecError {
+    #[error("Unsupported wire version {0}")]
+    UnsupportedVersion(u8),
+
+    #[error("Message truncated")]
+    Truncated,
+
+    #[error("Checksum mismatch")]
+    ChecksumMismatch,
+
+    #[error("Unknown message type 0x{0:02x}")]
+    UnknownMessageType(u8),
+
+    #[error("Unknown participant kind 0x{0:02x}")]
+    UnknownParticipant(u8),
+
+    #[error("Unexpected field 0x{0:02x}")]
+    UnexpectedField(u8),
+
+    #[error("Missing field 0x{0:02x}")]
+    MissingField(u8),
+
+    #[error("Invalid field 0x{tag:02x}: {reason}")]
+    InvalidField { tag: u8, reason: String },
+}
+
+// ============================================================================
+// 編碼
+// ============================================================================
+
+/// 將信封編碼為二進位格式
+pub fn encode(envelope: &TransportEnvelope) -> Result<Vec<u8>, CodecError> {
+    let message = &envelope.message;
+
+    let mut out = Vec::with_capacity(128);
+    out.push(WIRE_VERSION);
+    out.push(message_type_code(message));
+    out.extend_from_slice(message.session_id().0.as_bytes());
+    put_participant(&mut out, envelope.from);
+    put_participant(&mut out, envelope.to);
+
+    match message {
+        FrostMessage::CommitmentRequest { trace, .. } => {
+            if let Some(trace) = trace {
+                put_trace_context(&mut out, trace);
+            }
+        }
+        FrostMessage::Round1Commitment { commitment, .. } => {
+            put_commitment(&mut out, commitment)?;
+        }
+        FrostMessage::SigningPackage { package, trace, .. } => {
+            for commitment in &package.commitments {
+                put_commitment(&mut out, commitment)?;
+            }
+            put_field(&mut out, TAG_MESSAGE, &package.message);
+            if let Some(payload) = &package.payload {
+                put_json_field(&mut out, TAG_PAYLOAD, payload)?;
+            }
+            if let Some(trace) = trace {
+                put_trace_context(&mut out, trace);
+            }
+        }
+        FrostMessage::Round2SignatureShare { share, .. } => {
+            put_field(&mut out, TAG_SIGNER_ID, &share.signer_id.to_be_bytes());
+            put_hex_field(&mut out, TAG_SIGNATURE_SHARE, &share.signature_share)?;
+            if let Some(signature) = &share.envelope_signature {
+                put_hex_field(&mut out, TAG_ENVELOPE_SIGNATURE, signature)?;
+            }
+        }
+        FrostMessage::FinalSignature { signature, .. } => {
+            put_hex_field(&mut out, TAG_SIGNATURE, signature)?;
+        }
+        FrostMessage::Abort { reason, .. } => {
+            put_field(&mut out, TAG_REASON, reason.as_bytes());
+        }
+        FrostMessage::SessionProposal {
+            signer_ids,
+            payload,
+            proposer_signature,
+            ..
+        } => {
+            for signer_id in signer_ids {
+                put_field(&mut out, TAG_SIGNER_ID, &signer_id.to_be_bytes());
+            }
+            put_json_field(&mut out, TAG_PAYLOAD, payload)?;
+            if let Some(signature) = proposer_signature {
+                put_hex_field(&mut out, TAG_ENVELOPE_SIGNATURE, signature)?;
+            }
+        }
+    }
+
+    let checksum = Sha256::digest(&out);
+    out.extend_from_slice(&checksum[..CHECKSUM_LEN]);
+    Ok(out)
+}
+
+fn message_type_code(message: &FrostMessage) -> u8 {
+    match message {
+        FrostMessage::CommitmentRequest { .. } => TYPE_COMMITMENT_REQUEST,
+        FrostMessage::Round1Commitment { .. } => TYPE_ROUND1_COMMITMENT,
+        FrostMessage::SigningPackage { .. } => TYPE_SIGNING_PACKAGE,
+        FrostMessage::Round2SignatureShare { .. } => TYPE_ROUND2_SIGNATURE_SHARE,
+        FrostMessage::FinalSignature { .. } => TYPE_FINAL_SIGNATURE,
+        FrostMessage::Abort { .. } => TYPE_ABORT,
+        FrostMessage::SessionProposal { .. } => TYPE_SESSION_PROPOSAL,
+    }
+}
+
+fn put_participant(out: &mut Vec<u8>, participant: Participant) {
+    let (kind, id) = match participant {
+        Participant::Coordinator => (PARTICIPANT_COORDINATOR, 0),
+        Participant::Signer(id) => (PARTICIPANT_SIGNER, id),
+    };
+    out.push(kind);
+    out.extend_from_slice(&id.to_be_bytes());
+}
+
+fn put_commitment(out: &mut Vec<u8>, commitment: &CommitmentData) -> Result<(), CodecError> {
+    put_field(out, TAG_SIGNER_ID, &commitment.signer_id.to_be_bytes());
+    put_hex_field(out, TAG_COMMITMENT, &commitment.commitment)?;
+    if let Some(signature) = &commitment.envelope_signature {
+        put_hex_field(out, TAG_ENVELOPE_SIGNATURE, signature)?;
+    }
+    Ok(())
+}
+
+fn put_trace_context(out: &mut Vec<u8>, trace: &TraceContext) {
+    let mut value = Vec::with_capacity(TRACE_CONTEXT_LEN);
+    value.extend_from_slice(&trace.trace_id);
+    value.extend_from_slice(&trace.span_id);
+    value.push(u8::from(trace.sampled));
+    put_field(out, TAG_TRACE_CONTEXT, &value);
+}
+
+fn put_json_field<T: serde::Serialize>(
+    out: &mut Vec<u8>,
+    tag: u8,
+    value: &T,
+) -> Result<(), CodecError> {
+    let json = serde_json::to_vec(value).map_err(|e| CodecError::InvalidField {
+        tag,
+        reason: e.to_string(),
+    })?;
+    put_field(out, tag, &json);
+    Ok(())
+}
+
+fn put_hex_field(out: &mut Vec<u8>, tag: u8, value: &str) -> Result<(), CodecError> {
+    let bytes = hex::decode(value).map_err(|e| CodecError::InvalidField {
+        tag,
+        reason: e.to_string(),
+    })?;
+    put_field(out, tag, &bytes);
+    Ok(())
+}
+
+fn put_field(out: &mut Vec<u8>, tag: u8, value: &[u8]) {
+    out.push(tag);
+    let mut len = value.len();
+    loop {
+        let byte = (len & 0x7f) as u8;
+        len >>= 7;
+        if len == 0 {
+            out.push(byte);
+            break;
+        }
+        out.push(byte | 0x80);
+    }
+    out.extend_from_slice(value);
+}
+
+// ============================================================================
+// 解碼
+// ============================================================================
+
+/// 從二進位格式解碼信封（會先驗證版本與校驗碼）
+pub fn decode(bytes: &[u8]) -> Result<TransportEnvelope, CodecError> {
+    if let Some(&version) = bytes.first() {
+        if version != WIRE_VERSION {
+            return Err(CodecError::UnsupportedVersion(version));
+        }
+    }
+    if bytes.len() < HEADER_LEN + CHECKSUM_LEN {
+        return Err(CodecError::Truncated);
+    }
+
+    let (body, checksum) = bytes.split_at(bytes.len() - CHECKSUM_LEN);
+    if Sha256::digest(body)[..CHECKSUM_LEN] != *checksum {
+        return Err(CodecError::ChecksumMismatch);
+    }
+
+    let message_type = body[1];
+    let mut uuid = [0u8; 16];
+    uuid.copy_from_slice(&body[2..18]);
+    let session_id = SessionId(Uuid::from_bytes(uuid));
+    let from = read_participant(&body[18..21])?;
+    let to = read_participant(&body[21..24])?;
+    let fields = read_fields(&body[HEADER_LEN..])?;
+
+    let message = match message_type {
+        TYPE_COMMITMENT_REQUEST => {
+            expect_only(&fields, &[TAG_TRACE_CONTEXT])?;
+            FrostMessage::CommitmentRequest {
+                session_id,
+                trace: read_trace_context(&fields)?,
+            }
+        }
+        TYPE_ROUND1_COMMITMENT => {
+            let mut commitments = read_commitments(&fields)?;
+            if commitments.len() != 1 {
+                return Err(CodecError::InvalidField {
+                    tag: TAG_SIGNER_ID,
+                    reason: format!("expected 1 commitment, got {}", commitments.len()),
+                });
+            }
+            FrostMessage::Round1Commitment {
+                session_id,
+                commitment: commitments.remove(0),
+            }
+        }
+        TYPE_SIGNING_PACKAGE => {
+            let commitment_fields: Vec<_> = fields
+                .iter()
+                .copied()
+                .filter(|(tag, _)| ![TAG_MESSAGE, TAG_PAYLOAD, TAG_TRACE_CONTEXT].contains(tag))
+                .collect();
+            let payload = match find_field(&fields, TAG_PAYLOAD) {
+                Some(json) => Some(read_json(json, TAG_PAYLOAD)?),
+                None => None,
+            };
+            FrostMessage::SigningPackage {
+                session_id,
+                package: SigningPackageData {
+                    commitments: read_commitments(&commitment_fields)?,
+                    message: require_field(&fields, TAG_MESSAGE)?.to_vec(),
+                    payload,
+                },
+                trace: read_trace_context(&fields)?,
+            }
+        }
+        TYPE_ROUND2_SIGNATURE_SHARE => {
+            expect_only(&fields, &[TAG_SIGNER_ID, TAG_SIGNATURE_SHARE, TAG_ENVELOPE_SIGNATURE])?;
+            FrostMessage::Round2SignatureShare {
+                session_id,
+                share: SignatureShareData {
+                    signer_id: read_u16(require_field(&fields, TAG_SIGNER_ID)?, TAG_SIGNER_ID)?,
+                    signature_share: hex::encode(require_field(&fields, TAG_SIGNATURE_SHARE)?),
+                    envelope_signature: find_field(&fields, TAG_ENVELOPE_SIGNATURE).map(hex::encode),
+                },
+            }
+        }
+        TYPE_FINAL_SIGNATURE => {
+            expect_only(&fields, &[TAG_SIGNATURE])?;
+            FrostMessage::FinalSignature {
+                session_id,
+                signature: hex::encode(require_field(&fields, TAG_SIGNATURE)?),
+            }
+        }
+        TYPE_ABORT => {
+            expect_only(&fields, &[TAG_REASON])?;
+            let reason = require_field(&fields, TAG_REASON)?;
+            FrostMessage::Abort {
+                session_id,
+                reason: String::from_utf8(reason.to_vec()).map_err(|e| {
+                    CodecError::InvalidField {
+                        tag: TAG_REASON,
+                        reason: e.to_string(),
+                    }
+                })?,
+            }
+        }
+        TYPE_SESSION_PROPOSAL => {
+            expect_only(&fields, &[TAG_SIGNER_ID, TAG_PAYLOAD, TAG_ENVELOPE_SIGNATURE])?;
+            let signer_ids = fields
+                .iter()
+                .filter(|(tag, _)| *tag == TAG_SIGNER_ID)
+                .map(|(tag, value)| read_u16(value, *tag))
+                .collect::<Result<Vec<_>, _>>()?;
+            FrostMessage::SessionProposal {
+                session_id,
+                signer_ids,
+                payload: read_json(require_field(&fields, TAG_PAYLOAD)?, TAG_PAYLOAD)?,
+                proposer_signature: find_field(&fields, TAG_ENVELOPE_SIGNATURE).map(hex::encode),
+            }
+        }
+        other => return Err(CodecError::UnknownMessageType(other)),
+    };
+
+    Ok(TransportEnvelope { from, to, message })
+}
+
+fn read_participant(bytes: &[u8]) -> Result<Participant, CodecError> {
+    let id = u16::from_be_bytes([bytes[1], bytes[2]]);
+    match bytes[0] {
+        PARTICIPANT_COORDINATOR => Ok(Participant::Coordinator),
+        PARTICIPANT_SIGNER => Ok(Participant::Signer(id)),
+        other => Err(CodecError::UnknownParticipant(other)),
+    }
+}
+
+/// 依序讀出所有 TLV 欄位
+fn read_fields(mut bytes: &[u8]) -> Result<Vec<(u8, &[u8])>, CodecError> {
+    let mut fields = Vec::new();
+    while let Some((&tag, rest)) = bytes.split_first() {
+        let (len, rest) = read_varint(rest)?;
+        if rest.len() < len {
+            return Err(CodecError::Truncated);
+        }
+        let (value, rest) = rest.split_at(len);
+        fields.push((tag, value));
+        bytes = rest;
+    }
+    Ok(fields)
+}
+
+fn read_varint(bytes: &[u8]) -> Result<(usize, &[u8]), CodecError> {
+    let mut value = 0usize;
+    for (i, &byte) in bytes.iter().enumerate().take(4) {
+        value |= ((byte & 0x7f) as usize) << (7 * i);
+        if byte & 0x80 == 0 {
+            return Ok((value, &bytes[i + 1..]));
+        }
+    }
+    Err(CodecError::Truncated)
+}
+
+fn read_u16(value: &[u8], tag: u8) -> Result<u16, CodecError> {
+    let bytes: [u8; 2] = value.try_into().map_err(|_| CodecError::InvalidField {
+        tag,
+        reason: format!("expected 2 bytes, got {}", value.len()),
+    })?;
+    Ok(u16::from_be_bytes(bytes))
+}
+
+fn read_json<T: serde::de::DeserializeOwned>(value: &[u8], tag: u8) -> Result<T, CodecError> {
+    serde_json::from_slice(value).map_err(|e| CodecError::InvalidField {
+        tag,
+        reason: e.to_string(),
+    })
+}
+
+fn read_trace_context(fields: &[(u8, &[u8])]) -> Result<Option<TraceContext>, CodecError> {
+    let Some(value) = find_field(fields, TAG_TRACE_CONTEXT) else {
+        return Ok(None);
+    };
+    if value.len() != TRACE_CONTEXT_LEN {
+        return Err(CodecError::InvalidField {
+            tag: TAG_TRACE_CONTEXT,
+            reason: format!("expected {} bytes, got {}", TRACE_CONTEXT_LEN, value.len()),
+        });
+    }
+
+    let mut trace = TraceContext {
+        trace_id: [0; 16],
+        span_id: [0; 8],
+        sampled: value[24] & 0x01 != 0,
+    };
+    trace.trace_id.copy_from_slice(&value[..16]);
+    trace.span_id.copy_from_slice(&value[16..24]);
+    Ok(Some(trace))
+}
+
+/// 讀出以 `SIGNER_ID` 分組的承諾
+fn read_commitments(fields: &[(u8, &[u8])]) -> Result<Vec<CommitmentData>, CodecError> {
+    let mut commitments: Vec<CommitmentData> = Vec::new();
+    for &(tag, value) in fields {
+        match tag {
+            TAG_SIGNER_ID => commitments.push(CommitmentData {
+                signer_id: read_u16(value, tag)?,
+                commitment: String::new(),
+                envelope_signature: None,
+            }),
+            TAG_COMMITMENT | TAG_ENVELOPE_SIGNATURE => {
+                let current = commitments
+                    .last_mut()
+                    .ok_or(CodecError::MissingField(TAG_SIGNER_ID))?;
+                if tag == TAG_COMMITMENT {
+                    current.commitment = hex::encode(value);
+                } else {
+                    current.envelope_signature = Some(hex::encode(value));
+                }
+            }
+            other => return Err(CodecError::UnexpectedField(other)),
+        }
+    }
+
+    if commitments.iter().any(|c| c.commitment.is_empty()) {
+        return Err(CodecError::MissingField(TAG_COMMITMENT));
+    }
+    Ok(commitments)
+}
+
+fn find_field<'a>(fields: &[(u8, &'a [u8])], tag: u8) -> Option<&'a [u8]> {
+    fields.iter().find(|(t, _)| *t == tag).map(|(_, value)| *value)
+}
+
+fn require_field<'a>(fields: &[(u8, &'a [u8])], tag: u8) -> Result<&'a [u8], CodecError> {
+    find_field(fields, tag).ok_or(CodecError::MissingField(tag))
+}
+
+fn expect_only(fields: &[(u8, &[u8])], allowed: &[u8]) -> Result<(), CodecError> {
+    match fields.iter().find(|(tag, _)| !allowed.contains(tag)) {
+        Some((tag, _)) => Err(CodecError::UnexpectedField(*tag)),
+        None => Ok(()),
+    }
+}
+
+// ============================================================================
+// 測試
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_round_trip_is_smaller_than_json() {
+        let session_id = SessionId::new();
+        let commitment = CommitmentData {
+            signer_id: 2,
+            commitment: "ab".repeat(69),
+            envelope_signature: Some("cd".repeat(64)),
+        };
+        let envelope = TransportEnvelope {
+            from: Participant::Coordinator,
+            to: Participant::Signer(2),
+            message: FrostMessage::SigningPackage {
+                session_id,
+                package: SigningPackageData {
+                    commitments: vec![
+                        commitment.clone(),
+                        CommitmentData {
+                            signer_id: 3,
+                            ..commitment
+                        },
+                    ],
+                    message: b"hello".to_vec(),
+                    payload: None,
+                },
+                trace: Some(TraceContext::new_root()),
+            },
+        };
+
+        let wire = encode(&envelope).unwrap();
+        let json = serde_json::to_vec(&envelope).unwrap();
+        assert!(wire.len() * 2 < json.len());
+
+        let decoded = decode(&wire).unwrap();
+        assert_eq!(serde_json::to_vec(&decoded).unwrap(), json);
+
+        // 任何一個 bit 被翻轉都會被校驗碼發現
+        let mut corrupted = wire.clone();
+        corrupted[30] ^= 0x01;
+        assert!(matches!(decode(&corrupted), Err(CodecError::ChecksumMismatch)));
+
+        let mut future = wire;
+        future[0] = WIRE_VERSION + 1;
+        assert!(matches!(decode(&future), Err(CodecError::UnsupportedVersion(_))));
+    }
+
+    /// 以新的校驗碼封裝修改過的內容，讓錯誤停在校驗碼之後的解析階段
+    fn reseal(body: &[u8]) -> Vec<u8> {
+        let mut wire = body.to_vec();
+        wire.extend_from_slice(&Sha256::digest(body)[..CHECKSUM_LEN]);
+        wire
+    }
+
+    #[test]
+    fn test_every_message_type_round_trips() {
+        let session_id = SessionId::new();
+        let messages = vec![
+            FrostMessage::CommitmentRequest {
+                session_id,
+                trace: None,
+            },
+            FrostMessage::Round1Commitment {
+                session_id,
+                commitment: CommitmentData {
+                    signer_id: 3,
+                    commitment: "ab".repeat(69),
+                    envelope_signature: Some("cd".repeat(64)),
+                },
+            },
+            FrostMessage::Round2SignatureShare {
+                session_id,
+                share: SignatureShareData {
+                    signer_id: 3,
+                    signature_share: "11".repeat(32),
+                    envelope_signature: Some("22".repeat(64)),
+                },
+            },
+            FrostMessage::Round2SignatureShare {
+                session_id,
+                share: SignatureShareData {
+                    signer_id: 300,
+                    signature_share: "11".repeat(32),
+                    envelope_signature: None,
+                },
+            },
+            FrostMessage::FinalSignature {
+                session_id,
+                signature: "33".repeat(65),
+            },
+            FrostMessage::Abort {
+                session_id,
+                reason: "signer 3 timed out in round1".to_string(),
+            },
+        ];
+
+        for message in messages {
+            let envelope = TransportEnvelope {
+                from: Participant::Signer(3),
+                to: Participant::Coordinator,
+                message,
+            };
+            let decoded = decode(&encode(&envelope).unwrap()).unwrap();
+            assert_eq!(
+                serde_json::to_value(&decoded).unwrap(),
+                serde_json::to_value(&envelope).unwrap()
+            );
+        }
+    }
+
+    #[test]
+    fn test_rejects_malformed_input() {
+        let envelope = TransportEnvelope {
+            from: Participant::Coordinator,
+            to: Participant::Signer(1),
+            message: FrostMessage::Abort {
+                session_id: SessionId::new(),
+                reason: "shutting down".to_string(),
+            },
+        };
+        let wire = encode(&envelope).unwrap();
+        let body = &wire[..wire.len() - CHECKSUM_LEN];
+
+        // 校驗碼被竄改
+        let mut corrupted = wire.clone();
+        *corrupted.last_mut().unwrap() ^= 0xFF;
+        assert!(matches!(decode(&corrupted), Err(CodecError::ChecksumMismatch)));
+
+        // 比標頭還短、空輸入，以及 TLV 長度超出剩餘內容
+        assert!(matches!(decode(&wire[..HEADER_LEN]), Err(CodecError::Truncated)));
+        assert!(matches!(decode(&[]), Err(CodecError::Truncated)));
+        assert!(matches!(
+            decode(&reseal(&body[..body.len() - 3])),
+            Err(CodecError::Truncated)
+        ));
+
+        // 未知的訊息類型、參與者種類與欄位 tag
+        let mut unknown_type = body.to_vec();
+        unknown_type[1] = 0xFF;
+        assert!(matches!(
+            decode(&reseal(&unknown_type)),
+            Err(CodecError::UnknownMessageType(0xFF))
+        ));
+
+        let mut unknown_participant = body.to_vec();
+        unknown_participant[18] = 0x7F;
+        assert!(matches!(
+            decode(&reseal(&unknown_participant)),
+            Err(CodecError::UnknownParticipant(0x7F))
+        ));
+
+        let request = TransportEnvelope {
+            from: Participant::Coordinator,
+            to: Participant::Signer(1),
+            message: FrostMessage::CommitmentRequest {
+                session_id: SessionId::new(),
+                trace: None,
+            },
+        };
+        let wire = encode(&request).unwrap();
+        let mut unknown_tag = wire[..wire.len() - CHECKSUM_LEN].to_vec();
+        put_field(&mut unknown_tag, 0x7E, b"?");
+        assert!(matches!(
+            decode(&reseal(&unknown_tag)),
+            Err(CodecError::UnexpectedField(0x7E))
+        ));
+    }
+}
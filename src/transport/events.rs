@@ -0,0 +1,276 @@
+//! # Event Stream - Dashboard 即時事件流
+//!
+//! `LoRaTransportState` 的每次變化（傳輸事件、階段切換、CLI 日誌、統計更新）都會
+//! 發布到 `EventStream`，再由 `GET /events` 以 Server-Sent Events 推送給 Dashboard，
+//! 取代反覆輪詢整份 `/status`。
+//!
+//! ## 序號與續傳
+//!
+//! 每個事件帶有單調遞增的序號，作為 SSE 的 `id:` 欄位。瀏覽器斷線重連時會在
+//! `Last-Event-ID` 標頭帶回最後收到的序號，伺服器從環形緩衝區補送之後的事件。
+//! 首次連線無法設定標頭，因此也接受 `?last_event_id=` 查詢參數：客戶端先抓取
+//! `/status` 快照（其中包含 `last_event_id`），再從該序號訂閱。
+//! 若需要的事件已被擠出緩衝區（或訂閱者落後太多），改送一個 `resync` 事件，
+//! 通知客戶端重新抓取 `/status` 快照。
+
+use super::simulated_lora::TransportEvent;
+use axum::response::sse::{Event, KeepAlive, Sse};
+use futures::stream::{self, Stream, StreamExt};
+use std::collections::VecDeque;
+use std::convert::Infallible;
+use std::sync::{Arc, Mutex};
+use tokio::sync::broadcast;
+
+/// 預設保留的事件數量（供 `Last-Event-ID` 續傳）
+pub const DEFAULT_REPLAY_CAPACITY: usize = 1024;
+
+/// SSE 中通知客戶端重新抓取快照的事件名稱
+pub const RESYNC_EVENT: &str = "resync";
+
+// ============================================================================
+// 事件類型
+// ============================================================================
+
+/// 推送給 Dashboard 的事件
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum StreamEvent {
+    /// 傳輸事件（與 `recent_events` 中的記錄相同）
+    Transport { event: TransportEvent },
+
+    /// 階段或進度變化
+    Phase { phase: String, progress: f64 },
+
+    /// 一行 CLI 輸出
+    Log { line: String },
+
+    /// 累計統計更新（每則訊息傳輸結束後發布）
+    Stats {
+        total_messages: usize,
+        total_bytes: usize,
+        total_retries: u32,
+        rssi: i32,
+        snr_db: Option<f64>,
+        simulated_time_ms: u64,
+        total_airtime_ms: u64,
+        duty_cycle_wait_ms: u64,
+    },
+}
+
+/// 帶序號的事件
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct SequencedEvent {
+    /// 單調遞增的序號（從 1 開始）
+    pub id: u64,
+
+    /// 事件內容
+    #[serde(flatten)]
+    pub event: StreamEvent,
+}
+
+/// 訂閱結果：需要補送的事件與之後的即時事件
+#[derive(Debug)]
+pub struct Subscription {
+    /// 緩衝區中序號大於 `Last-Event-ID` 的事件
+    pub backlog: Vec<SequencedEvent>,
+
+    /// 要求的事件已被擠出緩衝區，客戶端必須重新抓取快照
+    pub gap: bool,
+
+    /// 即時事件
+    pub receiver: broadcast::Receiver<SequencedEvent>,
+}
+
+// ============================================================================
+// Event Stream
+// ============================================================================
+
+/// 事件發布者（可廉價複製，所有複本共享同一個緩衝區）
+#[derive(Debug, Clone)]
+pub struct EventStream {
+    inner: Arc<Mutex<Ring>>,
+    sender: broadcast::Sender<SequencedEvent>,
+}
+
+#[derive(Debug)]
+struct Ring {
+    next_id: u64,
+    capacity: usize,
+    buffer: VecDeque<SequencedEvent>,
+}
+
+impl EventStream {
+    /// 建立事件流，保留最近 `capacity` 個事件
+    pub fn new(capacity: usize) -> Self {
+        let capacity = capacity.max(1);
+        let (sender, _) = broadcast::channel(capacity);
+        Self {
+            inner: Arc::new(Mutex::new(Ring {
+                next_id: 1,
+                capacity,
+                buffer: VecDeque::with_capacity(capacity),
+            })),
+            sender,
+        }
+    }
+
+    /// 發布事件，返回其序號
+    pub fn publish(&self, event: StreamEvent) -> u64 {
+        // 在鎖內廣播，確保訂閱者看到的順序與序號一致
+        let mut ring = self.inner.lock().unwrap();
+        let sequenced = SequencedEvent {
+            id: ring.next_id,
+            event,
+        };
+        ring.next_id += 1;
+        if ring.buffer.len() == ring.capacity {
+            ring.buffer.pop_front();
+        }
+        ring.buffer.push_back(sequenced.clone());
+
+        // 沒有訂閱者時發送失敗，可忽略
+        let _ = self.sender.send(sequenced);
+        ring.next_id - 1
+    }
+
+    /// 最後發布的事件序號（尚未發布時為 0）
+    pub fn last_id(&self) -> u64 {
+        self.inner.lock().unwrap().next_id - 1
+    }
+
+    /// 訂閱 `last_event_id` 之後的事件
+    ///
+    /// `None` 表示新的客戶端：補送整個緩衝區。補送與訂閱在同一把鎖內完成，
+    /// 因此事件不會重複也不會遺漏。
+    pub fn subscribe(&self, last_event_id: Option<u64>) -> Subscription {
+        let ring = self.inner.lock().unwrap();
+        let receiver = self.sender.subscribe();
+        let after = last_event_id.unwrap_or(0);
+
+        // 序號比目前還大（例如伺服器重啟過）也視為斷層
+        let oldest = ring.buffer.front().map_or(ring.next_id, |event| event.id);
+        let gap = last_event_id.is_some_and(|last| last + 1 < oldest || last >= ring.next_id);
+
+        let backlog = if gap {
+            Vec::new()
+        } else {
+            ring.buffer
+                .iter()
+                .filter(|event| event.id > after)
+                .cloned()
+                .collect()
+        };
+
+        Subscription {
+            backlog,
+            gap,
+            receiver,
+        }
+    }
+
+    /// 以 Server-Sent Events 回應訂閱
+    pub fn sse(
+        &self,
+        last_event_id: Option<u64>,
+    ) -> Sse<impl Stream<Item = Result<Event, Infallible>>> {
+        let Subscription {
+            backlog,
+            gap,
+            receiver,
+        } = self.subscribe(last_event_id);
+
+        let head = gap.then(resync_event);
+        let replay = stream::iter(head.into_iter().chain(backlog.into_iter().map(|event| to_sse(&event))));
+        let live = stream::unfold(receiver, |mut receiver| async move {
+            match receiver.recv().await {
+                Ok(event) => Some((to_sse(&event), receiver)),
+                // 落後太多：事件已遺失，要求客戶端重新同步
+                Err(broadcast::error::RecvError::Lagged(_)) => Some((resync_event(), receiver)),
+                Err(broadcast::error::RecvError::Closed) => None,
+            }
+        });
+
+        Sse::new(replay.chain(live).map(Ok)).keep_alive(KeepAlive::default())
+    }
+}
+
+impl Default for EventStream {
+    fn default() -> Self {
+        Self::new(DEFAULT_REPLAY_CAPACITY)
+    }
+}
+
+/// `GET /events` 的查詢參數
+#[derive(Debug, Default, serde::Deserialize)]
+pub struct EventsQuery {
+    /// 從此序號之後開始（`Last-Event-ID` 標頭優先）
+    pub last_event_id: Option<u64>,
+}
+
+/// 決定續傳的起點：`Last-Event-ID` 標頭優先，其次為查詢參數
+pub fn last_event_id(headers: &axum::http::HeaderMap, query: &EventsQuery) -> Option<u64> {
+    headers
+        .get("last-event-id")
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| value.trim().parse().ok())
+        .or(query.last_event_id)
+}
+
+fn to_sse(event: &SequencedEvent) -> Event {
+    Event::default()
+        .id(event.id.to_string())
+        .json_data(&event.event)
+        .expect("stream events are always serializable")
+}
+
+fn resync_event() -> Event {
+    Event::default().event(RESYNC_EVENT).data("snapshot required")
+}
+
+// ============================================================================
+// 測試
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn log(line: &str) -> StreamEvent {
+        StreamEvent::Log {
+            line: line.to_string(),
+        }
+    }
+
+    #[test]
+    fn test_resume_after_last_event_id_and_detect_gap() {
+        let events = EventStream::new(3);
+        for line in ["a", "b", "c", "d"] {
+            events.publish(log(line));
+        }
+        assert_eq!(events.last_id(), 4);
+
+        // 緩衝區保留 2..=4，從 2 之後續傳
+        let mut subscription = events.subscribe(Some(2));
+        assert!(!subscription.gap);
+        let ids: Vec<u64> = subscription.backlog.iter().map(|event| event.id).collect();
+        assert_eq!(ids, vec![3, 4]);
+
+        // 訂閱後發布的事件即時送達
+        events.publish(log("e"));
+        let live = subscription.receiver.try_recv().unwrap();
+        assert_eq!(live.id, 5);
+        assert!(matches!(live.event, StreamEvent::Log { ref line } if line == "e"));
+
+        // 已經收到最新事件的客戶端不需要補送
+        assert!(events.subscribe(Some(5)).backlog.is_empty());
+
+        // 序號 1 已被擠出緩衝區，或序號來自未來：都必須重新同步
+        assert!(events.subscribe(Some(1)).gap);
+        assert!(events.subscribe(Some(42)).gap);
+
+        // 新客戶端取得整個緩衝區
+        let fresh = events.subscribe(None);
+        assert!(!fresh.gap);
+        assert_eq!(fresh.backlog.len(), 3);
+    }
+}
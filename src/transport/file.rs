@@ -0,0 +1,301 @@
+//! # File Transport - 以目錄交換訊息檔（適用於 air-gapped 儀式）
+//!
+//! 每則協議訊息是一個檔案，內容為 `codec` 的二進位格式：
+//!
+//! - **發送**：寫入 `outbox/<接收者>/`，先寫成 `.tmp` 再原子 rename 為 `.frost`，
+//!   讀取端永遠不會看到寫到一半的檔案
+//! - **接收**：輪詢 `inbox/` 中的 `.frost` 檔，依檔名（時間戳）順序處理；
+//!   處理後移到 `inbox/processed/`，無法解碼或收件者不符的檔案移到 `inbox/rejected/`。
+//!   inbox 不存在或暫時無法讀取時持續輪詢，不會結束接收
+//!
+//! ## 目錄慣例（USB 隨身碟）
+//!
+//! ```text
+//! /media/usb/
+//! ├── coordinator/   ← 簽署者寫給協調者的訊息
+//! ├── signer_1/      ← 協調者寫給簽署者 1 的訊息
+//! └── signer_3/
+//! ```
+//!
+//! 每台機器都以 `outbox = /media/usb`、`inbox = /media/usb/<自己>` 執行，
+//! 把隨身碟在機器之間來回傳遞即可完成整個簽章流程。
+
+use super::{
+    codec, AsyncTransport, FrostMessage, Participant, TransportEnvelope, TransportError,
+    TransportStats,
+};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant};
+
+/// 訊息檔的副檔名
+const MESSAGE_EXTENSION: &str = "frost";
+
+/// 已處理訊息的子目錄
+const PROCESSED_DIR: &str = "processed";
+
+/// 無法處理訊息的子目錄
+const REJECTED_DIR: &str = "rejected";
+
+/// 以目錄交換訊息的 `AsyncTransport`
+///
+/// 訊息檔很小，直接使用同步的檔案 I/O。
+pub struct FileTransport {
+    local: Participant,
+    inbox: PathBuf,
+    outbox: PathBuf,
+    poll_interval: Duration,
+    idle_timeout: Option<Duration>,
+    stats: TransportStats,
+}
+
+impl FileTransport {
+    /// 建立檔案傳輸端點
+    ///
+    /// # 參數
+    /// - `local`: 此端點代表的參與者
+    /// - `inbox`: 讀取寄給自己的訊息的目錄
+    /// - `outbox`: 寫出訊息的根目錄（每個接收者一個子目錄）
+    pub fn new(local: Participant, inbox: impl Into<PathBuf>, outbox: impl Into<PathBuf>) -> Self {
+        Self {
+            local,
+            inbox: inbox.into(),
+            outbox: outbox.into(),
+            poll_interval: Duration::from_millis(500),
+            idle_timeout: None,
+            stats: TransportStats::default(),
+        }
+    }
+
+    /// 設定輪詢 inbox 的間隔（預設 500ms）
+    pub fn with_poll_interval(mut self, interval: Duration) -> Self {
+        self.poll_interval = interval;
+        self
+    }
+
+    /// 設定閒置逾時：超過此時間沒有新訊息時 `recv` 返回 `Closed`（預設永遠等待）
+    pub fn with_idle_timeout(mut self, timeout: Duration) -> Self {
+        self.idle_timeout = Some(timeout);
+        self
+    }
+
+    /// 接收者在 outbox 中的目錄
+    pub fn outbox_dir(&self, to: Participant) -> PathBuf {
+        self.outbox.join(to.to_string())
+    }
+
+    /// inbox 中等待處理的訊息檔（依檔名排序）
+    ///
+    /// inbox 不存在時（例如隨身碟尚未插入）視為沒有訊息；不會自行建立目錄，
+    /// 以免在掛載點底下寫入本機磁碟。
+    fn pending_files(&self) -> Result<Vec<PathBuf>, TransportError> {
+        let entries = match fs::read_dir(&self.inbox) {
+            Ok(entries) => entries,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(Vec::new()),
+            Err(e) => return Err(e.into()),
+        };
+
+        let mut files: Vec<PathBuf> = entries
+            .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+            .filter(|path| {
+                path.is_file()
+                    && path.extension().and_then(|ext| ext.to_str()) == Some(MESSAGE_EXTENSION)
+            })
+            .collect();
+        files.sort();
+
+        Ok(files)
+    }
+
+    /// 把處理完的訊息檔移到 inbox 的子目錄
+    fn archive(&self, path: &Path, subdir: &str) -> Result<(), TransportError> {
+        let dir = self.inbox.join(subdir);
+        fs::create_dir_all(&dir)?;
+        if let Some(name) = path.file_name() {
+            fs::rename(path, dir.join(name))?;
+        }
+        Ok(())
+    }
+
+    /// 取出下一則寄給自己的訊息（inbox 為空時返回 `None`）
+    fn take_next(&mut self) -> Result<Option<TransportEnvelope>, TransportError> {
+        for path in self.pending_files()? {
+            let envelope = match codec::decode(&fs::read(&path)?) {
+                Ok(envelope) => envelope,
+                Err(e) => {
+                    tracing::warn!(
+                        file = %path.display(),
+                        error = %e,
+                        "Rejecting undecodable message file"
+                    );
+                    self.archive(&path, REJECTED_DIR)?;
+                    continue;
+                }
+            };
+
+            if envelope.to != self.local {
+                tracing::warn!(
+                    file = %path.display(),
+                    to = %envelope.to,
+                    local = %self.local,
+                    "Rejecting message addressed to another participant"
+                );
+                self.archive(&path, REJECTED_DIR)?;
+                continue;
+            }
+
+            self.archive(&path, PROCESSED_DIR)?;
+            return Ok(Some(envelope));
+        }
+
+        Ok(None)
+    }
+}
+
+impl AsyncTransport for FileTransport {
+    fn local(&self) -> Participant {
+        self.local
+    }
+
+    async fn send(&mut self, to: Participant, message: FrostMessage) -> Result<(), TransportError> {
+        let message_type = message.message_type();
+        let envelope = TransportEnvelope {
+            from: self.local,
+            to,
+            message,
+        };
+        let wire = codec::encode(&envelope)?;
+        let baseline =
+            serde_json::to_vec(&envelope).map_err(|e| TransportError::Encoding(e.to_string()))?;
+
+        // 時間戳在前，讓接收端依檔名排序即為發送順序
+        let dir = self.outbox_dir(to);
+        fs::create_dir_all(&dir)?;
+        let name = format!(
+            "{:020}-{}-{}",
+            chrono::Utc::now().timestamp_micros(),
+            self.local,
+            uuid::Uuid::new_v4().simple()
+        );
+        let tmp_path = dir.join(format!("{}.tmp", name));
+        let final_path = dir.join(format!("{}.{}", name, MESSAGE_EXTENSION));
+
+        // 先寫入暫存檔並 fsync，再原子 rename
+        {
+            use std::io::Write;
+            let mut file = fs::File::create(&tmp_path)?;
+            file.write_all(&wire)?;
+            file.sync_all()?;
+        }
+        fs::rename(&tmp_path, &final_path)?;
+
+        self.stats.record(message_type, wire.len(), baseline.len());
+        tracing::debug!(file = %final_path.display(), to = %to, "Wrote message file");
+
+        Ok(())
+    }
+
+    async fn recv(&mut self) -> Result<TransportEnvelope, TransportError> {
+        let started = Instant::now();
+        loop {
+            // 讀取失敗（例如隨身碟被拔除）只記錄警告，下一次輪詢再試
+            match self.take_next() {
+                Ok(Some(envelope)) => return Ok(envelope),
+                Ok(None) => {}
+                Err(e) => tracing::warn!(
+                    inbox = %self.inbox.display(),
+                    error = %e,
+                    "Failed to read inbox, retrying"
+                ),
+            }
+
+            if let Some(timeout) = self.idle_timeout {
+                if started.elapsed() >= timeout {
+                    return Err(TransportError::Closed);
+                }
+            }
+
+            tokio::time::sleep(self.poll_interval).await;
+        }
+    }
+
+    fn get_stats(&self) -> Option<TransportStats> {
+        Some(self.stats.clone())
+    }
+}
+
+// ============================================================================
+// 測試
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::api::SignPayload;
+    use crate::{Coordinator, Signer};
+    use frost_secp256k1 as frost;
+    use rand::thread_rng;
+
+    #[tokio::test]
+    async fn test_signing_over_shared_directory() {
+        let root = std::env::temp_dir().join(format!("frost-files-{}", uuid::Uuid::new_v4()));
+
+        let (shares, pubkey_package) = frost::keys::generate_with_dealer(
+            3,
+            2,
+            frost::keys::IdentifierList::Default,
+            thread_rng(),
+        )
+        .unwrap();
+        let coordinator = Coordinator::new(pubkey_package, 2);
+
+        // 每個簽署者只看得到自己的 inbox，所有人寫到同一個 outbox 根目錄
+        let mut handles = Vec::new();
+        for share in shares.into_values() {
+            let signer = Signer::new(share).unwrap();
+            let local = Participant::Signer(signer.numeric_id());
+            let mut transport = FileTransport::new(local, root.join(local.to_string()), &root)
+                .with_poll_interval(Duration::from_millis(10))
+                .with_idle_timeout(Duration::from_secs(2));
+            handles.push(tokio::spawn(async move { signer.serve(&mut transport).await }));
+        }
+
+        let mut transport = FileTransport::new(
+            Participant::Coordinator,
+            root.join(Participant::Coordinator.to_string()),
+            &root,
+        )
+        .with_poll_interval(Duration::from_millis(10));
+        let payload = SignPayload::Raw {
+            message: hex::encode(b"sneakernet"),
+        };
+        let outcome = coordinator
+            .orchestrate_over_transport(&mut transport, &[1, 3], &payload, Duration::from_secs(5))
+            .await
+            .unwrap();
+        assert!(outcome.verify(coordinator.group_public_key()));
+
+        // 簽署者閒置逾時後結束
+        for handle in handles {
+            handle.await.unwrap().unwrap();
+        }
+
+        // 所有訊息都已處理並歸檔，沒有殘留的暫存檔
+        let processed = fs::read_dir(root.join("coordinator").join(PROCESSED_DIR))
+            .unwrap()
+            .count();
+        assert_eq!(processed, 4);
+        assert!(transport.pending_files().unwrap().is_empty());
+        assert_eq!(transport.get_stats().unwrap().total_messages, 6);
+
+        // 不存在的 inbox 視為空的，輪詢到逾時也不會建立目錄
+        let unmounted = root.join("unmounted");
+        let mut idle = FileTransport::new(Participant::Signer(2), &unmounted, &root)
+            .with_poll_interval(Duration::from_millis(10))
+            .with_idle_timeout(Duration::from_millis(50));
+        assert!(matches!(idle.recv().await, Err(TransportError::Closed)));
+        assert!(!unmounted.exists());
+
+        let _ = fs::remove_dir_all(&root);
+    }
+}
@@ -0,0 +1,473 @@
+//! # Fountain Code - 多段 QR Code 的噴泉編碼
+//!
+//! 仿照 BC-UR 的做法，把一則訊息編碼成「可無限產生」的分段：
+//!
+//! - 前 `seq_len` 段是原始片段，依序掃描即可完成
+//! - 之後的每一段是數個片段的 XOR，組合由 `(seq, checksum)` 決定性地選出
+//!   （度數分布 1/i，與 BC-UR 相同）
+//!
+//! 掃描端不需要依序、也不需要每一段都掃到：收集到足夠的分段後，
+//! 以剝離（peeling）解碼還原所有片段。動畫 QR Code 循環播放即可。
+//!
+//! ## 文字格式
+//!
+//! ```text
+//! UR:<TYPE>/<seq>-<seq_len>/<HEX>
+//! HEX = [message_len: u32 BE][checksum: u32 BE][fragment ...]
+//! ```
+//!
+//! 只使用 QR Code 英數模式的字元（大寫、數字、`:` `/` `-`），比位元組模式更密。
+//! `checksum` 為整則訊息 SHA-256 的前 4 bytes。
+//!
+//! 片段長度固定為 `ceil(message_len / seq_len)`（至少 1），長度不符或
+//! `seq_len` 超過 `MAX_SEQ_LEN` 的分段會被拒絕。
+
+use sha2::{Digest, Sha256};
+use std::collections::{BTreeMap, BTreeSet};
+use thiserror::Error;
+
+/// 分段本體的標頭長度（message_len + checksum）
+const PART_HEADER_LEN: usize = 8;
+
+/// 原始片段數的上限（片段選擇與解碼的成本與 `seq_len` 成正比）
+pub const MAX_SEQ_LEN: usize = 4096;
+
+/// 訊息切成 `seq_len` 個片段時每個片段的長度
+fn fragment_len(message_len: usize, seq_len: usize) -> usize {
+    message_len.div_ceil(seq_len).max(1)
+}
+
+// ============================================================================
+// 錯誤定義
+// ============================================================================
+
+#[derive(Debug, Error)]
+pub enum FountainError {
+    #[error("Invalid UR part: {0}")]
+    InvalidPart(String),
+
+    #[error("Part belongs to a different message")]
+    InconsistentPart,
+
+    #[error("Reassembled message failed checksum verification")]
+    ChecksumMismatch,
+}
+
+// ============================================================================
+// 決定性的片段選擇
+// ============================================================================
+
+/// 以 SHA-256 計數模式產生的決定性亂數（跨平台、跨版本一致）
+struct HashRng {
+    seed: [u8; 32],
+    counter: u64,
+    block: [u8; 32],
+    offset: usize,
+}
+
+impl HashRng {
+    fn new(seq: u32, checksum: u32) -> Self {
+        let mut hasher = Sha256::new();
+        hasher.update(seq.to_be_bytes());
+        hasher.update(checksum.to_be_bytes());
+
+        Self {
+            seed: hasher.finalize().into(),
+            counter: 0,
+            block: [0; 32],
+            offset: 32,
+        }
+    }
+
+    fn next_u32(&mut self) -> u32 {
+        if self.offset + 4 > self.block.len() {
+            let mut hasher = Sha256::new();
+            hasher.update(self.seed);
+            hasher.update(self.counter.to_be_bytes());
+            self.block = hasher.finalize().into();
+            self.counter += 1;
+            self.offset = 0;
+        }
+
+        let bytes = &self.block[self.offset..self.offset + 4];
+        self.offset += 4;
+        u32::from_be_bytes([bytes[0], bytes[1], bytes[2], bytes[3]])
+    }
+
+    /// [0, 1) 之間的浮點數
+    fn next_f64(&mut self) -> f64 {
+        f64::from(self.next_u32()) / (f64::from(u32::MAX) + 1.0)
+    }
+}
+
+/// 第 `seq` 段包含哪些片段（`seq` 從 1 開始，`seq_len` 不超過 `MAX_SEQ_LEN`）
+fn choose_fragments(seq: u32, seq_len: usize, checksum: u32) -> BTreeSet<usize> {
+    let seq_len = seq_len.clamp(1, MAX_SEQ_LEN);
+    if seq as usize <= seq_len {
+        return BTreeSet::from([seq as usize - 1]);
+    }
+
+    let mut rng = HashRng::new(seq, checksum);
+
+    // 度數分布：P(d) ∝ 1/d
+    let total: f64 = (1..=seq_len).map(|d| 1.0 / d as f64).sum();
+    let mut target = rng.next_f64() * total;
+    let mut degree = seq_len;
+    for d in 1..=seq_len {
+        target -= 1.0 / d as f64;
+        if target < 0.0 {
+            degree = d;
+            break;
+        }
+    }
+
+    // Fisher–Yates 洗牌後取前 degree 個
+    let mut indexes: Vec<usize> = (0..seq_len).collect();
+    for i in (1..seq_len).rev() {
+        let j = rng.next_u32() as usize % (i + 1);
+        indexes.swap(i, j);
+    }
+    indexes.into_iter().take(degree).collect()
+}
+
+fn xor_into(target: &mut [u8], other: &[u8]) {
+    for (a, b) in target.iter_mut().zip(other) {
+        *a ^= b;
+    }
+}
+
+fn message_checksum(message: &[u8]) -> u32 {
+    let digest = Sha256::digest(message);
+    u32::from_be_bytes([digest[0], digest[1], digest[2], digest[3]])
+}
+
+// ============================================================================
+// 分段
+// ============================================================================
+
+/// 一個噴泉編碼分段
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FountainPart {
+    /// 分段序號（從 1 開始，可超過 `seq_len`）
+    pub seq: u32,
+
+    /// 原始片段數
+    pub seq_len: usize,
+
+    /// 訊息總長度
+    pub message_len: usize,
+
+    /// 訊息校驗碼
+    pub checksum: u32,
+
+    /// 片段（或數個片段的 XOR）
+    pub data: Vec<u8>,
+}
+
+impl FountainPart {
+    /// 轉換為 QR Code 內容（`UR:<TYPE>/<seq>-<seq_len>/<HEX>`）
+    pub fn to_ur(&self, ur_type: &str) -> String {
+        let mut body = Vec::with_capacity(PART_HEADER_LEN + self.data.len());
+        body.extend_from_slice(&(self.message_len as u32).to_be_bytes());
+        body.extend_from_slice(&self.checksum.to_be_bytes());
+        body.extend_from_slice(&self.data);
+
+        format!(
+            "UR:{}/{}-{}/{}",
+            ur_type.to_ascii_uppercase(),
+            self.seq,
+            self.seq_len,
+            hex::encode_upper(body)
+        )
+    }
+
+    /// 解析 QR Code 內容，返回 `(類型, 分段)`
+    pub fn from_ur(text: &str) -> Result<(String, Self), FountainError> {
+        let invalid = |reason: &str| FountainError::InvalidPart(reason.to_string());
+
+        let rest = text
+            .trim()
+            .strip_prefix("UR:")
+            .or_else(|| text.trim().strip_prefix("ur:"))
+            .ok_or_else(|| invalid("missing UR: prefix"))?;
+        let mut fields = rest.splitn(3, '/');
+        let (Some(ur_type), Some(sequence), Some(body)) =
+            (fields.next(), fields.next(), fields.next())
+        else {
+            return Err(invalid("expected UR:<TYPE>/<seq>-<seq_len>/<HEX>"));
+        };
+
+        let (seq, seq_len) = sequence
+            .split_once('-')
+            .ok_or_else(|| invalid("malformed sequence"))?;
+        let seq: u32 = seq.parse().map_err(|_| invalid("malformed seq"))?;
+        let seq_len: usize = seq_len.parse().map_err(|_| invalid("malformed seq_len"))?;
+
+        let body = hex::decode(body).map_err(|_| invalid("body is not hex"))?;
+        if body.len() < PART_HEADER_LEN {
+            return Err(invalid("body too short"));
+        }
+        let message_len = u32::from_be_bytes([body[0], body[1], body[2], body[3]]) as usize;
+        let checksum = u32::from_be_bytes([body[4], body[5], body[6], body[7]]);
+
+        let part = Self {
+            seq,
+            seq_len,
+            message_len,
+            checksum,
+            data: body[PART_HEADER_LEN..].to_vec(),
+        };
+        part.validate()?;
+        Ok((ur_type.to_ascii_uppercase(), part))
+    }
+
+    /// 檢查分段參數：序號從 1 開始、`seq_len` 不超過上限、片段長度與訊息長度一致
+    fn validate(&self) -> Result<(), FountainError> {
+        let invalid = |reason: String| Err(FountainError::InvalidPart(reason));
+
+        if self.seq == 0 || self.seq_len == 0 {
+            return invalid("seq and seq_len start at 1".to_string());
+        }
+        if self.seq_len > MAX_SEQ_LEN {
+            return invalid(format!(
+                "seq_len {} exceeds the limit of {}",
+                self.seq_len, MAX_SEQ_LEN
+            ));
+        }
+        let expected = fragment_len(self.message_len, self.seq_len);
+        if self.data.len() != expected {
+            return invalid(format!(
+                "fragment is {} bytes, expected {} for a {}-byte message in {} parts",
+                self.data.len(),
+                expected,
+                self.message_len,
+                self.seq_len
+            ));
+        }
+        Ok(())
+    }
+}
+
+// ============================================================================
+// 編碼器
+// ============================================================================
+
+/// 噴泉編碼器
+pub struct FountainEncoder {
+    fragments: Vec<Vec<u8>>,
+    message_len: usize,
+    checksum: u32,
+}
+
+impl FountainEncoder {
+    /// 建立編碼器（每個片段不超過 `max_fragment_len` bytes，長度平均分配）
+    ///
+    /// 片段數超過 `MAX_SEQ_LEN` 時改用較長的片段。
+    pub fn new(message: &[u8], max_fragment_len: usize) -> Self {
+        let max_fragment_len = max_fragment_len.max(1);
+        let seq_len = message
+            .len()
+            .div_ceil(max_fragment_len)
+            .clamp(1, MAX_SEQ_LEN);
+        let fragment_len = fragment_len(message.len(), seq_len);
+
+        let mut padded = message.to_vec();
+        padded.resize(fragment_len * seq_len, 0);
+
+        Self {
+            fragments: padded.chunks(fragment_len).map(<[u8]>::to_vec).collect(),
+            message_len: message.len(),
+            checksum: message_checksum(message),
+        }
+    }
+
+    /// 原始片段數
+    pub fn seq_len(&self) -> usize {
+        self.fragments.len()
+    }
+
+    /// 產生第 `seq` 段（從 1 開始）
+    pub fn part(&self, seq: u32) -> FountainPart {
+        let mut data = vec![0u8; self.fragments[0].len()];
+        for index in choose_fragments(seq, self.seq_len(), self.checksum) {
+            xor_into(&mut data, &self.fragments[index]);
+        }
+
+        FountainPart {
+            seq,
+            seq_len: self.seq_len(),
+            message_len: self.message_len,
+            checksum: self.checksum,
+            data,
+        }
+    }
+
+    /// 產生前 `count` 段
+    pub fn parts(&self, count: usize) -> Vec<FountainPart> {
+        (1..=count as u32).map(|seq| self.part(seq)).collect()
+    }
+}
+
+// ============================================================================
+// 解碼器
+// ============================================================================
+
+/// 噴泉解碼器（接受任意順序、可重複的分段）
+#[derive(Debug, Default)]
+pub struct FountainDecoder {
+    /// 第一個分段決定的訊息參數 `(seq_len, message_len, checksum)`
+    params: Option<(usize, usize, u32)>,
+
+    /// 已還原的片段
+    simple: BTreeMap<usize, Vec<u8>>,
+
+    /// 尚未能還原的混合分段
+    mixed: Vec<(BTreeSet<usize>, Vec<u8>)>,
+
+    /// 已處理的分段序號
+    seen: BTreeSet<u32>,
+}
+
+impl FountainDecoder {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// 接收一個分段，返回訊息是否已可還原
+    pub fn receive(&mut self, part: FountainPart) -> Result<bool, FountainError> {
+        part.validate()?;
+        let params = (part.seq_len, part.message_len, part.checksum);
+        match self.params {
+            Some(expected) if expected != params => return Err(FountainError::InconsistentPart),
+            Some(_) => {}
+            None => self.params = Some(params),
+        }
+        if self.is_complete() || !self.seen.insert(part.seq) {
+            return Ok(self.is_complete());
+        }
+
+        let indexes = choose_fragments(part.seq, part.seq_len, part.checksum);
+        self.add(indexes, part.data);
+        Ok(self.is_complete())
+    }
+
+    /// 加入一個（可能是混合的）分段並進行剝離解碼
+    fn add(&mut self, indexes: BTreeSet<usize>, data: Vec<u8>) {
+        let mut queue = vec![(indexes, data)];
+
+        while let Some((mut indexes, mut data)) = queue.pop() {
+            // 以已知片段化簡
+            for index in indexes.clone() {
+                if let Some(known) = self.simple.get(&index) {
+                    xor_into(&mut data, known);
+                    indexes.remove(&index);
+                }
+            }
+
+            match indexes.len() {
+                0 => {}
+                1 => {
+                    let index = *indexes.iter().next().expect("one index");
+                    self.simple.insert(index, data);
+
+                    // 新片段可能讓其他混合分段降為單一片段
+                    let (reducible, pending): (Vec<_>, Vec<_>) = std::mem::take(&mut self.mixed)
+                        .into_iter()
+                        .partition(|(mixed, _)| mixed.contains(&index));
+                    self.mixed = pending;
+                    queue.extend(reducible);
+                }
+                _ => {
+                    if !self.mixed.iter().any(|(mixed, _)| *mixed == indexes) {
+                        self.mixed.push((indexes, data));
+                    }
+                }
+            }
+        }
+    }
+
+    /// 是否已還原所有片段
+    pub fn is_complete(&self) -> bool {
+        matches!(self.params, Some((seq_len, _, _)) if self.simple.len() == seq_len)
+    }
+
+    /// 已還原片段的比例（0.0 ~ 1.0）
+    pub fn progress(&self) -> f64 {
+        match self.params {
+            Some((seq_len, _, _)) => self.simple.len() as f64 / seq_len as f64,
+            None => 0.0,
+        }
+    }
+
+    /// 還原出的訊息（尚未完整時返回 `None`）
+    pub fn message(&self) -> Option<Result<Vec<u8>, FountainError>> {
+        if !self.is_complete() {
+            return None;
+        }
+        let (_, message_len, checksum) = self.params?;
+
+        let mut message: Vec<u8> = self.simple.values().flatten().copied().collect();
+        message.truncate(message_len);
+
+        Some(if message_checksum(&message) == checksum {
+            Ok(message)
+        } else {
+            Err(FountainError::ChecksumMismatch)
+        })
+    }
+}
+
+// ============================================================================
+// 測試
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_fountain_recovers_message_without_some_simple_parts() {
+        let message: Vec<u8> = (0..1000u32).map(|i| (i * 7 % 251) as u8).collect();
+        let encoder = FountainEncoder::new(&message, 120);
+        assert_eq!(encoder.seq_len(), 9);
+
+        // 丟掉兩個原始片段，只靠後續的混合分段還原（順序打亂、含重複）
+        let mut decoder = FountainDecoder::new();
+        let mut parts: Vec<String> = encoder
+            .parts(60)
+            .into_iter()
+            .filter(|part| part.seq != 2 && part.seq != 5)
+            .map(|part| part.to_ur("frost-json"))
+            .collect();
+        parts.reverse();
+        parts.push(parts[0].clone());
+
+        for text in parts {
+            let (ur_type, part) = FountainPart::from_ur(&text).unwrap();
+            assert_eq!(ur_type, "FROST-JSON");
+            if decoder.receive(part).unwrap() {
+                break;
+            }
+        }
+
+        assert_eq!(decoder.message().unwrap().unwrap(), message);
+
+        // 其他訊息的分段會被拒絕
+        let other = FountainEncoder::new(b"another message", 120).part(1);
+        assert!(matches!(
+            decoder.receive(other),
+            Err(FountainError::InconsistentPart)
+        ));
+
+        // 過大的 seq_len 與長度不符的片段在解析時就被拒絕
+        let mut oversized = encoder.part(1);
+        oversized.seq_len = usize::MAX;
+        assert!(FountainPart::from_ur(&oversized.to_ur("frost-json")).is_err());
+        let mut truncated = encoder.part(1);
+        truncated.data.pop();
+        assert!(matches!(
+            FountainPart::from_ur(&truncated.to_ur("frost-json")),
+            Err(FountainError::InvalidPart(_))
+        ));
+    }
+}
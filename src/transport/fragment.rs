@@ -0,0 +1,366 @@
+//! # Fragmentation - 分片、重組與選擇性確認
+//!
+//! LoRa 一個封包只能承載數十 bytes，較大的訊息必須分片傳送。
+//! 此模組提供鏈路兩端共用的資料結構：
+//!
+//! - `Fragment`：帶有訊息序號、片段索引與總片段數的分片
+//! - `SelectiveAck`：接收端回報的選擇性確認（列出仍缺少的片段，空清單即為 ACK）
+//! - `Reassembler`：接收端的重組緩衝區，處理亂序、重複與已完成訊息；
+//!   總片段數與既有緩衝區不符的分片會被拒絕，太久沒有新片段的緩衝區會被清除
+//!
+//! ## 分片格式（`Fragment::encode` / `Fragment::decode`）
+//!
+//! ```text
+//! [message_seq: u16 BE][index: u8][total: u8][data ...]
+//! ```
+
+use std::collections::{BTreeMap, HashMap, VecDeque};
+
+/// 分片標頭長度（bytes）
+pub const FRAGMENT_HEADER_LEN: usize = 4;
+
+/// 接收端記住的已完成訊息數量（用於重複抑制）
+const COMPLETED_HISTORY: usize = 256;
+
+/// 預設的重組緩衝區存活時間：超過此時間沒有新片段即清除（毫秒）
+///
+/// 受 1% 工作週期限制時，SF12 的相鄰片段可相隔兩分鐘以上，因此取較寬鬆的值。
+pub const DEFAULT_STALE_AFTER_MS: u64 = 10 * 60 * 1000;
+
+// ============================================================================
+// 分片
+// ============================================================================
+
+/// 一個分片
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Fragment {
+    /// 訊息序號（每則訊息遞增）
+    pub message_seq: u16,
+
+    /// 片段索引（從 0 開始）
+    pub index: u8,
+
+    /// 此訊息的總片段數
+    pub total: u8,
+
+    /// 片段內容
+    pub data: Vec<u8>,
+}
+
+impl Fragment {
+    /// 空中傳輸的位元組數（標頭 + 內容）
+    pub fn frame_len(&self) -> usize {
+        FRAGMENT_HEADER_LEN + self.data.len()
+    }
+
+    /// 編碼為空中傳輸的封包
+    pub fn encode(&self) -> Vec<u8> {
+        let mut frame = Vec::with_capacity(self.frame_len());
+        frame.extend_from_slice(&self.message_seq.to_be_bytes());
+        frame.push(self.index);
+        frame.push(self.total);
+        frame.extend_from_slice(&self.data);
+        frame
+    }
+
+    /// 由封包解碼（標頭不完整、總片段數為 0 或索引越界時返回 `None`）
+    pub fn decode(frame: &[u8]) -> Option<Self> {
+        if frame.len() < FRAGMENT_HEADER_LEN {
+            return None;
+        }
+        let (index, total) = (frame[2], frame[3]);
+        if index >= total {
+            return None;
+        }
+
+        Some(Self {
+            message_seq: u16::from_be_bytes([frame[0], frame[1]]),
+            index,
+            total,
+            data: frame[FRAGMENT_HEADER_LEN..].to_vec(),
+        })
+    }
+}
+
+/// 將訊息切割成分片
+///
+/// `frame_size` 為每個封包的上限（含標頭）。訊息需要超過 255 個分片時返回 `None`。
+pub fn split(message_seq: u16, payload: &[u8], frame_size: usize) -> Option<Vec<Fragment>> {
+    let chunk_size = frame_size.saturating_sub(FRAGMENT_HEADER_LEN).max(1);
+    let chunks: Vec<&[u8]> = if payload.is_empty() {
+        vec![payload]
+    } else {
+        payload.chunks(chunk_size).collect()
+    };
+    let total = u8::try_from(chunks.len()).ok()?;
+
+    Some(
+        chunks
+            .into_iter()
+            .enumerate()
+            .map(|(index, data)| Fragment {
+                message_seq,
+                index: index as u8,
+                total,
+                data: data.to_vec(),
+            })
+            .collect(),
+    )
+}
+
+// ============================================================================
+// 選擇性確認
+// ============================================================================
+
+/// 接收端回報的選擇性確認
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SelectiveAck {
+    /// 確認的訊息序號
+    pub message_seq: u16,
+
+    /// 仍缺少的片段索引（空清單表示訊息已完整收到）
+    pub missing: Vec<u8>,
+}
+
+impl SelectiveAck {
+    /// 是否已完整收到（ACK；否則為 NACK）
+    pub fn is_complete(&self) -> bool {
+        self.missing.is_empty()
+    }
+
+    /// 空中傳輸的位元組數（`[message_seq: u16][count: u8][missing ...]`）
+    pub fn frame_len(&self) -> usize {
+        3 + self.missing.len()
+    }
+}
+
+// ============================================================================
+// 接收端重組
+// ============================================================================
+
+/// 接收一個分片的結果
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum FragmentOutcome {
+    /// 已收下，訊息尚未完整
+    Accepted,
+
+    /// 重複的分片（或已完成訊息的分片），已忽略
+    Duplicate,
+
+    /// 與同一訊息的其他分片不一致（總片段數不符或索引越界），已丟棄
+    Rejected,
+
+    /// 訊息已完整重組（每則訊息只會返回一次）
+    Complete(Vec<u8>),
+}
+
+/// 單一訊息的重組緩衝區
+#[derive(Debug)]
+struct ReassemblyBuffer {
+    total: u8,
+    fragments: BTreeMap<u8, Vec<u8>>,
+
+    /// 最後一次收到片段的時間（毫秒）
+    last_seen_ms: u64,
+}
+
+/// 接收端的重組器
+#[derive(Debug)]
+pub struct Reassembler {
+    /// 進行中的訊息
+    buffers: HashMap<u16, ReassemblyBuffer>,
+
+    /// 最近完成的訊息序號（重複抑制）
+    completed: VecDeque<u16>,
+
+    /// 緩衝區超過此時間沒有新片段即清除（毫秒）
+    stale_after_ms: u64,
+}
+
+impl Default for Reassembler {
+    fn default() -> Self {
+        Self {
+            buffers: HashMap::new(),
+            completed: VecDeque::new(),
+            stale_after_ms: DEFAULT_STALE_AFTER_MS,
+        }
+    }
+}
+
+impl Reassembler {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// 設定緩衝區的存活時間（預設 `DEFAULT_STALE_AFTER_MS`）
+    pub fn with_stale_after(mut self, stale_after_ms: u64) -> Self {
+        self.stale_after_ms = stale_after_ms;
+        self
+    }
+
+    /// 接收一個分片（可亂序、可重複）
+    ///
+    /// `now_ms` 為接收端的時鐘，用來清除太久沒有新片段的緩衝區。
+    pub fn receive(&mut self, fragment: Fragment, now_ms: u64) -> FragmentOutcome {
+        self.evict_stale(now_ms);
+
+        if self.completed.contains(&fragment.message_seq) {
+            return FragmentOutcome::Duplicate;
+        }
+        if fragment.index >= fragment.total {
+            return FragmentOutcome::Rejected;
+        }
+
+        let buffer = self
+            .buffers
+            .entry(fragment.message_seq)
+            .or_insert_with(|| ReassemblyBuffer {
+                total: fragment.total,
+                fragments: BTreeMap::new(),
+                last_seen_ms: now_ms,
+            });
+
+        // 同一訊息的分片必須宣告相同的總片段數，否則無法判斷何時重組完成
+        if fragment.total != buffer.total {
+            return FragmentOutcome::Rejected;
+        }
+        if buffer.fragments.contains_key(&fragment.index) {
+            return FragmentOutcome::Duplicate;
+        }
+        buffer.fragments.insert(fragment.index, fragment.data);
+        buffer.last_seen_ms = now_ms;
+
+        if buffer.fragments.len() < buffer.total as usize {
+            return FragmentOutcome::Accepted;
+        }
+
+        // 所有片段到齊：依索引順序重組
+        let buffer = self
+            .buffers
+            .remove(&fragment.message_seq)
+            .expect("buffer exists");
+        self.completed.push_back(fragment.message_seq);
+        if self.completed.len() > COMPLETED_HISTORY {
+            self.completed.pop_front();
+        }
+
+        FragmentOutcome::Complete(buffer.fragments.into_values().flatten().collect())
+    }
+
+    /// 產生指定訊息的選擇性確認
+    ///
+    /// `total` 為發送端宣告的總片段數（接收端可能一個片段都還沒收到）。
+    pub fn ack(&self, message_seq: u16, total: u8) -> SelectiveAck {
+        let missing = if self.completed.contains(&message_seq) {
+            Vec::new()
+        } else {
+            match self.buffers.get(&message_seq) {
+                Some(buffer) => (0..buffer.total)
+                    .filter(|index| !buffer.fragments.contains_key(index))
+                    .collect(),
+                None => (0..total).collect(),
+            }
+        };
+
+        SelectiveAck {
+            message_seq,
+            missing,
+        }
+    }
+
+    /// 放棄一則訊息的重組（發送端已放棄）
+    pub fn discard(&mut self, message_seq: u16) {
+        self.buffers.remove(&message_seq);
+    }
+
+    /// 清除超過存活時間沒有新片段的緩衝區，返回被清除的訊息序號
+    pub fn evict_stale(&mut self, now_ms: u64) -> Vec<u16> {
+        let stale_after_ms = self.stale_after_ms;
+        let stale: Vec<u16> = self
+            .buffers
+            .iter()
+            .filter(|(_, buffer)| now_ms.saturating_sub(buffer.last_seen_ms) > stale_after_ms)
+            .map(|(message_seq, _)| *message_seq)
+            .collect();
+        for message_seq in &stale {
+            self.buffers.remove(message_seq);
+        }
+        stale
+    }
+
+    /// 進行中的重組數量
+    pub fn pending_messages(&self) -> usize {
+        self.buffers.len()
+    }
+}
+
+// ============================================================================
+// 測試
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_reassembly_out_of_order_with_duplicates() {
+        let payload: Vec<u8> = (0..=200).collect();
+        let mut fragments = split(7, &payload, 64).unwrap();
+        assert_eq!(fragments.len(), 4);
+        assert!(fragments.iter().all(|f| f.frame_len() <= 64));
+
+        let mut receiver = Reassembler::new();
+        let last = fragments.pop().unwrap();
+        fragments.reverse();
+
+        for fragment in fragments.clone() {
+            assert_eq!(receiver.receive(fragment, 0), FragmentOutcome::Accepted);
+        }
+        assert_eq!(receiver.receive(fragments[0].clone(), 0), FragmentOutcome::Duplicate);
+        assert_eq!(receiver.ack(7, 4).missing, vec![3]);
+
+        // 經過空中編碼後仍是同一個分片
+        let frame = last.encode();
+        assert_eq!(frame.len(), last.frame_len());
+        assert_eq!(&frame[..FRAGMENT_HEADER_LEN], &[0, 7, 3, 4]);
+        let last = Fragment::decode(&frame).unwrap();
+
+        assert_eq!(receiver.receive(last.clone(), 0), FragmentOutcome::Complete(payload));
+        assert!(receiver.ack(7, 4).is_complete());
+
+        // 已完成訊息的重傳不會被再次交付
+        assert_eq!(receiver.receive(last, 0), FragmentOutcome::Duplicate);
+        assert_eq!(receiver.pending_messages(), 0);
+    }
+
+    #[test]
+    fn test_rejects_inconsistent_fragments_and_evicts_stale_buffers() {
+        let mut fragments = split(9, &[0xAB; 100], 24).unwrap();
+        let mut receiver = Reassembler::new().with_stale_after(1_000);
+        assert_eq!(receiver.receive(fragments[0].clone(), 0), FragmentOutcome::Accepted);
+
+        // 總片段數與既有緩衝區不符：不可被當成同一則訊息的片段
+        let mut forged = fragments[1].clone();
+        forged.total = 2;
+        assert_eq!(receiver.receive(forged, 0), FragmentOutcome::Rejected);
+        assert_eq!(receiver.ack(9, 5).missing, vec![1, 2, 3, 4]);
+
+        // 索引越界的封包無法解碼
+        let mut frame = fragments[1].encode();
+        frame[2] = frame[3];
+        assert!(Fragment::decode(&frame).is_none());
+        assert!(Fragment::decode(&frame[..3]).is_none());
+
+        // 發送端消失後，緩衝區在存活時間過後被清除
+        assert_eq!(receiver.evict_stale(1_000), Vec::<u16>::new());
+        assert_eq!(receiver.evict_stale(1_001), vec![9]);
+        assert_eq!(receiver.pending_messages(), 0);
+
+        // 清除後重新開始重組同一則訊息
+        let last = fragments.pop().unwrap();
+        for fragment in fragments {
+            assert_eq!(receiver.receive(fragment, 2_000), FragmentOutcome::Accepted);
+        }
+        assert!(matches!(receiver.receive(last, 2_500), FragmentOutcome::Complete(_)));
+    }
+}
@@ -14,23 +14,51 @@
 //!
 //! ## 模組結構
 //!
-//! - `Transport` trait：定義傳輸介面
+//! - `Transport` trait：同步的單向傳輸介面（只能發送，用於展示與記錄）
+//! - `AsyncTransport` trait：異步的雙向傳輸介面，收發有型別的 `FrostMessage`
 //! - `StdoutTransport`：終端機輸出實作（用於展示）
-//! - `SimulatedLoRaTransport`：模擬 LoRa 傳輸（延遲、掉包、分片）
+//! - `SimulatedLoRaTransport`：模擬 LoRa 傳輸（延遲、掉包、分片重組與選擇性確認）
+//! - `EventStream`：Dashboard 的即時事件流（Server-Sent Events，支援 `Last-Event-ID` 續傳）
+//! - `RadioConfig`：LoRa 物理層模型（空中時間、工作週期、路徑損耗、突發掉包）
+//! - `ChannelNetwork`：行程內的 `AsyncTransport` 網路，可選擇經過模擬 LoRa 鏈路
+//! - `FileTransport`：以目錄交換訊息檔的 `AsyncTransport`（適用於 air-gapped 儀式）
+//! - `QrTransport`：以噴泉編碼的動畫 QR Code 交換訊息（僅有相機與螢幕的冷簽署者）
+//! - `WebSocketHub` / `WebSocketSigner`：簽署者主動連線、由協調者推送請求（NAT 之後的簽署者）
 //! - 未來擴展：
-//!   - `FileTransport`：檔案系統傳輸
 //!   - `HttpTransport`：HTTP API 傳輸
 
 use serde::{Deserialize, Serialize};
+use std::future::Future;
+use thiserror::Error;
 
 // ============================================================================
 // 子模組
 // ============================================================================
 
+pub mod channel;
+pub mod codec;
+pub mod events;
+pub mod file;
+pub mod fountain;
+pub mod fragment;
+pub mod protocol;
+pub mod qr;
+pub mod radio;
 pub mod simulated_lora;
+pub mod websocket;
 
 // 重新匯出常用類型
-pub use simulated_lora::{LoRaConfig, LoRaTransportState, SimulatedLoRaTransport, TransportEvent};
+pub use channel::{ChannelNetwork, ChannelTransport};
+pub use codec::CodecError;
+pub use events::{EventStream, StreamEvent};
+pub use file::FileTransport;
+pub use protocol::{FrostMessage, Participant, TransportEnvelope};
+pub use qr::{QrError, QrFormat, QrTransport};
+pub use radio::{GilbertElliott, RadioConfig, RadioError};
+pub use simulated_lora::{
+    LoRaConfig, LoRaTransportState, SimulatedLoRaTransport, SimulationClock, TransportEvent,
+};
+pub use websocket::{WebSocketHub, WebSocketHubTransport, WebSocketSigner};
 
 // ============================================================================
 // 訊息結構定義
@@ -56,6 +84,9 @@ pub struct MessageMetadata {
 /// 訊息類型（用於分類和統計）
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub enum MessageType {
+    /// Round 1: Coordinator -> Signer 的承諾請求
+    CommitmentRequest,
+
     /// Round 1: Signer -> Coordinator 的承諾
     Round1Commitment,
 
@@ -68,6 +99,12 @@ pub enum MessageType {
     /// 最終簽章（Coordinator -> 廣播）
     FinalSignature,
 
+    /// 會話中止（Coordinator -> Signers）
+    Abort,
+
+    /// P2P 模式的 Session 提議（Peer -> Peers）
+    SessionProposal,
+
     /// 其他訊息
     Other,
 }
@@ -75,10 +112,13 @@ pub enum MessageType {
 impl std::fmt::Display for MessageType {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
+            MessageType::CommitmentRequest => write!(f, "CommitmentRequest"),
             MessageType::Round1Commitment => write!(f, "Round1Commitment"),
             MessageType::SigningPackage => write!(f, "SigningPackage"),
             MessageType::Round2SignatureShare => write!(f, "Round2SignatureShare"),
             MessageType::FinalSignature => write!(f, "FinalSignature"),
+            MessageType::Abort => write!(f, "Abort"),
+            MessageType::SessionProposal => write!(f, "SessionProposal"),
             MessageType::Other => write!(f, "Other"),
         }
     }
@@ -95,7 +135,8 @@ impl std::fmt::Display for MessageType {
 /// 為什麼設計成同步介面而不是異步？
 /// - 方便實作最簡單的版本（Stdout、File）
 /// - FROST 協議本身是「分輪次」的，不需要高並發傳輸
-/// - 未來如果需要異步版本，可以再定義 `AsyncTransport` trait
+///
+/// 這個介面只能發送、無法接收；要實際在傳輸層上執行協議，請使用 `AsyncTransport`。
 ///
 /// ## 使用範例
 ///
@@ -135,6 +176,71 @@ pub trait Transport {
     }
 }
 
+// ============================================================================
+// AsyncTransport Trait - 可收發的異步介面
+// ============================================================================
+
+/// 傳輸錯誤
+#[derive(Debug, Error)]
+pub enum TransportError {
+    /// 傳輸已關閉，不會再有訊息
+    #[error("Transport closed")]
+    Closed,
+
+    /// 接收者不存在（未連上網路或已離線）
+    #[error("Unknown recipient: {0}")]
+    UnknownRecipient(Participant),
+
+    /// 訊息編碼 / 解碼失敗
+    #[error("Message encoding error: {0}")]
+    Encoding(String),
+
+    /// 二進位格式錯誤（版本不符、校驗碼錯誤、欄位缺漏）
+    #[error("Wire format error: {0}")]
+    Codec(#[from] CodecError),
+
+    /// 檔案系統錯誤（`FileTransport`）
+    #[error("I/O error: {0}")]
+    Io(#[from] std::io::Error),
+
+    /// QR Code 渲染或辨識失敗（`QrTransport`）
+    #[error("QR error: {0}")]
+    Qr(#[from] QrError),
+
+    /// WebSocket 連線或登記失敗（`WebSocketSigner`）
+    #[error("WebSocket error: {0}")]
+    WebSocket(String),
+}
+
+/// 異步的訊息傳輸介面
+///
+/// 每個實例代表網路上的一個端點（`local()`），以 `Participant` 定址收發
+/// `FrostMessage`。協調者與簽署者可以只依賴這個 trait 執行完整協議
+/// （見 `Coordinator::orchestrate_over_transport` 與 `Signer::serve`）。
+///
+/// 實作不應阻塞執行緒；模擬延遲請使用 `tokio::time::sleep`。
+pub trait AsyncTransport: Send {
+    /// 本端點代表的參與者
+    fn local(&self) -> Participant;
+
+    /// 發送訊息給指定的參與者
+    ///
+    /// 返回 `Ok` 只代表訊息已交給傳輸層；不可靠的鏈路上訊息仍可能遺失。
+    fn send(
+        &mut self,
+        to: Participant,
+        message: FrostMessage,
+    ) -> impl Future<Output = Result<(), TransportError>> + Send;
+
+    /// 接收下一則送給本端點的訊息（沒有訊息時等待）
+    fn recv(&mut self) -> impl Future<Output = Result<TransportEnvelope, TransportError>> + Send;
+
+    /// 獲取傳輸統計資訊（可選實作）
+    fn get_stats(&self) -> Option<TransportStats> {
+        None
+    }
+}
+
 /// 傳輸統計資訊
 #[derive(Debug, Clone, Default)]
 pub struct TransportStats {
@@ -146,6 +252,27 @@ pub struct TransportStats {
 
     /// 總發送位元組數
     pub total_bytes: usize,
+
+    /// 同一批訊息以 JSON 信封（hex 欄位）編碼時的位元組數（0 表示未量測）
+    pub baseline_bytes: usize,
+}
+
+impl TransportStats {
+    /// 計入一則已編碼的訊息（`baseline` 為同一訊息的 JSON 信封大小）
+    pub fn record(&mut self, message_type: MessageType, wire: usize, baseline: usize) {
+        self.total_messages += 1;
+        self.total_bytes += wire;
+        self.baseline_bytes += baseline;
+        *self.by_type.entry(message_type).or_insert(0) += 1;
+    }
+
+    /// 相對於 JSON 編碼節省的比例（0.0 ~ 1.0），未量測時返回 `None`
+    pub fn size_reduction(&self) -> Option<f64> {
+        if self.baseline_bytes == 0 {
+            return None;
+        }
+        Some(1.0 - self.total_bytes as f64 / self.baseline_bytes as f64)
+    }
 }
 
 // ============================================================================
@@ -0,0 +1,160 @@
+//! # 協議訊息 - 在 Transport 上流動的 FROST 訊息
+//!
+//! `AsyncTransport` 傳遞的是有型別的 `FrostMessage`，而不是任意字串：
+//! - `Participant`：訊息的定址（協調者或第 N 個簽署者）
+//! - `FrostMessage`：協議每一步的內容
+//! - `TransportEnvelope`：發送者 + 接收者 + 訊息
+//!
+//! 在鏈路上以 `codec` 模組的精簡二進位格式傳送；JSON 只用於 API 與除錯。
+
+use super::MessageType;
+use crate::api::{CommitmentData, SessionId, SignatureShareData, SigningPackageData};
+use crate::message::SignPayload;
+use crate::telemetry::TraceContext;
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+// ============================================================================
+// 參與者定址
+// ============================================================================
+
+/// 協議參與者
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Participant {
+    /// 協調者
+    Coordinator,
+
+    /// 簽署者（數字 ID）
+    Signer(u16),
+}
+
+impl fmt::Display for Participant {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Participant::Coordinator => write!(f, "coordinator"),
+            Participant::Signer(id) => write!(f, "signer_{}", id),
+        }
+    }
+}
+
+// ============================================================================
+// 協議訊息
+// ============================================================================
+
+/// FROST 協議訊息
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum FrostMessage {
+    /// Coordinator -> Signer：請求 Round 1 承諾
+    CommitmentRequest {
+        session_id: SessionId,
+
+        /// 協調者 `signing_session` span 的 trace context
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        trace: Option<TraceContext>,
+    },
+
+    /// Signer -> Coordinator：Round 1 承諾
+    Round1Commitment {
+        session_id: SessionId,
+        commitment: CommitmentData,
+    },
+
+    /// Coordinator -> Signer：簽章套件
+    SigningPackage {
+        session_id: SessionId,
+        package: SigningPackageData,
+
+        /// 協調者 `signing_session` span 的 trace context
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        trace: Option<TraceContext>,
+    },
+
+    /// Signer -> Coordinator：Round 2 簽章分片
+    Round2SignatureShare {
+        session_id: SessionId,
+        share: SignatureShareData,
+    },
+
+    /// Coordinator -> Signer：最終簽章（hex）
+    FinalSignature {
+        session_id: SessionId,
+        signature: String,
+    },
+
+    /// Coordinator -> Signer：會話已中止，丟棄該 Session 的 Nonce
+    Abort {
+        session_id: SessionId,
+        reason: String,
+    },
+
+    /// Peer -> Peers：提議一個無協調者的簽章 Session（P2P 模式，見 `peer` 模組）
+    SessionProposal {
+        session_id: SessionId,
+        signer_ids: Vec<u16>,
+        payload: SignPayload,
+        /// 提議者以身分金鑰簽署的信封簽章（hex）
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        proposer_signature: Option<String>,
+    },
+}
+
+impl FrostMessage {
+    /// 訊息所屬的 Session
+    pub fn session_id(&self) -> SessionId {
+        match self {
+            FrostMessage::CommitmentRequest { session_id, .. }
+            | FrostMessage::Round1Commitment { session_id, .. }
+            | FrostMessage::SigningPackage { session_id, .. }
+            | FrostMessage::Round2SignatureShare { session_id, .. }
+            | FrostMessage::FinalSignature { session_id, .. }
+            | FrostMessage::Abort { session_id, .. }
+            | FrostMessage::SessionProposal { session_id, .. } => *session_id,
+        }
+    }
+
+    /// 協調者附帶的 trace context（只有送給簽署者的請求會攜帶）
+    pub fn trace_context(&self) -> Option<&TraceContext> {
+        match self {
+            FrostMessage::CommitmentRequest { trace, .. }
+            | FrostMessage::SigningPackage { trace, .. } => trace.as_ref(),
+            _ => None,
+        }
+    }
+
+    /// 移除 trace context（頻寬有限的鏈路上不傳遞）
+    pub fn clear_trace_context(&mut self) {
+        match self {
+            FrostMessage::CommitmentRequest { trace, .. }
+            | FrostMessage::SigningPackage { trace, .. } => *trace = None,
+            _ => {}
+        }
+    }
+
+    /// 訊息類型（用於統計與 Dashboard）
+    pub fn message_type(&self) -> MessageType {
+        match self {
+            FrostMessage::CommitmentRequest { .. } => MessageType::CommitmentRequest,
+            FrostMessage::Round1Commitment { .. } => MessageType::Round1Commitment,
+            FrostMessage::SigningPackage { .. } => MessageType::SigningPackage,
+            FrostMessage::Round2SignatureShare { .. } => MessageType::Round2SignatureShare,
+            FrostMessage::FinalSignature { .. } => MessageType::FinalSignature,
+            FrostMessage::Abort { .. } => MessageType::Abort,
+            FrostMessage::SessionProposal { .. } => MessageType::SessionProposal,
+        }
+    }
+}
+
+/// 已定址的協議訊息
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TransportEnvelope {
+    /// 發送者
+    pub from: Participant,
+
+    /// 接收者
+    pub to: Participant,
+
+    /// 訊息內容
+    pub message: FrostMessage,
+}
@@ -0,0 +1,540 @@
+//! # QR Transport - 以動畫 QR Code 傳遞訊息（僅有相機與螢幕的冷簽署者）
+//!
+//! 訊息以 `fountain` 模組編碼成多段 QR Code：
+//!
+//! - **發送**：每則訊息輸出一組 frame（PNG / SVG 檔，或直接在終端機循環播放）
+//! - **接收**：從掃描（拍照）得到的影像檔辨識 QR Code，收集足夠的分段後還原
+//!
+//! 分段可以亂序、重複或缺漏，只要掃到的數量足夠即可完成解碼。
+//!
+//! ## UR 類型
+//!
+//! - `FROST-MSG`：`codec` 編碼的 `TransportEnvelope`（`QrTransport` 使用）
+//! - `FROST-JSON`：CLI 的承諾、簽章套件、簽章分片 JSON 檔（`qr-export` / `qr-import`）
+
+use super::fountain::{FountainDecoder, FountainEncoder, FountainError, FountainPart};
+use super::{
+    codec, AsyncTransport, FrostMessage, Participant, TransportEnvelope, TransportError,
+    TransportStats,
+};
+use qrcode::render::{svg, unicode};
+use qrcode::{EcLevel, QrCode};
+use std::collections::{BTreeSet, HashMap};
+use std::fmt;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+use std::time::Duration;
+use thiserror::Error;
+
+/// `QrTransport` 的 UR 類型
+pub const UR_TYPE_MESSAGE: &str = "FROST-MSG";
+
+/// CLI JSON 檔的 UR 類型
+pub const UR_TYPE_JSON: &str = "FROST-JSON";
+
+/// 預設的片段大小（bytes）：hex 後約 400 個英數字元，一般手機相機可穩定辨識
+pub const DEFAULT_MAX_FRAGMENT_LEN: usize = 200;
+
+// ============================================================================
+// 錯誤定義
+// ============================================================================
+
+#[derive(Debug, Error)]
+pub enum QrError {
+    #[error("Failed to render QR code: {0}")]
+    Render(String),
+
+    #[error("Failed to read image {path}: {reason}")]
+    Image { path: PathBuf, reason: String },
+
+    #[error("Unexpected UR type {found} (expected {expected})")]
+    UnexpectedType { expected: String, found: String },
+
+    #[error("Incomplete: recovered {recovered:.0}% of the message")]
+    Incomplete { recovered: f64 },
+
+    #[error("Fountain decoding error: {0}")]
+    Fountain(#[from] FountainError),
+
+    #[error("I/O error: {0}")]
+    Io(#[from] std::io::Error),
+}
+
+// ============================================================================
+// 輸出格式
+// ============================================================================
+
+/// QR Code 的輸出格式
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum QrFormat {
+    /// 每個 frame 一個 PNG 檔
+    Png,
+
+    /// 每個 frame 一個 SVG 檔
+    Svg,
+
+    /// 在終端機循環播放（Unicode 方塊字元）
+    Terminal,
+}
+
+impl fmt::Display for QrFormat {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            QrFormat::Png => write!(f, "png"),
+            QrFormat::Svg => write!(f, "svg"),
+            QrFormat::Terminal => write!(f, "terminal"),
+        }
+    }
+}
+
+impl FromStr for QrFormat {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_ascii_lowercase().as_str() {
+            "png" => Ok(QrFormat::Png),
+            "svg" => Ok(QrFormat::Svg),
+            "terminal" | "term" => Ok(QrFormat::Terminal),
+            other => Err(format!("unknown QR format '{}' (png, svg, terminal)", other)),
+        }
+    }
+}
+
+// ============================================================================
+// 編碼與渲染
+// ============================================================================
+
+/// 把資料編碼成多段 UR 字串
+///
+/// 產生 `seq_len + extra_parts` 段：前 `seq_len` 段是原始片段，
+/// 之後的混合分段讓掃描端可以漏掉部分 frame。
+pub fn encode_parts(
+    data: &[u8],
+    ur_type: &str,
+    max_fragment_len: usize,
+    extra_parts: usize,
+) -> Vec<String> {
+    let encoder = FountainEncoder::new(data, max_fragment_len);
+    encoder
+        .parts(encoder.seq_len() + extra_parts)
+        .iter()
+        .map(|part| part.to_ur(ur_type))
+        .collect()
+}
+
+fn qr_code(text: &str) -> Result<QrCode, QrError> {
+    QrCode::with_error_correction_level(text.as_bytes(), EcLevel::M)
+        .map_err(|e| QrError::Render(e.to_string()))
+}
+
+/// 以終端機字元渲染一個 frame
+pub fn render_terminal(text: &str) -> Result<String, QrError> {
+    Ok(qr_code(text)?
+        .render::<unicode::Dense1x2>()
+        .dark_color(unicode::Dense1x2::Light)
+        .light_color(unicode::Dense1x2::Dark)
+        .build())
+}
+
+/// 把每一段寫成 `dir/frame-0001.png`（或 `.svg`），返回寫出的檔案
+///
+/// `QrFormat::Terminal` 不寫檔，請使用 `play_terminal`。
+pub fn write_frames(
+    parts: &[String],
+    dir: &Path,
+    format: QrFormat,
+) -> Result<Vec<PathBuf>, QrError> {
+    fs::create_dir_all(dir)?;
+
+    let mut paths = Vec::with_capacity(parts.len());
+    for (index, text) in parts.iter().enumerate() {
+        let code = qr_code(text)?;
+        let path = dir.join(format!("frame-{:04}.{}", index + 1, format));
+
+        match format {
+            QrFormat::Png => code
+                .render::<image::Luma<u8>>()
+                .min_dimensions(480, 480)
+                .build()
+                .save(&path)
+                .map_err(|e| QrError::Render(e.to_string()))?,
+            QrFormat::Svg => fs::write(
+                &path,
+                code.render::<svg::Color>().min_dimensions(480, 480).build(),
+            )?,
+            QrFormat::Terminal => continue,
+        }
+        paths.push(path);
+    }
+
+    Ok(paths)
+}
+
+/// 在終端機循環播放 frame（動畫 QR Code）
+pub async fn play_terminal(
+    parts: &[String],
+    interval: Duration,
+    loops: usize,
+) -> Result<(), QrError> {
+    let frames = parts
+        .iter()
+        .map(|text| render_terminal(text))
+        .collect::<Result<Vec<_>, _>>()?;
+
+    for _ in 0..loops.max(1) {
+        for (index, frame) in frames.iter().enumerate() {
+            // 清除畫面並回到左上角
+            print!("\x1b[2J\x1b[H");
+            println!("{}", frame);
+            println!("frame {}/{}", index + 1, frames.len());
+            tokio::time::sleep(interval).await;
+        }
+    }
+
+    Ok(())
+}
+
+// ============================================================================
+// 掃描與解碼
+// ============================================================================
+
+/// 辨識影像檔中的所有 QR Code 內容
+pub fn scan_image(path: &Path) -> Result<Vec<String>, QrError> {
+    let image = image::open(path)
+        .map_err(|e| QrError::Image {
+            path: path.to_path_buf(),
+            reason: e.to_string(),
+        })?
+        .to_luma8();
+
+    let mut prepared = rqrr::PreparedImage::prepare(image);
+    Ok(prepared
+        .detect_grids()
+        .into_iter()
+        .filter_map(|grid| grid.decode().ok().map(|(_, content)| content))
+        .collect())
+}
+
+/// 收集多段 QR Code 並還原資料
+pub struct QrDecoder {
+    ur_type: String,
+    decoder: FountainDecoder,
+}
+
+impl QrDecoder {
+    pub fn new(ur_type: &str) -> Self {
+        Self {
+            ur_type: ur_type.to_ascii_uppercase(),
+            decoder: FountainDecoder::new(),
+        }
+    }
+
+    /// 加入一段掃描到的文字，返回是否已可還原
+    pub fn receive(&mut self, text: &str) -> Result<bool, QrError> {
+        let (ur_type, part) = FountainPart::from_ur(text)?;
+        if ur_type != self.ur_type {
+            return Err(QrError::UnexpectedType {
+                expected: self.ur_type.clone(),
+                found: ur_type,
+            });
+        }
+        Ok(self.decoder.receive(part)?)
+    }
+
+    /// 加入一個影像檔中的所有 QR Code，返回是否已可還原
+    pub fn receive_image(&mut self, path: &Path) -> Result<bool, QrError> {
+        for text in scan_image(path)? {
+            if self.receive(&text)? {
+                return Ok(true);
+            }
+        }
+        Ok(self.decoder.is_complete())
+    }
+
+    /// 已還原的比例（0.0 ~ 1.0）
+    pub fn progress(&self) -> f64 {
+        self.decoder.progress()
+    }
+
+    /// 還原出的資料
+    pub fn finish(&self) -> Result<Vec<u8>, QrError> {
+        match self.decoder.message() {
+            Some(result) => Ok(result?),
+            None => Err(QrError::Incomplete {
+                recovered: self.progress() * 100.0,
+            }),
+        }
+    }
+}
+
+// ============================================================================
+// QrTransport
+// ============================================================================
+
+/// 以動畫 QR Code 交換訊息的 `AsyncTransport`
+///
+/// - `send` 把每則訊息渲染成一組 frame，寫到 `display_dir/<時間戳>-<接收者>/`
+///   （或在終端機播放）
+/// - `recv` 輪詢 `scan_dir` 中的影像檔（相機拍下的 frame），還原出寄給自己的訊息；
+///   用過的影像移到 `scan_dir/processed/`
+pub struct QrTransport {
+    local: Participant,
+    format: QrFormat,
+    display_dir: PathBuf,
+    scan_dir: PathBuf,
+    max_fragment_len: usize,
+    poll_interval: Duration,
+    frame_interval: Duration,
+
+    /// 進行中的解碼（依 checksum 區分不同訊息）
+    decoders: HashMap<String, FountainDecoder>,
+
+    /// 已讀過的影像檔
+    scanned: BTreeSet<PathBuf>,
+
+    stats: TransportStats,
+}
+
+impl QrTransport {
+    /// 建立 QR 傳輸端點
+    ///
+    /// # 參數
+    /// - `local`: 此端點代表的參與者
+    /// - `display_dir`: 發送時寫出 frame 的目錄
+    /// - `scan_dir`: 接收時讀取掃描影像的目錄
+    pub fn new(
+        local: Participant,
+        format: QrFormat,
+        display_dir: impl Into<PathBuf>,
+        scan_dir: impl Into<PathBuf>,
+    ) -> Self {
+        Self {
+            local,
+            format,
+            display_dir: display_dir.into(),
+            scan_dir: scan_dir.into(),
+            max_fragment_len: DEFAULT_MAX_FRAGMENT_LEN,
+            poll_interval: Duration::from_millis(500),
+            frame_interval: Duration::from_millis(400),
+            decoders: HashMap::new(),
+            scanned: BTreeSet::new(),
+            stats: TransportStats::default(),
+        }
+    }
+
+    /// 設定每段的最大片段大小（越小 QR Code 越容易掃描，但 frame 越多）
+    pub fn with_max_fragment_len(mut self, len: usize) -> Self {
+        self.max_fragment_len = len.max(1);
+        self
+    }
+
+    /// 設定輪詢 `scan_dir` 的間隔（預設 500ms）
+    pub fn with_poll_interval(mut self, interval: Duration) -> Self {
+        self.poll_interval = interval;
+        self
+    }
+
+    /// 終端機播放時每個 frame 的顯示時間（預設 400ms）
+    pub fn with_frame_interval(mut self, interval: Duration) -> Self {
+        self.frame_interval = interval;
+        self
+    }
+
+    /// 處理一段掃描到的文字，完成一則訊息時返回其內容
+    fn accept(&mut self, text: &str) -> Option<Vec<u8>> {
+        let (ur_type, part) = match FountainPart::from_ur(text) {
+            Ok(parsed) => parsed,
+            Err(e) => {
+                tracing::warn!(error = %e, "Ignoring unrecognized QR code");
+                return None;
+            }
+        };
+        if ur_type != UR_TYPE_MESSAGE {
+            tracing::warn!(ur_type = %ur_type, "Ignoring QR code of another type");
+            return None;
+        }
+
+        let key = format!("{:08x}-{}", part.checksum, part.message_len);
+        let decoder = self.decoders.entry(key.clone()).or_default();
+        match decoder.receive(part) {
+            Ok(true) => {}
+            Ok(false) => return None,
+            Err(e) => {
+                tracing::warn!(error = %e, "Ignoring inconsistent QR part");
+                return None;
+            }
+        }
+
+        let decoder = self.decoders.remove(&key)?;
+        match decoder.message()? {
+            Ok(message) => Some(message),
+            Err(e) => {
+                tracing::warn!(error = %e, "Discarding corrupted QR message");
+                None
+            }
+        }
+    }
+
+    /// 掃描 `scan_dir` 中的新影像，返回第一則寄給自己的完整訊息
+    fn scan_once(&mut self) -> Result<Option<TransportEnvelope>, TransportError> {
+        fs::create_dir_all(&self.scan_dir)?;
+
+        let mut images: Vec<PathBuf> = fs::read_dir(&self.scan_dir)?
+            .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+            .filter(|path| path.is_file() && !self.scanned.contains(path))
+            .collect();
+        images.sort();
+
+        for path in images {
+            self.scanned.insert(path.clone());
+            let texts = match scan_image(&path) {
+                Ok(texts) => texts,
+                Err(e) => {
+                    tracing::warn!(
+                        file = %path.display(),
+                        error = %e,
+                        "Skipping unreadable image"
+                    );
+                    continue;
+                }
+            };
+
+            for text in texts {
+                let Some(wire) = self.accept(&text) else {
+                    continue;
+                };
+                // 校驗碼相符但內容無法解碼（例如其他版本產生的 QR Code）時略過
+                let envelope = match codec::decode(&wire) {
+                    Ok(envelope) => envelope,
+                    Err(e) => {
+                        tracing::warn!(error = %e, "Skipping undecodable QR message");
+                        continue;
+                    }
+                };
+                if envelope.to != self.local {
+                    tracing::warn!(
+                        to = %envelope.to,
+                        "Ignoring QR message addressed to another participant"
+                    );
+                    continue;
+                }
+                self.archive_scanned()?;
+                return Ok(Some(envelope));
+            }
+        }
+
+        Ok(None)
+    }
+
+    /// 把已讀過的影像移到 `scan_dir/processed/`
+    fn archive_scanned(&mut self) -> Result<(), TransportError> {
+        let processed = self.scan_dir.join("processed");
+        fs::create_dir_all(&processed)?;
+        for path in std::mem::take(&mut self.scanned) {
+            if let Some(name) = path.file_name() {
+                fs::rename(&path, processed.join(name))?;
+            }
+        }
+        Ok(())
+    }
+}
+
+impl AsyncTransport for QrTransport {
+    fn local(&self) -> Participant {
+        self.local
+    }
+
+    async fn send(&mut self, to: Participant, message: FrostMessage) -> Result<(), TransportError> {
+        let message_type = message.message_type();
+        let envelope = TransportEnvelope {
+            from: self.local,
+            to,
+            message,
+        };
+        let wire = codec::encode(&envelope)?;
+        let baseline =
+            serde_json::to_vec(&envelope).map_err(|e| TransportError::Encoding(e.to_string()))?;
+
+        // 多產生一倍的混合分段，讓掃描端可以漏掉部分 frame
+        let seq_len = wire.len().div_ceil(self.max_fragment_len).max(1);
+        let parts = encode_parts(&wire, UR_TYPE_MESSAGE, self.max_fragment_len, seq_len);
+
+        match self.format {
+            QrFormat::Terminal => play_terminal(&parts, self.frame_interval, 3).await?,
+            format => {
+                let dir = self.display_dir.join(format!(
+                    "{:020}-{}-{}",
+                    chrono::Utc::now().timestamp_micros(),
+                    to,
+                    message_type
+                ));
+                let frames = write_frames(&parts, &dir, format)?;
+                tracing::info!(
+                    dir = %dir.display(),
+                    frames = frames.len(),
+                    to = %to,
+                    "Wrote QR frames"
+                );
+            }
+        }
+
+        self.stats.record(message_type, wire.len(), baseline.len());
+        Ok(())
+    }
+
+    async fn recv(&mut self) -> Result<TransportEnvelope, TransportError> {
+        loop {
+            if let Some(envelope) = self.scan_once()? {
+                return Ok(envelope);
+            }
+            tokio::time::sleep(self.poll_interval).await;
+        }
+    }
+
+    fn get_stats(&self) -> Option<TransportStats> {
+        Some(self.stats.clone())
+    }
+}
+
+// ============================================================================
+// 測試
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_png_frames_round_trip() {
+        let dir = std::env::temp_dir().join(format!("frost-qr-{}", uuid::Uuid::new_v4()));
+        let json = serde_json::to_vec_pretty(&serde_json::json!({
+            "session_id": "5f0c4a3e-9d1b-4c8e-a7f2-3b6d8e1c0a94",
+            "signer_id": 1,
+            "commitment_hex": "ab".repeat(66),
+        }))
+        .unwrap();
+
+        let parts = encode_parts(&json, UR_TYPE_JSON, 120, 10);
+        let frames = write_frames(&parts, &dir, QrFormat::Png).unwrap();
+        assert_eq!(frames.len(), parts.len());
+
+        // 跳過第一個 frame，仍可由混合分段還原
+        let mut decoder = QrDecoder::new(UR_TYPE_JSON);
+        for frame in frames.iter().skip(1) {
+            if decoder.receive_image(frame).unwrap() {
+                break;
+            }
+        }
+        assert_eq!(decoder.finish().unwrap(), json);
+
+        // 類型不符的分段會被拒絕
+        let mut wrong = QrDecoder::new(UR_TYPE_MESSAGE);
+        assert!(matches!(
+            wrong.receive(&parts[0]),
+            Err(QrError::UnexpectedType { .. })
+        ));
+
+        let _ = fs::remove_dir_all(&dir);
+    }
+}
@@ -0,0 +1,337 @@
+//! # Radio Model - LoRa 物理層模型
+//!
+//! 以物理參數取代固定延遲與均勻掉包，用於估算 FROST 簽章在真實 LoRa 上的延遲：
+//!
+//! - **空中時間**：依 Semtech SX127x 資料手冊的公式，由 SF / BW / CR、前導碼與
+//!   payload 長度計算
+//! - **最大 payload**：EU868 區域參數（依 SF 而定）
+//! - **工作週期**：EU868 g1 子頻段限制 1%，發送後必須靜默 `airtime × 99`
+//! - **訊號強度**：對數距離路徑損耗 + 對數常態遮蔽，得出 RSSI 與 SNR；
+//!   SNR 低於該 SF 的解調門檻即掉包
+//! - **突發掉包**：Gilbert–Elliott 兩態馬可夫模型（好 / 壞通道）
+
+use rand::Rng;
+use std::ops::RangeInclusive;
+use thiserror::Error;
+
+/// LoRa 支援的擴頻因子
+const SPREADING_FACTORS: RangeInclusive<u8> = 7..=12;
+
+// ============================================================================
+// 錯誤定義
+// ============================================================================
+
+#[derive(Debug, Error)]
+pub enum RadioError {
+    #[error("Spreading factor must be between 7 and 12, got {0}")]
+    InvalidSpreadingFactor(u8),
+}
+
+// ============================================================================
+// 無線電參數
+// ============================================================================
+
+/// LoRa 無線電參數
+///
+/// 擴頻因子只能透過 `eu868` / `with_spreading_factor` 設定，保證落在 7 ~ 12。
+#[derive(Debug, Clone)]
+pub struct RadioConfig {
+    /// 擴頻因子（7 ~ 12）
+    spreading_factor: u8,
+
+    /// 頻寬（Hz，例如 125_000）
+    pub bandwidth_hz: u32,
+
+    /// 編碼率 4/(4+n)，n 為 1 ~ 4（1 即 4/5）
+    pub coding_rate: u8,
+
+    /// 前導碼符號數
+    pub preamble_symbols: u16,
+
+    /// 是否使用顯式標頭
+    pub explicit_header: bool,
+
+    /// 是否附加 payload CRC
+    pub crc: bool,
+
+    /// 載波頻率（MHz）
+    pub frequency_mhz: f64,
+
+    /// 發射功率（dBm）
+    pub tx_power_dbm: f64,
+
+    /// 收發兩端的距離（公尺）
+    pub distance_m: f64,
+
+    /// 路徑損耗指數（自由空間為 2，郊區約 2.7 ~ 3.5）
+    pub path_loss_exponent: f64,
+
+    /// 遮蔽效應的標準差（dB，0 表示不隨機變化）
+    pub shadowing_sigma_db: f64,
+
+    /// 接收端雜訊指數（dB）
+    pub noise_figure_db: f64,
+
+    /// 工作週期上限（例如 0.01 = 1%；`None` 表示不限制）
+    pub duty_cycle: Option<f64>,
+}
+
+impl Default for RadioConfig {
+    fn default() -> Self {
+        Self {
+            spreading_factor: 7,
+            bandwidth_hz: 125_000,
+            coding_rate: 1,
+            preamble_symbols: 8,
+            explicit_header: true,
+            crc: true,
+            frequency_mhz: 868.1,
+            tx_power_dbm: 14.0,
+            distance_m: 2_000.0,
+            path_loss_exponent: 2.7,
+            shadowing_sigma_db: 4.0,
+            noise_figure_db: 6.0,
+            duty_cycle: Some(0.01),
+        }
+    }
+}
+
+impl RadioConfig {
+    /// EU868 的典型配置（125 kHz、CR 4/5、14 dBm、1% 工作週期）
+    ///
+    /// # 錯誤
+    /// 擴頻因子不在 7 ~ 12 時返回 `InvalidSpreadingFactor`
+    pub fn eu868(spreading_factor: u8, distance_m: f64) -> Result<Self, RadioError> {
+        Self {
+            distance_m,
+            ..Self::default()
+        }
+        .with_spreading_factor(spreading_factor)
+    }
+
+    /// 設定擴頻因子（7 ~ 12）
+    pub fn with_spreading_factor(mut self, spreading_factor: u8) -> Result<Self, RadioError> {
+        if !SPREADING_FACTORS.contains(&spreading_factor) {
+            return Err(RadioError::InvalidSpreadingFactor(spreading_factor));
+        }
+        self.spreading_factor = spreading_factor;
+        Ok(self)
+    }
+
+    /// 擴頻因子
+    pub fn spreading_factor(&self) -> u8 {
+        self.spreading_factor
+    }
+
+    /// 一個符號的時間（毫秒）
+    pub fn symbol_time_ms(&self) -> f64 {
+        f64::from(1u32 << self.spreading_factor) / f64::from(self.bandwidth_hz) * 1000.0
+    }
+
+    /// 是否啟用低速率最佳化（符號時間超過 16ms 時強制啟用）
+    fn low_data_rate_optimize(&self) -> bool {
+        self.symbol_time_ms() > 16.0
+    }
+
+    /// 傳送 `payload_len` bytes 的空中時間（毫秒）
+    pub fn time_on_air_ms(&self, payload_len: usize) -> f64 {
+        let sf = f64::from(self.spreading_factor);
+        let de = if self.low_data_rate_optimize() { 1.0 } else { 0.0 };
+        let ih = if self.explicit_header { 0.0 } else { 1.0 };
+        let crc = if self.crc { 1.0 } else { 0.0 };
+        let cr = f64::from(self.coding_rate.clamp(1, 4));
+
+        let preamble = (f64::from(self.preamble_symbols) + 4.25) * self.symbol_time_ms();
+        let numerator = 8.0 * payload_len as f64 - 4.0 * sf + 28.0 + 16.0 * crc - 20.0 * ih;
+        let payload_symbols =
+            8.0 + ((numerator / (4.0 * (sf - 2.0 * de))).ceil() * (cr + 4.0)).max(0.0);
+
+        preamble + payload_symbols * self.symbol_time_ms()
+    }
+
+    /// 單一封包可承載的最大 payload（EU868 區域參數）
+    pub fn max_payload(&self) -> usize {
+        match self.spreading_factor {
+            0..=8 => 250,
+            9 => 123,
+            _ => 59,
+        }
+    }
+
+    /// 發送 `airtime_ms` 後依工作週期必須靜默的時間（毫秒）
+    pub fn off_time_ms(&self, airtime_ms: f64) -> f64 {
+        match self.duty_cycle {
+            Some(duty_cycle) if duty_cycle > 0.0 && duty_cycle < 1.0 => {
+                airtime_ms * (1.0 / duty_cycle - 1.0)
+            }
+            _ => 0.0,
+        }
+    }
+
+    /// 接收端的雜訊底（dBm）
+    pub fn noise_floor_dbm(&self) -> f64 {
+        -174.0 + 10.0 * f64::from(self.bandwidth_hz).log10() + self.noise_figure_db
+    }
+
+    /// 此 SF 可解調的最低 SNR（dB）
+    pub fn demodulation_floor_db(&self) -> f64 {
+        -2.5 * f64::from(self.spreading_factor) + 10.0
+    }
+
+    /// 平均路徑損耗（dB，對數距離模型，參考距離 1 公尺）
+    pub fn path_loss_db(&self) -> f64 {
+        let reference = 20.0 * self.frequency_mhz.log10() - 27.55;
+        reference + 10.0 * self.path_loss_exponent * self.distance_m.max(1.0).log10()
+    }
+
+    /// 取樣一個封包的訊號品質（含隨機遮蔽）
+    pub fn sample_link<R: Rng>(&self, rng: &mut R) -> LinkQuality {
+        let shadowing = gaussian(rng) * self.shadowing_sigma_db;
+        let rssi_dbm = self.tx_power_dbm - self.path_loss_db() - shadowing;
+
+        LinkQuality {
+            rssi_dbm,
+            snr_db: rssi_dbm - self.noise_floor_dbm(),
+        }
+    }
+}
+
+/// 標準常態分布取樣（Box–Muller）
+fn gaussian<R: Rng>(rng: &mut R) -> f64 {
+    let u1: f64 = 1.0 - rng.gen::<f64>(); // (0, 1]，避免 ln(0)
+    let u2: f64 = rng.gen();
+    (-2.0 * u1.ln()).sqrt() * (2.0 * std::f64::consts::PI * u2).cos()
+}
+
+/// 單一封包的訊號品質
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct LinkQuality {
+    /// 接收訊號強度（dBm）
+    pub rssi_dbm: f64,
+
+    /// 訊噪比（dB）
+    pub snr_db: f64,
+}
+
+impl LinkQuality {
+    /// 接收端能否解調此封包
+    pub fn can_demodulate(&self, radio: &RadioConfig) -> bool {
+        self.snr_db >= radio.demodulation_floor_db()
+    }
+}
+
+// ============================================================================
+// 突發掉包（Gilbert–Elliott）
+// ============================================================================
+
+/// Gilbert–Elliott 突發掉包模型參數
+#[derive(Debug, Clone, Copy)]
+pub struct GilbertElliott {
+    /// 每個封包由「好」轉為「壞」通道的機率
+    pub p_good_to_bad: f64,
+
+    /// 每個封包由「壞」轉回「好」通道的機率
+    pub p_bad_to_good: f64,
+
+    /// 「好」通道的掉包率
+    pub loss_good: f64,
+
+    /// 「壞」通道的掉包率
+    pub loss_bad: f64,
+}
+
+impl Default for GilbertElliott {
+    fn default() -> Self {
+        Self {
+            p_good_to_bad: 0.05,
+            p_bad_to_good: 0.3,
+            loss_good: 0.01,
+            loss_bad: 0.6,
+        }
+    }
+}
+
+impl GilbertElliott {
+    /// 長期平均掉包率
+    pub fn mean_loss_rate(&self) -> f64 {
+        let transitions = self.p_good_to_bad + self.p_bad_to_good;
+        if transitions <= 0.0 {
+            return self.loss_good;
+        }
+        let bad_share = self.p_good_to_bad / transitions;
+        (1.0 - bad_share) * self.loss_good + bad_share * self.loss_bad
+    }
+}
+
+/// Gilbert–Elliott 通道（保存目前的好 / 壞狀態）
+#[derive(Debug, Clone)]
+pub struct BurstLossChannel {
+    model: GilbertElliott,
+    bad: bool,
+}
+
+impl BurstLossChannel {
+    pub fn new(model: GilbertElliott) -> Self {
+        Self { model, bad: false }
+    }
+
+    /// 目前是否處於「壞」通道
+    pub fn is_bad(&self) -> bool {
+        self.bad
+    }
+
+    /// 推進一個封包，返回該封包是否遺失
+    pub fn sample<R: Rng>(&mut self, rng: &mut R) -> bool {
+        let flip = if self.bad {
+            self.model.p_bad_to_good
+        } else {
+            self.model.p_good_to_bad
+        };
+        if rng.gen::<f64>() < flip {
+            self.bad = !self.bad;
+        }
+
+        let loss = if self.bad {
+            self.model.loss_bad
+        } else {
+            self.model.loss_good
+        };
+        rng.gen::<f64>() < loss
+    }
+}
+
+// ============================================================================
+// 測試
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_time_on_air_matches_semtech_calculator() {
+        // SF7 / 125 kHz / CR 4/5 / 20 bytes ≈ 56.6ms
+        let sf7 = RadioConfig::eu868(7, 1_000.0).unwrap();
+        assert!((sf7.time_on_air_ms(20) - 56.576).abs() < 0.01);
+        assert_eq!(sf7.max_payload(), 250);
+
+        // SF12 會啟用低速率最佳化：≈ 1318.9ms
+        let sf12 = RadioConfig::eu868(12, 1_000.0).unwrap();
+        assert!((sf12.time_on_air_ms(20) - 1318.912).abs() < 0.01);
+        assert_eq!(sf12.max_payload(), 59);
+
+        // 1% 工作週期：每傳 1 秒需靜默 99 秒
+        assert!((sf12.off_time_ms(1_000.0) - 99_000.0).abs() < 1e-6);
+
+        // 距離越遠訊號越弱
+        let far = RadioConfig::eu868(7, 20_000.0).unwrap();
+        assert!(far.path_loss_db() > sf7.path_loss_db());
+
+        // 超出範圍的擴頻因子在建構時被拒絕，而不是算出無意義的空中時間
+        assert!(matches!(
+            RadioConfig::eu868(6, 1_000.0),
+            Err(RadioError::InvalidSpreadingFactor(6))
+        ));
+        assert!(sf7.with_spreading_factor(13).is_err());
+    }
+}
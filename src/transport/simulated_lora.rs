@@ -5,45 +5,153 @@
 //!
 //! ## 特性
 //!
-//! - **延遲模擬**：每個封包傳輸有固定延遲（例如 500ms）
-//! - **掉包重傳**：模擬封包遺失（例如 10% 機率），並自動重傳
-//! - **封包分片**：大型訊息切割成小片段（例如 64 bytes）
-//! - **狀態追蹤**：記錄所有傳輸事件，供 Dashboard 查詢
-
+//! - **延遲模擬**：每個封包傳輸有固定延遲（例如 500ms），可加上隨機抖動
+//! - **掉包重傳**：模擬封包遺失（例如 10% 機率），以選擇性 ACK/NACK 只重傳缺少的片段
+//! - **重複與亂序**：片段可能被重複接收或亂序抵達
+//! - **封包分片**：大型訊息切割成帶序號的小片段（例如 64 bytes），接收端亂序重組並抑制重複
+//! - **送達語意**：超過重傳上限的訊息記錄為 `MessageFailed`，不會被交付
+//! - **無線電模型**（可選）：以 `RadioConfig` 計算空中時間、工作週期與 RSSI/SNR，
+//!   並以 Gilbert–Elliott 模型模擬突發掉包
+//! - **狀態追蹤**：記錄所有傳輸事件與每則訊息的空中時間，供 Dashboard 查詢，
+//!   並即時發布到 `EventStream`（`GET /events`）
+//!
+//! ## 同步與異步
+//!
+//! 傳輸延遲以 `tokio::time::sleep` 模擬，不會阻塞 Tokio 執行緒。
+//! 異步程式碼應使用 `transmit`（或透過 `ChannelNetwork::with_lora` 讓整個網路
+//! 經過這條鏈路）。同步的 `Transport::send` 在 multi-thread runtime 內以
+//! `block_in_place` 等待，其他情況在輔助執行緒上以臨時的 runtime 執行。
+//!
+//! ## 可重現的模擬
+//!
+//! `LoRaConfig::seed` 固定隨機數種子，`SimulationClock::Virtual` 以虛擬時鐘取代真實的
+//! 等待：延遲只推進模擬時間，不會真的 sleep。兩者搭配（`LoRaConfig::deterministic`）
+//! 時，同樣的訊息序列必定產生同樣的掉包、重複、亂序與事件記錄，且瞬間完成，
+//! 適合撰寫測試。
+
+use super::events::{EventStream, StreamEvent};
+use super::fragment::{
+    self, Fragment, FragmentOutcome, Reassembler, SelectiveAck, FRAGMENT_HEADER_LEN,
+};
+use super::radio::{BurstLossChannel, GilbertElliott, RadioConfig};
 use super::{MessageMetadata, MessageType, Transport, TransportStats};
-use rand::Rng;
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
 use std::collections::HashMap;
 use std::sync::{Arc, Mutex};
-use std::thread;
 use std::time::Duration;
 
 // ============================================================================
 // LoRa 傳輸配置
 // ============================================================================
 
+/// 模擬使用的時鐘
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum SimulationClock {
+    /// 真實時間：以 `tokio::time::sleep` 等待延遲（Demo 使用）
+    #[default]
+    RealTime,
+
+    /// 虛擬時間：延遲只推進模擬時鐘，傳輸瞬間完成（測試使用）
+    Virtual,
+}
+
 /// LoRa 傳輸配置參數
 #[derive(Debug, Clone)]
 pub struct LoRaConfig {
-    /// 每個封包的延遲（毫秒）
+    /// 每個封包的延遲（毫秒；設定 `radio` 時改用計算出的空中時間）
     pub latency_ms: u64,
 
-    /// 封包遺失率（0.0 ~ 1.0）
+    /// 延遲的隨機抖動上限（毫秒，每個封包額外延遲 0 ~ jitter_ms）
+    pub jitter_ms: u64,
+
+    /// 封包遺失率（0.0 ~ 1.0；設定 `burst_loss` 時不使用）
     pub packet_loss_rate: f64,
 
-    /// 分片大小（bytes）- LoRa 典型的 payload 限制
+    /// 片段被重複接收的機率（0.0 ~ 1.0）
+    pub duplicate_rate: f64,
+
+    /// 片段與下一個片段交換抵達順序的機率（0.0 ~ 1.0）
+    pub reorder_rate: f64,
+
+    /// 分片大小（bytes，含 4 bytes 分片標頭）- LoRa 典型的 payload 限制
+    ///
+    /// 設定 `radio` 時不會超過該 SF 的最大 payload。
     pub fragment_size: usize,
 
-    /// 最大重傳次數
+    /// 最大重傳輪數（每輪只重送 NACK 列出的片段）
     pub max_retries: u32,
+
+    /// 隨機數種子（`None` 時每次執行結果不同）
+    pub seed: Option<u64>,
+
+    /// 延遲使用真實時間或虛擬時鐘
+    pub clock: SimulationClock,
+
+    /// （可選）LoRa 物理層模型：空中時間、最大 payload、工作週期、RSSI/SNR
+    pub radio: Option<RadioConfig>,
+
+    /// （可選）Gilbert–Elliott 突發掉包模型，取代均勻的 `packet_loss_rate`
+    pub burst_loss: Option<GilbertElliott>,
+
+    /// 送給簽署者的請求是否附帶 trace context
+    ///
+    /// 每則 `CommitmentRequest` / `SigningPackage` 多 27 bytes，預設關閉以節省空中時間。
+    pub trace_context: bool,
+}
+
+impl LoRaConfig {
+    /// 可重現的配置：固定種子 + 虛擬時鐘，其餘沿用預設值
+    pub fn deterministic(seed: u64) -> Self {
+        Self {
+            seed: Some(seed),
+            clock: SimulationClock::Virtual,
+            ..Self::default()
+        }
+    }
+
+    /// 使用 LoRa 物理層模型
+    pub fn with_radio(mut self, radio: RadioConfig) -> Self {
+        self.radio = Some(radio);
+        self
+    }
+
+    /// 使用 Gilbert–Elliott 突發掉包模型
+    pub fn with_burst_loss(mut self, model: GilbertElliott) -> Self {
+        self.burst_loss = Some(model);
+        self
+    }
+
+    /// 在鏈路上傳遞 trace context（見 `trace_context`）
+    pub fn with_trace_context(mut self, enabled: bool) -> Self {
+        self.trace_context = enabled;
+        self
+    }
+
+    /// 實際使用的分片大小（含標頭）
+    pub fn frame_size(&self) -> usize {
+        match &self.radio {
+            Some(radio) => self.fragment_size.min(radio.max_payload()),
+            None => self.fragment_size,
+        }
+    }
 }
 
 impl Default for LoRaConfig {
     fn default() -> Self {
         Self {
             latency_ms: 500,        // 500ms 延遲（模擬遠距離傳輸）
+            jitter_ms: 0,           // 無抖動
             packet_loss_rate: 0.1,  // 10% 掉包率
+            duplicate_rate: 0.0,    // 不重複
+            reorder_rate: 0.0,      // 依序抵達
             fragment_size: 64,      // 64 bytes per fragment（LoRa SF7 典型值）
             max_retries: 3,         // 最多重傳 3 次
+            seed: None,             // 每次執行使用不同的隨機數
+            clock: SimulationClock::RealTime,
+            radio: None,
+            burst_loss: None,
+            trace_context: false,
         }
     }
 }
@@ -88,6 +196,68 @@ pub enum TransportEvent {
         total_time_ms: u64,
         retries: u32,
     },
+
+    /// 接收端收到重複的片段（已忽略）
+    FragmentDuplicate {
+        message_seq: u16,
+        fragment_id: usize,
+    },
+
+    /// 發送端收到選擇性確認（`missing` 為空時為 ACK，否則為 NACK）
+    AckReceived {
+        message_seq: u16,
+        missing: Vec<u8>,
+    },
+
+    /// 確認封包遺失
+    AckLost { message_seq: u16 },
+
+    /// 訊息最終未能送達（不會被交付給接收者）
+    MessageFailed {
+        from: String,
+        to: String,
+        message_type: MessageType,
+        message_seq: u16,
+        missing_fragments: usize,
+        reason: String,
+    },
+
+    /// 發送端受工作週期限制，必須等待後才能再次發送
+    DutyCycleWait { node: String, wait_ms: u64 },
+}
+
+/// 單則訊息的空中時間統計
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct MessageAirtime {
+    /// 訊息序號
+    pub message_seq: u16,
+
+    /// 發送者
+    pub from: String,
+
+    /// 接收者
+    pub to: String,
+
+    /// 訊息類型
+    pub message_type: MessageType,
+
+    /// 訊息大小（bytes）
+    pub bytes: usize,
+
+    /// 分片數
+    pub fragments: usize,
+
+    /// 所有封包（含重傳與確認封包）的空中時間（毫秒）
+    pub airtime_ms: u64,
+
+    /// 因工作週期限制而等待的時間（毫秒）
+    pub duty_cycle_wait_ms: u64,
+
+    /// 從開始發送到結束的總時間（毫秒）
+    pub total_time_ms: u64,
+
+    /// 是否送達
+    pub delivered: bool,
 }
 
 /// LoRa 傳輸狀態（共享給 HTTP API）
@@ -108,6 +278,10 @@ pub struct LoRaTransportState {
     /// 虛擬訊號強度 (RSSI, -120 ~ -30 dBm)
     pub rssi: i32,
 
+    /// 最近一個封包的訊噪比（dB，僅在使用無線電模型時提供）
+    #[serde(default)]
+    pub snr_db: Option<f64>,
+
     /// 最近的傳輸事件（最多保留 100 條）
     pub recent_events: Vec<TransportEvent>,
 
@@ -117,8 +291,91 @@ pub struct LoRaTransportState {
     /// 總重傳次數
     pub total_retries: u32,
 
+    /// 鏈路的模擬時鐘（毫秒，累計所有封包的空中時間與等待）
+    #[serde(default)]
+    pub simulated_time_ms: u64,
+
+    /// 累計空中時間（毫秒）
+    #[serde(default)]
+    pub total_airtime_ms: u64,
+
+    /// 累計因工作週期限制而等待的時間（毫秒）
+    #[serde(default)]
+    pub duty_cycle_wait_ms: u64,
+
+    /// 最近訊息的空中時間（最多保留 100 條）
+    #[serde(default)]
+    pub message_airtime: Vec<MessageAirtime>,
+
     /// CLI 輸出日誌（最多保留 500 行）
     pub cli_output: Vec<String>,
+
+    /// 狀態變化的即時事件流
+    ///
+    /// 快照中只序列化為 `last_event_id`：客戶端以此序號訂閱 `/events`，
+    /// 即可從快照無縫接上即時事件。
+    #[serde(
+        rename = "last_event_id",
+        serialize_with = "serialize_last_event_id",
+        skip_deserializing
+    )]
+    pub events: EventStream,
+}
+
+impl LoRaTransportState {
+    /// 記錄傳輸事件（保持最近 100 條）並發布
+    pub fn push_event(&mut self, event: TransportEvent) {
+        self.recent_events.push(event.clone());
+        if self.recent_events.len() > 100 {
+            self.recent_events.remove(0);
+        }
+        self.events.publish(StreamEvent::Transport { event });
+    }
+
+    /// 記錄一行 CLI 輸出（保持最近 500 行）並發布
+    pub fn push_log(&mut self, line: String) {
+        self.cli_output.push(line.clone());
+        if self.cli_output.len() > 500 {
+            self.cli_output.remove(0);
+        }
+        self.events.publish(StreamEvent::Log { line });
+    }
+
+    /// 切換階段並設定進度
+    pub fn set_phase(&mut self, phase: impl Into<String>, progress: f64) {
+        self.current_phase = phase.into();
+        self.set_progress(progress);
+    }
+
+    /// 更新當前階段的進度
+    pub fn set_progress(&mut self, progress: f64) {
+        self.progress = progress;
+        self.events.publish(StreamEvent::Phase {
+            phase: self.current_phase.clone(),
+            progress,
+        });
+    }
+
+    /// 發布目前的累計統計
+    pub fn publish_stats(&self) {
+        self.events.publish(StreamEvent::Stats {
+            total_messages: self.total_messages,
+            total_bytes: self.total_bytes,
+            total_retries: self.total_retries,
+            rssi: self.rssi,
+            snr_db: self.snr_db,
+            simulated_time_ms: self.simulated_time_ms,
+            total_airtime_ms: self.total_airtime_ms,
+            duty_cycle_wait_ms: self.duty_cycle_wait_ms,
+        });
+    }
+}
+
+fn serialize_last_event_id<S: serde::Serializer>(
+    events: &EventStream,
+    serializer: S,
+) -> Result<S::Ok, S::Error> {
+    serializer.serialize_u64(events.last_id())
 }
 
 impl Default for LoRaTransportState {
@@ -129,10 +386,16 @@ impl Default for LoRaTransportState {
             total_bytes: 0,
             progress: 0.0,
             rssi: -80, // 初始訊號強度
+            snr_db: None,
             recent_events: Vec::new(),
             by_type: HashMap::new(),
             total_retries: 0,
+            simulated_time_ms: 0,
+            total_airtime_ms: 0,
+            duty_cycle_wait_ms: 0,
+            message_airtime: Vec::new(),
             cli_output: Vec::new(),
+            events: EventStream::default(),
         }
     }
 }
@@ -155,8 +418,27 @@ pub struct SimulatedLoRaTransport {
     /// 共享狀態（供 HTTP API 讀取）
     state: Arc<Mutex<LoRaTransportState>>,
 
-    /// 隨機數生成器（用於模擬掉包）
-    rng: rand::rngs::ThreadRng,
+    /// 隨機數生成器（用於模擬掉包、抖動、重複與亂序）
+    rng: StdRng,
+
+    /// 模擬時鐘（毫秒）
+    clock_ms: u64,
+
+    /// 突發掉包通道（使用 Gilbert–Elliott 模型時）
+    burst: Option<BurstLossChannel>,
+
+    /// 每個節點在工作週期限制下可再次發送的時間（模擬時鐘，毫秒）
+    next_tx_allowed: HashMap<String, u64>,
+
+    /// 目前訊息累計的空中時間與工作週期等待（毫秒）
+    message_airtime_ms: u64,
+    message_duty_wait_ms: u64,
+
+    /// 下一則訊息的序號
+    next_seq: u16,
+
+    /// 鏈路接收端的重組緩衝區
+    receiver: Reassembler,
 
     /// 累計統計
     stats: TransportStats,
@@ -170,179 +452,385 @@ impl SimulatedLoRaTransport {
 
     /// 建立新的 LoRa Transport（自訂配置）
     pub fn new_with_config(config: LoRaConfig) -> Self {
+        let rng = match config.seed {
+            Some(seed) => StdRng::seed_from_u64(seed),
+            None => StdRng::from_entropy(),
+        };
+
         Self {
+            burst: config.burst_loss.map(BurstLossChannel::new),
             config,
             state: Arc::new(Mutex::new(LoRaTransportState::default())),
-            rng: rand::thread_rng(),
+            rng,
+            clock_ms: 0,
+            next_tx_allowed: HashMap::new(),
+            message_airtime_ms: 0,
+            message_duty_wait_ms: 0,
+            next_seq: 0,
+            receiver: Reassembler::new(),
             stats: TransportStats::default(),
         }
     }
 
+    /// 鏈路配置
+    pub fn config(&self) -> &LoRaConfig {
+        &self.config
+    }
+
     /// 獲取共享狀態（供 HTTP API 使用）
     pub fn get_state(&self) -> Arc<Mutex<LoRaTransportState>> {
         Arc::clone(&self.state)
     }
 
+    /// 改為記錄到既有的共享狀態（多條鏈路顯示在同一個 Dashboard）
+    pub fn with_shared_state(mut self, state: Arc<Mutex<LoRaTransportState>>) -> Self {
+        self.state = state;
+        self
+    }
+
     /// 記錄 CLI 輸出（供 Dashboard 顯示）
     pub fn log_cli_output(&self, line: String) {
-        let mut state = self.state.lock().unwrap();
-        state.cli_output.push(line);
+        self.state.lock().unwrap().push_log(line);
+    }
 
-        // 保持最近 500 行
-        if state.cli_output.len() > 500 {
-            state.cli_output.remove(0);
+    /// 記錄傳輸事件（保持最近 100 條）
+    fn record_event(&self, event: TransportEvent) {
+        self.state.lock().unwrap().push_event(event);
+    }
+
+    /// 模擬時鐘目前的時間（毫秒）
+    pub fn clock_ms(&self) -> u64 {
+        self.clock_ms
+    }
+
+    /// 推進模擬時鐘（真實時間模式下同時等待）
+    async fn advance_clock(&mut self, ms: u64) {
+        self.clock_ms += ms;
+        self.state.lock().unwrap().simulated_time_ms = self.clock_ms;
+
+        if self.config.clock == SimulationClock::RealTime {
+            tokio::time::sleep(Duration::from_millis(ms)).await;
         }
     }
 
-    /// 模擬封包傳輸（包含延遲和可能的掉包）
-    fn transmit_fragment(&mut self, fragment_id: usize, total_fragments: usize, data: &[u8]) -> bool {
-        // 模擬傳輸延遲
-        thread::sleep(Duration::from_millis(self.config.latency_ms));
+    /// 模擬單一封包在空中的傳輸，返回接收端是否收到
+    ///
+    /// 1. 工作週期限制：發送者尚在靜默期時先等待
+    /// 2. 空中時間：無線電模型計算的 time-on-air（否則為固定延遲）+ 隨機抖動
+    /// 3. 掉包：Gilbert–Elliott 或均勻掉包；使用無線電模型時，SNR 低於解調門檻也會掉包
+    async fn transmit_packet(&mut self, sender: &str, frame_len: usize) -> bool {
+        // 工作週期限制
+        if self.config.radio.is_some() {
+            let allowed_at = self.next_tx_allowed.get(sender).copied().unwrap_or(0);
+            if allowed_at > self.clock_ms {
+                let wait_ms = allowed_at - self.clock_ms;
+                self.record_event(TransportEvent::DutyCycleWait {
+                    node: sender.to_string(),
+                    wait_ms,
+                });
+                self.message_duty_wait_ms += wait_ms;
+                self.advance_clock(wait_ms).await;
+            }
+        }
 
-        // 模擬掉包
-        let lost = self.rng.gen::<f64>() < self.config.packet_loss_rate;
+        // 空中時間
+        let airtime_ms = match &self.config.radio {
+            Some(radio) => radio.time_on_air_ms(frame_len).ceil() as u64,
+            None => self.config.latency_ms,
+        };
+        let jitter = match self.config.jitter_ms {
+            0 => 0,
+            max => self.rng.gen_range(0..=max),
+        };
+        self.message_airtime_ms += airtime_ms;
+        self.advance_clock(airtime_ms + jitter).await;
+
+        if let Some(radio) = &self.config.radio {
+            let off_time = radio.off_time_ms(airtime_ms as f64).ceil() as u64;
+            self.next_tx_allowed
+                .insert(sender.to_string(), self.clock_ms + off_time);
+        }
 
-        // 更新狀態
+        // 掉包
+        let mut lost = match &mut self.burst {
+            Some(burst) => burst.sample(&mut self.rng),
+            None => self.rng.gen::<f64>() < self.config.packet_loss_rate,
+        };
+
+        // 更新訊號強度
+        let quality = self
+            .config
+            .radio
+            .as_ref()
+            .map(|radio| (radio.sample_link(&mut self.rng), radio.demodulation_floor_db()));
         let mut state = self.state.lock().unwrap();
+        match quality {
+            Some((quality, floor)) => {
+                lost |= quality.snr_db < floor;
+                state.rssi = quality.rssi_dbm.round() as i32;
+                state.snr_db = Some(quality.snr_db);
+            }
+            None => {
+                state.rssi = if lost {
+                    (state.rssi - 5).max(-120)
+                } else {
+                    (state.rssi + 2).min(-50)
+                };
+            }
+        }
 
-        if lost {
-            state.recent_events.push(TransportEvent::PacketLost {
-                fragment_id,
-                retry_count: 0,
-            });
+        !lost
+    }
 
-            // 保持最近 100 條事件
-            if state.recent_events.len() > 100 {
-                state.recent_events.remove(0);
-            }
+    /// 模擬片段傳輸（包含延遲和可能的掉包）
+    async fn transmit_fragment(
+        &mut self,
+        sender: &str,
+        fragment: &Fragment,
+        retry_count: u32,
+    ) -> bool {
+        let delivered = self.transmit_packet(sender, fragment.frame_len()).await;
+        let fragment_id = fragment.index as usize;
+        let total_fragments = fragment.total as usize;
 
-            // 訊號強度下降
-            state.rssi = (state.rssi - 5).max(-120);
-        } else {
-            state.recent_events.push(TransportEvent::TransmitFragment {
+        if delivered {
+            self.record_event(TransportEvent::TransmitFragment {
                 fragment_id,
                 total_fragments,
-                bytes: data.len(),
+                bytes: fragment.frame_len(),
+            });
+        } else {
+            self.record_event(TransportEvent::PacketLost {
+                fragment_id,
+                retry_count,
             });
-
-            if state.recent_events.len() > 100 {
-                state.recent_events.remove(0);
-            }
-
-            // 訊號強度略微改善
-            state.rssi = (state.rssi + 2).min(-50);
         }
 
         // 更新進度
-        state.progress = (fragment_id + 1) as f64 / total_fragments as f64;
+        self.state
+            .lock()
+            .unwrap()
+            .set_progress((fragment_id + 1) as f64 / total_fragments as f64);
 
-        !lost
+        delivered
     }
 
-    /// 傳輸一個完整的訊息（包含分片和重傳）
-    fn transmit_with_fragmentation(&mut self, metadata: &MessageMetadata, payload: &str) {
-        let payload_bytes = payload.as_bytes();
-        let total_bytes = payload_bytes.len();
+    /// 模擬接收端回傳選擇性確認（確認封包同樣可能遺失）
+    async fn transmit_ack(&mut self, sender: &str, ack: &SelectiveAck) -> bool {
+        let delivered = self.transmit_packet(sender, ack.frame_len()).await;
+        if delivered {
+            self.record_event(TransportEvent::AckReceived {
+                message_seq: ack.message_seq,
+                missing: ack.missing.clone(),
+            });
+        } else {
+            self.record_event(TransportEvent::AckLost {
+                message_seq: ack.message_seq,
+            });
+        }
+
+        delivered
+    }
 
-        // 計算需要的片段數
-        let total_fragments = (total_bytes + self.config.fragment_size - 1) / self.config.fragment_size;
+    /// 傳輸一個完整的訊息（分片、選擇性確認與重傳）
+    ///
+    /// 1. 發送所有尚未確認的片段，接收端把收到的片段放進重組緩衝區
+    /// 2. 接收端回覆 `SelectiveAck`：空清單為 ACK，否則為缺少片段的 NACK
+    /// 3. 只重送 NACK 列出的片段；確認封包遺失時重送上次所有未確認的片段
+    ///    （接收端會忽略重複的片段）
+    /// 4. 超過 `max_retries` 輪仍未完整時放棄，並記錄 `MessageFailed`
+    ///
+    /// 返回接收端重組出的訊息；未送達時返回 `None`。
+    async fn transmit_with_fragmentation(
+        &mut self,
+        metadata: &MessageMetadata,
+        payload_bytes: &[u8],
+    ) -> Option<Vec<u8>> {
+        let total_bytes = payload_bytes.len();
+        let message_seq = self.next_seq;
+        self.next_seq = self.next_seq.wrapping_add(1);
 
-        // 更新狀態：開始傳輸
-        {
-            let mut state = self.state.lock().unwrap();
-            state.current_phase = format!("{:?}", metadata.message_type);
-            state.recent_events.push(TransportEvent::TransmitStart {
+        let Some(fragments) = fragment::split(message_seq, payload_bytes, self.config.frame_size())
+        else {
+            println!("  ❌ 訊息過大，超過 255 個片段");
+            self.record_event(TransportEvent::MessageFailed {
                 from: metadata.from.clone(),
                 to: metadata.to.clone(),
                 message_type: metadata.message_type,
-                total_bytes,
-                fragments: total_fragments,
+                message_seq,
+                missing_fragments: 0,
+                reason: "message exceeds 255 fragments".to_string(),
             });
+            return None;
+        };
+        let total_fragments = fragments.len();
 
-            if state.recent_events.len() > 100 {
-                state.recent_events.remove(0);
-            }
-        }
-
-        let start_time = std::time::Instant::now();
+        // 更新狀態：開始傳輸
+        self.state
+            .lock()
+            .unwrap()
+            .set_phase(format!("{:?}", metadata.message_type), 0.0);
+        self.record_event(TransportEvent::TransmitStart {
+            from: metadata.from.clone(),
+            to: metadata.to.clone(),
+            message_type: metadata.message_type,
+            total_bytes,
+            fragments: total_fragments,
+        });
+
+        let start_ms = self.clock_ms;
+        self.message_airtime_ms = 0;
+        self.message_duty_wait_ms = 0;
         let mut total_retries = 0u32;
-
-        // 傳輸每個片段
-        for i in 0..total_fragments {
-            let start = i * self.config.fragment_size;
-            let end = ((i + 1) * self.config.fragment_size).min(total_bytes);
-            let fragment = &payload_bytes[start..end];
-
-            // 嘗試傳輸（包含重傳）
-            let mut retry_count = 0u32;
-            loop {
-                print!("  📡 Fragment {}/{} ({} bytes)... ", i + 1, total_fragments, fragment.len());
-
-                if self.transmit_fragment(i, total_fragments, fragment) {
-                    println!("✓");
-                    break;
-                } else {
+        let mut delivered = None;
+        let mut pending: Vec<u8> = (0..fragments.len() as u8).collect();
+        let mut round = 0u32;
+
+        loop {
+            // 發送尚未確認的片段，收集實際抵達接收端的封包
+            let mut arrived = Vec::new();
+            for &index in &pending {
+                let fragment = &fragments[index as usize];
+                print!(
+                    "  📡 Fragment {}/{} ({} bytes)... ",
+                    index + 1,
+                    total_fragments,
+                    fragment.frame_len()
+                );
+
+                if !self.transmit_fragment(&metadata.from, fragment, round).await {
                     println!("✗ (掉包)");
+                    continue;
+                }
+                println!("✓");
 
-                    retry_count += 1;
-                    total_retries += 1;
-
-                    if retry_count >= self.config.max_retries {
-                        println!("     ❌ 超過最大重傳次數，放棄此片段");
-                        break;
-                    }
-
-                    println!("     🔄 重傳 {}/{}...", retry_count, self.config.max_retries);
+                let frame = fragment.encode();
+                arrived.push(frame.clone());
+                if self.rng.gen::<f64>() < self.config.duplicate_rate {
+                    arrived.push(frame);
+                }
+            }
 
-                    // 記錄重傳事件
-                    let mut state = self.state.lock().unwrap();
-                    state.recent_events.push(TransportEvent::PacketLost {
-                        fragment_id: i,
-                        retry_count,
-                    });
+            // 模擬亂序：相鄰的片段以 reorder_rate 的機率交換抵達順序
+            for i in 1..arrived.len() {
+                if self.rng.gen::<f64>() < self.config.reorder_rate {
+                    arrived.swap(i - 1, i);
+                }
+            }
 
-                    if state.recent_events.len() > 100 {
-                        state.recent_events.remove(0);
+            // 接收端解碼並重組
+            for frame in arrived {
+                let Some(fragment) = Fragment::decode(&frame) else {
+                    println!("  ⚠️  無法解碼的封包，已丟棄");
+                    continue;
+                };
+                let index = fragment.index;
+                match self.receiver.receive(fragment, self.clock_ms) {
+                    FragmentOutcome::Accepted => {}
+                    FragmentOutcome::Rejected => {
+                        println!("  ⚠️  Fragment {} 與訊息不一致，已丟棄", index + 1);
+                    }
+                    FragmentOutcome::Duplicate => {
+                        println!("  ♊ Fragment {} 重複，已忽略", index + 1);
+                        self.record_event(TransportEvent::FragmentDuplicate {
+                            message_seq,
+                            fragment_id: index as usize,
+                        });
+                    }
+                    FragmentOutcome::Complete(bytes) => {
+                        println!("  🧩 重組完成");
+                        delivered = Some(bytes);
                     }
+                }
+            }
 
-                    // 重傳前稍微等待
-                    thread::sleep(Duration::from_millis(200));
+            // 接收端回覆選擇性確認
+            let ack = self.receiver.ack(message_seq, total_fragments as u8);
+            if self.transmit_ack(&metadata.to, &ack).await {
+                if ack.is_complete() {
+                    break;
                 }
+                println!("  📨 NACK：缺少片段 {:?}", ack.missing);
+                pending = ack.missing;
+            } else {
+                println!("  📨 確認封包遺失，重送所有未確認的片段");
+            }
+
+            round += 1;
+            if round > self.config.max_retries {
+                println!("     ❌ 超過最大重傳次數");
+                break;
             }
-        }
 
-        let total_time_ms = start_time.elapsed().as_millis() as u64;
+            total_retries += pending.len() as u32;
+            println!("     🔄 重傳 {}/{}...", round, self.config.max_retries);
 
-        // 更新狀態：傳輸完成
+            // 重傳前稍微等待
+            self.advance_clock(200).await;
+        }
+
+        let total_time_ms = self.clock_ms - start_ms;
         {
             let mut state = self.state.lock().unwrap();
             state.total_retries += total_retries;
-            state.recent_events.push(TransportEvent::TransmitComplete {
+            state.total_airtime_ms += self.message_airtime_ms;
+            state.duty_cycle_wait_ms += self.message_duty_wait_ms;
+            state.message_airtime.push(MessageAirtime {
+                message_seq,
+                from: metadata.from.clone(),
+                to: metadata.to.clone(),
+                message_type: metadata.message_type,
+                bytes: total_bytes,
+                fragments: total_fragments,
+                airtime_ms: self.message_airtime_ms,
+                duty_cycle_wait_ms: self.message_duty_wait_ms,
                 total_time_ms,
-                retries: total_retries,
+                delivered: delivered.is_some(),
             });
-
-            if state.recent_events.len() > 100 {
-                state.recent_events.remove(0);
+            if state.message_airtime.len() > 100 {
+                state.message_airtime.remove(0);
             }
+            state.publish_stats();
         }
 
-        if total_retries > 0 {
-            println!("  ⚠️  傳輸完成（總重傳次數：{}，總耗時：{}ms）", total_retries, total_time_ms);
-        } else {
-            println!("  ✓ 傳輸完成（無掉包，總耗時：{}ms）", total_time_ms);
+        match &delivered {
+            Some(_) => {
+                self.record_event(TransportEvent::TransmitComplete {
+                    total_time_ms,
+                    retries: total_retries,
+                });
+                if total_retries > 0 {
+                    println!("  ⚠️  傳輸完成（總重傳次數：{}，總耗時：{}ms）", total_retries, total_time_ms);
+                } else {
+                    println!("  ✓ 傳輸完成（無掉包，總耗時：{}ms）", total_time_ms);
+                }
+            }
+            None => {
+                let missing = self.receiver.ack(message_seq, total_fragments as u8).missing;
+                self.receiver.discard(message_seq);
+                self.record_event(TransportEvent::MessageFailed {
+                    from: metadata.from.clone(),
+                    to: metadata.to.clone(),
+                    message_type: metadata.message_type,
+                    message_seq,
+                    missing_fragments: missing.len(),
+                    reason: format!(
+                        "{} fragments undelivered after {} retries",
+                        missing.len(),
+                        self.config.max_retries
+                    ),
+                });
+                println!("  ❌ 傳輸失敗（{} 個片段未送達，總耗時：{}ms）", missing.len(), total_time_ms);
+            }
         }
-    }
-}
 
-impl Default for SimulatedLoRaTransport {
-    fn default() -> Self {
-        Self::new()
+        delivered
     }
-}
 
-impl Transport for SimulatedLoRaTransport {
-    fn send(&mut self, metadata: MessageMetadata, payload: &str) {
+    /// 經由模擬鏈路傳輸一則訊息（異步，不阻塞執行緒）
+    ///
+    /// 返回接收端重組出的訊息；未送達的訊息返回 `None`，應視為遺失。
+    pub async fn transmit(&mut self, metadata: &MessageMetadata, payload: &[u8]) -> Option<Vec<u8>> {
         // 更新統計
         self.stats.total_messages += 1;
         self.stats.total_bytes += payload.len();
@@ -359,18 +847,64 @@ impl Transport for SimulatedLoRaTransport {
         }
 
         // 打印傳輸開始資訊
+        let chunk_size = self.config.frame_size().saturating_sub(FRAGMENT_HEADER_LEN).max(1);
         println!("━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━");
         println!("📡 LoRa 傳輸開始");
         println!("   類型: {:?}", metadata.message_type);
         println!("   從: {} → 到: {}", metadata.from, metadata.to);
         println!("   Payload 大小: {} bytes", payload.len());
-        println!("   預計片段數: {}", (payload.len() + self.config.fragment_size - 1) / self.config.fragment_size);
+        println!("   預計片段數: {}", payload.len().div_ceil(chunk_size));
         println!();
 
         // 執行分片傳輸
-        self.transmit_with_fragmentation(&metadata, payload);
+        let delivered = self.transmit_with_fragmentation(metadata, payload).await;
 
         println!();
+        delivered
+    }
+
+    /// 累計統計
+    pub fn stats(&self) -> &TransportStats {
+        &self.stats
+    }
+}
+
+impl Default for SimulatedLoRaTransport {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Transport for SimulatedLoRaTransport {
+    /// 同步發送（阻塞直到傳輸結束）
+    ///
+    /// 在 multi-thread runtime 內以 `block_in_place` 在目前的 runtime 上等待；
+    /// 其他情況（沒有 runtime 或 current-thread runtime）在輔助執行緒上以臨時的
+    /// runtime 執行，不會阻塞呼叫端的 runtime。異步程式碼仍應直接使用 `transmit`。
+    fn send(&mut self, metadata: MessageMetadata, payload: &str) {
+        use tokio::runtime::{Handle, RuntimeFlavor};
+
+        if let Ok(handle) = Handle::try_current() {
+            if handle.runtime_flavor() == RuntimeFlavor::MultiThread {
+                tokio::task::block_in_place(|| {
+                    handle.block_on(self.transmit(&metadata, payload.as_bytes()))
+                });
+                return;
+            }
+        }
+
+        std::thread::scope(|scope| {
+            scope
+                .spawn(|| {
+                    let runtime = tokio::runtime::Builder::new_current_thread()
+                        .enable_time()
+                        .build()
+                        .expect("failed to build LoRa simulation runtime");
+                    runtime.block_on(self.transmit(&metadata, payload.as_bytes()));
+                })
+                .join()
+                .expect("LoRa simulation thread panicked");
+        });
     }
 
     fn get_stats(&self) -> Option<TransportStats> {
@@ -379,9 +913,18 @@ impl Transport for SimulatedLoRaTransport {
 
     fn reset(&mut self) {
         self.stats = TransportStats::default();
+        self.clock_ms = 0;
+        self.next_tx_allowed.clear();
 
+        // 保留事件流，已連線的 Dashboard 不需要重新訂閱
         let mut state = self.state.lock().unwrap();
-        *state = LoRaTransportState::default();
+        let events = state.events.clone();
+        *state = LoRaTransportState {
+            events,
+            ..LoRaTransportState::default()
+        };
+        state.set_phase("Idle", 0.0);
+        state.publish_stats();
     }
 }
 
@@ -395,7 +938,8 @@ mod tests {
 
     #[test]
     fn test_lora_transport_fragmentation() {
-        let mut transport = SimulatedLoRaTransport::new();
+        // 虛擬時鐘：不會真的等待
+        let mut transport = SimulatedLoRaTransport::new_with_config(LoRaConfig::deterministic(7));
 
         // 測試小型訊息（不需分片）
         transport.send(
@@ -422,5 +966,146 @@ mod tests {
 
         let stats = transport.get_stats().unwrap();
         assert_eq!(stats.total_messages, 2);
+        assert!(transport.clock_ms() >= 5 * LoRaConfig::default().latency_ms);
+    }
+
+    #[tokio::test]
+    async fn test_lossy_link_retransmits_only_nacked_fragments() {
+        let config = LoRaConfig {
+            packet_loss_rate: 0.5,
+            max_retries: 30,
+            ..LoRaConfig::deterministic(11)
+        };
+        let mut transport = SimulatedLoRaTransport::new_with_config(config);
+        let metadata = MessageMetadata {
+            from: "coordinator".to_string(),
+            to: "signer_1".to_string(),
+            message_type: MessageType::SigningPackage,
+            timestamp: None,
+        };
+
+        let mut saw_loss = false;
+        let mut saw_nack = false;
+        for round in 0..5u8 {
+            let payload = vec![round; 200];
+            assert_eq!(transport.transmit(&metadata, &payload).await, Some(payload));
+
+            let state = transport.get_state();
+            let mut state = state.lock().unwrap();
+            for event in state.recent_events.drain(..) {
+                match event {
+                    TransportEvent::PacketLost { .. } => saw_loss = true,
+                    TransportEvent::AckReceived { missing, .. } if !missing.is_empty() => {
+                        // NACK 只列出確實缺少的片段
+                        assert!(missing.iter().all(|&index| index < 4));
+                        saw_nack = true;
+                    }
+                    TransportEvent::MessageFailed { .. } => panic!("message should be delivered"),
+                    _ => {}
+                }
+            }
+        }
+
+        assert!(saw_loss && saw_nack);
+        let state = transport.get_state();
+        let state = state.lock().unwrap();
+        assert!(state.total_retries > 0);
+        assert!(state.message_airtime.iter().all(|entry| entry.delivered));
+        assert_eq!(transport.receiver.pending_messages(), 0);
+    }
+
+    #[tokio::test]
+    async fn test_undeliverable_message_is_reported_as_failed() {
+        let config = LoRaConfig {
+            packet_loss_rate: 1.0,
+            max_retries: 2,
+            ..LoRaConfig::deterministic(3)
+        };
+        let mut transport = SimulatedLoRaTransport::new_with_config(config);
+        let metadata = MessageMetadata {
+            from: "signer_2".to_string(),
+            to: "coordinator".to_string(),
+            message_type: MessageType::Round2SignatureShare,
+            timestamp: None,
+        };
+
+        // 超過重傳上限後不交付，接收端也不留下半完成的緩衝區
+        assert_eq!(transport.transmit(&metadata, &[0x42; 150]).await, None);
+        assert_eq!(transport.receiver.pending_messages(), 0);
+
+        let state = transport.get_state();
+        let state = state.lock().unwrap();
+        let failure = state.recent_events.iter().find_map(|event| match event {
+            TransportEvent::MessageFailed {
+                missing_fragments,
+                ..
+            } => Some(*missing_fragments),
+            _ => None,
+        });
+        assert_eq!(failure, Some(3));
+        assert!(!state.message_airtime[0].delivered);
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn test_sync_send_inside_runtime() {
+        // 在 runtime 內呼叫同步介面不會 panic（不再嵌套建立 runtime）
+        let mut transport = SimulatedLoRaTransport::new_with_config(LoRaConfig::deterministic(7));
+        let metadata = MessageMetadata {
+            from: "test".to_string(),
+            to: "dest".to_string(),
+            message_type: MessageType::Other,
+            timestamp: None,
+        };
+        transport.send(metadata.clone(), "inside a runtime");
+
+        // current-thread runtime 內改由輔助執行緒執行
+        let transport = tokio::task::spawn_blocking(move || {
+            let runtime = tokio::runtime::Builder::new_current_thread()
+                .enable_time()
+                .build()
+                .unwrap();
+            runtime.block_on(async move {
+                transport.send(metadata, "inside a current-thread runtime");
+                transport
+            })
+        })
+        .await
+        .unwrap();
+        assert_eq!(transport.get_stats().unwrap().total_messages, 2);
+    }
+
+    #[tokio::test]
+    async fn test_radio_model_reports_airtime_and_duty_cycle() {
+        let radio = RadioConfig::eu868(12, 1_000.0).unwrap();
+        let max_payload = radio.max_payload();
+        let config = LoRaConfig {
+            packet_loss_rate: 0.0,
+            ..LoRaConfig::deterministic(1).with_radio(radio)
+        };
+        let mut transport = SimulatedLoRaTransport::new_with_config(config);
+        let metadata = MessageMetadata {
+            from: "coordinator".to_string(),
+            to: "signer_1".to_string(),
+            message_type: MessageType::SigningPackage,
+            timestamp: None,
+        };
+
+        let payload = vec![0xAB; 200];
+        let delivered = transport.transmit(&metadata, &payload).await;
+        assert_eq!(delivered, Some(payload));
+
+        let state = transport.get_state();
+        let state = state.lock().unwrap();
+        let entry = &state.message_airtime[0];
+
+        // SF12 的最大 payload 只有 59 bytes，分片受其限制
+        assert_eq!(entry.fragments, 200usize.div_ceil(max_payload - FRAGMENT_HEADER_LEN));
+
+        // 1% 工作週期：同一節點連續發送片段之間必須靜默
+        assert!(entry.airtime_ms > 0);
+        assert!(entry.duty_cycle_wait_ms > 50 * entry.airtime_ms / entry.fragments as u64);
+        assert_eq!(state.total_airtime_ms, entry.airtime_ms);
+        assert!(entry.total_time_ms >= entry.airtime_ms + entry.duty_cycle_wait_ms);
+        assert!(state.snr_db.is_some());
     }
 }
@@ -0,0 +1,922 @@
+//! # WebSocket Transport - 簽署者主動連線的推播通道
+//!
+//! 簽署者通常位於 NAT 或防火牆之後，協調者無法主動連入。此模組讓簽署者向
+//! Axum 服務發起 WebSocket 連線並登記身分，之後由協調者把 Round 1 / Round 2
+//! 請求推送過去，不再需要輪詢：
+//!
+//! - **登記**：連線到 `GET /signer/:id/ws`（或 `/groups/:group_id/signer/:id/ws`），
+//!   簽署者 ID 在路徑中，由認證中間件依 `signer_operator` Token 授權；
+//!   服務端先送出一次性的 `WsControl::Challenge`，簽署者以身分金鑰簽署後回覆
+//!   `WsControl::Authenticate`，通過名冊驗證才會送出 `WsControl::Registered`
+//! - **訊息**：協議訊息以 `codec` 的二進位格式放在 Binary frame；
+//!   Text frame 只用於 JSON 控制訊息
+//! - **心跳**：服務端定期送出 Ping，超過逾時未收到任何 frame 即斷線；
+//!   客戶端同樣在逾時內未收到任何 frame（包含 Ping）時視為斷線
+//! - **重新連線**：`WebSocketSigner` 以指數退避重新連線。只有證明身分的新連線能取代
+//!   同一簽署者仍存活的舊連線，否則新連線收到 `WsControl::Rejected` 後被關閉。
+//!   Nonce 保存在 `Signer` 中，斷線重連不影響進行中的 Session
+//! - **待送中止**：`WebSocketHub::queue_abort` 為尚未連線的簽署者保留 `Abort`
+//!   （例如協調者重新啟動後恢復的 Session），在簽署者登記後立即送出
+//!
+//! 協調者端的 `WebSocketHub::endpoint` 實作 `AsyncTransport`，可直接交給
+//! `Coordinator::orchestrate_over_transport`。登記決定訊息送往哪條連線；
+//! 承諾與分片的來源另外由名冊的信封簽章驗證。
+//!
+//! 客戶端目前只支援 `ws://`，需要 TLS 時請由反向代理終止。
+
+use super::{
+    codec, AsyncTransport, FrostMessage, Participant, TransportEnvelope, TransportError,
+    TransportStats,
+};
+use crate::api::{GroupId, SessionId};
+use crate::envelope::{EnvelopeKind, Roster};
+use crate::signer::Signer;
+use axum::extract::ws::{Message as ServerMessage, WebSocket};
+use dashmap::mapref::entry::Entry;
+use dashmap::DashMap;
+use futures::{SinkExt, StreamExt};
+use serde::{Deserialize, Serialize};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use tokio::net::TcpStream;
+use tokio::sync::{broadcast, mpsc};
+use tokio_tungstenite::tungstenite::client::IntoClientRequest;
+use tokio_tungstenite::tungstenite::http::{header, HeaderValue};
+use tokio_tungstenite::tungstenite::Message as ClientMessage;
+use tokio_tungstenite::{MaybeTlsStream, WebSocketStream};
+
+/// 服務端送出 Ping 的預設間隔
+pub const DEFAULT_HEARTBEAT_INTERVAL: Duration = Duration::from_secs(15);
+
+/// 未收到任何 frame 即視為斷線的預設時間
+pub const DEFAULT_HEARTBEAT_TIMEOUT: Duration = Duration::from_secs(45);
+
+/// 簽署者回覆的廣播緩衝（所有進行中的協調者端點共用）
+const INBOUND_CAPACITY: usize = 1024;
+
+// ============================================================================
+// 控制訊息
+// ============================================================================
+
+/// WebSocket 控制訊息（Text frame，JSON）
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum WsControl {
+    /// 服務端 → 簽署者：登記前的一次性挑戰
+    Challenge {
+        group_id: GroupId,
+        challenge: SessionId,
+    },
+
+    /// 簽署者 → 服務端：以身分金鑰簽署的挑戰（`EnvelopeKind::WsRegistration`）
+    Authenticate { signature: String },
+
+    /// 服務端 → 簽署者：登記成功，之後的請求會推送到這條連線
+    Registered {
+        group_id: GroupId,
+        signer_id: u16,
+        heartbeat_interval_ms: u64,
+    },
+
+    /// 服務端 → 簽署者：登記被拒絕，連線隨後關閉
+    Rejected { reason: String },
+}
+
+impl WsControl {
+    fn to_text(&self) -> String {
+        serde_json::to_string(self).expect("control messages always serialize")
+    }
+}
+
+/// 登記挑戰的信封內容：hex 編碼的群組 ID（挑戰本身放在 session_id 欄位）
+fn registration_payload(group_id: GroupId) -> String {
+    hex::encode(group_id.0.as_bytes())
+}
+
+fn websocket_error(e: impl std::fmt::Display) -> TransportError {
+    TransportError::WebSocket(e.to_string())
+}
+
+/// 編碼一則訊息，並返回（線上格式, JSON 基準大小）
+fn encode_envelope(envelope: &TransportEnvelope) -> Result<(Vec<u8>, usize), TransportError> {
+    let wire = codec::encode(envelope)?;
+    let baseline =
+        serde_json::to_vec(envelope).map_err(|e| TransportError::Encoding(e.to_string()))?;
+    Ok((wire, baseline.len()))
+}
+
+// ============================================================================
+// WebSocketHub - 協調者端（Axum 服務內）
+// ============================================================================
+
+/// 一條已登記的簽署者連線
+struct Connection {
+    /// 連線序號（重新連線時用來避免舊連線清除新連線）
+    id: u64,
+
+    /// 推送給簽署者的已編碼訊息
+    outbound: mpsc::UnboundedSender<Vec<u8>>,
+}
+
+struct HubInner {
+    /// (群組, 簽署者 ID) → 目前的連線
+    connections: DashMap<(GroupId, u16), Connection>,
+
+    /// 簽署者的回覆（每個協調者端點各自訂閱）
+    inbound: broadcast::Sender<(GroupId, TransportEnvelope)>,
+
+    /// (群組, 簽署者 ID) → 登記後要送出的已編碼 `Abort`
+    pending_aborts: DashMap<(GroupId, u16), Vec<Vec<u8>>>,
+
+    next_connection_id: AtomicU64,
+}
+
+/// 已登記簽署者的 WebSocket 連線表
+///
+/// Clone 後共用同一組連線；放在 Axum 的應用狀態中。
+#[derive(Clone)]
+pub struct WebSocketHub {
+    inner: Arc<HubInner>,
+    heartbeat_interval: Duration,
+    heartbeat_timeout: Duration,
+}
+
+impl Default for WebSocketHub {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl WebSocketHub {
+    pub fn new() -> Self {
+        let (inbound, _) = broadcast::channel(INBOUND_CAPACITY);
+        Self {
+            inner: Arc::new(HubInner {
+                connections: DashMap::new(),
+                inbound,
+                pending_aborts: DashMap::new(),
+                next_connection_id: AtomicU64::new(0),
+            }),
+            heartbeat_interval: DEFAULT_HEARTBEAT_INTERVAL,
+            heartbeat_timeout: DEFAULT_HEARTBEAT_TIMEOUT,
+        }
+    }
+
+    /// 設定心跳（Ping 間隔與斷線逾時）
+    pub fn with_heartbeat(mut self, interval: Duration, timeout: Duration) -> Self {
+        self.heartbeat_interval = interval;
+        self.heartbeat_timeout = timeout;
+        self
+    }
+
+    /// 簽署者目前是否已連線
+    pub fn is_connected(&self, group_id: GroupId, signer_id: u16) -> bool {
+        self.inner.connections.contains_key(&(group_id, signer_id))
+    }
+
+    /// 指定的簽署者是否全部已連線
+    pub fn all_connected(&self, group_id: GroupId, signer_ids: &[u16]) -> bool {
+        signer_ids
+            .iter()
+            .all(|&signer_id| self.is_connected(group_id, signer_id))
+    }
+
+    /// 群組中已連線的簽署者 ID（已排序）
+    pub fn connected_signers(&self, group_id: GroupId) -> Vec<u16> {
+        let mut ids: Vec<u16> = self
+            .inner
+            .connections
+            .iter()
+            .filter(|entry| entry.key().0 == group_id)
+            .map(|entry| entry.key().1)
+            .collect();
+        ids.sort_unstable();
+        ids
+    }
+
+    /// 中斷簽署者的連線（客戶端會自動重新連線）
+    pub fn disconnect(&self, group_id: GroupId, signer_id: u16) {
+        // 丟棄 outbound sender 後，連線任務會關閉 socket
+        self.inner.connections.remove(&(group_id, signer_id));
+    }
+
+    /// 通知簽署者丟棄指定 Session 的 Nonce
+    ///
+    /// 簽署者已連線時立即送出 `Abort`，否則保留到它下次登記。
+    pub fn queue_abort(
+        &self,
+        group_id: GroupId,
+        signer_id: u16,
+        session_id: SessionId,
+        reason: &str,
+    ) -> Result<(), TransportError> {
+        let (wire, _) = encode_envelope(&TransportEnvelope {
+            from: Participant::Coordinator,
+            to: Participant::Signer(signer_id),
+            message: FrostMessage::Abort {
+                session_id,
+                reason: reason.to_string(),
+            },
+        })?;
+
+        // 持有連線表的讀取鎖，避免與 `accept` 的登記交錯而讓訊息留在佇列中
+        let key = (group_id, signer_id);
+        let connection = self.inner.connections.get(&key);
+        if let Some(connection) = &connection {
+            if connection.outbound.send(wire.clone()).is_ok() {
+                return Ok(());
+            }
+        }
+        self.inner.pending_aborts.entry(key).or_default().push(wire);
+        Ok(())
+    }
+
+    /// 建立群組的協調者端點
+    ///
+    /// 端點建立後才開始接收簽署者的回覆，請在發送請求前建立。
+    pub fn endpoint(&self, group_id: GroupId) -> WebSocketHubTransport {
+        WebSocketHubTransport {
+            group_id,
+            hub: self.clone(),
+            inbound: self.inner.inbound.subscribe(),
+            stats: TransportStats::default(),
+        }
+    }
+
+    /// 接手一條已升級的 WebSocket 連線，直到斷線為止
+    ///
+    /// 由 Axum handler 在 `WebSocketUpgrade::on_upgrade` 中呼叫；
+    /// 群組與簽署者 ID 應已在升級前驗證。
+    ///
+    /// 提供名冊時，連線必須以簽署者的身分金鑰簽署挑戰才能登記；
+    /// 沒有名冊時無法證明身分，只接受目前沒有連線的簽署者。
+    pub async fn accept(
+        self,
+        mut socket: WebSocket,
+        group_id: GroupId,
+        signer_id: u16,
+        roster: Option<Roster>,
+    ) {
+        let Some(proven) = self
+            .authenticate(&mut socket, group_id, signer_id, roster.as_ref())
+            .await
+        else {
+            return;
+        };
+
+        let (outbound, mut outbound_rx) = mpsc::unbounded_channel();
+        let connection_id = self.inner.next_connection_id.fetch_add(1, Ordering::Relaxed);
+        let connection = Connection {
+            id: connection_id,
+            outbound,
+        };
+
+        let rejection = match self.inner.connections.entry((group_id, signer_id)) {
+            Entry::Occupied(mut entry) if proven => {
+                entry.insert(connection);
+                tracing::info!(
+                    group_id = %group_id,
+                    signer_id,
+                    "Signer reconnected, replacing previous WebSocket connection"
+                );
+                None
+            }
+            Entry::Occupied(_) => Some("signer is already connected"),
+            Entry::Vacant(entry) if proven || roster.is_none() => {
+                entry.insert(connection);
+                None
+            }
+            Entry::Vacant(_) => Some("identity proof failed"),
+        };
+        if let Some(reason) = rejection {
+            tracing::warn!(
+                group_id = %group_id,
+                signer_id,
+                reason,
+                "Rejected WebSocket registration"
+            );
+            let rejected = WsControl::Rejected {
+                reason: reason.to_string(),
+            };
+            let _ = socket.send(ServerMessage::Text(rejected.to_text())).await;
+            let _ = socket.close().await;
+            return;
+        }
+
+        let (mut sink, mut stream) = socket.split();
+        let registered = WsControl::Registered {
+            group_id,
+            signer_id,
+            heartbeat_interval_ms: self.heartbeat_interval.as_millis() as u64,
+        };
+        if sink
+            .send(ServerMessage::Text(registered.to_text()))
+            .await
+            .is_err()
+        {
+            self.unregister(group_id, signer_id, connection_id);
+            return;
+        }
+        tracing::info!(group_id = %group_id, signer_id, "Signer registered over WebSocket");
+
+        if let Some((_, pending)) = self.inner.pending_aborts.remove(&(group_id, signer_id)) {
+            tracing::info!(
+                group_id = %group_id,
+                signer_id,
+                count = pending.len(),
+                "Delivering queued session aborts"
+            );
+            let mut pending = pending.into_iter();
+            while let Some(wire) = pending.next() {
+                if sink
+                    .send(ServerMessage::Binary(wire.clone()))
+                    .await
+                    .is_err()
+                {
+                    // 連線中斷：未送出的留到下次登記
+                    self.unregister(group_id, signer_id, connection_id);
+                    self.inner
+                        .pending_aborts
+                        .entry((group_id, signer_id))
+                        .or_default()
+                        .extend(std::iter::once(wire).chain(pending));
+                    return;
+                }
+            }
+        }
+
+        let mut heartbeat = tokio::time::interval(self.heartbeat_interval);
+        heartbeat.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+        let mut last_seen = Instant::now();
+
+        loop {
+            tokio::select! {
+                frame = stream.next() => {
+                    let Some(Ok(frame)) = frame else { break };
+                    last_seen = Instant::now();
+                    match frame {
+                        ServerMessage::Binary(wire) => {
+                            self.route_inbound(group_id, signer_id, &wire)
+                        }
+                        ServerMessage::Close(_) => break,
+                        // Ping 由 tungstenite 自動回覆；Pong 與 Text 只代表連線仍存活
+                        _ => {}
+                    }
+                }
+                wire = outbound_rx.recv() => {
+                    // sender 被丟棄代表連線已被取代或被中斷
+                    let Some(wire) = wire else { break };
+                    if sink.send(ServerMessage::Binary(wire)).await.is_err() {
+                        break;
+                    }
+                }
+                _ = heartbeat.tick() => {
+                    if last_seen.elapsed() > self.heartbeat_timeout {
+                        tracing::warn!(
+                            group_id = %group_id,
+                            signer_id,
+                            "Signer missed heartbeats, closing WebSocket connection"
+                        );
+                        break;
+                    }
+                    if sink.send(ServerMessage::Ping(Vec::new())).await.is_err() {
+                        break;
+                    }
+                }
+            }
+        }
+
+        let _ = sink.close().await;
+        self.unregister(group_id, signer_id, connection_id);
+    }
+
+    /// 送出挑戰並驗證簽署者的回覆
+    ///
+    /// 返回連線是否證明了名冊中的身分金鑰；連線中斷或逾時未回覆時返回 `None`。
+    async fn authenticate(
+        &self,
+        socket: &mut WebSocket,
+        group_id: GroupId,
+        signer_id: u16,
+        roster: Option<&Roster>,
+    ) -> Option<bool> {
+        let challenge = SessionId::new();
+        let control = WsControl::Challenge {
+            group_id,
+            challenge,
+        };
+        socket
+            .send(ServerMessage::Text(control.to_text()))
+            .await
+            .ok()?;
+
+        let deadline = tokio::time::Instant::now() + self.heartbeat_timeout;
+        let signature = loop {
+            let frame = tokio::time::timeout_at(deadline, socket.recv())
+                .await
+                .ok()??
+                .ok()?;
+            match frame {
+                ServerMessage::Text(text) => match serde_json::from_str(&text) {
+                    Ok(WsControl::Authenticate { signature }) => break signature,
+                    _ => return Some(false),
+                },
+                ServerMessage::Close(_) => return None,
+                _ => continue,
+            }
+        };
+
+        let Some(roster) = roster else {
+            return Some(false);
+        };
+        match roster.verify(
+            EnvelopeKind::WsRegistration,
+            challenge,
+            signer_id,
+            &registration_payload(group_id),
+            Some(&signature),
+        ) {
+            Ok(()) => Some(true),
+            Err(e) => {
+                tracing::warn!(
+                    group_id = %group_id,
+                    signer_id,
+                    error = %e,
+                    "WebSocket registration failed identity check"
+                );
+                Some(false)
+            }
+        }
+    }
+
+    /// 移除連線（已被新連線取代時不動作）
+    fn unregister(&self, group_id: GroupId, signer_id: u16, connection_id: u64) {
+        let removed = self
+            .inner
+            .connections
+            .remove_if(&(group_id, signer_id), |_, connection| {
+                connection.id == connection_id
+            });
+        if removed.is_some() {
+            tracing::info!(group_id = %group_id, signer_id, "Signer WebSocket disconnected");
+        }
+    }
+
+    /// 將簽署者的回覆轉給協調者端點
+    fn route_inbound(&self, group_id: GroupId, signer_id: u16, wire: &[u8]) {
+        let envelope = match codec::decode(wire) {
+            Ok(envelope) => envelope,
+            Err(e) => {
+                tracing::warn!(
+                    group_id = %group_id,
+                    signer_id,
+                    error = %e,
+                    "Dropping undecodable WebSocket message"
+                );
+                return;
+            }
+        };
+
+        // 連線只能代表自己登記的簽署者發言
+        if envelope.from != Participant::Signer(signer_id)
+            || envelope.to != Participant::Coordinator
+        {
+            tracing::warn!(
+                group_id = %group_id,
+                signer_id,
+                from = %envelope.from,
+                to = %envelope.to,
+                "Dropping WebSocket message with mismatched addressing"
+            );
+            return;
+        }
+
+        // 沒有進行中的協調者端點時訊息直接丟棄
+        let _ = self.inner.inbound.send((group_id, envelope));
+    }
+}
+
+/// `WebSocketHub` 上某個群組的協調者端點
+pub struct WebSocketHubTransport {
+    group_id: GroupId,
+    hub: WebSocketHub,
+    inbound: broadcast::Receiver<(GroupId, TransportEnvelope)>,
+    stats: TransportStats,
+}
+
+impl AsyncTransport for WebSocketHubTransport {
+    fn local(&self) -> Participant {
+        Participant::Coordinator
+    }
+
+    async fn send(&mut self, to: Participant, message: FrostMessage) -> Result<(), TransportError> {
+        let Participant::Signer(signer_id) = to else {
+            return Err(TransportError::UnknownRecipient(to));
+        };
+
+        let message_type = message.message_type();
+        let (wire, baseline) = encode_envelope(&TransportEnvelope {
+            from: Participant::Coordinator,
+            to,
+            message,
+        })?;
+        let wire_len = wire.len();
+
+        let outbound = self
+            .hub
+            .inner
+            .connections
+            .get(&(self.group_id, signer_id))
+            .map(|connection| connection.outbound.clone())
+            .ok_or(TransportError::UnknownRecipient(to))?;
+        outbound
+            .send(wire)
+            .map_err(|_| TransportError::UnknownRecipient(to))?;
+
+        self.stats.record(message_type, wire_len, baseline);
+        Ok(())
+    }
+
+    async fn recv(&mut self) -> Result<TransportEnvelope, TransportError> {
+        loop {
+            match self.inbound.recv().await {
+                Ok((group_id, envelope)) if group_id == self.group_id => return Ok(envelope),
+                Ok(_) => continue,
+                Err(broadcast::error::RecvError::Lagged(skipped)) => {
+                    tracing::warn!(
+                        group_id = %self.group_id,
+                        skipped,
+                        "Coordinator endpoint lagged behind, dropped signer replies"
+                    );
+                }
+                Err(broadcast::error::RecvError::Closed) => return Err(TransportError::Closed),
+            }
+        }
+    }
+
+    fn get_stats(&self) -> Option<TransportStats> {
+        Some(self.stats.clone())
+    }
+}
+
+// ============================================================================
+// WebSocketSigner - 簽署者端（主動連線）
+// ============================================================================
+
+/// 主動連線到協調者服務的簽署者
+///
+/// `run` 會持續連線、處理推送的請求，斷線後以指數退避重新連線。
+pub struct WebSocketSigner {
+    /// 服務的基底 URL（例如 `ws://127.0.0.1:3000`）
+    url: String,
+    signer: Arc<Signer>,
+    group_id: Option<GroupId>,
+    bearer_token: Option<String>,
+    heartbeat_timeout: Duration,
+    min_backoff: Duration,
+    max_backoff: Duration,
+}
+
+impl WebSocketSigner {
+    /// 建立簽署者客戶端（預設連到服務的預設群組）
+    pub fn new(url: impl Into<String>, signer: Arc<Signer>) -> Self {
+        Self {
+            url: url.into(),
+            signer,
+            group_id: None,
+            bearer_token: None,
+            heartbeat_timeout: DEFAULT_HEARTBEAT_TIMEOUT,
+            min_backoff: Duration::from_millis(500),
+            max_backoff: Duration::from_secs(30),
+        }
+    }
+
+    /// 連到指定群組
+    pub fn with_group(mut self, group_id: GroupId) -> Self {
+        self.group_id = Some(group_id);
+        self
+    }
+
+    /// 設定 API Token（`signer_operator` 角色）
+    pub fn with_bearer_token(mut self, token: impl Into<String>) -> Self {
+        self.bearer_token = Some(token.into());
+        self
+    }
+
+    /// 設定未收到任何 frame 即視為斷線的時間（應大於服務端的 Ping 間隔）
+    pub fn with_heartbeat_timeout(mut self, timeout: Duration) -> Self {
+        self.heartbeat_timeout = timeout;
+        self
+    }
+
+    /// 設定重新連線的退避範圍（每次失敗加倍，直到上限）
+    pub fn with_reconnect_backoff(mut self, min: Duration, max: Duration) -> Self {
+        self.min_backoff = min;
+        self.max_backoff = max.max(min);
+        self
+    }
+
+    /// 登記端點的完整 URL
+    pub fn endpoint_url(&self) -> String {
+        let base = self.url.trim_end_matches('/');
+        let signer_id = self.signer.numeric_id();
+        match self.group_id {
+            Some(group_id) => format!("{}/groups/{}/signer/{}/ws", base, group_id, signer_id),
+            None => format!("{}/signer/{}/ws", base, signer_id),
+        }
+    }
+
+    /// 建立一條連線，回覆身分挑戰並等待登記確認
+    pub async fn connect(&self) -> Result<WebSocketSignerTransport, TransportError> {
+        let mut request = self
+            .endpoint_url()
+            .into_client_request()
+            .map_err(websocket_error)?;
+        if let Some(token) = &self.bearer_token {
+            let value =
+                HeaderValue::from_str(&format!("Bearer {}", token)).map_err(websocket_error)?;
+            request.headers_mut().insert(header::AUTHORIZATION, value);
+        }
+
+        let (mut socket, _) = tokio_tungstenite::connect_async(request)
+            .await
+            .map_err(websocket_error)?;
+
+        let signer_id = self.signer.numeric_id();
+        loop {
+            let frame = tokio::time::timeout(self.heartbeat_timeout, socket.next())
+                .await
+                .map_err(|_| websocket_error("timed out waiting for registration"))?;
+            match frame {
+                Some(Ok(ClientMessage::Text(text))) => {
+                    let control: WsControl =
+                        serde_json::from_str(&text).map_err(websocket_error)?;
+                    match control {
+                        WsControl::Challenge {
+                            group_id,
+                            challenge,
+                        } => {
+                            let signature = self.signer.sign_envelope(
+                                EnvelopeKind::WsRegistration,
+                                challenge,
+                                &registration_payload(group_id),
+                            );
+                            socket
+                                .send(ClientMessage::Text(
+                                    WsControl::Authenticate { signature }.to_text(),
+                                ))
+                                .await
+                                .map_err(websocket_error)?;
+                        }
+                        WsControl::Registered {
+                            signer_id: registered,
+                            group_id,
+                            ..
+                        } => {
+                            if registered != signer_id {
+                                return Err(websocket_error(format!(
+                                    "registered as signer {} instead of {}",
+                                    registered, signer_id
+                                )));
+                            }
+                            tracing::info!(
+                                group_id = %group_id,
+                                signer_id,
+                                "Registered with coordinator"
+                            );
+                            break;
+                        }
+                        WsControl::Rejected { reason } => {
+                            return Err(websocket_error(format!(
+                                "registration rejected: {}",
+                                reason
+                            )));
+                        }
+                        WsControl::Authenticate { .. } => continue,
+                    }
+                }
+                Some(Ok(_)) => continue,
+                Some(Err(e)) => return Err(websocket_error(e)),
+                None => return Err(TransportError::Closed),
+            }
+        }
+
+        Ok(WebSocketSignerTransport {
+            local: Participant::Signer(signer_id),
+            socket,
+            heartbeat_timeout: self.heartbeat_timeout,
+            stats: TransportStats::default(),
+        })
+    }
+
+    /// 持續連線並處理推送的請求（不會返回；以 task abort 停止）
+    pub async fn run(&self) {
+        let signer_id = self.signer.numeric_id();
+        let mut backoff = self.min_backoff;
+
+        loop {
+            match self.connect().await {
+                Ok(mut transport) => {
+                    backoff = self.min_backoff;
+                    if let Err(e) = self.signer.serve(&mut transport).await {
+                        tracing::warn!(signer_id, error = %e, "WebSocket session ended with error");
+                    }
+                    tracing::warn!(signer_id, "Connection to coordinator lost, reconnecting");
+                }
+                Err(e) => {
+                    tracing::warn!(
+                        signer_id,
+                        url = %self.endpoint_url(),
+                        error = %e,
+                        retry_in_ms = backoff.as_millis() as u64,
+                        "Failed to connect to coordinator"
+                    );
+                }
+            }
+
+            tokio::time::sleep(backoff).await;
+            backoff = (backoff * 2).min(self.max_backoff);
+        }
+    }
+}
+
+/// 簽署者端的一條 WebSocket 連線
+///
+/// 斷線或心跳逾時時 `recv` 返回 `Closed`，讓 `Signer::serve` 結束並重新連線。
+pub struct WebSocketSignerTransport {
+    local: Participant,
+    socket: WebSocketStream<MaybeTlsStream<TcpStream>>,
+    heartbeat_timeout: Duration,
+    stats: TransportStats,
+}
+
+impl AsyncTransport for WebSocketSignerTransport {
+    fn local(&self) -> Participant {
+        self.local
+    }
+
+    async fn send(&mut self, to: Participant, message: FrostMessage) -> Result<(), TransportError> {
+        let message_type = message.message_type();
+        let (wire, baseline) = encode_envelope(&TransportEnvelope {
+            from: self.local,
+            to,
+            message,
+        })?;
+        let wire_len = wire.len();
+
+        self.socket
+            .send(ClientMessage::Binary(wire))
+            .await
+            .map_err(websocket_error)?;
+
+        self.stats.record(message_type, wire_len, baseline);
+        Ok(())
+    }
+
+    async fn recv(&mut self) -> Result<TransportEnvelope, TransportError> {
+        loop {
+            let frame = match tokio::time::timeout(self.heartbeat_timeout, self.socket.next()).await
+            {
+                Ok(frame) => frame,
+                Err(_) => {
+                    tracing::warn!(local = %self.local, "No heartbeat from coordinator");
+                    return Err(TransportError::Closed);
+                }
+            };
+
+            match frame {
+                Some(Ok(ClientMessage::Binary(wire))) => {
+                    let envelope = match codec::decode(&wire) {
+                        Ok(envelope) => envelope,
+                        Err(e) => {
+                            tracing::warn!(error = %e, "Dropping undecodable WebSocket message");
+                            continue;
+                        }
+                    };
+                    if envelope.to != self.local {
+                        continue;
+                    }
+                    return Ok(envelope);
+                }
+                Some(Ok(ClientMessage::Close(_))) | None => return Err(TransportError::Closed),
+                // Ping 會在下次讀寫時自動回覆 Pong
+                Some(Ok(_)) => continue,
+                Some(Err(e)) => {
+                    tracing::debug!(local = %self.local, error = %e, "WebSocket read failed");
+                    return Err(TransportError::Closed);
+                }
+            }
+        }
+    }
+
+    fn get_stats(&self) -> Option<TransportStats> {
+        Some(self.stats.clone())
+    }
+}
+
+// ============================================================================
+// 測試
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::api::SignPayload;
+    use crate::group::{GroupServices, SigningGroup};
+    use axum::extract::{Path, State, WebSocketUpgrade};
+    use axum::routing::get;
+    use axum::Router;
+
+    async fn wait_until_connected(hub: &WebSocketHub, group_id: GroupId, signer_ids: &[u16]) {
+        let deadline = Instant::now() + Duration::from_secs(5);
+        while !hub.all_connected(group_id, signer_ids) {
+            assert!(Instant::now() < deadline, "signers did not connect in time");
+            tokio::time::sleep(Duration::from_millis(10)).await;
+        }
+    }
+
+    #[tokio::test]
+    async fn test_push_signing_survives_reconnect_and_rejects_impostors() {
+        let group = SigningGroup::generate_with_dealer(3, 2, &GroupServices::default()).unwrap();
+        let group_id = group.id();
+
+        let hub = WebSocketHub::new()
+            .with_heartbeat(Duration::from_millis(100), Duration::from_secs(1));
+        let app = Router::new()
+            .route(
+                "/groups/:group_id/signer/:signer_id/ws",
+                get(
+                    |ws: WebSocketUpgrade,
+                     Path((group_id, signer_id)): Path<(GroupId, u16)>,
+                     State((hub, roster)): State<(WebSocketHub, Option<Roster>)>| async move {
+                        ws.on_upgrade(move |socket| hub.accept(socket, group_id, signer_id, roster))
+                    },
+                ),
+            )
+            .with_state((hub.clone(), group.coordinator().roster().cloned()));
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("ws://{}", listener.local_addr().unwrap());
+        tokio::spawn(async move { axum::serve(listener, app).await.unwrap() });
+
+        // 簽署者主動連線，協調者不需要知道它們的位址
+        for signer_id in [1, 3] {
+            let client = WebSocketSigner::new(&url, group.get_signer(signer_id).unwrap())
+                .with_group(group_id)
+                .with_reconnect_backoff(Duration::from_millis(10), Duration::from_millis(100));
+            tokio::spawn(async move { client.run().await });
+        }
+        wait_until_connected(&hub, group_id, &[1, 3]).await;
+        assert_eq!(hub.connected_signers(group_id), vec![1, 3]);
+
+        let payload = SignPayload::Raw {
+            message: hex::encode(b"pushed over websocket"),
+        };
+        let mut transport = hub.endpoint(group_id);
+        let outcome = group
+            .coordinator()
+            .orchestrate_over_transport(&mut transport, &[1, 3], &payload, Duration::from_secs(5))
+            .await
+            .unwrap();
+        assert!(outcome.verify(group.coordinator().group_public_key()));
+        assert_eq!(transport.get_stats().unwrap().total_messages, 6);
+
+        // 冒用簽署者 3 的 ID、但沒有其身分金鑰的連線不能取代既有連線
+        let impostor = SigningGroup::generate_with_dealer(3, 2, &GroupServices::default()).unwrap();
+        let rejected = WebSocketSigner::new(&url, impostor.get_signer(3).unwrap())
+            .with_group(group_id)
+            .connect()
+            .await;
+        assert!(rejected.is_err());
+        assert!(hub.is_connected(group_id, 3));
+
+        // 服務端中斷簽署者 1：客戶端自動重新連線後仍可完成下一次簽章
+        hub.disconnect(group_id, 1);
+        assert!(!hub.is_connected(group_id, 1));
+        wait_until_connected(&hub, group_id, &[1, 3]).await;
+
+        let mut transport = hub.endpoint(group_id);
+        let outcome = group
+            .coordinator()
+            .orchestrate_over_transport(&mut transport, &[1, 3], &payload, Duration::from_secs(5))
+            .await
+            .unwrap();
+        assert!(outcome.verify(group.coordinator().group_public_key()));
+
+        // 尚未連線的簽署者 2：中止訊息保留到它登記後才送出
+        let signer = group.get_signer(2).unwrap();
+        let stale_session = SessionId::new();
+        signer.commit(stale_session).unwrap();
+        hub.queue_abort(group_id, 2, stale_session, "coordinator restarted")
+            .unwrap();
+        assert!(signer.has_nonce(&stale_session));
+
+        let client = WebSocketSigner::new(&url, Arc::clone(&signer)).with_group(group_id);
+        tokio::spawn(async move { client.run().await });
+        let deadline = Instant::now() + Duration::from_secs(5);
+        while signer.has_nonce(&stale_session) {
+            assert!(Instant::now() < deadline, "queued abort was not delivered");
+            tokio::time::sleep(Duration::from_millis(10)).await;
+        }
+    }
+}